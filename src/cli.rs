@@ -1,4 +1,8 @@
-use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+pub mod theme;
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "omarchist")]
@@ -10,6 +14,10 @@ pub struct CliArgs {
 
     #[arg(short, long, requires = "view")]
     pub theme: Option<String>,
+
+    // Headless subcommands; when present no window is opened
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,12 +30,100 @@ pub enum ViewOption {
     Config,
 }
 
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Manage themes without opening the GUI
+    Theme {
+        #[command(subcommand)]
+        action: ThemeCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ThemeCommand {
+    /// List system, Omarchist and community themes
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create a new editable theme from the bundled defaults
+    Create {
+        /// Directory name for the new theme (generated when omitted)
+        name: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Generate a theme from an image and print its palette as JSON
+    Generate {
+        image: PathBuf,
+        /// Directory name for the new theme (generated when omitted)
+        #[arg(long)]
+        name: Option<String>,
+        /// Force a light theme instead of detecting it from the image
+        #[arg(long, conflicts_with = "dark")]
+        light: bool,
+        /// Force a dark theme instead of detecting it from the image
+        #[arg(long)]
+        dark: bool,
+        /// Apply the theme once it has been generated
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Apply a theme with `omarchy-theme-set`
+    Apply { name: String },
+    /// Rename an Omarchist theme
+    Rename { old_name: String, new_name: String },
+    /// Delete a user theme (system themes cannot be deleted)
+    Delete { name: String },
+    /// Export an Omarchist theme as a portable `.omarchist-theme` bundle
+    Export {
+        name: String,
+        /// Bundle path (defaults to `<name>.omarchist-theme` in the current directory)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a `.omarchist-theme` bundle as a new Omarchist theme
+    Import {
+        bundle: PathBuf,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Install a community theme from a git URL or local folder
+    Install {
+        source: String,
+        /// Directory name for the theme (derived from the source when omitted)
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Copy a system or community theme into a new editable Omarchist theme
+    Duplicate {
+        name: String,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show details about a theme
+    Show {
+        name: String,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 impl CliArgs {
     pub fn parse_args() -> Self {
         Self::parse()
     }
 }
 
+// Run a headless subcommand and return the process exit code
+pub fn run_command(command: Command) -> i32 {
+    match command {
+        Command::Theme { action } => theme::run(action),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,4 +147,108 @@ mod tests {
         assert_eq!(args.view, Some(ViewOption::Themes));
         assert_eq!(args.theme, Some("my-theme".to_string()));
     }
+
+    #[test]
+    fn test_parse_no_subcommand() {
+        let args = CliArgs::parse_from(["omarchist"]);
+        assert_eq!(args.command, None);
+    }
+
+    #[test]
+    fn test_parse_theme_list_json() {
+        let args = CliArgs::parse_from(["omarchist", "theme", "list", "--json"]);
+        assert_eq!(
+            args.command,
+            Some(Command::Theme {
+                action: ThemeCommand::List { json: true }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_theme_create_without_name() {
+        let args = CliArgs::parse_from(["omarchist", "theme", "create"]);
+        assert_eq!(
+            args.command,
+            Some(Command::Theme {
+                action: ThemeCommand::Create {
+                    name: None,
+                    json: false
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_theme_rename() {
+        let args = CliArgs::parse_from(["omarchist", "theme", "rename", "old", "new"]);
+        assert_eq!(
+            args.command,
+            Some(Command::Theme {
+                action: ThemeCommand::Rename {
+                    old_name: "old".to_string(),
+                    new_name: "new".to_string()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_theme_generate() {
+        let args = CliArgs::parse_from([
+            "omarchist",
+            "theme",
+            "generate",
+            "wall.png",
+            "--name",
+            "foo",
+            "--light",
+            "--apply",
+        ]);
+        assert_eq!(
+            args.command,
+            Some(Command::Theme {
+                action: ThemeCommand::Generate {
+                    image: PathBuf::from("wall.png"),
+                    name: Some("foo".to_string()),
+                    light: true,
+                    dark: false,
+                    apply: true,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_theme_generate_light_conflicts_with_dark() {
+        let result = CliArgs::try_parse_from([
+            "omarchist",
+            "theme",
+            "generate",
+            "wall.png",
+            "--light",
+            "--dark",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_theme_export_with_output() {
+        let args = CliArgs::parse_from(["omarchist", "theme", "export", "foo", "-o", "foo.bundle"]);
+        assert_eq!(
+            args.command,
+            Some(Command::Theme {
+                action: ThemeCommand::Export {
+                    name: "foo".to_string(),
+                    output: Some(PathBuf::from("foo.bundle")),
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_theme_apply_requires_name() {
+        let result = CliArgs::try_parse_from(["omarchist", "theme", "apply"]);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,264 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::ThemeCommand;
+use crate::shell::theme_sh_commands::apply_theme;
+use crate::system::themes::custom_themes::get_user_themes;
+use crate::system::themes::system_themes::get_system_themes;
+use crate::system::themes::theme_bundle::{
+    BUNDLE_EXTENSION, export_theme_bundle, import_theme_bundle,
+};
+use crate::system::themes::theme_convert::duplicate_as_editable;
+use crate::system::themes::theme_file_ops::{delete_theme, get_theme_path, is_custom_theme};
+use crate::system::themes::theme_generator::generate_theme_from_image;
+use crate::system::themes::theme_install::{
+    cancel_theme_install, confirm_theme_install, prepare_theme_install,
+};
+use crate::system::themes::theme_management::{
+    create_theme_from_defaults, generate_unique_theme_name, load_theme_for_editing, rename_theme,
+};
+use crate::types::themes::{EditingTheme, ThemeEntry, ThemeOrigin};
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+
+#[derive(Debug, Serialize)]
+struct ThemeDetails {
+    #[serde(flatten)]
+    entry: ThemeEntry,
+    path: String,
+    // Full editor data, only present for Omarchist themes
+    data: Option<EditingTheme>,
+}
+
+pub fn run(command: ThemeCommand) -> i32 {
+    let result = match command {
+        ThemeCommand::List { json } => list(json),
+        ThemeCommand::Create { name, json } => create(name, json),
+        ThemeCommand::Generate {
+            image,
+            name,
+            light,
+            dark,
+            apply,
+        } => generate(&image, name, light, dark, apply),
+        ThemeCommand::Apply { name } => apply(&name),
+        ThemeCommand::Rename { old_name, new_name } => rename(&old_name, &new_name),
+        ThemeCommand::Delete { name } => delete(&name),
+        ThemeCommand::Export { name, output } => export(&name, output),
+        ThemeCommand::Import { bundle, json } => import(&bundle, json),
+        ThemeCommand::Install { source, name, json } => install(&source, name.as_deref(), json),
+        ThemeCommand::Duplicate { name, json } => duplicate(&name, json),
+        ThemeCommand::Show { name, json } => show(&name, json),
+    };
+
+    match result {
+        Ok(()) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+// User themes shadow system themes with the same directory name, matching
+// how `omarchy-theme-set` resolves them.
+fn all_themes() -> Result<Vec<ThemeEntry>, String> {
+    let mut themes = get_user_themes()?;
+    let system_themes: Vec<ThemeEntry> = get_system_themes()?
+        .into_iter()
+        .filter(|s| !themes.iter().any(|u| u.dir == s.dir))
+        .collect();
+
+    themes.extend(system_themes);
+    themes.sort_by_key(|t| t.title.to_lowercase());
+    Ok(themes)
+}
+
+fn find_theme(name: &str) -> Result<ThemeEntry, String> {
+    all_themes()?
+        .into_iter()
+        .find(|t| t.dir == name)
+        .ok_or_else(|| format!("Theme '{}' not found", name))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize output: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn list(json: bool) -> Result<(), String> {
+    let themes = all_themes()?;
+
+    if json {
+        return print_json(&themes);
+    }
+
+    for theme in &themes {
+        println!(
+            "{}\t{}\t{}",
+            theme.dir,
+            theme.origin.badge_text(),
+            theme.title
+        );
+    }
+
+    Ok(())
+}
+
+fn create(name: Option<String>, json: bool) -> Result<(), String> {
+    let name = name.unwrap_or_else(generate_unique_theme_name);
+    let created = create_theme_from_defaults(&name)?;
+
+    if json {
+        return print_json(&find_theme(&created)?);
+    }
+
+    println!("{}", created);
+    Ok(())
+}
+
+fn generate(
+    image: &Path,
+    name: Option<String>,
+    light: bool,
+    dark: bool,
+    apply_after: bool,
+) -> Result<(), String> {
+    if !image.is_file() {
+        return Err(format!("Image not found: {}", image.display()));
+    }
+
+    let name = name.unwrap_or_else(generate_unique_theme_name);
+    let force_light = match (light, dark) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    };
+
+    let (created, palette) = generate_theme_from_image(
+        image,
+        &name,
+        force_light,
+        Some(Box::new(|msg: &str| eprintln!("{}", msg))),
+    )?;
+
+    if apply_after {
+        eprintln!("Applying theme '{}'...", created);
+        apply(&created)?;
+    }
+
+    print_json(&palette)
+}
+
+fn apply(name: &str) -> Result<(), String> {
+    find_theme(name)?;
+    smol::block_on(apply_theme(name.to_string()))
+}
+
+fn rename(old_name: &str, new_name: &str) -> Result<(), String> {
+    let theme = find_theme(old_name)?;
+    if !theme.origin.is_editable() {
+        return Err(format!(
+            "Theme '{}' is a {} theme and cannot be renamed",
+            old_name,
+            theme.origin.badge_text()
+        ));
+    }
+
+    rename_theme(old_name, new_name)
+}
+
+fn delete(name: &str) -> Result<(), String> {
+    find_theme(name)?;
+    delete_theme(name, !is_custom_theme(name))
+}
+
+fn export(name: &str, output: Option<PathBuf>) -> Result<(), String> {
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("{}.{}", name, BUNDLE_EXTENSION)));
+    let written = export_theme_bundle(name, &output)?;
+
+    println!("{}", written.display());
+    Ok(())
+}
+
+fn import(bundle: &Path, json: bool) -> Result<(), String> {
+    let imported = import_theme_bundle(bundle)?;
+
+    if json {
+        return print_json(&find_theme(&imported)?);
+    }
+
+    println!("{}", imported);
+    Ok(())
+}
+
+fn install(source: &str, name: Option<&str>, json: bool) -> Result<(), String> {
+    let preview = prepare_theme_install(source, name)?;
+    let installed = confirm_theme_install(&preview).inspect_err(|_| {
+        cancel_theme_install(&preview);
+    })?;
+
+    if json {
+        return print_json(&find_theme(&installed)?);
+    }
+
+    println!("{}", installed);
+    Ok(())
+}
+
+fn duplicate(name: &str, json: bool) -> Result<(), String> {
+    let entry = find_theme(name)?;
+    if entry.origin.is_editable() {
+        return Err(format!("Theme '{}' is already editable", name));
+    }
+
+    let duplicated = duplicate_as_editable(name, entry.origin == ThemeOrigin::System)?;
+
+    if json {
+        return print_json(&find_theme(&duplicated)?);
+    }
+
+    println!("{}", duplicated);
+    Ok(())
+}
+
+fn show(name: &str, json: bool) -> Result<(), String> {
+    let entry = find_theme(name)?;
+    let is_system = entry.origin == ThemeOrigin::System;
+    let path = get_theme_path(name, is_system)
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+    let data = if entry.origin.is_editable() {
+        Some(load_theme_for_editing(name)?)
+    } else {
+        None
+    };
+
+    let details = ThemeDetails { entry, path, data };
+
+    if json {
+        return print_json(&details);
+    }
+
+    println!("Name:   {}", details.entry.dir);
+    println!("Title:  {}", details.entry.title);
+    println!("Origin: {}", details.entry.origin.badge_text());
+    println!("Path:   {}", details.path);
+    if let Some(ref colors) = details.entry.colors {
+        println!("Background: {}", colors.primary.background);
+        println!("Foreground: {}", colors.primary.foreground);
+    }
+    if let Some(ref data) = details.data {
+        println!("Accent: {}", data.colors.accent);
+        println!(
+            "Mode:   {}",
+            if data.is_light_theme { "light" } else { "dark" }
+        );
+    }
+
+    Ok(())
+}
@@ -89,6 +89,11 @@ fn main() {
     // Parse CLI arguments before starting the application
     let cli_args = CliArgs::parse_args();
 
+    // Headless subcommands run without opening a window
+    if let Some(command) = cli_args.command.clone() {
+        std::process::exit(omarchist::cli::run_command(command));
+    }
+
     let app = Application::new().with_assets(CombinedAssets::new());
 
     app.run(move |cx| {
@@ -222,6 +227,12 @@ fn main() {
                 Some("ThemeEditPage"),
             ),
             KeyBinding::new("escape", app_menu::NavigateBack, Some("ThemeEditPage")),
+            KeyBinding::new("ctrl-z", app_menu::ThemeEditUndo, Some("ThemeEditPage")),
+            KeyBinding::new(
+                "ctrl-shift-z",
+                app_menu::ThemeEditRedo,
+                Some("ThemeEditPage"),
+            ),
             // Settings page keyboard navigation
             KeyBinding::new("tab", app_menu::NextFocus, Some("SettingsPage")),
             KeyBinding::new("shift-tab", app_menu::PrevFocus, Some("SettingsPage")),
@@ -1,3 +1,4 @@
+pub mod git_sh_commands;
 pub mod omarchy_sh_commands;
 pub mod theme_sh_commands;
 pub mod waybar_sh_commands;
@@ -0,0 +1,106 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+// Shallow-clone `url` into `dest`. Works with remote URLs as well as local (bare) repositories.
+pub fn git_clone(url: &str, dest: &Path) -> Result<(), String> {
+    git_clone_with_cmd("git", url, dest)
+}
+
+fn git_clone_with_cmd(cmd: &str, url: &str, dest: &Path) -> Result<(), String> {
+    let output = Command::new(cmd)
+        .args(["clone", "--depth", "1", "--quiet", url])
+        .arg(dest)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to execute {cmd}: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to clone '{url}': {}", stderr.trim()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("git should be installed to run these tests");
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    /// Cloning a local bare repository must work without any network access.
+    #[test]
+    fn git_clone_local_bare_repo_returns_ok() {
+        let root = std::env::temp_dir().join(format!("omarchist-git-clone-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let work = root.join("work");
+        let bare = root.join("theme.git");
+        let dest = root.join("clone");
+        fs::create_dir_all(&work).unwrap();
+
+        fs::write(work.join("colors.toml"), "background = \"#000000\"\n").unwrap();
+        git(&work, &["init", "--quiet"]);
+        git(&work, &["add", "."]);
+        git(
+            &work,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "init",
+            ],
+        );
+        git(&root, &["clone", "--bare", "--quiet", "work", "theme.git"]);
+
+        let result = git_clone(bare.to_str().unwrap(), &dest);
+        assert!(
+            result.is_ok(),
+            "expected Ok cloning a bare repo, got {:?}",
+            result
+        );
+        assert!(dest.join("colors.toml").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    /// A path that is not a repository should resolve to Err mentioning the URL.
+    #[test]
+    fn git_clone_missing_repo_returns_err_with_url() {
+        let dest = std::env::temp_dir().join("omarchist-git-clone-missing");
+        let result = git_clone("/__omarchist_nonexistent_repo__", &dest);
+        assert!(result.is_err(), "expected Err for a missing repo, got Ok");
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("__omarchist_nonexistent_repo__"),
+            "error message should contain the url, got: {msg}"
+        );
+    }
+
+    /// A missing git binary should resolve to Err containing a descriptive message.
+    #[test]
+    fn git_clone_missing_binary_returns_err() {
+        let dest = std::env::temp_dir().join("omarchist-git-clone-nobin");
+        let result = git_clone_with_cmd("__omarchist_nonexistent_binary__", "any", &dest);
+        assert!(result.is_err(), "expected Err for missing binary, got Ok");
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("Failed to execute"),
+            "error message should mention 'Failed to execute', got: {msg}"
+        );
+    }
+}
@@ -1,7 +1,14 @@
 use smol::unblock;
 use std::process::{Command, Stdio};
 
+use crate::system::themes::theme_management::vscode::link_vscode_extension;
+
 pub async fn apply_theme(dir: String) -> Result<(), String> {
+    // Omarchy sets the VS Code theme by extension id, a generated one has to
+    // be in place before it looks
+    let name = dir.clone();
+    unblock(move || link_vscode_extension(&name)).await?;
+
     apply_theme_with_cmd("omarchy-theme-set", dir).await
 }
 
@@ -17,6 +17,14 @@ pub struct SettingsConfig {
     pub font_size: String,
     #[serde(default)]
     pub auto_apply_theme: bool,
+    #[serde(default)]
+    pub theme_snapshots: bool,
+    #[serde(default = "default_theme_snapshot_limit")]
+    pub theme_snapshot_limit: usize,
+}
+
+fn default_theme_snapshot_limit() -> usize {
+    20
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,7 +1,23 @@
+pub mod devices;
+pub mod document;
 pub mod hyprctl_reader;
+pub mod keybind_parser;
+pub mod keybind_writer;
+pub mod keybinds;
 pub mod keyboard;
 pub mod manager;
+pub mod monitor_rules;
+pub mod monitors;
 pub mod parser;
+pub mod profiles;
+pub mod rule_parser;
+pub mod rule_writer;
+pub mod rules;
+pub mod validation;
 pub mod writer;
 
+pub use keybinds::{KeybindManager, find_conflicts};
 pub use manager::{HyprlandConfigManager, config_exists, delete_config};
+pub use monitors::MonitorManager;
+pub use profiles::HyprlandProfiles;
+pub use rules::RuleManager;
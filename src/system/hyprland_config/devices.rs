@@ -0,0 +1,94 @@
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::types::hyprland_config::{InputDevice, InputDeviceKind};
+
+/// The JSON shape returned by `hyprctl devices -j`. Switches such as the lid
+/// take no input settings and are left out.
+#[derive(Debug, Default, Deserialize)]
+struct HyprctlDevices {
+    #[serde(default)]
+    mice: Vec<HyprctlDevice>,
+    #[serde(default)]
+    keyboards: Vec<HyprctlDevice>,
+    #[serde(default)]
+    tablets: Vec<HyprctlDevice>,
+    #[serde(default)]
+    touch: Vec<HyprctlDevice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HyprctlDevice {
+    #[serde(default)]
+    name: String,
+}
+
+/// Parse the output of `hyprctl devices -j`. A device that shows up in more
+/// than one list, such as a mouse with extra buttons that also registers as
+/// a keyboard, is listed once under its first kind.
+pub fn parse_devices_json(json: &str) -> Result<Vec<InputDevice>, String> {
+    let devices: HyprctlDevices =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse devices: {}", e))?;
+
+    let mut result: Vec<InputDevice> = Vec::new();
+    for (kind, list) in [
+        (InputDeviceKind::Mouse, devices.mice),
+        (InputDeviceKind::Keyboard, devices.keyboards),
+        (InputDeviceKind::Tablet, devices.tablets),
+        (InputDeviceKind::Touch, devices.touch),
+    ] {
+        for device in list {
+            if device.name.is_empty() || result.iter().any(|d| d.name == device.name) {
+                continue;
+            }
+            result.push(InputDevice {
+                name: device.name,
+                kind,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Run `hyprctl devices -j` to list the connected input devices.
+pub fn read_devices() -> Result<Vec<InputDevice>, String> {
+    let output = Command::new("hyprctl")
+        .args(["devices", "-j"])
+        .output()
+        .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
+
+    if !output.status.success() {
+        return Err("hyprctl could not list devices. Is Hyprland running?".to_string());
+    }
+
+    parse_devices_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICES: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/hyprctl/devices.json"
+    ));
+
+    #[test]
+    fn parse_devices_json_lists_each_device_once() {
+        let devices = parse_devices_json(DEVICES).expect("fixture should parse");
+        let names: Vec<(&str, InputDeviceKind)> =
+            devices.iter().map(|d| (d.name.as_str(), d.kind)).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("logitech-g502-hero-gaming-mouse", InputDeviceKind::Mouse),
+                ("elan0676:00-04f3:3195-touchpad", InputDeviceKind::Mouse),
+                ("at-translated-set-2-keyboard", InputDeviceKind::Keyboard),
+                ("elan9008:00-04f3:2bb3", InputDeviceKind::Touch),
+            ]
+        );
+    }
+}
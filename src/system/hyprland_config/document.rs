@@ -0,0 +1,637 @@
+use std::ops::Range;
+
+// Keywords that can appear many times, told apart by their first field
+const NAMED_KEYWORDS: [&str; 2] = ["bezier", "animation"];
+
+// Named keywords are written into this section when the file has none yet
+const KEYWORD_SECTION: &str = "animations";
+
+// Blocks that can appear many times, told apart by their `name = ...` line
+const DEVICE_BLOCK: &str = "device";
+
+const INDENT: &str = "    ";
+
+/// A Hyprland config file kept line by line, so values can be changed without
+/// touching comments, `source =` lines, `$variables` or keys Omarchist does
+/// not know about.
+///
+/// Settings are addressed by an id made of their section path and key, such
+/// as `general:snap:enabled`, whether the file nests blocks or uses the
+/// `general:snap {` form. `bezier` and `animation` lines are addressed by
+/// their name instead, as `bezier:easeOut` or `animation:windows`, and the
+/// settings of a `device` block by the device's name, as
+/// `device:logitech-mx-master:sensitivity`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HyprlandDocument {
+    lines: Vec<String>,
+}
+
+#[derive(Debug)]
+enum LineKind {
+    Setting { id: String, value: Range<usize> },
+    Open,
+    Close,
+    Other,
+}
+
+#[derive(Debug)]
+struct Block {
+    path: Vec<String>,
+    depth: usize,
+    open: usize,
+    close: usize,
+}
+
+struct Scan {
+    kinds: Vec<LineKind>,
+    blocks: Vec<Block>,
+}
+
+impl HyprlandDocument {
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = self.lines.join("\n");
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.trim().is_empty())
+    }
+
+    /// The value of the last line with this id, the one Hyprland ends up using.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        let scan = self.scan();
+        scan.kinds
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, kind)| match kind {
+                LineKind::Setting { id: line_id, value } if line_id == id => {
+                    Some(&self.lines[index][value.clone()])
+                }
+                _ => None,
+            })
+    }
+
+    /// Every setting in file order as `(id, value)`.
+    pub fn settings(&self) -> Vec<(String, String)> {
+        let scan = self.scan();
+        scan.kinds
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, kind)| match kind {
+                LineKind::Setting { id, value } => Some((id, self.lines[index][value].to_string())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Set a value in place, keeping the line's indentation and trailing
+    /// comment. Settings not in the file yet are added to their section,
+    /// which is created if needed.
+    pub fn set(&mut self, id: &str, value: &str) {
+        let scan = self.scan();
+
+        let existing = scan
+            .kinds
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, kind)| match kind {
+                LineKind::Setting { id: line_id, value } if line_id == id => {
+                    Some((index, value.clone()))
+                }
+                _ => None,
+            });
+
+        if let Some((index, range)) = existing {
+            let line = &mut self.lines[index];
+            if line[range.clone()] != *value {
+                line.replace_range(range, value);
+            }
+            return;
+        }
+
+        match named_keyword(id) {
+            Some((keyword, _)) => self.insert_keyword(&scan, keyword, value),
+            None => {
+                let (path, key) = split_id(id);
+                self.insert_setting(&scan, &path, key, value);
+            }
+        }
+    }
+
+    /// Remove every line with this id. Blocks left empty are removed too.
+    pub fn remove(&mut self, id: &str) {
+        loop {
+            let scan = self.scan();
+            let Some(index) = scan.kinds.iter().position(
+                |kind| matches!(kind, LineKind::Setting { id: line_id, .. } if line_id == id),
+            ) else {
+                return;
+            };
+
+            self.lines.remove(index);
+            self.remove_empty_block_around(index);
+        }
+    }
+
+    /// Write the settings that differ between `before` and `after`, and drop
+    /// the ones `after` no longer has. Settings that did not change are not
+    /// touched, whether or not the file has a line for them.
+    pub fn apply_changes(&mut self, before: &[(String, String)], after: &[(String, String)]) {
+        for (id, _) in before {
+            if !after.iter().any(|(after_id, _)| after_id == id) {
+                self.remove(id);
+            }
+        }
+
+        for (id, value) in after {
+            if !before.contains(&(id.clone(), value.clone())) {
+                self.set(id, value);
+            }
+        }
+    }
+
+    fn insert_keyword(&mut self, scan: &Scan, keyword: &str, value: &str) {
+        let last_of = |wanted: &str| {
+            scan.kinds.iter().rposition(|kind| {
+                matches!(kind, LineKind::Setting { id, .. }
+                    if named_keyword(id).is_some_and(|(k, _)| k == wanted))
+            })
+        };
+        let first_animation = scan.kinds.iter().position(|kind| {
+            matches!(kind, LineKind::Setting { id, .. }
+                if named_keyword(id).is_some_and(|(k, _)| k == "animation"))
+        });
+
+        // Curves have to be defined before the animations using them. The new
+        // line copies the indentation of the line it is placed next to.
+        let anchor = match keyword {
+            "bezier" => last_of("bezier")
+                .map(|index| (index + 1, index))
+                .or(first_animation.map(|index| (index, index))),
+            _ => last_of(keyword).map(|index| (index + 1, index)),
+        };
+
+        match anchor {
+            Some((at, reference)) => {
+                let indent = leading_whitespace(&self.lines[reference]).to_string();
+                self.lines
+                    .insert(at, format!("{}{} = {}", indent, keyword, value));
+            }
+            None => self.insert_setting(scan, &[KEYWORD_SECTION], keyword, value),
+        }
+    }
+
+    fn insert_setting(&mut self, scan: &Scan, path: &[&str], key: &str, value: &str) {
+        // The deepest block that is a prefix of the path, the last one if the
+        // file opens the same section more than once
+        let parent = scan
+            .blocks
+            .iter()
+            .filter(|block| {
+                block.path.len() <= path.len() && block.path.iter().zip(path).all(|(a, b)| a == b)
+            })
+            .max_by_key(|block| (block.path.len(), block.open));
+
+        match parent {
+            Some(block) => {
+                let lines = block_lines(block.depth + 1, &path[block.path.len()..], key, value);
+                let at = block.close;
+                self.lines.splice(at..at, lines);
+            }
+            None => {
+                if self
+                    .lines
+                    .last()
+                    .is_some_and(|line| !line.trim().is_empty())
+                {
+                    self.lines.push(String::new());
+                }
+                self.lines.extend(block_lines(0, path, key, value));
+            }
+        }
+    }
+
+    // After removing the line at `index`, drop the block it was in if nothing
+    // but blank lines is left, then check that block's parent the same way
+    fn remove_empty_block_around(&mut self, mut index: usize) {
+        loop {
+            let scan = self.scan();
+            let Some(block) = scan
+                .blocks
+                .iter()
+                .filter(|block| block.open < index && index <= block.close)
+                .max_by_key(|block| block.depth)
+            else {
+                return;
+            };
+
+            // A device block holding only its name sets nothing
+            let name_id = format!("{}:name", block.path.join(":"));
+            let empty = (block.open + 1..block.close).all(|line| {
+                self.lines[line].trim().is_empty()
+                    || (block.path.first().is_some_and(|s| s == DEVICE_BLOCK)
+                        && matches!(&scan.kinds[line], LineKind::Setting { id, .. } if *id == name_id))
+            });
+            if !empty {
+                return;
+            }
+
+            let (open, close) = (block.open, block.close);
+            self.lines.drain(open..=close);
+
+            // Don't leave a stray blank line where the block was
+            if open > 0
+                && self.lines[open - 1].trim().is_empty()
+                && self
+                    .lines
+                    .get(open)
+                    .is_none_or(|line| matches!(line.trim(), "" | "}"))
+            {
+                self.lines.remove(open - 1);
+                index = open - 1;
+            } else {
+                index = open;
+            }
+        }
+    }
+
+    fn scan(&self) -> Scan {
+        let mut kinds = Vec::with_capacity(self.lines.len());
+        let mut blocks: Vec<Block> = Vec::new();
+        // Indices into `blocks` of the blocks that are currently open
+        let mut open: Vec<usize> = Vec::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let code = strip_comment(line).trim();
+            let path: Vec<String> = open
+                .last()
+                .map(|&block| blocks[block].path.clone())
+                .unwrap_or_default();
+
+            if let Some(header) = code.strip_suffix('{') {
+                let mut block_path = path;
+                match header.trim().split_once(':') {
+                    // `device:name {`, where the name may contain colons
+                    Some((DEVICE_BLOCK, name)) if block_path.is_empty() => {
+                        block_path.extend([DEVICE_BLOCK.to_string(), name.trim().to_string()]);
+                    }
+                    _ => {
+                        block_path.extend(header.split(':').map(|part| part.trim().to_string()));
+                        if block_path == [DEVICE_BLOCK] {
+                            block_path.push(device_name(&self.lines[index + 1..]));
+                        }
+                    }
+                }
+                open.push(blocks.len());
+                blocks.push(Block {
+                    path: block_path,
+                    depth: open.len() - 1,
+                    open: index,
+                    close: self.lines.len(),
+                });
+                kinds.push(LineKind::Open);
+            } else if code == "}" {
+                if let Some(block) = open.pop() {
+                    blocks[block].close = index;
+                }
+                kinds.push(LineKind::Close);
+            } else if let Some(eq) = code.find('=') {
+                let key = code[..eq].trim();
+                let value = code[eq + 1..].trim();
+
+                // Byte range of the value within the untrimmed line
+                let code_start = line.len() - line.trim_start().len();
+                let value_start = code_start
+                    + eq
+                    + 1
+                    + (code[eq + 1..].len() - code[eq + 1..].trim_start().len());
+                let value_range = value_start..value_start + value.len();
+
+                let id = if NAMED_KEYWORDS.contains(&key) {
+                    let name = value.split(',').next().unwrap_or_default().trim();
+                    format!("{}:{}", key, name)
+                } else if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}:{}", path.join(":"), key)
+                };
+
+                kinds.push(LineKind::Setting {
+                    id,
+                    value: value_range,
+                });
+            } else {
+                kinds.push(LineKind::Other);
+            }
+        }
+
+        Scan { kinds, blocks }
+    }
+}
+
+fn named_keyword(id: &str) -> Option<(&str, &str)> {
+    id.split_once(':')
+        .filter(|(keyword, _)| NAMED_KEYWORDS.contains(keyword))
+}
+
+// The section path and key of an id. Device names can contain colons, keys
+// never do.
+fn split_id(id: &str) -> (Vec<&str>, &str) {
+    let (prefix, key) = id.rsplit_once(':').unwrap_or(("", id));
+    let path = match prefix.split_once(':') {
+        Some((DEVICE_BLOCK, name)) => vec![DEVICE_BLOCK, name],
+        _ if prefix.is_empty() => Vec::new(),
+        _ => prefix.split(':').collect(),
+    };
+    (path, key)
+}
+
+// The value of the `name = ...` line of the block starting at `lines`
+fn device_name(lines: &[String]) -> String {
+    let mut depth = 0;
+    for line in lines {
+        let code = strip_comment(line).trim();
+        if code.ends_with('{') {
+            depth += 1;
+        } else if code == "}" {
+            if depth == 0 {
+                break;
+            }
+            depth -= 1;
+        } else if depth == 0
+            && let Some((key, value)) = code.split_once('=')
+            && key.trim() == "name"
+        {
+            return value.trim().to_string();
+        }
+    }
+    String::new()
+}
+
+fn block_lines(depth: usize, sections: &[&str], key: &str, value: &str) -> Vec<String> {
+    // A device's block names the device inside it
+    if let [DEVICE_BLOCK, name, rest @ ..] = sections {
+        let mut lines = vec![
+            format!("{}{} {{", INDENT.repeat(depth), DEVICE_BLOCK),
+            format!("{}name = {}", INDENT.repeat(depth + 1), name),
+        ];
+        lines.extend(block_lines(depth + 1, rest, key, value));
+        lines.push(format!("{}}}", INDENT.repeat(depth)));
+        return lines;
+    }
+
+    let mut lines = Vec::new();
+    for (level, section) in sections.iter().enumerate() {
+        lines.push(format!("{}{} {{", INDENT.repeat(depth + level), section));
+    }
+    lines.push(format!(
+        "{}{} = {}",
+        INDENT.repeat(depth + sections.len()),
+        key,
+        value
+    ));
+    for level in (0..sections.len()).rev() {
+        lines.push(format!("{}}}", INDENT.repeat(depth + level)));
+    }
+    lines
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+// `#` starts a comment, `##` is an escaped literal `#`
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'#' {
+            if bytes.get(index + 1) == Some(&b'#') {
+                index += 2;
+                continue;
+            }
+            return &line[..index];
+        }
+        index += 1;
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_FILE: &str = "\
+# My tweaks
+source = ~/.config/hypr/extra.conf
+$terminal = alacritty
+
+general {
+    gaps_in = 3 # tighter than Omarchy
+    some_future_option = yes
+
+    snap {
+        enabled = true
+    }
+}
+
+decoration:blur {
+    size = 4
+}
+
+bezier = quick, 0.15, 0, 0.1, 1
+animation = windows, 1, 4, quick
+";
+
+    #[test]
+    fn parse_and_render_round_trip_unchanged() {
+        let document = HyprlandDocument::parse(USER_FILE);
+        assert_eq!(document.render(), USER_FILE);
+    }
+
+    #[test]
+    fn settings_are_addressed_by_section_path() {
+        let document = HyprlandDocument::parse(USER_FILE);
+        assert_eq!(document.get("general:gaps_in"), Some("3"));
+        assert_eq!(document.get("general:snap:enabled"), Some("true"));
+        assert_eq!(document.get("decoration:blur:size"), Some("4"));
+        assert_eq!(document.get("$terminal"), Some("alacritty"));
+        assert_eq!(document.get("bezier:quick"), Some("quick, 0.15, 0, 0.1, 1"));
+        assert_eq!(
+            document.get("animation:windows"),
+            Some("windows, 1, 4, quick")
+        );
+        assert_eq!(document.get("general:border_size"), None);
+    }
+
+    #[test]
+    fn set_keeps_indentation_and_trailing_comment() {
+        let mut document = HyprlandDocument::parse(USER_FILE);
+        document.set("general:gaps_in", "8");
+
+        let rendered = document.render();
+        assert!(rendered.contains("    gaps_in = 8 # tighter than Omarchy\n"));
+        assert!(rendered.contains("some_future_option = yes"));
+        assert!(rendered.contains("source = ~/.config/hypr/extra.conf"));
+        assert!(rendered.starts_with("# My tweaks\n"));
+    }
+
+    #[test]
+    fn set_inserts_into_existing_and_new_sections() {
+        let mut document = HyprlandDocument::parse(USER_FILE);
+        document.set("general:border_size", "2");
+        document.set("decoration:blur:passes", "3");
+        document.set("general:snap:window_gap", "12");
+        document.set("decoration:shadow:range", "6");
+        document.set("misc:vfr", "true");
+
+        let expected = "\
+# My tweaks
+source = ~/.config/hypr/extra.conf
+$terminal = alacritty
+
+general {
+    gaps_in = 3 # tighter than Omarchy
+    some_future_option = yes
+
+    snap {
+        enabled = true
+        window_gap = 12
+    }
+    border_size = 2
+}
+
+decoration:blur {
+    size = 4
+    passes = 3
+}
+
+bezier = quick, 0.15, 0, 0.1, 1
+animation = windows, 1, 4, quick
+
+decoration {
+    shadow {
+        range = 6
+    }
+}
+
+misc {
+    vfr = true
+}
+";
+        assert_eq!(document.render(), expected);
+    }
+
+    #[test]
+    fn set_keeps_curves_ahead_of_animations() {
+        let mut document = HyprlandDocument::parse(USER_FILE);
+        document.set("animation:fade", "fade, 1, 3, slow");
+        document.set("bezier:slow", "slow, 0.5, 0, 0.5, 1");
+
+        let lines: Vec<(String, String)> = document.settings();
+        let ids: Vec<&str> = lines.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(
+            &ids[ids.len() - 4..],
+            [
+                "bezier:quick",
+                "bezier:slow",
+                "animation:windows",
+                "animation:fade"
+            ]
+        );
+
+        let mut empty = HyprlandDocument::default();
+        empty.set("bezier:slow", "slow, 0.5, 0, 0.5, 1");
+        assert_eq!(
+            empty.render(),
+            "animations {\n    bezier = slow, 0.5, 0, 0.5, 1\n}\n"
+        );
+    }
+
+    #[test]
+    fn remove_drops_lines_and_blocks_left_empty() {
+        let mut document = HyprlandDocument::parse(USER_FILE);
+        document.remove("decoration:blur:size");
+        document.remove("general:snap:enabled");
+
+        let rendered = document.render();
+        assert!(!rendered.contains("decoration:blur"));
+        assert!(!rendered.contains("snap {"));
+        assert!(rendered.contains("some_future_option = yes\n}\n\nbezier = quick"));
+    }
+
+    #[test]
+    fn apply_changes_only_writes_what_changed() {
+        let settings = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(id, value)| (id.to_string(), value.to_string()))
+                .collect()
+        };
+        let before = settings(&[
+            ("general:gaps_in", "3"),
+            ("general:gaps_out", "10"),
+            ("bezier:quick", "quick, 0.15, 0, 0.1, 1"),
+        ]);
+        let after = settings(&[("general:gaps_in", "6"), ("general:gaps_out", "10")]);
+
+        let mut document = HyprlandDocument::parse(USER_FILE);
+        document.apply_changes(&before, &after);
+
+        assert_eq!(document.get("general:gaps_in"), Some("6"));
+        assert_eq!(document.get("bezier:quick"), None);
+        // Unchanged values are not copied into the file
+        assert_eq!(document.get("general:gaps_out"), None);
+        // Values the typed config knows nothing about stay as they were
+        assert_eq!(document.get("general:some_future_option"), Some("yes"));
+        assert_eq!(document.get("decoration:blur:size"), Some("4"));
+    }
+
+    #[test]
+    fn device_blocks_are_addressed_by_device_name() {
+        let mut document = HyprlandDocument::parse(
+            "device {\n    name = logitech-mouse\n    sensitivity = -0.5\n}\n",
+        );
+        assert_eq!(
+            document.get("device:logitech-mouse:sensitivity"),
+            Some("-0.5")
+        );
+
+        document.set("device:logitech-mouse:left_handed", "true");
+        document.set(
+            "device:elan0676:00-04f3:3195-touchpad:natural_scroll",
+            "true",
+        );
+        assert_eq!(
+            document.render(),
+            "device {\n    name = logitech-mouse\n    sensitivity = -0.5\n    left_handed = true\n}\n\ndevice {\n    name = elan0676:00-04f3:3195-touchpad\n    natural_scroll = true\n}\n"
+        );
+        assert_eq!(
+            document.get("device:elan0676:00-04f3:3195-touchpad:natural_scroll"),
+            Some("true")
+        );
+
+        document.remove("device:elan0676:00-04f3:3195-touchpad:natural_scroll");
+        assert_eq!(
+            document.render(),
+            "device {\n    name = logitech-mouse\n    sensitivity = -0.5\n    left_handed = true\n}\n"
+        );
+    }
+
+    #[test]
+    fn escaped_hash_is_not_a_comment() {
+        let document = HyprlandDocument::parse("misc {\n    font_family = C##\n}\n");
+        assert_eq!(document.get("misc:font_family"), Some("C##"));
+    }
+}
@@ -31,6 +31,92 @@ fn get_option(key: &str) -> Option<HyprctlOption> {
     serde_json::from_slice(&output.stdout).ok()
 }
 
+/// One animation as returned by `hyprctl animations -j`.
+#[derive(Debug, Deserialize)]
+struct HyprctlAnimation {
+    name: String,
+    #[serde(default)]
+    overridden: bool,
+    #[serde(default)]
+    bezier: String,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    speed: f64,
+    #[serde(default)]
+    style: String,
+}
+
+/// One curve as returned by `hyprctl animations -j`.
+#[derive(Debug, Deserialize)]
+struct HyprctlBezier {
+    name: String,
+    #[serde(rename = "X0")]
+    x0: f64,
+    #[serde(rename = "Y0")]
+    y0: f64,
+    #[serde(rename = "X1")]
+    x1: f64,
+    #[serde(rename = "Y1")]
+    y1: f64,
+}
+
+// Curves Hyprland defines itself
+const BUILTIN_BEZIERS: [&str; 2] = ["default", "linear"];
+
+/// Parse the output of `hyprctl animations -j`, a pair of lists holding the
+/// animation tree and the curves.
+///
+/// Animations that are not `overridden` only inherit from their parent, so
+/// they are left out to keep the tree inheriting.
+pub fn parse_animations_json(json: &str) -> Result<(Vec<BezierCurve>, Vec<Animation>), String> {
+    let (animations, beziers): (Vec<HyprctlAnimation>, Vec<HyprctlBezier>) =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse animations: {}", e))?;
+
+    let beziers = beziers
+        .into_iter()
+        .filter(|b| !BUILTIN_BEZIERS.contains(&b.name.as_str()) && !b.name.starts_with("__"))
+        .map(|b| BezierCurve {
+            name: b.name,
+            x1: b.x0,
+            y1: b.y0,
+            x2: b.x1,
+            y2: b.y1,
+        })
+        .collect();
+
+    let animations = animations
+        .into_iter()
+        .filter(|a| a.overridden)
+        .map(|a| {
+            let off_only = !a.enabled && a.bezier.is_empty();
+            Animation {
+                name: a.name,
+                enabled: a.enabled,
+                speed: if off_only { 0.0 } else { a.speed },
+                curve: a.bezier,
+                style: (!a.style.is_empty()).then_some(a.style),
+            }
+        })
+        .collect();
+
+    Ok((beziers, animations))
+}
+
+/// Run `hyprctl animations -j` and parse the result.
+fn read_animations() -> Option<(Vec<BezierCurve>, Vec<Animation>)> {
+    let output = Command::new("hyprctl")
+        .args(["animations", "-j"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_animations_json(&String::from_utf8_lossy(&output.stdout)).ok()
+}
+
 fn opt_int(key: &str) -> Option<i64> {
     get_option(key)?.int
 }
@@ -234,6 +320,10 @@ pub fn read_from_hyprctl() -> HyprlandConfig {
     if let Some(v) = opt_bool("animations:workspace_wraparound") {
         cfg.animations.workspace_wraparound = v;
     }
+    if let Some((beziers, animations)) = read_animations() {
+        cfg.animations.beziers = beziers;
+        cfg.animations.animations = animations;
+    }
 
     // ── input ────────────────────────────────────────────────────────────────
     if let Some(v) = opt_str("input:kb_model") {
@@ -573,3 +663,40 @@ pub fn read_from_hyprctl() -> HyprlandConfig {
 
     cfg
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANIMATIONS: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/hyprctl/animations.json"
+    ));
+
+    #[test]
+    fn parse_animations_json_reads_custom_curves() {
+        let (beziers, _) = parse_animations_json(ANIMATIONS).expect("fixture should parse");
+        let names: Vec<&str> = beziers.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["easeOutQuint", "almostLinear"],
+            "built-in curves should be skipped"
+        );
+        assert!((beziers[0].x1 - 0.23).abs() < f64::EPSILON);
+        assert!((beziers[0].y2 - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn parse_animations_json_keeps_only_overridden_animations() {
+        let (_, animations) = parse_animations_json(ANIMATIONS).unwrap();
+        let names: Vec<&str> = animations.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["global", "windows", "windowsIn", "layersIn"]);
+        assert_eq!(animations[2].style.as_deref(), Some("popin 87%"));
+        assert!(!animations[3].enabled && animations[3].curve.is_empty());
+    }
+
+    #[test]
+    fn parse_animations_json_rejects_other_shapes() {
+        assert!(parse_animations_json("[]").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::types::hyprland_config::*;
+
+// Canonical modifier order, so `SHIFT SUPER` and `SUPER_SHIFT` compare equal
+const MOD_ORDER: [&str; 8] = [
+    "SUPER", "CTRL", "ALT", "SHIFT", "CAPS", "MOD2", "MOD3", "MOD5",
+];
+
+/// Parse every global `bind*` line in a Hyprland config file.
+pub fn parse_keybinds(content: &str, source: KeybindSource) -> Vec<Keybind> {
+    let mut binds = Vec::new();
+    let mut in_submap = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // Binds inside a submap only fire while it is active, so they can not
+        // conflict with the global ones and are left alone
+        if let Some((key, value)) = split_assignment(trimmed)
+            && key == "submap"
+        {
+            in_submap = value != "reset";
+            continue;
+        }
+
+        if !in_submap && let Some(bind) = parse_keybind_line(trimmed, source) {
+            binds.push(bind);
+        }
+    }
+
+    binds
+}
+
+/// Parse the file Omarchist writes its keybind overrides to.
+pub fn parse_overrides(content: &str) -> KeybindOverrides {
+    let mut overrides = KeybindOverrides::default();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if let Some(combo) = parse_unbind_line(trimmed) {
+            overrides.unbinds.push(combo);
+        } else if let Some(bind) = parse_keybind_line(trimmed, KeybindSource::Omarchist) {
+            overrides.binds.push(bind);
+        }
+    }
+
+    overrides
+}
+
+/// Parse a single `bind[flags] = MODS, key, [description,] dispatcher, args` line.
+pub fn parse_keybind_line(line: &str, source: KeybindSource) -> Option<Keybind> {
+    let (keyword, value) = split_assignment(line)?;
+    let suffix = keyword.strip_prefix("bind")?;
+
+    let mut flags = KeybindFlags::default();
+    let mut has_description = false;
+    for flag in suffix.chars() {
+        match flag {
+            'l' => flags.locked = true,
+            'r' => flags.release = true,
+            'e' => flags.repeat = true,
+            'n' => flags.non_consuming = true,
+            'm' => flags.mouse = true,
+            't' => flags.transparent = true,
+            'i' => flags.ignore_mods = true,
+            's' => flags.separate = true,
+            'd' => has_description = true,
+            c if c.is_ascii_lowercase() => flags.other.push(c),
+            // Not a bind keyword at all (e.g. `binds:` options)
+            _ => return None,
+        }
+    }
+
+    let field_count = if has_description { 5 } else { 4 };
+    let mut fields: Vec<&str> = value.splitn(field_count, ',').map(str::trim).collect();
+
+    // Mouse binds and some dispatchers have no arguments
+    if fields.len() == field_count - 1 {
+        fields.push("");
+    }
+    if fields.len() != field_count {
+        return None;
+    }
+
+    let description = has_description.then(|| fields.remove(2).to_string());
+
+    Some(Keybind {
+        flags,
+        mods: parse_mods(fields[0]),
+        key: fields[1].to_string(),
+        description,
+        dispatcher: fields[2].to_string(),
+        args: fields[3].to_string(),
+        source,
+    })
+}
+
+/// Parse an `unbind = MODS, key` line.
+pub fn parse_unbind_line(line: &str) -> Option<KeyCombo> {
+    let (keyword, value) = split_assignment(line)?;
+    if keyword != "unbind" {
+        return None;
+    }
+
+    let (mods, key) = value.split_once(',')?;
+    Some(KeyCombo {
+        mods: parse_mods(mods),
+        key: key.trim().to_uppercase(),
+    })
+}
+
+/// Split a modifier string into canonical names. Hyprland accepts spaces,
+/// underscores and aliases such as `CONTROL` or `WIN`.
+pub fn parse_mods(value: &str) -> Vec<String> {
+    let mut mods: Vec<String> = value
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '+')
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            // Variables like `$mainMod` can not be resolved here and are kept verbatim
+            if m.starts_with('$') {
+                return m.to_string();
+            }
+            match m.to_uppercase().as_str() {
+                "CONTROL" => "CTRL".to_string(),
+                "WIN" | "LOGO" | "MOD4" | "META" => "SUPER".to_string(),
+                "MOD1" => "ALT".to_string(),
+                other => other.to_string(),
+            }
+        })
+        .collect();
+
+    mods.sort_by_key(|m| {
+        MOD_ORDER
+            .iter()
+            .position(|known| known == m)
+            .unwrap_or(MOD_ORDER.len())
+    });
+    mods.dedup();
+    mods
+}
+
+fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keybind_line_plain_bind() {
+        let bind = parse_keybind_line("bind = SUPER, Q, killactive,", KeybindSource::User)
+            .expect("plain bind should parse");
+        assert_eq!(bind.mods, vec!["SUPER"]);
+        assert_eq!(bind.key, "Q");
+        assert_eq!(bind.dispatcher, "killactive");
+        assert_eq!(bind.args, "");
+        assert_eq!(bind.description, None);
+    }
+
+    #[test]
+    fn parse_keybind_line_description_and_flags() {
+        let line = "bindeld = , XF86AudioRaiseVolume, Volume up, exec, wpctl set-volume -l 1 @DEFAULT_AUDIO_SINK@ 5%+";
+        let bind = parse_keybind_line(line, KeybindSource::Omarchy).unwrap();
+        assert!(bind.flags.repeat && bind.flags.locked);
+        assert!(bind.mods.is_empty());
+        assert_eq!(bind.description.as_deref(), Some("Volume up"));
+        assert_eq!(bind.dispatcher, "exec");
+        assert_eq!(bind.args, "wpctl set-volume -l 1 @DEFAULT_AUDIO_SINK@ 5%+");
+    }
+
+    #[test]
+    fn parse_keybind_line_keeps_commas_in_args() {
+        let bind = parse_keybind_line(
+            "bind = SUPER, R, exec, notify-send a,b",
+            KeybindSource::User,
+        )
+        .unwrap();
+        assert_eq!(bind.args, "notify-send a,b");
+    }
+
+    #[test]
+    fn parse_keybind_line_mouse_bind_without_args() {
+        let bind = parse_keybind_line("bindm = SUPER, mouse:272, movewindow", KeybindSource::User)
+            .unwrap();
+        assert!(bind.flags.mouse);
+        assert_eq!(bind.dispatcher, "movewindow");
+    }
+
+    #[test]
+    fn parse_keybind_line_keeps_unknown_flags() {
+        let bind = parse_keybind_line("bindot = SUPER, K, exec, kitty", KeybindSource::User)
+            .expect("binds with newer flags should parse");
+        assert!(bind.flags.transparent);
+        assert_eq!(bind.flags.other, "o");
+        assert!(
+            parse_keybind_line("bind_x = SUPER, K, exec, kitty", KeybindSource::User).is_none()
+        );
+    }
+
+    #[test]
+    fn parse_keybind_line_rejects_non_binds() {
+        assert!(parse_keybind_line("binds = true", KeybindSource::User).is_none());
+        assert!(parse_keybind_line("bind = SUPER", KeybindSource::User).is_none());
+        assert!(parse_keybind_line("unbind = SUPER, Q", KeybindSource::User).is_none());
+    }
+
+    #[test]
+    fn parse_mods_normalizes_aliases_and_order() {
+        assert_eq!(parse_mods("SHIFT SUPER"), vec!["SUPER", "SHIFT"]);
+        assert_eq!(parse_mods("super_control"), vec!["SUPER", "CTRL"]);
+        assert_eq!(parse_mods("$mainMod SHIFT"), vec!["SHIFT", "$mainMod"]);
+    }
+
+    #[test]
+    fn parse_keybinds_skips_submaps() {
+        let input = "bind = SUPER, R, submap, resize\nsubmap = resize\nbinde = , right, resizeactive, 10 0\nsubmap = reset\nbind = SUPER, Q, killactive,";
+        let binds = parse_keybinds(input, KeybindSource::User);
+        assert_eq!(binds.len(), 2);
+        assert_eq!(binds[1].key, "Q");
+    }
+
+    #[test]
+    fn parse_overrides_reads_unbinds_and_binds() {
+        let input = "# header\nunbind = SUPER, RETURN\nbind = SUPER, RETURN, exec, kitty";
+        let overrides = parse_overrides(input);
+        assert_eq!(overrides.unbinds.len(), 1);
+        assert_eq!(overrides.binds.len(), 1);
+        assert_eq!(overrides.binds[0].source, KeybindSource::Omarchist);
+    }
+}
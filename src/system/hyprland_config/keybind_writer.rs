@@ -0,0 +1,117 @@
+use crate::types::hyprland_config::*;
+use std::fmt::Write;
+
+pub fn write_overrides(overrides: &KeybindOverrides) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "# Omarchist Hyprland Keybindings\n# Generated automatically - Do not edit manually\n"
+    )
+    .unwrap();
+
+    // Unbinds must come first so a replaced combo is free again for the new bind
+    for combo in &overrides.unbinds {
+        writeln!(output, "unbind = {}, {}", combo.mods.join(" "), combo.key).unwrap();
+    }
+
+    if !overrides.unbinds.is_empty() && !overrides.binds.is_empty() {
+        writeln!(output).unwrap();
+    }
+
+    for bind in &overrides.binds {
+        writeln!(output, "{}", write_keybind(bind)).unwrap();
+    }
+
+    output
+}
+
+pub fn write_keybind(bind: &Keybind) -> String {
+    let mut fields = vec![bind.mods.join(" "), bind.key.clone()];
+    if let Some(ref description) = bind.description {
+        fields.push(description.clone());
+    }
+    fields.push(bind.dispatcher.clone());
+    fields.push(bind.args.clone());
+
+    format!(
+        "{} = {}",
+        bind_keyword(&bind.flags, bind.description.is_some()),
+        fields.join(", ").trim_end()
+    )
+}
+
+fn bind_keyword(flags: &KeybindFlags, has_description: bool) -> String {
+    let mut keyword = String::from("bind");
+
+    for (enabled, flag) in [
+        (flags.locked, 'l'),
+        (flags.release, 'r'),
+        (flags.repeat, 'e'),
+        (flags.non_consuming, 'n'),
+        (flags.mouse, 'm'),
+        (flags.transparent, 't'),
+        (flags.ignore_mods, 'i'),
+        (flags.separate, 's'),
+    ] {
+        if enabled {
+            keyword.push(flag);
+        }
+    }
+    keyword.push_str(&flags.other);
+    if has_description {
+        keyword.push('d');
+    }
+
+    keyword
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::hyprland_config::keybind_parser::{parse_keybind_line, parse_overrides};
+
+    #[test]
+    fn write_keybind_round_trips() {
+        for line in [
+            "bind = SUPER, Q, killactive,",
+            "bindeld = , XF86AudioRaiseVolume, Volume up, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+",
+            "bindm = SUPER, mouse:272, movewindow",
+            "bindod = SUPER, K, Long press, exec, kitty",
+            "bind = SUPER SHIFT, RETURN, exec, alacritty --class a,b",
+        ] {
+            let bind = parse_keybind_line(line, KeybindSource::User).unwrap();
+            let written = write_keybind(&bind);
+            assert_eq!(
+                parse_keybind_line(&written, KeybindSource::User),
+                Some(bind),
+                "'{}' should survive a round trip, got '{}'",
+                line,
+                written
+            );
+        }
+    }
+
+    #[test]
+    fn write_overrides_puts_unbinds_first() {
+        let overrides = KeybindOverrides {
+            unbinds: vec![KeyCombo {
+                mods: vec!["SUPER".to_string()],
+                key: "RETURN".to_string(),
+            }],
+            binds: vec![
+                parse_keybind_line(
+                    "bind = SUPER, RETURN, exec, kitty",
+                    KeybindSource::Omarchist,
+                )
+                .unwrap(),
+            ],
+        };
+
+        let output = write_overrides(&overrides);
+        let unbind_at = output.find("unbind = SUPER, RETURN").unwrap();
+        let bind_at = output.find("bind = SUPER, RETURN, exec, kitty").unwrap();
+        assert!(unbind_at < bind_at);
+        assert_eq!(parse_overrides(&output), overrides);
+    }
+}
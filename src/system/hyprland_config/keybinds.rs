@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::types::hyprland_config::*;
+
+use super::keybind_parser::{parse_keybinds, parse_overrides};
+use super::keybind_writer::write_overrides;
+use super::manager::{config_dir, ensure_config_dir};
+use super::validation::ConfigWrite;
+
+const KEYBINDS_FILE: &str = "keybinds.conf";
+const HYPR_CONFIG_PATH: &str = ".config/hypr/hyprland.conf";
+const OMARCHY_DIR: &str = ".local/share/omarchy";
+
+pub struct KeybindManager {
+    overrides_path: PathBuf,
+    // Binds from Omarchy's defaults and the user's own files, in source order
+    base: Vec<Keybind>,
+    overrides: KeybindOverrides,
+}
+
+impl KeybindManager {
+    pub fn load() -> Result<Self, String> {
+        ensure_config_dir()?;
+        let overrides_path = config_dir()?.join(KEYBINDS_FILE);
+
+        let overrides = if overrides_path.exists() {
+            let content = fs::read_to_string(&overrides_path)
+                .map_err(|e| format!("Failed to read keybinds file: {}", e))?;
+            parse_overrides(&content)
+        } else {
+            KeybindOverrides::default()
+        };
+
+        Ok(Self {
+            overrides_path,
+            base: read_base_keybinds(),
+            overrides,
+        })
+    }
+
+    /// The write that saves the current state, see `ConfigWrite`.
+    pub fn save_write(&self) -> ConfigWrite {
+        ConfigWrite::new(&self.overrides_path, write_overrides(&self.overrides))
+    }
+
+    /// The binds Hyprland ends up with once the overrides are applied.
+    pub fn keybinds(&self) -> Vec<Keybind> {
+        apply_overrides(&self.base, &self.overrides)
+    }
+
+    pub fn overrides(&self) -> &KeybindOverrides {
+        &self.overrides
+    }
+
+    /// Add a bind, or replace `original` with it. Omarchy and user binds are
+    /// unbound rather than edited in place.
+    pub fn set_keybind(&mut self, original: Option<&Keybind>, keybind: Keybind) {
+        let keybind = Keybind {
+            source: KeybindSource::Omarchist,
+            ..keybind
+        };
+
+        match original {
+            Some(original) if original.source == KeybindSource::Omarchist => {
+                match self.overrides.binds.iter().position(|b| b == original) {
+                    Some(index) => self.overrides.binds[index] = keybind,
+                    None => self.overrides.binds.push(keybind),
+                }
+            }
+            Some(original) => {
+                self.unbind(original.combo());
+                self.overrides.binds.push(keybind);
+            }
+            None => self.overrides.binds.push(keybind),
+        }
+    }
+
+    pub fn remove_keybind(&mut self, keybind: &Keybind) {
+        if keybind.source == KeybindSource::Omarchist {
+            self.overrides.binds.retain(|b| b != keybind);
+        } else {
+            self.unbind(keybind.combo());
+        }
+    }
+
+    /// Drop every override and go back to the Omarchy and user binds.
+    pub fn reset(&mut self) {
+        self.overrides = KeybindOverrides::default();
+    }
+
+    pub fn overrides_path(&self) -> &Path {
+        &self.overrides_path
+    }
+
+    fn unbind(&mut self, combo: KeyCombo) {
+        if !self.overrides.unbinds.contains(&combo) {
+            self.overrides.unbinds.push(combo);
+        }
+    }
+}
+
+pub fn apply_overrides(base: &[Keybind], overrides: &KeybindOverrides) -> Vec<Keybind> {
+    base.iter()
+        .filter(|bind| !overrides.unbinds.contains(&bind.combo()))
+        .chain(overrides.binds.iter())
+        .cloned()
+        .collect()
+}
+
+/// Groups of binds that fire on the same mods + key. Press and release binds on
+/// the same combo do not conflict.
+pub fn find_conflicts(binds: &[Keybind]) -> Vec<KeybindConflict> {
+    let mut groups: BTreeMap<(KeyCombo, bool), Vec<usize>> = BTreeMap::new();
+
+    for (index, bind) in binds.iter().enumerate() {
+        groups
+            .entry((bind.combo(), bind.flags.release))
+            .or_default()
+            .push(index);
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|((combo, _), indices)| KeybindConflict { combo, indices })
+        .collect()
+}
+
+// Follow the `source =` lines of the user's hyprland.conf, which is how Omarchy
+// pulls in its default bind files and `~/.config/hypr/bindings.conf`
+fn read_base_keybinds() -> Vec<Keybind> {
+    let Some(home_dir) = dirs::home_dir() else {
+        return Vec::new();
+    };
+
+    let hypr_config_path = home_dir.join(HYPR_CONFIG_PATH);
+    let Ok(content) = fs::read_to_string(&hypr_config_path) else {
+        return Vec::new();
+    };
+
+    let omarchy_dir = home_dir.join(OMARCHY_DIR);
+    let omarchist_dir = config_dir().ok();
+
+    let mut binds = parse_keybinds(&content, KeybindSource::User);
+
+    for path in sourced_files(&content, &home_dir) {
+        // Our own overrides are layered on separately
+        if omarchist_dir
+            .as_ref()
+            .is_some_and(|dir| path.starts_with(dir))
+        {
+            continue;
+        }
+
+        let source = if path.starts_with(&omarchy_dir) {
+            KeybindSource::Omarchy
+        } else {
+            KeybindSource::User
+        };
+
+        if let Ok(content) = fs::read_to_string(&path) {
+            binds.extend(parse_keybinds(&content, source));
+        }
+    }
+
+    binds
+}
+
+fn sourced_files(content: &str, home_dir: &Path) -> Vec<PathBuf> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            (key.trim() == "source").then(|| value.trim())
+        })
+        // Globs are only used for drop-in directories like ours
+        .filter(|value| !value.contains('*'))
+        .map(|value| match value.strip_prefix("~/") {
+            Some(rest) => home_dir.join(rest),
+            None => PathBuf::from(value),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::hyprland_config::keybind_parser::parse_keybind_line;
+
+    fn bind(line: &str, source: KeybindSource) -> Keybind {
+        parse_keybind_line(line, source).unwrap()
+    }
+
+    #[test]
+    fn find_conflicts_groups_same_combo() {
+        let binds = vec![
+            bind(
+                "bind = SUPER, RETURN, exec, alacritty",
+                KeybindSource::Omarchy,
+            ),
+            bind("bind = SUPER, Q, killactive,", KeybindSource::Omarchy),
+            bind("bind = super, return, exec, kitty", KeybindSource::User),
+        ];
+
+        let conflicts = find_conflicts(&binds);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].indices, vec![0, 2]);
+        assert_eq!(conflicts[0].combo.key, "RETURN");
+    }
+
+    #[test]
+    fn find_conflicts_ignores_press_and_release_pair() {
+        let binds = vec![
+            bind("bind = SUPER, SPACE, exec, a", KeybindSource::User),
+            bind("bindr = SUPER, SPACE, exec, b", KeybindSource::User),
+        ];
+        assert!(find_conflicts(&binds).is_empty());
+    }
+
+    #[test]
+    fn apply_overrides_replaces_unbound_combos() {
+        let base = vec![
+            bind(
+                "bind = SUPER, RETURN, exec, alacritty",
+                KeybindSource::Omarchy,
+            ),
+            bind("bind = SUPER, Q, killactive,", KeybindSource::Omarchy),
+        ];
+        let overrides = KeybindOverrides {
+            unbinds: vec![base[0].combo()],
+            binds: vec![bind(
+                "bind = SUPER, RETURN, exec, kitty",
+                KeybindSource::Omarchist,
+            )],
+        };
+
+        let binds = apply_overrides(&base, &overrides);
+        assert_eq!(binds.len(), 2);
+        assert_eq!(binds[0].key, "Q");
+        assert_eq!(binds[1].args, "kitty");
+    }
+
+    #[test]
+    fn sourced_files_expands_home_and_skips_globs() {
+        let content = "source = ~/.config/hypr/bindings.conf\nsource = ~/.config/omarchist/hyprland/*\n# source = ignored";
+        let files = sourced_files(content, Path::new("/home/me"));
+        assert_eq!(
+            files,
+            vec![PathBuf::from("/home/me/.config/hypr/bindings.conf")]
+        );
+    }
+}
@@ -3,8 +3,8 @@ use std::fs;
 use std::path::Path;
 
 use crate::types::hyprland_config::{
-    KeyboardCatalog, KeyboardLayout, KeyboardModel, KeyboardOption, KeyboardOptionGroup,
-    KeyboardVariant,
+    KeyboardCatalog, KeyboardLayout, KeyboardLayoutChoice, KeyboardModel, KeyboardOption,
+    KeyboardOptionGroup, KeyboardVariant,
 };
 
 const SYSTEM_BASE_LST: &str = "/usr/share/X11/xkb/rules/base.lst";
@@ -82,6 +82,84 @@ fn fallback_keyboard_catalog() -> Result<KeyboardCatalog, String> {
     })
 }
 
+/// Pair up the entries of `kb_layout = us,de` and `kb_variant = ,nodeadkeys`.
+pub fn parse_layout_list(kb_layout: &str, kb_variant: &str) -> Vec<KeyboardLayoutChoice> {
+    let variants: Vec<&str> = kb_variant.split(',').map(str::trim).collect();
+    kb_layout
+        .split(',')
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, layout)| !layout.is_empty())
+        .map(|(index, layout)| KeyboardLayoutChoice {
+            layout: layout.to_string(),
+            variant: variants.get(index).copied().unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+/// The `kb_layout` and `kb_variant` values for `layouts`. The variant list
+/// keeps one entry per layout so they stay paired, and is empty when every
+/// layout uses its default variant.
+pub fn format_layout_list(layouts: &[KeyboardLayoutChoice]) -> (String, String) {
+    let kb_layout = layouts
+        .iter()
+        .map(|choice| choice.layout.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    let kb_variant = if layouts.iter().all(|choice| choice.variant.is_empty()) {
+        String::new()
+    } else {
+        layouts
+            .iter()
+            .map(|choice| choice.variant.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    (kb_layout, kb_variant)
+}
+
+pub fn parse_option_list(kb_options: &str) -> Vec<String> {
+    kb_options
+        .split(',')
+        .map(str::trim)
+        .filter(|option| !option.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn format_option_list(options: &[String]) -> String {
+    options.join(",")
+}
+
+/// The `kb_options` entry for an option, e.g. `caps:escape`. The catalog
+/// stores options without their group's prefix.
+pub fn option_code(group: &KeyboardOptionGroup, option: &KeyboardOption) -> String {
+    format!("{}:{}", group.name, option.name)
+}
+
+/// Options whose code or description, or whose group's description, contains
+/// `query`, ignoring case.
+pub fn search_options<'a>(
+    catalog: &'a KeyboardCatalog,
+    query: &str,
+) -> Vec<(&'a KeyboardOptionGroup, &'a KeyboardOption)> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    catalog
+        .option_groups
+        .iter()
+        .flat_map(|group| group.options.iter().map(move |option| (group, option)))
+        .filter(|(group, option)| {
+            option_code(group, option).to_lowercase().contains(&query)
+                || option.description.to_lowercase().contains(&query)
+                || group.description.to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
 fn parse_keyboard_catalog_from_path(path: &Path) -> Result<KeyboardCatalog, String> {
     if !path.exists() {
         return Err(format!("File not found: {}", path.display()));
@@ -308,3 +386,66 @@ fn split_code_description(line: &str) -> Option<(String, String)> {
         Some((code, description))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_lists_round_trip_with_paired_variants() {
+        let layouts = parse_layout_list("us, de,fr", ",nodeadkeys");
+        assert_eq!(
+            layouts,
+            vec![
+                KeyboardLayoutChoice {
+                    layout: "us".to_string(),
+                    variant: String::new(),
+                },
+                KeyboardLayoutChoice {
+                    layout: "de".to_string(),
+                    variant: "nodeadkeys".to_string(),
+                },
+                KeyboardLayoutChoice {
+                    layout: "fr".to_string(),
+                    variant: String::new(),
+                },
+            ]
+        );
+        assert_eq!(
+            format_layout_list(&layouts),
+            ("us,de,fr".to_string(), ",nodeadkeys,".to_string())
+        );
+        assert_eq!(
+            format_layout_list(&parse_layout_list("us,de", "")),
+            ("us,de".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn option_lists_drop_empty_entries() {
+        let options = parse_option_list("grp:alt_shift_toggle, ,caps:escape");
+        assert_eq!(options, vec!["grp:alt_shift_toggle", "caps:escape"]);
+        assert_eq!(
+            format_option_list(&options),
+            "grp:alt_shift_toggle,caps:escape"
+        );
+    }
+
+    #[test]
+    fn search_options_matches_codes_and_descriptions() {
+        let catalog = parse_keyboard_catalog(FALLBACK_RULES).unwrap();
+
+        let names = |query: &str| -> Vec<String> {
+            search_options(&catalog, query)
+                .into_iter()
+                .map(|(group, option)| option_code(group, option))
+                .collect()
+        };
+
+        assert_eq!(names("caps:esc"), vec!["caps:escape"]);
+        assert!(names("ESCAPE").contains(&"caps:escape".to_string()));
+        // Matching the group finds all of its options
+        assert_eq!(names("caps lock behavior").len(), 2);
+        assert!(names("  ").is_empty());
+    }
+}
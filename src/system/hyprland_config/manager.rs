@@ -1,15 +1,46 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 use crate::types::hyprland_config::HyprlandConfig;
 
+use super::document::HyprlandDocument;
+use super::hyprctl_reader::read_from_hyprctl;
+use super::parser::parse_config_onto;
+use super::validation::ConfigWrite;
+use super::writer::config_settings;
+
 const CONFIG_DIR: &str = ".config/omarchist/hyprland";
 const CONFIG_FILE: &str = "hyprland.conf";
 
+const CONFIG_HEADER: &str = "\
+# Omarchist Hyprland Configuration
+# Settings changed in Omarchist are updated in place, anything you add by hand is kept
+";
+
+/// A save on its way to disk: the write to run, and the state the manager
+/// takes on once it succeeds.
+pub struct PendingSave {
+    pub write: ConfigWrite,
+    document: HyprlandDocument,
+    config: HyprlandConfig,
+}
+
+/// A reset on its way to disk, see `begin_reset`.
+pub struct PendingReset {
+    pub write: ConfigWrite,
+    document: HyprlandDocument,
+    config: HyprlandConfig,
+}
+
 pub struct HyprlandConfigManager {
     config_path: PathBuf,
     config: HyprlandConfig,
+    // The file as it is on disk, kept so comments and keys the typed config
+    // doesn't know about survive a save
+    document: HyprlandDocument,
+    // The config as of the last load or save, to see which settings the user
+    // changed. Only those are written, the rest keep following Omarchy.
+    saved: HyprlandConfig,
 }
 
 impl HyprlandConfigManager {
@@ -23,38 +54,107 @@ impl HyprlandConfigManager {
         // Hyprland is actually running, regardless of whether we have a saved
         // config file.  If a saved file exists, parse it on top so any stored
         // overrides take precedence.
-        let base = super::hyprctl_reader::read_from_hyprctl();
+        let base = read_from_hyprctl();
 
-        let config = if config_path.exists() {
+        let (config, document) = if config_path.exists() {
             let content = fs::read_to_string(&config_path)
                 .map_err(|e| format!("Failed to read config file: {}", e))?;
-            super::parser::parse_config_onto(&content, base)
+            (
+                parse_config_onto(&content, base),
+                HyprlandDocument::parse(&content),
+            )
         } else {
-            base
+            (base, HyprlandDocument::parse(CONFIG_HEADER))
         };
 
         Ok(Self {
             config_path,
+            saved: config.clone(),
             config,
+            document,
         })
     }
 
-    pub fn save(&self) -> Result<(), String> {
-        let content = super::writer::write_config(&self.config);
-        fs::write(&self.config_path, content)
-            .map_err(|e| format!("Failed to write config file: {}", e))?;
+    pub fn save(&mut self) -> Result<(), String> {
+        let pending = self.begin_save();
+        let result = pending.write.run();
+        self.finish_save(pending, result)
+    }
+
+    /// Prepare a save of the current config, to run with `ConfigWrite::run`
+    /// off the UI thread and complete with `finish_save`.
+    pub fn begin_save(&self) -> PendingSave {
+        let mut document = self.document.clone();
+        document.apply_changes(
+            &config_settings(&self.saved),
+            &config_settings(&self.config),
+        );
 
-        // Reload Hyprland to apply changes
-        Self::reload_hyprland();
+        PendingSave {
+            write: ConfigWrite::new(&self.config_path, document.render()),
+            document,
+            config: self.config.clone(),
+        }
+    }
+
+    pub fn finish_save(
+        &mut self,
+        pending: PendingSave,
+        result: Result<(), String>,
+    ) -> Result<(), String> {
+        if let Err(e) = result {
+            // The file was rolled back, show the values that are in effect
+            // but keep whatever was changed while the save ran
+            self.config = self.with_edits_since(&pending.config, &self.saved);
+            return Err(e);
+        }
+
+        self.document = pending.document;
+        self.saved = pending.config;
 
         Ok(())
     }
 
-    fn reload_hyprland() {
-        // Run hyprctl reload in background - don't block on it
-        std::thread::spawn(|| {
-            let _ = Command::new("hyprctl").arg("reload").output();
-        });
+    /// Drop the user's values for the options `ids` so Omarchy's apply again,
+    /// along with saving any other changes. Run the write and then
+    /// `read_from_hyprctl` off the UI thread and pass what it reads to
+    /// `finish_reset`; hyprctl only reports the values without our lines once
+    /// the reload is done.
+    pub fn begin_reset(&self, ids: &[&str]) -> PendingReset {
+        let mut document = self.document.clone();
+        document.apply_changes(
+            &config_settings(&self.saved),
+            &config_settings(&self.config),
+        );
+        for id in ids {
+            document.remove(id);
+        }
+
+        PendingReset {
+            write: ConfigWrite::new(&self.config_path, document.render()),
+            document,
+            config: self.config.clone(),
+        }
+    }
+
+    pub fn finish_reset(
+        &mut self,
+        pending: PendingReset,
+        result: Result<HyprlandConfig, String>,
+    ) -> Result<(), String> {
+        let live = result?;
+
+        self.document = pending.document;
+        self.saved = parse_config_onto(&self.document.render(), live);
+        self.config = self.with_edits_since(&pending.config, &self.saved);
+
+        Ok(())
+    }
+
+    /// Whether Omarchist's file sets the option `id`, as opposed to it coming
+    /// from Omarchy or Hyprland's defaults.
+    pub fn is_overridden(&self, id: &str) -> bool {
+        self.document.get(id).is_some()
     }
 
     pub fn get(&self) -> &HyprlandConfig {
@@ -84,6 +184,14 @@ impl HyprlandConfigManager {
         &self.config_path
     }
 
+    // `onto` with the changes the user made since `since`, so a save or reset
+    // finishing doesn't undo edits made while it ran
+    fn with_edits_since(&self, since: &HyprlandConfig, onto: &HyprlandConfig) -> HyprlandConfig {
+        let mut edits = HyprlandDocument::default();
+        edits.apply_changes(&config_settings(since), &config_settings(&self.config));
+        parse_config_onto(&edits.render(), onto.clone())
+    }
+
     pub fn reset_to_defaults(&mut self) {
         self.config = HyprlandConfig::default();
     }
@@ -94,18 +202,24 @@ impl Clone for HyprlandConfigManager {
         Self {
             config_path: self.config_path.clone(),
             config: self.config.clone(),
+            document: self.document.clone(),
+            saved: self.saved.clone(),
         }
     }
 }
 
 fn get_config_path() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-    Ok(home_dir.join(CONFIG_DIR).join(CONFIG_FILE))
+    Ok(config_dir()?.join(CONFIG_FILE))
 }
 
-fn ensure_config_dir() -> Result<(), String> {
+// Every file in this directory is sourced by hyprland.conf (see `hypr_setup`)
+pub(super) fn config_dir() -> Result<PathBuf, String> {
     let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-    let config_dir = home_dir.join(CONFIG_DIR);
+    Ok(home_dir.join(CONFIG_DIR))
+}
+
+pub(super) fn ensure_config_dir() -> Result<(), String> {
+    let config_dir = config_dir()?;
 
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir)
@@ -127,3 +241,31 @@ pub fn delete_config() -> Result<(), String> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_save_keeps_changes_made_while_it_ran() {
+        let saved = HyprlandConfig::default();
+        let mut manager = HyprlandConfigManager {
+            config_path: std::env::temp_dir().join("omarchist-rollback.conf"),
+            config: saved.clone(),
+            document: HyprlandDocument::default(),
+            saved: saved.clone(),
+        };
+
+        manager.update(|config| config.general.gaps_in = saved.general.gaps_in + 4);
+        let pending = manager.begin_save();
+        manager.update(|config| config.general.border_size = saved.general.border_size + 1);
+
+        let result = manager.finish_save(pending, Err("rolled back".to_string()));
+        assert!(result.is_err());
+        assert_eq!(manager.config.general.gaps_in, saved.general.gaps_in);
+        assert_eq!(
+            manager.config.general.border_size,
+            saved.general.border_size + 1
+        );
+    }
+}
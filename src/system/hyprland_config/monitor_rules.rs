@@ -0,0 +1,213 @@
+use crate::types::hyprland_config::*;
+use std::fmt::Write;
+
+pub fn write_monitors(monitors: &[MonitorSettings]) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "# Omarchist Hyprland Monitors\n# Generated automatically - Do not edit manually\n"
+    )
+    .unwrap();
+
+    for monitor in monitors {
+        writeln!(output, "{}", write_monitor_line(monitor)).unwrap();
+    }
+
+    output
+}
+
+/// Format one `monitor = name, WxH@Hz, XxY, scale[, transform, T][, mirror, name]` line.
+pub fn write_monitor_line(monitor: &MonitorSettings) -> String {
+    if !monitor.enabled {
+        return format!("monitor = {}, disable", monitor.name);
+    }
+
+    let mut line = format!(
+        "monitor = {}, {}, {}x{}, {}",
+        monitor.name,
+        format_mode(&monitor.mode),
+        monitor.x,
+        monitor.y,
+        format_scale(monitor.scale)
+    );
+
+    if monitor.transform != 0 {
+        write!(line, ", transform, {}", monitor.transform).unwrap();
+    }
+    if let Some(ref mirror) = monitor.mirror {
+        write!(line, ", mirror, {}", mirror).unwrap();
+    }
+
+    line
+}
+
+pub fn format_mode(mode: &MonitorMode) -> String {
+    format!("{}x{}@{:.2}", mode.width, mode.height, mode.refresh_rate)
+}
+
+// Fractional scales like 1.666667 must be written with enough precision for
+// Hyprland to accept them, but whole ones read better without decimals
+pub fn format_scale(scale: f64) -> String {
+    let formatted = format!("{:.6}", scale);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Parse the `monitor =` lines Omarchist wrote back into settings.
+pub fn parse_monitors(content: &str) -> Vec<MonitorSettings> {
+    content.lines().filter_map(parse_monitor_line).collect()
+}
+
+/// Parse a single `monitor =` line. Rules Omarchist does not write itself, such
+/// as `preferred` modes, `auto` positions or `desc:` names, return `None`.
+pub fn parse_monitor_line(line: &str) -> Option<MonitorSettings> {
+    let (keyword, value) = line.trim().split_once('=')?;
+    if keyword.trim() != "monitor" {
+        return None;
+    }
+
+    let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+    let name = fields.first()?.to_string();
+    if name.is_empty() || name.starts_with("desc:") {
+        return None;
+    }
+
+    if fields.get(1) == Some(&"disable") {
+        return Some(MonitorSettings {
+            name,
+            mode: MonitorMode {
+                width: 0,
+                height: 0,
+                refresh_rate: 0.0,
+            },
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            transform: 0,
+            mirror: None,
+            enabled: false,
+        });
+    }
+
+    if fields.len() < 4 {
+        return None;
+    }
+
+    let mode = parse_mode(fields[1])?;
+    let (x, y) = fields[2].split_once('x')?;
+    let mut monitor = MonitorSettings {
+        name,
+        mode,
+        x: x.parse().ok()?,
+        y: y.parse().ok()?,
+        scale: fields[3].parse().ok()?,
+        transform: 0,
+        mirror: None,
+        enabled: true,
+    };
+
+    // Trailing options come in `key, value` pairs
+    for pair in fields[4..].chunks(2) {
+        match pair {
+            ["transform", value] => monitor.transform = value.parse().ok()?,
+            ["mirror", value] => monitor.mirror = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Some(monitor)
+}
+
+/// Parse a mode such as `1920x1080@60.00Hz` or `1920x1080@60`.
+pub fn parse_mode(value: &str) -> Option<MonitorMode> {
+    let (size, rate) = value.split_once('@')?;
+    let (width, height) = size.split_once('x')?;
+
+    Some(MonitorMode {
+        width: width.trim().parse().ok()?,
+        height: height.trim().parse().ok()?,
+        refresh_rate: rate.trim().trim_end_matches("Hz").parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> MonitorSettings {
+        MonitorSettings {
+            name: "DP-2".to_string(),
+            mode: MonitorMode {
+                width: 3840,
+                height: 2160,
+                refresh_rate: 60.0,
+            },
+            x: 1440,
+            y: 0,
+            scale: 1.5,
+            transform: 0,
+            mirror: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn write_monitor_line_basic() {
+        assert_eq!(
+            write_monitor_line(&settings()),
+            "monitor = DP-2, 3840x2160@60.00, 1440x0, 1.5"
+        );
+    }
+
+    #[test]
+    fn write_monitor_line_transform_mirror_and_disable() {
+        let rotated = MonitorSettings {
+            transform: 1,
+            mirror: Some("eDP-1".to_string()),
+            scale: 5.0 / 3.0,
+            ..settings()
+        };
+        assert_eq!(
+            write_monitor_line(&rotated),
+            "monitor = DP-2, 3840x2160@60.00, 1440x0, 1.666667, transform, 1, mirror, eDP-1"
+        );
+
+        let disabled = MonitorSettings {
+            enabled: false,
+            ..settings()
+        };
+        assert_eq!(write_monitor_line(&disabled), "monitor = DP-2, disable");
+    }
+
+    #[test]
+    fn parse_monitor_line_round_trips() {
+        let monitor = MonitorSettings {
+            transform: 3,
+            mirror: Some("eDP-1".to_string()),
+            ..settings()
+        };
+        let parsed =
+            parse_monitor_line(&write_monitor_line(&monitor)).expect("written line should parse");
+        assert_eq!(parsed, monitor);
+    }
+
+    #[test]
+    fn parse_monitor_line_skips_rules_we_do_not_write() {
+        assert!(parse_monitor_line("monitor = , preferred, auto, 1").is_none());
+        assert!(parse_monitor_line("monitor = DP-1, preferred, auto, 1").is_none());
+        assert!(parse_monitor_line("monitor = desc:Dell Inc., 1920x1080@60, 0x0, 1").is_none());
+        assert!(parse_monitor_line("workspace = 1, monitor:DP-1").is_none());
+    }
+
+    #[test]
+    fn parse_mode_accepts_hyprctl_and_config_forms() {
+        let mode = parse_mode("2560x1440@59.95Hz").unwrap();
+        assert_eq!((mode.width, mode.height), (2560, 1440));
+        assert!((mode.refresh_rate - 59.95).abs() < f64::EPSILON);
+        assert!(parse_mode("1920x1080@60").is_some());
+        assert!(parse_mode("preferred").is_none());
+    }
+}
@@ -0,0 +1,354 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::types::hyprland_config::*;
+
+use super::manager::{config_dir, ensure_config_dir};
+use super::monitor_rules::{parse_mode, parse_monitors, write_monitors};
+use super::validation::ConfigWrite;
+
+const MONITORS_FILE: &str = "monitors.conf";
+
+// How close, in layout pixels, a dragged output has to get to another one's
+// edge before it snaps to it
+const SNAP_DISTANCE: i32 = 64;
+
+/// The JSON shape of one entry returned by `hyprctl monitors all -j`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HyprctlMonitor {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    width: i32,
+    #[serde(default)]
+    height: i32,
+    #[serde(default)]
+    refresh_rate: f64,
+    #[serde(default)]
+    x: i32,
+    #[serde(default)]
+    y: i32,
+    #[serde(default)]
+    scale: f64,
+    #[serde(default)]
+    transform: i32,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(default)]
+    mirror_of: Option<String>,
+    #[serde(default)]
+    available_modes: Vec<String>,
+}
+
+impl From<HyprctlMonitor> for MonitorInfo {
+    fn from(monitor: HyprctlMonitor) -> Self {
+        let available_modes: Vec<MonitorMode> = monitor
+            .available_modes
+            .iter()
+            .filter_map(|mode| parse_mode(mode))
+            .collect();
+
+        // Disabled outputs report a 0x0 mode, fall back to their preferred one
+        let mode = if monitor.width > 0 && monitor.height > 0 {
+            MonitorMode {
+                width: monitor.width,
+                height: monitor.height,
+                refresh_rate: (monitor.refresh_rate * 100.0).round() / 100.0,
+            }
+        } else {
+            available_modes.first().copied().unwrap_or(MonitorMode {
+                width: 1920,
+                height: 1080,
+                refresh_rate: 60.0,
+            })
+        };
+
+        Self {
+            name: monitor.name,
+            description: monitor.description,
+            mode,
+            x: monitor.x,
+            y: monitor.y,
+            scale: if monitor.scale > 0.0 {
+                monitor.scale
+            } else {
+                1.0
+            },
+            transform: monitor.transform,
+            mirror_of: monitor.mirror_of.filter(|m| m != "none" && !m.is_empty()),
+            disabled: monitor.disabled,
+            available_modes,
+        }
+    }
+}
+
+/// Parse the output of `hyprctl monitors all -j`.
+pub fn parse_monitors_json(json: &str) -> Result<Vec<MonitorInfo>, String> {
+    let monitors: Vec<HyprctlMonitor> =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse monitors: {}", e))?;
+    Ok(monitors.into_iter().map(MonitorInfo::from).collect())
+}
+
+/// Run `hyprctl monitors all -j`. `all` includes disabled outputs so they can
+/// be switched back on.
+pub fn read_monitors() -> Result<Vec<MonitorInfo>, String> {
+    let output = Command::new("hyprctl")
+        .args(["monitors", "all", "-j"])
+        .output()
+        .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
+
+    if !output.status.success() {
+        return Err("hyprctl could not list monitors. Is Hyprland running?".to_string());
+    }
+
+    parse_monitors_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+pub struct MonitorManager {
+    monitors_path: PathBuf,
+    // What hyprctl reported, used for descriptions and the modes to pick from
+    monitors: Vec<MonitorInfo>,
+    settings: Vec<MonitorSettings>,
+}
+
+impl MonitorManager {
+    pub fn load() -> Result<Self, String> {
+        ensure_config_dir()?;
+        let monitors_path = config_dir()?.join(MONITORS_FILE);
+
+        let saved = if monitors_path.exists() {
+            let content = fs::read_to_string(&monitors_path)
+                .map_err(|e| format!("Failed to read monitors file: {}", e))?;
+            parse_monitors(&content)
+        } else {
+            Vec::new()
+        };
+
+        let monitors = read_monitors()?;
+        let settings = merge_settings(&monitors, &saved);
+
+        Ok(Self {
+            monitors_path,
+            monitors,
+            settings,
+        })
+    }
+
+    // The write that saves the current state, see `ConfigWrite`
+    pub fn save_write(&self) -> ConfigWrite {
+        ConfigWrite::new(&self.monitors_path, write_monitors(&self.settings))
+    }
+
+    pub fn monitors(&self) -> &[MonitorInfo] {
+        &self.monitors
+    }
+
+    pub fn settings(&self) -> &[MonitorSettings] {
+        &self.settings
+    }
+
+    pub fn update<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(&mut MonitorSettings),
+    {
+        if let Some(settings) = self.settings.get_mut(index) {
+            f(settings);
+        }
+    }
+
+    // Move an output, snapping it against the edges of the others
+    pub fn move_to(&mut self, index: usize, x: i32, y: i32) {
+        let (x, y) = snap_position(&self.settings, index, x, y);
+        self.update(index, |settings| {
+            settings.x = x;
+            settings.y = y;
+        });
+    }
+
+    // Outputs mirroring one that gets disabled would go blank, so they are
+    // put back into the layout
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        self.update(index, |settings| settings.enabled = enabled);
+
+        if !enabled && let Some(name) = self.settings.get(index).map(|s| s.name.clone()) {
+            for settings in &mut self.settings {
+                if settings.mirror.as_deref() == Some(name.as_str()) {
+                    settings.mirror = None;
+                }
+            }
+        }
+    }
+
+    pub fn monitors_path(&self) -> &Path {
+        &self.monitors_path
+    }
+}
+
+// Start from what is connected right now and apply what we saved for the same
+// outputs. Saved rules for unplugged outputs are kept so they still apply the
+// next time that output shows up.
+pub fn merge_settings(monitors: &[MonitorInfo], saved: &[MonitorSettings]) -> Vec<MonitorSettings> {
+    let mut settings: Vec<MonitorSettings> = monitors
+        .iter()
+        .map(|info| match saved.iter().find(|s| s.name == info.name) {
+            // A saved `disable` line carries no mode, keep the live one
+            Some(saved) if !saved.enabled => MonitorSettings {
+                enabled: false,
+                ..MonitorSettings::from(info)
+            },
+            Some(saved) => saved.clone(),
+            None => MonitorSettings::from(info),
+        })
+        .collect();
+
+    settings.extend(
+        saved
+            .iter()
+            .filter(|s| !monitors.iter().any(|info| info.name == s.name))
+            .cloned(),
+    );
+
+    settings
+}
+
+/// Snap a proposed position so the output lines up with, or sits flush
+/// against, the nearest edges of the other arranged outputs.
+pub fn snap_position(settings: &[MonitorSettings], index: usize, x: i32, y: i32) -> (i32, i32) {
+    let Some(moving) = settings.get(index) else {
+        return (x, y);
+    };
+    let (width, height) = moving.logical_size();
+
+    let others: Vec<&MonitorSettings> = settings
+        .iter()
+        .enumerate()
+        .filter(|(i, other)| *i != index && other.is_arranged())
+        .map(|(_, other)| other)
+        .collect();
+
+    let x_candidates = others.iter().flat_map(|other| {
+        let (other_width, _) = other.logical_size();
+        [
+            other.x + other_width,
+            other.x - width,
+            other.x,
+            other.x + other_width - width,
+        ]
+    });
+    let y_candidates = others.iter().flat_map(|other| {
+        let (_, other_height) = other.logical_size();
+        [
+            other.y + other_height,
+            other.y - height,
+            other.y,
+            other.y + other_height - height,
+        ]
+    });
+
+    (snap_axis(x, x_candidates), snap_axis(y, y_candidates))
+}
+
+fn snap_axis(value: i32, candidates: impl Iterator<Item = i32>) -> i32 {
+    candidates
+        .filter(|candidate| (candidate - value).abs() <= SNAP_DISTANCE)
+        .min_by_key(|candidate| (candidate - value).abs())
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::hyprland_config::monitor_rules::parse_monitor_line;
+
+    const LAPTOP_DOCK: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/hyprctl/monitors_laptop_dock.json"
+    ));
+    const PROJECTOR: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/hyprctl/monitors_projector.json"
+    ));
+
+    #[test]
+    fn parse_monitors_json_reads_outputs_and_modes() {
+        let monitors = parse_monitors_json(LAPTOP_DOCK).expect("fixture should parse");
+        assert_eq!(monitors.len(), 2);
+
+        let external = &monitors[1];
+        assert_eq!(external.name, "DP-2");
+        assert_eq!(external.description, "Dell Inc. DELL U2723QE 8KXYZ13");
+        assert_eq!((external.mode.width, external.mode.height), (3840, 2160));
+        assert!((external.mode.refresh_rate - 60.0).abs() < 0.01);
+        assert_eq!((external.x, external.y), (1440, 0));
+        assert_eq!(external.scale, 1.5);
+        assert_eq!(external.mirror_of, None);
+        assert_eq!(external.available_modes.len(), 6);
+    }
+
+    #[test]
+    fn parse_monitors_json_reads_mirrors_and_disabled_outputs() {
+        let monitors = parse_monitors_json(PROJECTOR).unwrap();
+
+        assert_eq!(monitors[1].mirror_of.as_deref(), Some("eDP-1"));
+
+        let disabled = &monitors[2];
+        assert!(disabled.disabled);
+        assert_eq!(disabled.transform, 1);
+        // 0x0 is reported while disabled, the first available mode is used
+        assert_eq!((disabled.mode.width, disabled.mode.height), (2560, 1440));
+    }
+
+    #[test]
+    fn parse_monitors_json_rejects_garbage() {
+        assert!(parse_monitors_json("not json").is_err());
+    }
+
+    #[test]
+    fn merge_settings_prefers_saved_rules() {
+        let monitors = parse_monitors_json(PROJECTOR).unwrap();
+        let saved = vec![
+            parse_monitor_line("monitor = eDP-1, 1920x1200@48.00, 0x0, 1.25").unwrap(),
+            parse_monitor_line("monitor = DP-1, disable").unwrap(),
+            parse_monitor_line("monitor = DP-3, 1920x1080@60.00, 1920x0, 1").unwrap(),
+        ];
+
+        let settings = merge_settings(&monitors, &saved);
+        assert_eq!(settings.len(), 4);
+        assert_eq!(settings[0].scale, 1.25);
+        assert_eq!(settings[1].mirror.as_deref(), Some("eDP-1"));
+        assert!(!settings[2].enabled);
+        assert_eq!(settings[2].mode.width, 2560);
+        assert_eq!(settings[3].name, "DP-3");
+    }
+
+    #[test]
+    fn logical_size_accounts_for_scale_and_rotation() {
+        let monitors = parse_monitors_json(LAPTOP_DOCK).unwrap();
+        let laptop = MonitorSettings::from(&monitors[0]);
+        assert_eq!(laptop.logical_size(), (1440, 960));
+
+        let rotated = MonitorSettings {
+            transform: 1,
+            ..laptop
+        };
+        assert_eq!(rotated.logical_size(), (960, 1440));
+    }
+
+    #[test]
+    fn snap_position_sits_flush_against_neighbours() {
+        let monitors = parse_monitors_json(LAPTOP_DOCK).unwrap();
+        let settings: Vec<MonitorSettings> = monitors.iter().map(MonitorSettings::from).collect();
+
+        // Dropped slightly right of and below the laptop's right edge
+        assert_eq!(snap_position(&settings, 1, 1470, 20), (1440, 0));
+        // Dropped to the left of the laptop
+        assert_eq!(snap_position(&settings, 1, -2600, 900), (-2560, 960));
+        // Far away from everything nothing snaps
+        assert_eq!(snap_position(&settings, 1, 5000, 5000), (5000, 5000));
+    }
+}
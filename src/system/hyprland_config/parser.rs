@@ -19,6 +19,8 @@ fn parse_into(content: &str, config: &mut HyprlandConfig) {
     let mut current_section: Option<String> = None;
     let mut current_subsection: Option<String> = None;
     let mut brace_depth = 0;
+    // The `device` block being read, added once it is closed
+    let mut current_device: Option<DeviceConfig> = None;
 
     for line in content.lines() {
         let trimmed = line.trim();
@@ -33,6 +35,12 @@ fn parse_into(content: &str, config: &mut HyprlandConfig) {
             let section_def = section_def.trim();
             brace_depth += 1;
 
+            // A block nested in a section, e.g. `snap {` inside `general {`
+            if brace_depth > 1 {
+                current_subsection = Some(section_def.to_string());
+                continue;
+            }
+
             if section_def.contains(':') {
                 let parts: Vec<&str> = section_def.splitn(2, ':').collect();
                 current_section = Some(parts[0].trim().to_string());
@@ -41,13 +49,27 @@ fn parse_into(content: &str, config: &mut HyprlandConfig) {
                 current_section = Some(section_def.to_string());
                 current_subsection = None;
             }
+
+            // `device:name {` is the older form of `device { name = ... }`
+            if current_section.as_deref() == Some("device") {
+                current_device = Some(DeviceConfig {
+                    name: current_subsection.clone().unwrap_or_default(),
+                    ..DeviceConfig::default()
+                });
+            }
             continue;
         }
 
         // Handle section end
         if trimmed == "}" {
             brace_depth -= 1;
-            if brace_depth == 0 {
+            if brace_depth == 0
+                && let Some(device) = current_device.take()
+                && !device.name.is_empty()
+            {
+                upsert_by_name(&mut config.devices, device, |d| &d.name);
+            }
+            if brace_depth <= 1 {
                 current_subsection = None;
             } else if brace_depth < 0 {
                 brace_depth = 0;
@@ -59,6 +81,28 @@ fn parse_into(content: &str, config: &mut HyprlandConfig) {
 
         // Parse key-value pairs
         if let Some((key, value)) = parse_key_value(trimmed) {
+            // Curves and animations are keywords, valid inside or outside a section
+            match key.as_str() {
+                "bezier" => {
+                    if let Some(bezier) = parse_bezier(&value) {
+                        upsert_by_name(&mut config.animations.beziers, bezier, |b| &b.name);
+                    }
+                    continue;
+                }
+                "animation" => {
+                    if let Some(animation) = parse_animation(&value) {
+                        upsert_by_name(&mut config.animations.animations, animation, |a| &a.name);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(device) = current_device.as_mut() {
+                apply_device_setting(device, &key, &value);
+                continue;
+            }
+
             let section = current_section.as_deref().unwrap_or("");
             let subsection = current_subsection.as_deref();
 
@@ -492,6 +536,79 @@ fn apply_debug_setting(config: &mut DebugConfig, key: &str, value: &str) {
     }
 }
 
+fn apply_device_setting(device: &mut DeviceConfig, key: &str, value: &str) {
+    match key {
+        "name" => device.name = value.to_string(),
+        "enabled" => device.enabled = Some(parse_bool(value)),
+        "sensitivity" => device.sensitivity = Some(parse_float(value)),
+        "accel_profile" => device.accel_profile = Some(value.to_string()),
+        "natural_scroll" => device.natural_scroll = Some(parse_bool(value)),
+        "left_handed" => device.left_handed = Some(parse_bool(value)),
+        "scroll_factor" => device.scroll_factor = Some(parse_float(value)),
+        "kb_layout" => device.kb_layout = Some(value.to_string()),
+        "kb_variant" => device.kb_variant = Some(value.to_string()),
+        "kb_options" => device.kb_options = Some(value.to_string()),
+        "repeat_rate" => device.repeat_rate = Some(parse_int(value)),
+        "repeat_delay" => device.repeat_delay = Some(parse_int(value)),
+        _ => {}
+    }
+}
+
+/// Parse the value of a `bezier = name, x1, y1, x2, y2` line.
+pub fn parse_bezier(value: &str) -> Option<BezierCurve> {
+    let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+    let [name, x1, y1, x2, y2] = fields.as_slice() else {
+        return None;
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(BezierCurve {
+        name: name.to_string(),
+        x1: x1.parse().ok()?,
+        y1: y1.parse().ok()?,
+        x2: x2.parse().ok()?,
+        y2: y2.parse().ok()?,
+    })
+}
+
+/// Parse the value of an `animation = name, onoff, speed, curve[, style]` line.
+/// Speed and curve may be left out when the animation is turned off.
+pub fn parse_animation(value: &str) -> Option<Animation> {
+    let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+    let name = fields.first().filter(|name| !name.is_empty())?;
+    let enabled = parse_bool(fields.get(1)?);
+
+    let (speed, curve) = match (fields.get(2), fields.get(3)) {
+        (Some(speed), Some(curve)) => (speed.parse().ok()?, curve.to_string()),
+        (None, None) if !enabled => (0.0, String::new()),
+        _ => return None,
+    };
+
+    Some(Animation {
+        name: name.to_string(),
+        enabled,
+        speed,
+        curve,
+        style: fields
+            .get(4)
+            .filter(|style| !style.is_empty())
+            .map(|style| style.to_string()),
+    })
+}
+
+// Hyprland keeps the last definition of a curve or animation
+fn upsert_by_name<T>(items: &mut Vec<T>, item: T, name: impl Fn(&T) -> &String) {
+    match items
+        .iter()
+        .position(|existing| name(existing) == name(&item))
+    {
+        Some(index) => items[index] = item,
+        None => items.push(item),
+    }
+}
+
 fn parse_bool(value: &str) -> bool {
     matches!(value.to_lowercase().as_str(), "true" | "yes" | "on" | "1")
 }
@@ -737,4 +854,123 @@ mod tests {
             "animations.enabled should be false when set to false"
         );
     }
+
+    #[test]
+    fn parse_config_beziers_and_animations() {
+        let input = "animations {\n    enabled = yes\n    bezier = easeOutQuint, 0.23, 1, 0.32, 1\n    animation = windows, 1, 4.79, easeOutQuint\n    animation = workspaces, 1, 1.94, easeOutQuint, fade\n    animation = layersIn, 0\n}\nbezier = linear, 0, 0, 1, 1";
+        let config = parse_config(input);
+        let animations = &config.animations;
+        assert_eq!(animations.beziers.len(), 2, "both curves should be read");
+        assert_eq!(animations.beziers[0].name, "easeOutQuint");
+        assert!((animations.beziers[0].x1 - 0.23).abs() < f64::EPSILON);
+        assert_eq!(animations.animations.len(), 3);
+        assert!((animations.animations[0].speed - 4.79).abs() < f64::EPSILON);
+        assert_eq!(animations.animations[1].style.as_deref(), Some("fade"));
+        assert!(
+            !animations.animations[2].enabled && animations.animations[2].curve.is_empty(),
+            "animation = layersIn, 0 should turn the animation off"
+        );
+    }
+
+    #[test]
+    fn parse_config_onto_replaces_same_named_animations() {
+        let base =
+            parse_config("animation = windows, 1, 7, default\nbezier = quick, 0.15, 0, 0.1, 1");
+        let config = parse_config_onto(
+            "animation = windows, 1, 3, quick, popin 80%\nbezier = quick, 0, 0, 1, 1",
+            base,
+        );
+        assert_eq!(config.animations.animations.len(), 1);
+        assert_eq!(config.animations.animations[0].curve, "quick");
+        assert_eq!(
+            config.animations.animations[0].style.as_deref(),
+            Some("popin 80%")
+        );
+        assert_eq!(config.animations.beziers.len(), 1);
+        assert!(config.animations.beziers[0].x1.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn parse_animation_rejects_incomplete_lines() {
+        assert!(parse_animation("windows, 1").is_none());
+        assert!(parse_animation("windows, 1, fast, default").is_none());
+        assert!(parse_bezier("broken, 0.1, 0.2").is_none());
+    }
+
+    #[test]
+    fn write_config_round_trips_animations() {
+        let input = "bezier = easeOutQuint, 0.23, 1, 0.32, 1\nanimation = windows, 1, 4.79, easeOutQuint, popin 87%\nanimation = fade, 0";
+        let config = parse_config(input);
+        let written = crate::system::hyprland_config::writer::write_config(&config);
+        let reparsed = parse_config(&written);
+        assert_eq!(reparsed.animations.beziers, config.animations.beziers);
+        assert_eq!(reparsed.animations.animations, config.animations.animations);
+    }
+
+    #[test]
+    fn config_settings_parse_back_to_the_same_config() {
+        let mut config = parse_config(
+            "bezier = quick, 0.15, 0, 0.1, 1\nanimation = windows, 1, 4, quick, popin 80%",
+        );
+        config.general.gaps_in = 3;
+        config.general.snap.enabled = true;
+        config.decoration.active_opacity = 0.9;
+        config.decoration.shadow.offset_x = 2.0;
+        config.decoration.shadow.offset_y = -1.5;
+        config.input.touchpad.natural_scroll = true;
+
+        // Written one by one, as a save does for the options that changed
+        let mut document = crate::system::hyprland_config::document::HyprlandDocument::default();
+        for (id, value) in crate::system::hyprland_config::writer::config_settings(&config) {
+            document.set(&id, &value);
+        }
+        let reparsed = parse_config(&document.render());
+
+        assert_eq!(reparsed.general.gaps_in, 3);
+        assert!(reparsed.general.snap.enabled);
+        assert_eq!(reparsed.decoration.active_opacity, 0.9);
+        assert_eq!(reparsed.decoration.shadow.offset_x, 2.0);
+        assert_eq!(reparsed.decoration.shadow.offset_y, -1.5);
+        assert!(reparsed.input.touchpad.natural_scroll);
+        assert_eq!(reparsed.animations.beziers, config.animations.beziers);
+        assert_eq!(reparsed.animations.animations, config.animations.animations);
+    }
+
+    #[test]
+    fn parse_config_reads_device_blocks() {
+        let config = parse_config(
+            "device {\n    name = logitech-g502\n    sensitivity = -0.5\n    accel_profile = flat\n}\n\ndevice:at-translated-set-2-keyboard {\n    kb_layout = de\n}\n\ninput {\n    sensitivity = 0.2\n}\n",
+        );
+
+        assert_eq!(config.input.sensitivity, 0.2);
+        assert_eq!(config.devices.len(), 2);
+        assert_eq!(config.devices[0].name, "logitech-g502");
+        assert_eq!(config.devices[0].sensitivity, Some(-0.5));
+        assert_eq!(config.devices[0].accel_profile.as_deref(), Some("flat"));
+        assert_eq!(config.devices[0].left_handed, None);
+        assert_eq!(config.devices[1].name, "at-translated-set-2-keyboard");
+        assert_eq!(config.devices[1].kb_layout.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn device_blocks_round_trip_through_the_writer() {
+        let mut config = HyprlandConfig::default();
+        config.devices.push(DeviceConfig {
+            name: "logitech-g502".to_string(),
+            sensitivity: Some(-0.5),
+            natural_scroll: Some(false),
+            ..DeviceConfig::default()
+        });
+
+        let written = crate::system::hyprland_config::writer::write_config(&config);
+        assert_eq!(parse_config(&written).devices, config.devices);
+
+        let mut document = crate::system::hyprland_config::document::HyprlandDocument::default();
+        for (id, value) in crate::system::hyprland_config::writer::config_settings(&config) {
+            if id.starts_with("device:") {
+                document.set(&id, &value);
+            }
+        }
+        assert_eq!(parse_config(&document.render()).devices, config.devices);
+    }
 }
@@ -0,0 +1,347 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::manager::{config_dir, ensure_config_dir};
+
+const PROFILES_DIR: &str = "profiles";
+const CURRENT_PROFILE_FILE: &str = "current-profile";
+
+pub const DEFAULT_HYPRLAND_PROFILE: &str = "default";
+
+/// Named sets of Omarchist's Hyprland files, stored as directories under
+/// `~/.config/omarchist/hyprland/profiles/`.
+///
+/// The active profile is copied into place, next to the `profiles` directory,
+/// where Hyprland sources it. Edits made while a profile is active go to
+/// those live files and are copied back into the profile when switching away.
+pub struct HyprlandProfiles {
+    config_dir: PathBuf,
+}
+
+impl HyprlandProfiles {
+    pub fn open() -> Result<Self, String> {
+        ensure_config_dir()?;
+        let profiles = Self::at(config_dir()?);
+        profiles.ensure_default()?;
+        Ok(profiles)
+    }
+
+    /// Profiles for the Omarchist config directory `config_dir`.
+    pub fn at(config_dir: PathBuf) -> Self {
+        Self { config_dir }
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(self.profiles_dir())
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_dir())
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    pub fn current(&self) -> Option<String> {
+        let value = fs::read_to_string(self.profiles_dir().join(CURRENT_PROFILE_FILE)).ok()?;
+        let name = value.trim();
+        if name.is_empty() || !self.profile_dir(name).is_dir() {
+            return None;
+        }
+        Some(name.to_string())
+    }
+
+    /// Create an empty profile, which leaves every setting to Omarchy.
+    pub fn create(&self, profile_name: &str) -> Result<String, String> {
+        let name = self.new_profile_name(profile_name)?;
+        let dest = self.profile_dir(&name);
+        fs::create_dir_all(&dest).map_err(|e| format!("Failed to create profile: {}", e))?;
+        Ok(name)
+    }
+
+    pub fn duplicate(&self, source_name: &str, new_name: &str) -> Result<String, String> {
+        let src = self.existing_profile_dir(source_name)?;
+        let name = self.new_profile_name(new_name)?;
+
+        // The active profile's latest edits only exist in the live files
+        if self.current().as_deref() == Some(source_name) {
+            self.store_live(source_name)?;
+        }
+
+        copy_files(&src, &self.profile_dir(&name))?;
+        Ok(name)
+    }
+
+    pub fn rename(&self, old_name: &str, new_name: &str) -> Result<String, String> {
+        let src = self.existing_profile_dir(old_name)?;
+        let name = self.new_profile_name(new_name)?;
+
+        fs::rename(&src, self.profile_dir(&name))
+            .map_err(|e| format!("Failed to rename profile: {}", e))?;
+
+        if self.current().is_none() {
+            self.write_current(&name)?;
+        }
+
+        Ok(name)
+    }
+
+    /// Delete a profile. Deleting the active one switches to the first
+    /// remaining profile, which is returned either way.
+    pub fn delete(&self, profile_name: &str) -> Result<String, String> {
+        let target = self.existing_profile_dir(profile_name)?;
+
+        let remaining: Vec<String> = self
+            .list()
+            .into_iter()
+            .filter(|name| name != profile_name)
+            .collect();
+        let Some(next) = remaining.first().cloned() else {
+            return Err("Cannot delete the last profile".to_string());
+        };
+
+        let was_active = self.current().as_deref() == Some(profile_name);
+        fs::remove_dir_all(&target).map_err(|e| format!("Failed to delete profile: {}", e))?;
+
+        if was_active {
+            self.apply(&next)?;
+            return Ok(next);
+        }
+
+        Ok(self.current().unwrap_or(next))
+    }
+
+    /// Copy a profile into place. The caller reloads Hyprland afterwards.
+    pub fn apply(&self, profile_name: &str) -> Result<(), String> {
+        let profile_dir = self.existing_profile_dir(profile_name)?;
+
+        // Already in place, and the live files may be newer than the profile
+        match self.current() {
+            Some(current) if current == profile_name => return Ok(()),
+            Some(current) => self.store_live(&current)?,
+            None => {}
+        }
+
+        for file in live_files(&self.config_dir)? {
+            fs::remove_file(&file).map_err(|e| format!("Failed to remove {:?}: {}", file, e))?;
+        }
+        copy_files(&profile_dir, &self.config_dir)
+            .map_err(|e| format!("Failed to apply profile \"{}\": {}", profile_name, e))?;
+
+        self.write_current(profile_name)
+    }
+
+    // The first time profiles are used, whatever is live becomes the default
+    // profile so nothing is lost
+    fn ensure_default(&self) -> Result<(), String> {
+        if !self.list().is_empty() {
+            return Ok(());
+        }
+
+        let dest = self.profile_dir(DEFAULT_HYPRLAND_PROFILE);
+        copy_files(&self.config_dir, &dest)?;
+        self.write_current(DEFAULT_HYPRLAND_PROFILE)
+    }
+
+    // Replace the profile's files with the live ones
+    fn store_live(&self, profile_name: &str) -> Result<(), String> {
+        let profile_dir = self.profile_dir(profile_name);
+        for file in live_files(&profile_dir)? {
+            fs::remove_file(&file).map_err(|e| format!("Failed to remove {:?}: {}", file, e))?;
+        }
+        copy_files(&self.config_dir, &profile_dir)
+    }
+
+    fn new_profile_name(&self, profile_name: &str) -> Result<String, String> {
+        let name = profile_name.trim();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty".to_string());
+        }
+        if name.contains('/') || name.starts_with('.') {
+            return Err(format!("\"{}\" is not a valid profile name", name));
+        }
+        if self.profiles_dir().join(name).exists() {
+            return Err(format!("A profile named \"{}\" already exists", name));
+        }
+        Ok(name.to_string())
+    }
+
+    fn existing_profile_dir(&self, profile_name: &str) -> Result<PathBuf, String> {
+        let dir = self.profile_dir(profile_name);
+        if profile_name.is_empty() || !dir.is_dir() {
+            return Err(format!("Profile \"{}\" not found", profile_name));
+        }
+        Ok(dir)
+    }
+
+    fn write_current(&self, profile_name: &str) -> Result<(), String> {
+        let path = self.profiles_dir().join(CURRENT_PROFILE_FILE);
+        fs::write(&path, format!("{}\n", profile_name))
+            .map_err(|e| format!("Failed to write current profile {:?}: {}", path, e))
+    }
+
+    fn profiles_dir(&self) -> PathBuf {
+        self.config_dir.join(PROFILES_DIR)
+    }
+
+    fn profile_dir(&self, profile_name: &str) -> PathBuf {
+        self.profiles_dir().join(profile_name)
+    }
+}
+
+// The regular files directly in `dir`, which is all a profile holds
+fn live_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+fn copy_files(src: &Path, dst: &Path) -> Result<(), String> {
+    fs::create_dir_all(dst).map_err(|e| format!("Failed to create directory {:?}: {}", dst, e))?;
+
+    for file in live_files(src)? {
+        let Some(file_name) = file.file_name() else {
+            continue;
+        };
+        let dest = dst.join(file_name);
+        fs::copy(&file, &dest)
+            .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", file, dest, e))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "omarchist-hypr-profiles-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("hyprland.conf"), "general {\n    gaps_in = 3\n}\n").unwrap();
+        fs::write(dir.join("monitors.conf"), "monitor = eDP-1, disable\n").unwrap();
+        dir
+    }
+
+    fn read(dir: &Path, file: &str) -> Option<String> {
+        fs::read_to_string(dir.join(file)).ok()
+    }
+
+    #[test]
+    fn first_use_keeps_live_files_as_default_profile() {
+        let dir = temp_config_dir("default");
+        let profiles = HyprlandProfiles::at(dir.clone());
+        profiles.ensure_default().unwrap();
+
+        assert_eq!(profiles.list(), vec![DEFAULT_HYPRLAND_PROFILE]);
+        assert_eq!(
+            profiles.current().as_deref(),
+            Some(DEFAULT_HYPRLAND_PROFILE)
+        );
+        assert!(
+            read(&dir.join("profiles/default"), "monitors.conf")
+                .unwrap()
+                .contains("disable")
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn apply_swaps_live_files_and_keeps_edits_of_the_previous_profile() {
+        let dir = temp_config_dir("apply");
+        let profiles = HyprlandProfiles::at(dir.clone());
+        profiles.ensure_default().unwrap();
+        profiles.create("presentation").unwrap();
+
+        // Edited while the default profile is active
+        fs::write(dir.join("hyprland.conf"), "general {\n    gaps_in = 8\n}\n").unwrap();
+
+        profiles.apply("presentation").unwrap();
+        assert_eq!(profiles.current().as_deref(), Some("presentation"));
+        assert_eq!(read(&dir, "hyprland.conf"), None);
+        assert_eq!(read(&dir, "monitors.conf"), None);
+
+        profiles.apply(DEFAULT_HYPRLAND_PROFILE).unwrap();
+        assert!(read(&dir, "hyprland.conf").unwrap().contains("gaps_in = 8"));
+        assert!(read(&dir, "monitors.conf").is_some());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn duplicate_of_active_profile_includes_live_edits() {
+        let dir = temp_config_dir("duplicate");
+        let profiles = HyprlandProfiles::at(dir.clone());
+        profiles.ensure_default().unwrap();
+        fs::write(
+            dir.join("rules.conf"),
+            "windowrule = float, class:^(pavucontrol)$\n",
+        )
+        .unwrap();
+
+        let name = profiles
+            .duplicate(DEFAULT_HYPRLAND_PROFILE, " docked ")
+            .unwrap();
+
+        assert_eq!(name, "docked");
+        assert!(read(&dir.join("profiles/docked"), "rules.conf").is_some());
+        assert!(
+            profiles
+                .duplicate(DEFAULT_HYPRLAND_PROFILE, "docked")
+                .unwrap_err()
+                .contains("already exists")
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rename_keeps_the_active_profile() {
+        let dir = temp_config_dir("rename");
+        let profiles = HyprlandProfiles::at(dir.clone());
+        profiles.ensure_default().unwrap();
+
+        profiles.rename(DEFAULT_HYPRLAND_PROFILE, "laptop").unwrap();
+
+        assert_eq!(profiles.list(), vec!["laptop"]);
+        assert_eq!(profiles.current().as_deref(), Some("laptop"));
+        assert!(profiles.rename("laptop", "").is_err());
+        assert!(profiles.rename("laptop", "../escape").is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn delete_switches_away_from_the_active_profile() {
+        let dir = temp_config_dir("delete");
+        let profiles = HyprlandProfiles::at(dir.clone());
+        profiles.ensure_default().unwrap();
+
+        assert!(profiles.delete(DEFAULT_HYPRLAND_PROFILE).is_err());
+
+        profiles.create("docked").unwrap();
+        let active = profiles.delete(DEFAULT_HYPRLAND_PROFILE).unwrap();
+
+        assert_eq!(active, "docked");
+        assert_eq!(profiles.current().as_deref(), Some("docked"));
+        assert_eq!(read(&dir, "hyprland.conf"), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
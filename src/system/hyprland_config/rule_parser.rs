@@ -0,0 +1,277 @@
+use crate::types::hyprland_config::*;
+
+/// Parse every `windowrule`, `windowrulev2` and `workspace` line in a file.
+pub fn parse_rules(content: &str) -> RuleSet {
+    let mut rules = RuleSet::default();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if let Some(rule) = parse_window_rule_line(trimmed) {
+            rules.window_rules.push(rule);
+        } else if let Some(rule) = parse_workspace_rule_line(trimmed) {
+            rules.workspace_rules.push(rule);
+        }
+    }
+
+    rules
+}
+
+/// Parse a single `windowrule[v2] = action, matcher, matcher...` line.
+pub fn parse_window_rule_line(line: &str) -> Option<WindowRule> {
+    let (keyword, value) = split_assignment(line)?;
+    let keyword = match keyword {
+        "windowrule" => WindowRuleKeyword::WindowRule,
+        "windowrulev2" => WindowRuleKeyword::WindowRuleV2,
+        _ => return None,
+    };
+
+    let mut fields = value.split(',').map(str::trim);
+    let action = parse_action(fields.next()?)?;
+    let matchers: Vec<WindowMatcher> = fields
+        .filter(|field| !field.is_empty())
+        .map(parse_matcher)
+        .collect();
+
+    // A rule that matches nothing is rejected by Hyprland
+    if matchers.is_empty() {
+        return None;
+    }
+
+    Some(WindowRule {
+        keyword,
+        action,
+        matchers,
+    })
+}
+
+/// Parse the action of a window rule, e.g. `float` or `size 800 600`.
+pub fn parse_action(value: &str) -> Option<WindowRuleAction> {
+    let value = value.trim();
+    let (name, args) = match value.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (value, ""),
+    };
+    let words: Vec<&str> = args.split_whitespace().collect();
+
+    let action = match (name, words.as_slice()) {
+        ("", _) => return None,
+        ("float", []) => WindowRuleAction::Float,
+        ("tile", []) => WindowRuleAction::Tile,
+        ("fullscreen", []) => WindowRuleAction::Fullscreen,
+        ("maximize", []) => WindowRuleAction::Maximize,
+        ("pin", []) => WindowRuleAction::Pin,
+        ("center", []) => WindowRuleAction::Center,
+        ("size", [width, height]) => WindowRuleAction::Size {
+            width: width.to_string(),
+            height: height.to_string(),
+        },
+        ("move", [x, y]) => WindowRuleAction::Move {
+            x: x.to_string(),
+            y: y.to_string(),
+        },
+        ("opacity", [_, ..]) => WindowRuleAction::Opacity(words.join(" ")),
+        ("workspace", [workspace]) => WindowRuleAction::Workspace {
+            workspace: workspace.to_string(),
+            silent: false,
+        },
+        ("workspace", [workspace, "silent"]) => WindowRuleAction::Workspace {
+            workspace: workspace.to_string(),
+            silent: true,
+        },
+        _ => WindowRuleAction::Other(value.to_string()),
+    };
+
+    Some(action)
+}
+
+/// Parse one `key:value` matcher of a window rule.
+pub fn parse_matcher(value: &str) -> WindowMatcher {
+    let Some((key, value)) = value.split_once(':') else {
+        return WindowMatcher::Other {
+            key: String::new(),
+            value: value.to_string(),
+        };
+    };
+    let (key, value) = (key.trim(), value.trim());
+
+    let flag = parse_bool(value);
+    match (key, flag) {
+        ("class", _) => WindowMatcher::Class(value.to_string()),
+        ("title", _) => WindowMatcher::Title(value.to_string()),
+        ("initialClass", _) => WindowMatcher::InitialClass(value.to_string()),
+        ("initialTitle", _) => WindowMatcher::InitialTitle(value.to_string()),
+        ("workspace", _) => WindowMatcher::Workspace(value.to_string()),
+        ("floating", Some(flag)) => WindowMatcher::Floating(flag),
+        ("xwayland", Some(flag)) => WindowMatcher::XWayland(flag),
+        ("fullscreen", Some(flag)) => WindowMatcher::Fullscreen(flag),
+        ("pinned", Some(flag)) => WindowMatcher::Pinned(flag),
+        _ => WindowMatcher::Other {
+            key: key.to_string(),
+            value: value.to_string(),
+        },
+    }
+}
+
+/// Parse a single `workspace = name, option:value...` line.
+pub fn parse_workspace_rule_line(line: &str) -> Option<WorkspaceRule> {
+    let (keyword, value) = split_assignment(line)?;
+    if keyword != "workspace" {
+        return None;
+    }
+
+    let mut fields = value.split(',').map(str::trim);
+    let workspace = fields.next()?.to_string();
+    if workspace.is_empty() {
+        return None;
+    }
+
+    Some(WorkspaceRule {
+        workspace,
+        options: fields
+            .filter(|field| !field.is_empty())
+            .map(parse_workspace_option)
+            .collect(),
+    })
+}
+
+pub fn parse_workspace_option(value: &str) -> WorkspaceRuleOption {
+    let (key, value) = match value.split_once(':') {
+        Some((key, value)) => (key.trim(), value.trim()),
+        None => (value.trim(), ""),
+    };
+
+    match (key, parse_bool(value)) {
+        ("monitor", _) => WorkspaceRuleOption::Monitor(value.to_string()),
+        ("on-created-empty", _) => WorkspaceRuleOption::OnCreatedEmpty(value.to_string()),
+        ("default", Some(flag)) => WorkspaceRuleOption::Default(flag),
+        ("persistent", Some(flag)) => WorkspaceRuleOption::Persistent(flag),
+        _ => WorkspaceRuleOption::Other {
+            key: key.to_string(),
+            value: value.to_string(),
+        },
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_window_rule_line_typed_action_and_matchers() {
+        let rule = parse_window_rule_line(
+            "windowrulev2 = size 800 600, class:^(org.gnome.Calculator)$, floating:1",
+        )
+        .expect("window rule should parse");
+        assert_eq!(rule.keyword, WindowRuleKeyword::WindowRuleV2);
+        assert_eq!(
+            rule.action,
+            WindowRuleAction::Size {
+                width: "800".to_string(),
+                height: "600".to_string()
+            }
+        );
+        assert_eq!(
+            rule.matchers,
+            vec![
+                WindowMatcher::Class("^(org.gnome.Calculator)$".to_string()),
+                WindowMatcher::Floating(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_action_covers_workspace_opacity_and_unknown() {
+        assert_eq!(
+            parse_action("workspace 3 silent"),
+            Some(WindowRuleAction::Workspace {
+                workspace: "3".to_string(),
+                silent: true
+            })
+        );
+        assert_eq!(
+            parse_action("opacity 0.97 0.9"),
+            Some(WindowRuleAction::Opacity("0.97 0.9".to_string()))
+        );
+        assert_eq!(
+            parse_action("idleinhibit fullscreen"),
+            Some(WindowRuleAction::Other(
+                "idleinhibit fullscreen".to_string()
+            ))
+        );
+        assert_eq!(parse_action(""), None);
+    }
+
+    #[test]
+    fn parse_window_rule_line_keeps_legacy_regex_and_unknown_matchers() {
+        let rule = parse_window_rule_line("windowrule = float, ^(pavucontrol)$").unwrap();
+        assert_eq!(rule.keyword, WindowRuleKeyword::WindowRule);
+        assert_eq!(
+            rule.matchers,
+            vec![WindowMatcher::Other {
+                key: String::new(),
+                value: "^(pavucontrol)$".to_string()
+            }]
+        );
+
+        let rule = parse_window_rule_line("windowrule = pin, tag:pip").unwrap();
+        assert_eq!(
+            rule.matchers,
+            vec![WindowMatcher::Other {
+                key: "tag".to_string(),
+                value: "pip".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_window_rule_line_rejects_rules_without_matchers() {
+        assert!(parse_window_rule_line("windowrule = float").is_none());
+        assert!(parse_window_rule_line("windowrule = , class:kitty").is_none());
+        assert!(parse_window_rule_line("layerrule = blur, waybar").is_none());
+    }
+
+    #[test]
+    fn parse_workspace_rule_line_options() {
+        let rule =
+            parse_workspace_rule_line("workspace = 1, monitor:DP-1, default:true, gapsout:0")
+                .unwrap();
+        assert_eq!(rule.workspace, "1");
+        assert_eq!(
+            rule.options,
+            vec![
+                WorkspaceRuleOption::Monitor("DP-1".to_string()),
+                WorkspaceRuleOption::Default(true),
+                WorkspaceRuleOption::Other {
+                    key: "gapsout".to_string(),
+                    value: "0".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rules_splits_window_and_workspace_rules() {
+        let input = "# header\nwindowrule = float, class:kitty\nworkspace = special:scratch, on-created-empty:kitty\nbind = SUPER, Q, killactive,";
+        let rules = parse_rules(input);
+        assert_eq!(rules.window_rules.len(), 1);
+        assert_eq!(rules.workspace_rules.len(), 1);
+        assert_eq!(
+            rules.workspace_rules[0].options,
+            vec![WorkspaceRuleOption::OnCreatedEmpty("kitty".to_string())]
+        );
+    }
+}
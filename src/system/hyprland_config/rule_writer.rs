@@ -0,0 +1,148 @@
+use crate::types::hyprland_config::*;
+use std::fmt::Write;
+
+pub fn write_rules(rules: &RuleSet) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "# Omarchist Hyprland Rules\n# Generated automatically - Do not edit manually\n"
+    )
+    .unwrap();
+
+    for rule in &rules.window_rules {
+        writeln!(output, "{}", write_window_rule(rule)).unwrap();
+    }
+
+    if !rules.window_rules.is_empty() && !rules.workspace_rules.is_empty() {
+        writeln!(output).unwrap();
+    }
+
+    for rule in &rules.workspace_rules {
+        writeln!(output, "{}", write_workspace_rule(rule)).unwrap();
+    }
+
+    output
+}
+
+pub fn write_window_rule(rule: &WindowRule) -> String {
+    let keyword = match rule.keyword {
+        WindowRuleKeyword::WindowRule => "windowrule",
+        WindowRuleKeyword::WindowRuleV2 => "windowrulev2",
+    };
+
+    let mut fields = vec![format_action(&rule.action)];
+    fields.extend(rule.matchers.iter().map(format_matcher));
+
+    format!("{} = {}", keyword, fields.join(", "))
+}
+
+pub fn write_workspace_rule(rule: &WorkspaceRule) -> String {
+    let mut fields = vec![rule.workspace.clone()];
+    fields.extend(rule.options.iter().map(format_workspace_option));
+
+    format!("workspace = {}", fields.join(", "))
+}
+
+pub fn format_action(action: &WindowRuleAction) -> String {
+    match action {
+        WindowRuleAction::Float => "float".to_string(),
+        WindowRuleAction::Tile => "tile".to_string(),
+        WindowRuleAction::Fullscreen => "fullscreen".to_string(),
+        WindowRuleAction::Maximize => "maximize".to_string(),
+        WindowRuleAction::Pin => "pin".to_string(),
+        WindowRuleAction::Center => "center".to_string(),
+        WindowRuleAction::Size { width, height } => format!("size {} {}", width, height),
+        WindowRuleAction::Move { x, y } => format!("move {} {}", x, y),
+        WindowRuleAction::Opacity(values) => format!("opacity {}", values),
+        WindowRuleAction::Workspace { workspace, silent } => {
+            if *silent {
+                format!("workspace {} silent", workspace)
+            } else {
+                format!("workspace {}", workspace)
+            }
+        }
+        WindowRuleAction::Other(action) => action.clone(),
+    }
+}
+
+pub fn format_matcher(matcher: &WindowMatcher) -> String {
+    let flag = |value: bool| if value { "1" } else { "0" };
+
+    match matcher {
+        WindowMatcher::Class(regex) => format!("class:{}", regex),
+        WindowMatcher::Title(regex) => format!("title:{}", regex),
+        WindowMatcher::InitialClass(regex) => format!("initialClass:{}", regex),
+        WindowMatcher::InitialTitle(regex) => format!("initialTitle:{}", regex),
+        WindowMatcher::Floating(value) => format!("floating:{}", flag(*value)),
+        WindowMatcher::XWayland(value) => format!("xwayland:{}", flag(*value)),
+        WindowMatcher::Fullscreen(value) => format!("fullscreen:{}", flag(*value)),
+        WindowMatcher::Pinned(value) => format!("pinned:{}", flag(*value)),
+        WindowMatcher::Workspace(value) => format!("workspace:{}", value),
+        WindowMatcher::Other { key, value } if key.is_empty() => value.clone(),
+        WindowMatcher::Other { key, value } => format!("{}:{}", key, value),
+    }
+}
+
+pub fn format_workspace_option(option: &WorkspaceRuleOption) -> String {
+    match option {
+        WorkspaceRuleOption::Monitor(monitor) => format!("monitor:{}", monitor),
+        WorkspaceRuleOption::Default(value) => format!("default:{}", value),
+        WorkspaceRuleOption::Persistent(value) => format!("persistent:{}", value),
+        WorkspaceRuleOption::OnCreatedEmpty(command) => format!("on-created-empty:{}", command),
+        WorkspaceRuleOption::Other { key, value } if value.is_empty() => key.clone(),
+        WorkspaceRuleOption::Other { key, value } => format!("{}:{}", key, value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::hyprland_config::rule_parser::{
+        parse_rules, parse_window_rule_line, parse_workspace_rule_line,
+    };
+
+    #[test]
+    fn write_window_rule_round_trips() {
+        for line in [
+            "windowrule = float, class:^(org.gnome.Calculator)$",
+            "windowrulev2 = size 800 600, class:^(kitty)$, floating:1",
+            "windowrule = workspace 3 silent, initialClass:^(steam)$",
+            "windowrule = opacity 0.97 0.9, class:.*",
+            "windowrule = idleinhibit fullscreen, fullscreen:1, tag:video",
+            "windowrule = float, ^(pavucontrol)$",
+        ] {
+            let rule = parse_window_rule_line(line).unwrap();
+            assert_eq!(
+                write_window_rule(&rule),
+                line,
+                "'{}' should round trip",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn write_workspace_rule_round_trips() {
+        for line in [
+            "workspace = 1, monitor:DP-1, default:true",
+            "workspace = special:scratch, on-created-empty:kitty, persistent:false",
+            "workspace = w[tv1], gapsout:0, gapsin:0",
+        ] {
+            let rule = parse_workspace_rule_line(line).unwrap();
+            assert_eq!(
+                write_workspace_rule(&rule),
+                line,
+                "'{}' should round trip",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn write_rules_parses_back() {
+        let input = "windowrule = float, class:kitty\nworkspace = 2, monitor:HDMI-A-1";
+        let rules = parse_rules(input);
+        assert_eq!(parse_rules(&write_rules(&rules)), rules);
+    }
+}
@@ -0,0 +1,243 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::types::hyprland_config::*;
+
+use super::manager::{config_dir, ensure_config_dir};
+use super::rule_parser::parse_rules;
+use super::rule_writer::write_rules;
+use super::validation::ConfigWrite;
+
+const RULES_FILE: &str = "rules.conf";
+
+/// The JSON shape of one entry returned by `hyprctl clients -j`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HyprctlClient {
+    #[serde(default)]
+    class: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    initial_class: String,
+    #[serde(default)]
+    initial_title: String,
+    #[serde(default)]
+    floating: bool,
+    #[serde(default)]
+    xwayland: bool,
+    #[serde(default)]
+    workspace: HyprctlWorkspace,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HyprctlWorkspace {
+    #[serde(default)]
+    name: String,
+}
+
+impl From<HyprctlClient> for ClientInfo {
+    fn from(client: HyprctlClient) -> Self {
+        Self {
+            class: client.class,
+            title: client.title,
+            initial_class: client.initial_class,
+            initial_title: client.initial_title,
+            floating: client.floating,
+            xwayland: client.xwayland,
+            workspace: client.workspace.name,
+        }
+    }
+}
+
+/// Parse the output of `hyprctl clients -j`.
+pub fn parse_clients_json(json: &str) -> Result<Vec<ClientInfo>, String> {
+    let clients: Vec<HyprctlClient> =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse clients: {}", e))?;
+    Ok(clients
+        .into_iter()
+        // Windows that have not mapped yet have no class to match on
+        .filter(|client| !client.class.is_empty())
+        .map(ClientInfo::from)
+        .collect())
+}
+
+/// Run `hyprctl clients -j` to list the open windows.
+pub fn read_clients() -> Result<Vec<ClientInfo>, String> {
+    let output = Command::new("hyprctl")
+        .args(["clients", "-j"])
+        .output()
+        .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
+
+    if !output.status.success() {
+        return Err("hyprctl could not list windows. Is Hyprland running?".to_string());
+    }
+
+    parse_clients_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// A floating rule for an open window, matching its class exactly. The title
+/// is pre-filled too since it is the next most useful thing to narrow on.
+pub fn rule_from_client(client: &ClientInfo) -> WindowRule {
+    let mut matchers = vec![WindowMatcher::Class(exact_regex(&client.class))];
+    if !client.title.is_empty() {
+        matchers.push(WindowMatcher::Title(exact_regex(&client.title)));
+    }
+
+    WindowRule {
+        keyword: WindowRuleKeyword::default(),
+        action: WindowRuleAction::Float,
+        matchers,
+    }
+}
+
+// `^(...)$` with regex metacharacters escaped. Commas separate rule fields, so
+// they are matched with `.` instead.
+fn exact_regex(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        match c {
+            ',' => escaped.push('.'),
+            '\\' | '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    format!("^({})$", escaped)
+}
+
+pub struct RuleManager {
+    rules_path: PathBuf,
+    rules: RuleSet,
+}
+
+impl RuleManager {
+    pub fn load() -> Result<Self, String> {
+        ensure_config_dir()?;
+        let rules_path = config_dir()?.join(RULES_FILE);
+
+        let rules = if rules_path.exists() {
+            let content = fs::read_to_string(&rules_path)
+                .map_err(|e| format!("Failed to read rules file: {}", e))?;
+            parse_rules(&content)
+        } else {
+            RuleSet::default()
+        };
+
+        Ok(Self { rules_path, rules })
+    }
+
+    // The write that saves the current state, see `ConfigWrite`
+    pub fn save_write(&self) -> ConfigWrite {
+        ConfigWrite::new(&self.rules_path, write_rules(&self.rules))
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    // Replace the rule at `index`, or append when there is none
+    pub fn set_window_rule(&mut self, index: Option<usize>, rule: WindowRule) {
+        set_item(&mut self.rules.window_rules, index, rule);
+    }
+
+    pub fn remove_window_rule(&mut self, index: usize) {
+        if index < self.rules.window_rules.len() {
+            self.rules.window_rules.remove(index);
+        }
+    }
+
+    // Later rules win in Hyprland, so order matters
+    pub fn move_window_rule(&mut self, from: usize, to: usize) {
+        move_item(&mut self.rules.window_rules, from, to);
+    }
+
+    pub fn set_workspace_rule(&mut self, index: Option<usize>, rule: WorkspaceRule) {
+        set_item(&mut self.rules.workspace_rules, index, rule);
+    }
+
+    pub fn remove_workspace_rule(&mut self, index: usize) {
+        if index < self.rules.workspace_rules.len() {
+            self.rules.workspace_rules.remove(index);
+        }
+    }
+
+    pub fn move_workspace_rule(&mut self, from: usize, to: usize) {
+        move_item(&mut self.rules.workspace_rules, from, to);
+    }
+
+    pub fn rules_path(&self) -> &Path {
+        &self.rules_path
+    }
+}
+
+fn set_item<T>(items: &mut Vec<T>, index: Option<usize>, item: T) {
+    match index {
+        Some(index) if index < items.len() => items[index] = item,
+        _ => items.push(item),
+    }
+}
+
+fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    if from < items.len() && to < items.len() {
+        let item = items.remove(from);
+        items.insert(to, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENTS: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/hyprctl/clients.json"
+    ));
+
+    #[test]
+    fn parse_clients_json_reads_windows() {
+        let clients = parse_clients_json(CLIENTS).expect("fixture should parse");
+        assert_eq!(clients.len(), 3);
+        assert_eq!(clients[1].class, "org.gnome.Calculator");
+        assert!(clients[1].floating);
+        assert!(clients[2].xwayland);
+        assert_eq!(clients[2].workspace, "special:scratch");
+    }
+
+    #[test]
+    fn rule_from_client_matches_class_and_title_exactly() {
+        let clients = parse_clients_json(CLIENTS).unwrap();
+        let rule = rule_from_client(&clients[0]);
+        assert_eq!(rule.action, WindowRuleAction::Float);
+        assert_eq!(
+            rule.matchers,
+            vec![
+                WindowMatcher::Class("^(Alacritty)$".to_string()),
+                WindowMatcher::Title("^(nvim ~/\\.config/hypr)$".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn exact_regex_escapes_metacharacters_and_commas() {
+        assert_eq!(
+            exact_regex("org.gnome.Calculator"),
+            "^(org\\.gnome\\.Calculator)$"
+        );
+        assert_eq!(exact_regex("a, b (c)"), "^(a. b \\(c\\))$");
+    }
+
+    #[test]
+    fn move_item_reorders() {
+        let mut items = vec!["a", "b", "c"];
+        move_item(&mut items, 2, 0);
+        assert_eq!(items, vec!["c", "a", "b"]);
+        move_item(&mut items, 0, 5);
+        assert_eq!(items, vec!["c", "a", "b"]);
+    }
+}
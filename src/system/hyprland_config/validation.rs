@@ -0,0 +1,370 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Saves run on background threads, one at a time so a rollback never
+// restores over another save. Holds the newest `ConfigWrite` written to each
+// file, so one that was overtaken is dropped rather than undoing a newer one.
+static WRITES: Mutex<Vec<(PathBuf, u64)>> = Mutex::new(Vec::new());
+static NEXT_WRITE: AtomicU64 = AtomicU64::new(0);
+// Writes created before this were superseded, see `supersede_writes`
+static FIRST_CURRENT_WRITE: AtomicU64 = AtomicU64::new(0);
+
+/// One entry of `hyprctl configerrors -j`, e.g.
+/// `Config error in file /path/hyprland.conf at line 12: invalid field foo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Parse the output of `hyprctl configerrors -j`, a list of strings holding a
+/// single empty string when there are no errors.
+pub fn parse_config_errors_json(json: &str) -> Result<Vec<ConfigError>, String> {
+    let errors: Vec<String> =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse config errors: {}", e))?;
+
+    Ok(errors
+        .iter()
+        .flat_map(|error| error.lines())
+        .map(str::trim)
+        .filter(|error| !error.is_empty())
+        .map(parse_config_error)
+        .collect())
+}
+
+/// Split an error into file, line and message. Errors that don't name a
+/// file are kept as a bare message.
+pub fn parse_config_error(error: &str) -> ConfigError {
+    let located = error
+        .strip_prefix("Config error in file ")
+        .and_then(|rest| rest.split_once(" at line "))
+        .and_then(|(file, rest)| {
+            let (line, message) = rest.split_once(':')?;
+            Some(ConfigError {
+                file: Some(PathBuf::from(file.trim())),
+                line: line.trim().parse().ok(),
+                message: message.trim().to_string(),
+            })
+        });
+
+    located.unwrap_or_else(|| ConfigError {
+        file: None,
+        line: None,
+        message: error.to_string(),
+    })
+}
+
+/// A file for `write_and_reload`, prepared on the UI thread so the blocking
+/// write and reload can run on another one.
+#[derive(Debug, Clone)]
+pub struct ConfigWrite {
+    pub path: PathBuf,
+    pub content: String,
+    // Creation order, which is the order the writes should land in
+    sequence: u64,
+}
+
+impl ConfigWrite {
+    pub fn new(path: &Path, content: String) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            content,
+            sequence: NEXT_WRITE.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Blocks until Hyprland has reloaded, see `write_and_reload`. Does
+    /// nothing when a write created after this one already ran.
+    pub fn run(&self) -> Result<(), String> {
+        self.run_with_cmd("hyprctl")
+    }
+
+    fn run_with_cmd(&self, cmd: &str) -> Result<(), String> {
+        let mut writes = WRITES.lock().unwrap_or_else(|e| e.into_inner());
+        if self.sequence < FIRST_CURRENT_WRITE.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        match writes.iter_mut().find(|(path, _)| *path == self.path) {
+            Some((_, newest)) if *newest > self.sequence => return Ok(()),
+            Some((_, newest)) => *newest = self.sequence,
+            None => writes.push((self.path.clone(), self.sequence)),
+        }
+        write_and_reload_with_cmd(cmd, &self.path, &self.content)
+    }
+}
+
+/// Run `replace`, e.g. putting a profile in place, once no write is in
+/// flight. Writes created before it are dropped rather than landing on top of
+/// the files it put there.
+pub fn supersede_writes<T>(replace: impl FnOnce() -> T) -> T {
+    let _writes = WRITES.lock().unwrap_or_else(|e| e.into_inner());
+    FIRST_CURRENT_WRITE.store(NEXT_WRITE.load(Ordering::Relaxed), Ordering::Relaxed);
+    replace()
+}
+
+/// Write `content` to one of Omarchist's sourced files and reload Hyprland.
+/// If Hyprland then reports new errors in the file, the previous version is
+/// put back, Hyprland is reloaded again and the errors are returned.
+pub fn write_and_reload(path: &Path, content: &str) -> Result<(), String> {
+    ConfigWrite::new(path, content.to_string()).run()
+}
+
+fn write_and_reload_with_cmd(cmd: &str, path: &Path, content: &str) -> Result<(), String> {
+    // Kept in memory rather than next to the file, everything in Omarchist's
+    // directory is sourced by Hyprland
+    let backup = if path.exists() {
+        Some(
+            fs::read_to_string(path)
+                .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?,
+        )
+    } else {
+        None
+    };
+
+    // Errors the file already had, e.g. from hand edits, are not ours to undo
+    let existing = file_errors(cmd, path).unwrap_or_default();
+
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    if !reload(cmd) {
+        // Without a running Hyprland there is nothing to check against
+        return Ok(());
+    }
+
+    let introduced: Vec<ConfigError> = file_errors(cmd, path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|error| !existing.iter().any(|e| e.message == error.message))
+        .collect();
+    if introduced.is_empty() {
+        return Ok(());
+    }
+
+    // Put the previous file back so Hyprland keeps running on a config it accepts
+    let restored = match backup {
+        Some(previous) => fs::write(path, previous),
+        None => fs::remove_file(path),
+    };
+    restored.map_err(|e| format!("Failed to restore {}: {}", path.display(), e))?;
+    reload(cmd);
+
+    let messages: Vec<String> = introduced.iter().map(ToString::to_string).collect();
+    Err(format!(
+        "Hyprland rejected the change, it was rolled back:\n{}",
+        messages.join("\n")
+    ))
+}
+
+/// Run `hyprctl reload` and wait for it, e.g. after switching profiles.
+pub fn reload_hyprland() -> bool {
+    reload("hyprctl")
+}
+
+fn reload(cmd: &str) -> bool {
+    Command::new(cmd)
+        .arg("reload")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+// Errors Hyprland currently reports for `path`
+fn file_errors(cmd: &str, path: &Path) -> Option<Vec<ConfigError>> {
+    let output = Command::new(cmd)
+        .args(["configerrors", "-j"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let errors = parse_config_errors_json(&String::from_utf8_lossy(&output.stdout)).ok()?;
+    Some(
+        errors
+            .into_iter()
+            .filter(|error| error.file.as_deref() == Some(path))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    // A fake `hyprctl` that reports an error for every line of `conf`
+    // containing "broken", the way Hyprland does after a reload
+    fn stub_hyprctl(dir: &Path, conf: &Path) -> PathBuf {
+        let script = format!(
+            r#"#!/bin/sh
+case "$1" in
+  reload) echo ok ;;
+  configerrors)
+    line=$(grep -n broken "{conf}" 2>/dev/null | head -n1 | cut -d: -f1)
+    if [ -n "$line" ]; then
+      printf '["Config error in file {conf} at line %s: invalid field broken"]' "$line"
+    else
+      printf '[""]'
+    fi ;;
+esac
+"#,
+            conf = conf.display()
+        );
+        let path = dir.join("hyprctl");
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("omarchist-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_config_errors_json_reads_file_line_and_message() {
+        let json = r#"["Config error in file /home/u/.config/omarchist/hyprland/hyprland.conf at line 7: invalid field gaps: value out of range\nConfig error in file /home/u/.config/hypr/hyprland.conf at line 3: source= globbing error"]"#;
+        let errors = parse_config_errors_json(json).expect("json should parse");
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].file.as_deref(),
+            Some(Path::new(
+                "/home/u/.config/omarchist/hyprland/hyprland.conf"
+            ))
+        );
+        assert_eq!(errors[0].line, Some(7));
+        assert_eq!(errors[0].message, "invalid field gaps: value out of range");
+        assert_eq!(
+            errors[0].to_string(),
+            "line 7: invalid field gaps: value out of range"
+        );
+    }
+
+    #[test]
+    fn parse_config_errors_json_without_errors_is_empty() {
+        assert!(parse_config_errors_json(r#"[""]"#).unwrap().is_empty());
+        assert!(parse_config_errors_json("[]").unwrap().is_empty());
+        assert!(parse_config_errors_json("not json").is_err());
+    }
+
+    #[test]
+    fn parse_config_error_keeps_unrecognised_errors() {
+        let error = parse_config_error("Something went wrong");
+        assert_eq!(error.file, None);
+        assert_eq!(error.message, "Something went wrong");
+    }
+
+    #[test]
+    fn write_and_reload_keeps_accepted_changes() {
+        let dir = temp_dir("reload-ok");
+        let conf = dir.join("hyprland.conf");
+        fs::write(&conf, "general {\n    gaps_in = 3\n}\n").unwrap();
+        let hyprctl = stub_hyprctl(&dir, &conf);
+
+        let result = write_and_reload_with_cmd(
+            hyprctl.to_str().unwrap(),
+            &conf,
+            "general {\n    gaps_in = 6\n}\n",
+        );
+
+        assert!(
+            result.is_ok(),
+            "expected Ok for a valid file, got {:?}",
+            result
+        );
+        assert!(fs::read_to_string(&conf).unwrap().contains("gaps_in = 6"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn write_and_reload_rolls_back_on_new_errors() {
+        let dir = temp_dir("reload-rollback");
+        let conf = dir.join("hyprland.conf");
+        fs::write(&conf, "general {\n    gaps_in = 3\n}\n").unwrap();
+        let hyprctl = stub_hyprctl(&dir, &conf);
+
+        let result = write_and_reload_with_cmd(
+            hyprctl.to_str().unwrap(),
+            &conf,
+            "general {\n    broken = 1\n}\n",
+        );
+
+        let msg = result.expect_err("expected Err when Hyprland reports errors");
+        assert!(
+            msg.contains("line 2: invalid field broken"),
+            "error message should contain the parsed error, got: {msg}"
+        );
+        assert!(fs::read_to_string(&conf).unwrap().contains("gaps_in = 3"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn write_and_reload_ignores_errors_the_file_already_had() {
+        let dir = temp_dir("reload-existing");
+        let conf = dir.join("hyprland.conf");
+        fs::write(&conf, "broken = 1\n").unwrap();
+        let hyprctl = stub_hyprctl(&dir, &conf);
+
+        let result =
+            write_and_reload_with_cmd(hyprctl.to_str().unwrap(), &conf, "broken = 1\nvfr = true\n");
+
+        assert!(
+            result.is_ok(),
+            "expected Ok for a pre-existing error, got {:?}",
+            result
+        );
+        assert!(fs::read_to_string(&conf).unwrap().contains("vfr = true"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn write_and_reload_without_hyprland_just_writes() {
+        let dir = temp_dir("reload-missing");
+        let conf = dir.join("rules.conf");
+
+        let result =
+            write_and_reload_with_cmd("__omarchist_nonexistent_binary__", &conf, "vfr = true\n");
+
+        assert!(
+            result.is_ok(),
+            "expected Ok without hyprctl, got {:?}",
+            result
+        );
+        assert_eq!(fs::read_to_string(&conf).unwrap(), "vfr = true\n");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn overtaken_writes_are_dropped() {
+        let dir = temp_dir("write-order");
+        let conf = dir.join("rules.conf");
+        let missing = "__omarchist_nonexistent_binary__";
+
+        let older = ConfigWrite::new(&conf, "vfr = false\n".to_string());
+        let newer = ConfigWrite::new(&conf, "vfr = true\n".to_string());
+        newer.run_with_cmd(missing).unwrap();
+        older.run_with_cmd(missing).unwrap();
+        assert_eq!(fs::read_to_string(&conf).unwrap(), "vfr = true\n");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::hyprland_config::*;
+use serde_json::Value;
 use std::fmt::Write;
 
 pub fn write_config(config: &HyprlandConfig) -> String {
@@ -27,10 +28,98 @@ pub fn write_config(config: &HyprlandConfig) -> String {
     write_ecosystem_section(&mut output, &config.ecosystem);
     write_quirks_section(&mut output, &config.quirks);
     write_debug_section(&mut output, &config.debug);
+    write_device_sections(&mut output, &config.devices);
 
     output
 }
 
+/// Every option in `config` as `(id, value)` pairs, using the `section:key`
+/// ids of [`HyprlandDocument`](super::document::HyprlandDocument). Unlike
+/// [`write_config`] this includes options at Hyprland's default, so a value
+/// changed back to the default can still be written.
+pub fn config_settings(config: &HyprlandConfig) -> Vec<(String, String)> {
+    let mut settings = Vec::new();
+    // The option structs use Hyprland's key names for their fields
+    if let Ok(value) = serde_json::to_value(config) {
+        collect_settings(&mut settings, "", &value);
+    }
+
+    // The shadow offset is a single `offset = x y` option
+    let shadow = &config.decoration.shadow;
+    settings.retain(|(id, _)| !id.starts_with("decoration:shadow:offset_"));
+    settings.push((
+        "decoration:shadow:offset".to_string(),
+        format!("{} {}", shadow.offset_x, shadow.offset_y),
+    ));
+
+    // Curves and animations are keywords, addressed by their name
+    for bezier in &config.animations.beziers {
+        settings.push((
+            format!("bezier:{}", bezier.name),
+            keyword_value(&write_bezier(bezier)),
+        ));
+    }
+    for animation in &config.animations.animations {
+        settings.push((
+            format!("animation:{}", animation.name),
+            keyword_value(&write_animation(animation)),
+        ));
+    }
+
+    // Each device has its own block, addressed by the device's name
+    for device in &config.devices {
+        for (key, value) in device_settings(device) {
+            settings.push((format!("device:{}:{}", device.name, key), value));
+        }
+    }
+
+    settings
+}
+
+// The options a device overrides, as `(key, value)` pairs
+fn device_settings(device: &DeviceConfig) -> Vec<(String, String)> {
+    let mut settings = Vec::new();
+    if let Ok(value) = serde_json::to_value(device) {
+        collect_settings(&mut settings, "", &value);
+    }
+    // The name is the block's header line, not an option
+    settings.retain(|(key, _)| key != "name");
+    settings
+}
+
+fn collect_settings(settings: &mut Vec<(String, String)>, path: &str, value: &Value) {
+    let formatted = match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let id = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}:{}", path, key)
+                };
+                collect_settings(settings, &id, value);
+            }
+            return;
+        }
+        Value::Bool(value) => value.to_string(),
+        Value::Number(number) => match number.as_i64() {
+            Some(int) => int.to_string(),
+            None => number.as_f64().unwrap_or_default().to_string(),
+        },
+        Value::String(value) => value.clone(),
+        // Lists are written by the caller
+        Value::Array(_) | Value::Null => return,
+    };
+
+    settings.push((path.to_string(), formatted));
+}
+
+// `bezier = name, ...` -> `name, ...`
+fn keyword_value(line: &str) -> String {
+    line.split_once('=')
+        .map(|(_, value)| value.trim().to_string())
+        .unwrap_or_default()
+}
+
 fn write_general_section(output: &mut String, config: &GeneralConfig) {
     writeln!(output, "general {{").unwrap();
 
@@ -300,7 +389,11 @@ fn write_decoration_section(output: &mut String, config: &DecorationConfig) {
 }
 
 fn write_animations_section(output: &mut String, config: &AnimationsConfig) {
-    if !config.enabled || config.workspace_wraparound {
+    if !config.enabled
+        || config.workspace_wraparound
+        || !config.beziers.is_empty()
+        || !config.animations.is_empty()
+    {
         writeln!(output, "animations {{").unwrap();
         if !config.enabled {
             writeln!(output, "    enabled = false").unwrap();
@@ -308,11 +401,57 @@ fn write_animations_section(output: &mut String, config: &AnimationsConfig) {
         if config.workspace_wraparound {
             writeln!(output, "    workspace_wraparound = true").unwrap();
         }
+        // Curves first, animations can only use curves defined before them
+        for bezier in &config.beziers {
+            writeln!(output, "    {}", write_bezier(bezier)).unwrap();
+        }
+        for animation in &config.animations {
+            writeln!(output, "    {}", write_animation(animation)).unwrap();
+        }
         writeln!(output, "}}").unwrap();
         writeln!(output).unwrap();
     }
 }
 
+pub fn write_bezier(bezier: &BezierCurve) -> String {
+    format!(
+        "bezier = {}, {}, {}, {}, {}",
+        bezier.name,
+        format_number(bezier.x1),
+        format_number(bezier.y1),
+        format_number(bezier.x2),
+        format_number(bezier.y2)
+    )
+}
+
+pub fn write_animation(animation: &Animation) -> String {
+    let onoff = if animation.enabled { 1 } else { 0 };
+    if animation.curve.is_empty() {
+        return format!("animation = {}, {}", animation.name, onoff);
+    }
+
+    let mut line = format!(
+        "animation = {}, {}, {}, {}",
+        animation.name,
+        onoff,
+        format_number(animation.speed),
+        animation.curve
+    );
+    if let Some(ref style) = animation.style {
+        write!(line, ", {}", style).unwrap();
+    }
+    line
+}
+
+// Up to three decimals without trailing zeros, e.g. `0.23` or `1`
+fn format_number(value: f64) -> String {
+    let formatted = format!("{:.3}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
 fn write_input_section(output: &mut String, config: &InputConfig) {
     writeln!(output, "input {{").unwrap();
 
@@ -1016,6 +1155,23 @@ fn write_quirks_section(output: &mut String, config: &QuirksConfig) {
     }
 }
 
+fn write_device_sections(output: &mut String, devices: &[DeviceConfig]) {
+    for device in devices {
+        let settings = device_settings(device);
+        if device.name.is_empty() || settings.is_empty() {
+            continue;
+        }
+
+        writeln!(output, "device {{").unwrap();
+        writeln!(output, "    name = {}", device.name).unwrap();
+        for (key, value) in settings {
+            writeln!(output, "    {} = {}", key, value).unwrap();
+        }
+        writeln!(output, "}}").unwrap();
+        writeln!(output).unwrap();
+    }
+}
+
 fn write_debug_section(output: &mut String, config: &DebugConfig) {
     if config.overlay
         || config.damage_blink
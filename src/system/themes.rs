@@ -1,10 +1,16 @@
 pub mod color_extractor;
 pub mod color_utils;
+pub mod contrast;
 pub mod custom_themes;
 pub mod parse_colors;
 pub mod preview_img;
 pub mod system_themes;
+pub mod theme_bundle;
+pub mod theme_convert;
 pub mod theme_file_ops;
 pub mod theme_generator;
+pub mod theme_history;
+pub mod theme_install;
 pub mod theme_management;
+pub mod theme_snapshots;
 pub mod utils;
@@ -2,11 +2,12 @@ use std::path::Path;
 
 use image::imageops::FilterType;
 use palette::{FromColor, Hsl, Hsv, Srgb};
+use serde::Serialize;
 
 use crate::system::themes::color_utils::{darken_color, lighten_color};
 use crate::types::themes::TerminalPalette;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum ImageType {
     Monochrome,
     LowDiversity,
@@ -22,7 +23,7 @@ pub struct ColorInfo {
     pub hsv: Hsv,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ColorPalette {
     pub background: String,
     pub foreground: String,
@@ -102,6 +103,14 @@ fn analyze_image_type(colors: &[ColorInfo]) -> ImageType {
 }
 
 pub fn extract_palette(image_path: &Path) -> Result<ColorPalette, String> {
+    extract_palette_with_mode(image_path, None)
+}
+
+// Same as `extract_palette`, but `force_light` overrides the luminance-based light/dark detection.
+pub fn extract_palette_with_mode(
+    image_path: &Path,
+    force_light: Option<bool>,
+) -> Result<ColorPalette, String> {
     let img = image::open(image_path).map_err(|e| format!("Failed to open image: {}", e))?;
 
     let resized = img.resize(800, 600, FilterType::Triangle);
@@ -132,7 +141,7 @@ pub fn extract_palette(image_path: &Path) -> Result<ColorPalette, String> {
 
     let avg_luminance: f32 =
         analyzed.iter().map(|c| c.hsl.lightness).sum::<f32>() / analyzed.len() as f32;
-    let is_light_theme = avg_luminance > 0.55;
+    let is_light_theme = force_light.unwrap_or(avg_luminance > 0.55);
 
     let mut sorted_by_lightness = analyzed.clone();
     sorted_by_lightness.sort_by(|a, b| a.hsl.lightness.total_cmp(&b.hsl.lightness));
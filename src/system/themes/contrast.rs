@@ -0,0 +1,510 @@
+// WCAG 2.x contrast checks for the foreground/background pairs of every app config.
+
+use super::color_utils::{adjust_lightness, hex_to_rgb};
+use crate::types::themes::{EditingTheme, TerminalPalette, ThemeEditTab};
+
+// WCAG AA for body text
+pub const AA_NORMAL_TEXT: f32 = 4.5;
+// WCAG AA for large text and UI components. Used for ANSI colors, which are
+// accents in terminal output rather than body text.
+pub const AA_LARGE_TEXT: f32 = 3.0;
+
+const FIX_STEP: f32 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl AnsiColor {
+    pub fn all() -> [AnsiColor; 7] {
+        [
+            AnsiColor::Red,
+            AnsiColor::Green,
+            AnsiColor::Yellow,
+            AnsiColor::Blue,
+            AnsiColor::Magenta,
+            AnsiColor::Cyan,
+            AnsiColor::White,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnsiColor::Red => "red",
+            AnsiColor::Green => "green",
+            AnsiColor::Yellow => "yellow",
+            AnsiColor::Blue => "blue",
+            AnsiColor::Magenta => "magenta",
+            AnsiColor::Cyan => "cyan",
+            AnsiColor::White => "white",
+        }
+    }
+
+    fn field<'a>(&self, palette: &'a TerminalPalette) -> &'a String {
+        match self {
+            AnsiColor::Red => &palette.red,
+            AnsiColor::Green => &palette.green,
+            AnsiColor::Yellow => &palette.yellow,
+            AnsiColor::Blue => &palette.blue,
+            AnsiColor::Magenta => &palette.magenta,
+            AnsiColor::Cyan => &palette.cyan,
+            AnsiColor::White => &palette.white,
+        }
+    }
+
+    fn field_mut<'a>(&self, palette: &'a mut TerminalPalette) -> &'a mut String {
+        match self {
+            AnsiColor::Red => &mut palette.red,
+            AnsiColor::Green => &mut palette.green,
+            AnsiColor::Yellow => &mut palette.yellow,
+            AnsiColor::Blue => &mut palette.blue,
+            AnsiColor::Magenta => &mut palette.magenta,
+            AnsiColor::Cyan => &mut palette.cyan,
+            AnsiColor::White => &mut palette.white,
+        }
+    }
+}
+
+// A foreground/background pair that has to stay readable. Fixes always change
+// the foreground so backgrounds, which define the look of a theme, stay put.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContrastTarget {
+    TerminalForeground,
+    TerminalNormal(AnsiColor),
+    TerminalBright(AnsiColor),
+    WaybarForeground,
+    WalkerForeground,
+    WalkerText,
+    WalkerSelectedText,
+    MakoText,
+    HyprlockFont,
+    SwayosdLabel,
+    BtopMain,
+    BtopTitle,
+    BtopHighlight,
+    BtopSelected,
+}
+
+impl ContrastTarget {
+    pub fn all() -> Vec<ContrastTarget> {
+        let mut targets = vec![ContrastTarget::TerminalForeground];
+        targets.extend(AnsiColor::all().map(ContrastTarget::TerminalNormal));
+        targets.extend(AnsiColor::all().map(ContrastTarget::TerminalBright));
+        targets.extend([
+            ContrastTarget::WaybarForeground,
+            ContrastTarget::WalkerForeground,
+            ContrastTarget::WalkerText,
+            ContrastTarget::WalkerSelectedText,
+            ContrastTarget::MakoText,
+            ContrastTarget::HyprlockFont,
+            ContrastTarget::SwayosdLabel,
+            ContrastTarget::BtopMain,
+            ContrastTarget::BtopTitle,
+            ContrastTarget::BtopHighlight,
+            ContrastTarget::BtopSelected,
+        ]);
+        targets
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ContrastTarget::TerminalForeground => "Terminal foreground".to_string(),
+            ContrastTarget::TerminalNormal(color) => format!("Terminal {}", color.as_str()),
+            ContrastTarget::TerminalBright(color) => format!("Terminal bright {}", color.as_str()),
+            ContrastTarget::WaybarForeground => "Waybar foreground".to_string(),
+            ContrastTarget::WalkerForeground => "Menu foreground".to_string(),
+            ContrastTarget::WalkerText => "Menu text".to_string(),
+            ContrastTarget::WalkerSelectedText => "Menu selected text".to_string(),
+            ContrastTarget::MakoText => "Notification text".to_string(),
+            ContrastTarget::HyprlockFont => "Lock screen font".to_string(),
+            ContrastTarget::SwayosdLabel => "SwayOSD label".to_string(),
+            ContrastTarget::BtopMain => "Btop main text".to_string(),
+            ContrastTarget::BtopTitle => "Btop title".to_string(),
+            ContrastTarget::BtopHighlight => "Btop highlight".to_string(),
+            ContrastTarget::BtopSelected => "Btop selected text".to_string(),
+        }
+    }
+
+    // The editor tab where the foreground color of this pair is edited
+    pub fn tab(&self) -> ThemeEditTab {
+        match self {
+            ContrastTarget::TerminalForeground
+            | ContrastTarget::TerminalNormal(_)
+            | ContrastTarget::TerminalBright(_) => ThemeEditTab::Terminal,
+            ContrastTarget::WaybarForeground => ThemeEditTab::Waybar,
+            ContrastTarget::WalkerForeground
+            | ContrastTarget::WalkerText
+            | ContrastTarget::WalkerSelectedText => ThemeEditTab::Menu,
+            ContrastTarget::MakoText => ThemeEditTab::Notification,
+            ContrastTarget::HyprlockFont => ThemeEditTab::LockScreen,
+            ContrastTarget::SwayosdLabel => ThemeEditTab::Swayosd,
+            ContrastTarget::BtopMain
+            | ContrastTarget::BtopTitle
+            | ContrastTarget::BtopHighlight
+            | ContrastTarget::BtopSelected => ThemeEditTab::Btop,
+        }
+    }
+
+    pub fn min_ratio(&self) -> f32 {
+        match self {
+            ContrastTarget::TerminalNormal(_) | ContrastTarget::TerminalBright(_) => AA_LARGE_TEXT,
+            _ => AA_NORMAL_TEXT,
+        }
+    }
+
+    // (foreground, background) as stored in the config. Hyprlock colors have no `#`.
+    fn colors(&self, theme: &EditingTheme) -> Option<(String, String)> {
+        let apps = &theme.apps;
+        match self {
+            ContrastTarget::TerminalForeground => apps
+                .terminal
+                .as_ref()
+                .map(|t| (t.primary.foreground.clone(), t.primary.background.clone())),
+            ContrastTarget::TerminalNormal(color) => apps
+                .terminal
+                .as_ref()
+                .map(|t| (color.field(&t.normal).clone(), t.primary.background.clone())),
+            ContrastTarget::TerminalBright(color) => apps
+                .terminal
+                .as_ref()
+                .map(|t| (color.field(&t.bright).clone(), t.primary.background.clone())),
+            ContrastTarget::WaybarForeground => apps
+                .waybar
+                .as_ref()
+                .map(|w| (w.foreground.clone(), w.background.clone())),
+            ContrastTarget::WalkerForeground => apps
+                .walker
+                .as_ref()
+                .map(|w| (w.foreground.clone(), w.background.clone())),
+            ContrastTarget::WalkerText => apps
+                .walker
+                .as_ref()
+                .map(|w| (w.text.clone(), w.base.clone())),
+            ContrastTarget::WalkerSelectedText => apps
+                .walker
+                .as_ref()
+                .map(|w| (w.selected_text.clone(), w.base.clone())),
+            ContrastTarget::MakoText => apps
+                .mako
+                .as_ref()
+                .map(|m| (m.text_color.clone(), m.background_color.clone())),
+            ContrastTarget::HyprlockFont => apps
+                .hyprlock
+                .as_ref()
+                .map(|h| (h.font_color.clone(), h.inner_color.clone())),
+            ContrastTarget::SwayosdLabel => apps
+                .swayosd
+                .as_ref()
+                .map(|s| (s.label.clone(), s.background_color.clone())),
+            ContrastTarget::BtopMain => apps
+                .btop
+                .as_ref()
+                .map(|b| (b.main_fg.clone(), b.main_bg.clone())),
+            ContrastTarget::BtopTitle => apps
+                .btop
+                .as_ref()
+                .map(|b| (b.title.clone(), b.main_bg.clone())),
+            ContrastTarget::BtopHighlight => apps
+                .btop
+                .as_ref()
+                .map(|b| (b.hi_fg.clone(), b.main_bg.clone())),
+            ContrastTarget::BtopSelected => apps
+                .btop
+                .as_ref()
+                .map(|b| (b.selected_fg.clone(), b.selected_bg.clone())),
+        }
+    }
+
+    fn set_foreground(&self, theme: &mut EditingTheme, hex: String) {
+        let apps = &mut theme.apps;
+        match self {
+            ContrastTarget::TerminalForeground => {
+                if let Some(t) = apps.terminal.as_mut() {
+                    t.primary.foreground = hex;
+                }
+            }
+            ContrastTarget::TerminalNormal(color) => {
+                if let Some(t) = apps.terminal.as_mut() {
+                    *color.field_mut(&mut t.normal) = hex;
+                }
+            }
+            ContrastTarget::TerminalBright(color) => {
+                if let Some(t) = apps.terminal.as_mut() {
+                    *color.field_mut(&mut t.bright) = hex;
+                }
+            }
+            ContrastTarget::WaybarForeground => {
+                if let Some(w) = apps.waybar.as_mut() {
+                    w.foreground = hex;
+                }
+            }
+            ContrastTarget::WalkerForeground => {
+                if let Some(w) = apps.walker.as_mut() {
+                    w.foreground = hex;
+                }
+            }
+            ContrastTarget::WalkerText => {
+                if let Some(w) = apps.walker.as_mut() {
+                    w.text = hex;
+                }
+            }
+            ContrastTarget::WalkerSelectedText => {
+                if let Some(w) = apps.walker.as_mut() {
+                    w.selected_text = hex;
+                }
+            }
+            ContrastTarget::MakoText => {
+                if let Some(m) = apps.mako.as_mut() {
+                    m.text_color = hex;
+                }
+            }
+            ContrastTarget::HyprlockFont => {
+                if let Some(h) = apps.hyprlock.as_mut() {
+                    h.font_color = hex.trim_start_matches('#').to_lowercase();
+                }
+            }
+            ContrastTarget::SwayosdLabel => {
+                if let Some(s) = apps.swayosd.as_mut() {
+                    s.label = hex;
+                }
+            }
+            ContrastTarget::BtopMain => {
+                if let Some(b) = apps.btop.as_mut() {
+                    b.main_fg = hex;
+                }
+            }
+            ContrastTarget::BtopTitle => {
+                if let Some(b) = apps.btop.as_mut() {
+                    b.title = hex;
+                }
+            }
+            ContrastTarget::BtopHighlight => {
+                if let Some(b) = apps.btop.as_mut() {
+                    b.hi_fg = hex;
+                }
+            }
+            ContrastTarget::BtopSelected => {
+                if let Some(b) = apps.btop.as_mut() {
+                    b.selected_fg = hex;
+                }
+            }
+        }
+    }
+}
+
+// A pair whose contrast ratio is below its WCAG AA minimum
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastIssue {
+    pub target: ContrastTarget,
+    pub foreground: String,
+    pub background: String,
+    pub ratio: f32,
+    pub min_ratio: f32,
+}
+
+// WCAG relative luminance of a `#RRGGBB` (or `RRGGBB`) color
+pub fn relative_luminance(hex: &str) -> Option<f32> {
+    // Ignore a trailing alpha channel
+    let (r, g, b) = hex_to_rgb(hex.trim_start_matches('#').get(..6)?)?;
+
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    Some(0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b))
+}
+
+// Contrast ratio between two colors, from 1.0 (identical) to 21.0 (black on white)
+pub fn contrast_ratio(foreground: &str, background: &str) -> Option<f32> {
+    let l1 = relative_luminance(foreground)?;
+    let l2 = relative_luminance(background)?;
+    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+
+    Some((lighter + 0.05) / (darker + 0.05))
+}
+
+// Every pair in the theme that fails its minimum contrast ratio
+pub fn check_theme_contrast(theme: &EditingTheme) -> Vec<ContrastIssue> {
+    ContrastTarget::all()
+        .into_iter()
+        .filter_map(|target| {
+            let (foreground, background) = target.colors(theme)?;
+            let ratio = contrast_ratio(&with_hash(&foreground), &with_hash(&background))?;
+            let min_ratio = target.min_ratio();
+
+            (ratio < min_ratio).then_some(ContrastIssue {
+                target,
+                foreground,
+                background,
+                ratio,
+                min_ratio,
+            })
+        })
+        .collect()
+}
+
+// Nudge the lightness of `foreground` away from `background` until the pair reaches
+// `min_ratio`. Returns None when no lightness reaches it (e.g. a mid-gray background).
+pub fn fix_contrast(foreground: &str, background: &str, min_ratio: f32) -> Option<String> {
+    let foreground = with_hash(foreground);
+    let background = with_hash(background);
+
+    if contrast_ratio(&foreground, &background)? >= min_ratio {
+        return Some(foreground);
+    }
+
+    // Move toward whichever end of the scale has more room, then try the other one
+    let bg_luminance = relative_luminance(&background)?;
+    let directions = if bg_luminance < 0.18 {
+        [FIX_STEP, -FIX_STEP]
+    } else {
+        [-FIX_STEP, FIX_STEP]
+    };
+
+    directions.into_iter().find_map(|step| {
+        let mut candidate = foreground.clone();
+        for _ in 0..(1.0 / FIX_STEP) as usize {
+            candidate = adjust_lightness(&candidate, step);
+            if contrast_ratio(&candidate, &background)? >= min_ratio {
+                return Some(candidate);
+            }
+        }
+        None
+    })
+}
+
+// Apply `fix_contrast` to one pair of the theme. Returns false if it could not be fixed.
+pub fn fix_theme_contrast(theme: &mut EditingTheme, target: ContrastTarget) -> bool {
+    let Some((foreground, background)) = target.colors(theme) else {
+        return false;
+    };
+
+    match fix_contrast(&foreground, &background, target.min_ratio()) {
+        Some(fixed) => {
+            target.set_foreground(theme, fixed);
+            true
+        }
+        None => false,
+    }
+}
+
+fn with_hash(hex: &str) -> String {
+    format!("#{}", hex.trim_start_matches('#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::themes::{MakoConfig, TerminalConfig};
+
+    #[test]
+    fn black_on_white_is_max_ratio() {
+        let ratio = contrast_ratio("#000000", "#FFFFFF").unwrap();
+        assert!((ratio - 21.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn identical_colors_have_ratio_one() {
+        let ratio = contrast_ratio("#777777", "#777777").unwrap();
+        assert!((ratio - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn ratio_accepts_colors_without_hash_and_alpha() {
+        let with = contrast_ratio("#336699", "#FFFFFF").unwrap();
+        let without = contrast_ratio("336699", "ffffffcc").unwrap();
+        assert!((with - without).abs() < 0.001);
+    }
+
+    #[test]
+    fn invalid_color_has_no_ratio() {
+        assert!(contrast_ratio("not-a-color", "#FFFFFF").is_none());
+    }
+
+    #[test]
+    fn fix_contrast_lightens_on_dark_background() {
+        let fixed = fix_contrast("#333344", "#0F0F19", AA_NORMAL_TEXT).unwrap();
+        assert!(contrast_ratio(&fixed, "#0F0F19").unwrap() >= AA_NORMAL_TEXT);
+        assert!(relative_luminance(&fixed).unwrap() > relative_luminance("#333344").unwrap());
+    }
+
+    #[test]
+    fn fix_contrast_darkens_on_light_background() {
+        let fixed = fix_contrast("#DDDDDD", "#FAFAFA", AA_NORMAL_TEXT).unwrap();
+        assert!(contrast_ratio(&fixed, "#FAFAFA").unwrap() >= AA_NORMAL_TEXT);
+    }
+
+    #[test]
+    fn fix_contrast_keeps_passing_color() {
+        assert_eq!(
+            fix_contrast("#FFFFFF", "#000000", AA_NORMAL_TEXT).as_deref(),
+            Some("#FFFFFF")
+        );
+    }
+
+    #[test]
+    fn check_theme_contrast_reports_and_fixes_low_contrast_pair() {
+        let mut theme = EditingTheme::default();
+        theme.apps.mako = Some(MakoConfig {
+            text_color: "#202030".to_string(),
+            border_color: "#00F59B".to_string(),
+            background_color: "#0F0F19".to_string(),
+        });
+
+        let issues = check_theme_contrast(&theme);
+        assert!(issues.iter().any(|i| i.target == ContrastTarget::MakoText));
+
+        assert!(fix_theme_contrast(&mut theme, ContrastTarget::MakoText));
+        let issues = check_theme_contrast(&theme);
+        assert!(!issues.iter().any(|i| i.target == ContrastTarget::MakoText));
+    }
+
+    #[test]
+    fn ansi_colors_use_large_text_threshold() {
+        let mut theme = EditingTheme::default();
+        let mut terminal = TerminalConfig::default();
+        terminal.primary.background = "#000000".to_string();
+        // About 3.6:1 on black: fine for ANSI colors, too low for body text
+        terminal.normal.red = "#D01010".to_string();
+        terminal.primary.foreground = "#D01010".to_string();
+        theme.apps.terminal = Some(terminal);
+
+        let issues = check_theme_contrast(&theme);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.target == ContrastTarget::TerminalNormal(AnsiColor::Red))
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.target == ContrastTarget::TerminalForeground)
+        );
+    }
+
+    #[test]
+    fn hyprlock_fix_is_stored_without_hash() {
+        let mut theme = EditingTheme::default();
+        let mut hyprlock = theme.apps.hyprlock.clone().unwrap_or_default();
+        hyprlock.font_color = "1a1a24".to_string();
+        hyprlock.inner_color = "0f0f19".to_string();
+        theme.apps.hyprlock = Some(hyprlock);
+
+        assert!(fix_theme_contrast(&mut theme, ContrastTarget::HyprlockFont));
+        let font_color = &theme.apps.hyprlock.as_ref().unwrap().font_color;
+        assert!(!font_color.starts_with('#'));
+        assert_eq!(font_color.len(), 6);
+    }
+}
@@ -0,0 +1,301 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::Utc;
+
+use super::custom_themes::get_user_themes_dir;
+use super::theme_management::generate_unique_theme_name_from;
+use super::theme_snapshots::SNAPSHOTS_DIR;
+use crate::types::themes::{EditingTheme, ThemeBundle, ThemeBundleFile, ThemeBundleManifest};
+
+pub const BUNDLE_EXTENSION: &str = "omarchist-theme";
+const BUNDLE_FORMAT: &str = "omarchist-theme";
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+// Pack an Omarchist theme directory into a single `.omarchist-theme` file at `dest`.
+pub fn export_theme_bundle(theme_name: &str, dest: &Path) -> Result<PathBuf, String> {
+    let themes_dir = get_user_themes_dir()
+        .ok_or_else(|| "Could not determine user themes directory".to_string())?;
+    let theme_dir = themes_dir.join(theme_name);
+
+    if !theme_dir.exists() {
+        return Err(format!("Theme '{}' not found", theme_name));
+    }
+
+    let json_path = theme_dir.join("omarchist.json");
+    if !json_path.exists() {
+        return Err(format!(
+            "Theme '{}' was not created with Omarchist and cannot be exported",
+            theme_name
+        ));
+    }
+
+    let content = fs::read_to_string(&json_path)
+        .map_err(|e| format!("Failed to read omarchist.json: {}", e))?;
+    let theme: EditingTheme = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse omarchist.json: {}", e))?;
+
+    let mut files = Vec::new();
+    collect_files(&theme_dir, &theme_dir, &mut files)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let bundle = ThemeBundle {
+        manifest: ThemeBundleManifest {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            name: theme_name.to_string(),
+            author: theme.author,
+            exported_at: Utc::now().to_rfc3339(),
+            exported_by: format!("omarchist {}", env!("CARGO_PKG_VERSION")),
+            is_light_theme: theme_dir.join("light.mode").exists(),
+            file_count: files.len(),
+        },
+        files,
+    };
+
+    let json = serde_json::to_string(&bundle)
+        .map_err(|e| format!("Failed to serialize theme bundle: {}", e))?;
+    fs::write(dest, json).map_err(|e| format!("Failed to write theme bundle: {}", e))?;
+
+    Ok(dest.to_path_buf())
+}
+
+// Read and validate a `.omarchist-theme` file without installing it.
+pub fn read_theme_bundle(path: &Path) -> Result<ThemeBundle, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read theme bundle: {}", e))?;
+    let bundle: ThemeBundle =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid theme bundle: {}", e))?;
+
+    validate_bundle(&bundle)?;
+
+    Ok(bundle)
+}
+
+// Install a `.omarchist-theme` file as a new Omarchist theme. A free name is picked when
+// a theme with the bundled name already exists. Returns the installed theme name.
+pub fn import_theme_bundle(path: &Path) -> Result<String, String> {
+    let bundle = read_theme_bundle(path)?;
+
+    let themes_dir = get_user_themes_dir()
+        .ok_or_else(|| "Could not determine user themes directory".to_string())?;
+    let theme_name = generate_unique_theme_name_from(&bundle.manifest.name);
+    let theme_dir = themes_dir.join(&theme_name);
+
+    if let Err(e) = write_bundle_files(&bundle, &theme_dir, &theme_name) {
+        // Do not leave a half-written theme behind
+        let _ = fs::remove_dir_all(&theme_dir);
+        return Err(e);
+    }
+
+    Ok(theme_name)
+}
+
+fn write_bundle_files(
+    bundle: &ThemeBundle,
+    theme_dir: &Path,
+    theme_name: &str,
+) -> Result<(), String> {
+    fs::create_dir_all(theme_dir)
+        .map_err(|e| format!("Failed to create theme directory: {}", e))?;
+
+    for file in &bundle.files {
+        let data = STANDARD
+            .decode(&file.data)
+            .map_err(|e| format!("Failed to decode '{}': {}", file.path, e))?;
+        let dest = theme_dir.join(&file.path);
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory for '{}': {}", file.path, e))?;
+        }
+
+        fs::write(&dest, data).map_err(|e| format!("Failed to write '{}': {}", file.path, e))?;
+    }
+
+    // Keep the stored name in sync with the directory, like `rename_theme` does
+    let json_path = theme_dir.join("omarchist.json");
+    let content = fs::read_to_string(&json_path)
+        .map_err(|e| format!("Failed to read omarchist.json: {}", e))?;
+    let mut theme: EditingTheme = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse omarchist.json: {}", e))?;
+    theme.name = theme_name.to_string();
+    theme.modified_at = Utc::now().to_rfc3339();
+
+    let updated_content = serde_json::to_string_pretty(&theme)
+        .map_err(|e| format!("Failed to serialize theme data: {}", e))?;
+    fs::write(&json_path, updated_content)
+        .map_err(|e| format!("Failed to write omarchist.json: {}", e))?;
+
+    Ok(())
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<ThemeBundleFile>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read '{}': {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        // Snapshots are local revision history, not part of the theme
+        if path.is_dir() && dir == root && entry.file_name() == SNAPSHOTS_DIR {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|e| format!("Failed to resolve '{}': {}", path.display(), e))?;
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let data =
+            fs::read(&path).map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+
+        files.push(ThemeBundleFile {
+            path: relative,
+            data: STANDARD.encode(data),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_bundle(bundle: &ThemeBundle) -> Result<(), String> {
+    let manifest = &bundle.manifest;
+
+    if manifest.format != BUNDLE_FORMAT {
+        return Err(format!("Unknown bundle format '{}'", manifest.format));
+    }
+
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Bundle format version {} is newer than supported version {}",
+            manifest.format_version, BUNDLE_FORMAT_VERSION
+        ));
+    }
+
+    if manifest.name.is_empty()
+        || !is_safe_relative_path(&manifest.name)
+        || manifest.name.contains('/')
+    {
+        return Err(format!("Invalid theme name '{}'", manifest.name));
+    }
+
+    if manifest.file_count != bundle.files.len() {
+        return Err(format!(
+            "Manifest lists {} files but bundle contains {}",
+            manifest.file_count,
+            bundle.files.len()
+        ));
+    }
+
+    if let Some(file) = bundle
+        .files
+        .iter()
+        .find(|f| !is_safe_relative_path(&f.path))
+    {
+        return Err(format!("Unsafe file path in bundle: '{}'", file.path));
+    }
+
+    if !bundle.files.iter().any(|f| f.path == "omarchist.json") {
+        return Err("Bundle does not contain omarchist.json".to_string());
+    }
+
+    Ok(())
+}
+
+// Only plain relative paths are allowed so a bundle cannot write outside its theme directory
+fn is_safe_relative_path(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_with(name: &str, paths: &[&str]) -> ThemeBundle {
+        ThemeBundle {
+            manifest: ThemeBundleManifest {
+                format: BUNDLE_FORMAT.to_string(),
+                format_version: BUNDLE_FORMAT_VERSION,
+                name: name.to_string(),
+                author: None,
+                exported_at: String::new(),
+                exported_by: String::new(),
+                is_light_theme: false,
+                file_count: paths.len(),
+            },
+            files: paths
+                .iter()
+                .map(|p| ThemeBundleFile {
+                    path: p.to_string(),
+                    data: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn validate_bundle_accepts_valid_bundle() {
+        let bundle = bundle_with("nord", &["omarchist.json", "backgrounds/wall.png"]);
+        assert!(validate_bundle(&bundle).is_ok());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_unknown_format() {
+        let mut bundle = bundle_with("nord", &["omarchist.json"]);
+        bundle.manifest.format = "zip".to_string();
+        assert!(validate_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_newer_format_version() {
+        let mut bundle = bundle_with("nord", &["omarchist.json"]);
+        bundle.manifest.format_version = BUNDLE_FORMAT_VERSION + 1;
+        assert!(validate_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_missing_omarchist_json() {
+        let bundle = bundle_with("nord", &["colors.toml"]);
+        assert!(validate_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_file_count_mismatch() {
+        let mut bundle = bundle_with("nord", &["omarchist.json"]);
+        bundle.manifest.file_count = 3;
+        assert!(validate_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_path_traversal() {
+        let bundle = bundle_with("nord", &["omarchist.json", "../../.bashrc"]);
+        assert!(validate_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_absolute_path() {
+        let bundle = bundle_with("nord", &["omarchist.json", "/etc/passwd"]);
+        assert!(validate_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn validate_bundle_rejects_nested_theme_name() {
+        let bundle = bundle_with("a/b", &["omarchist.json"]);
+        assert!(validate_bundle(&bundle).is_err());
+    }
+}
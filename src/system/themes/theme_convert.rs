@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+
+use super::color_extractor::{ColorPalette, ImageType};
+use super::color_utils::is_dark_color;
+use super::custom_themes::get_user_themes_dir;
+use super::parse_colors::parse_alacritty_toml;
+use super::theme_file_ops::{copy_dir_recursive, get_theme_path};
+use super::theme_generator::build_theme_from_palette;
+use super::theme_management::{
+    generate_unique_theme_name_from, parse_colors_config, read_app_configs_from_files,
+    terminal_config_from_colors,
+};
+use crate::types::themes::{
+    ColorsConfig, EditingTheme, EditorThemeMode, NeovimConfig, VscodeConfig,
+};
+
+// Copy a System or Community theme into a new editable Omarchist theme.
+// Returns the name of the new theme.
+pub fn duplicate_as_editable(source_name: &str, is_system: bool) -> Result<String, String> {
+    let source_dir = get_theme_path(source_name, is_system)
+        .ok_or_else(|| "Could not determine theme path".to_string())?;
+
+    if !source_dir.exists() {
+        return Err(format!("Theme '{}' not found", source_name));
+    }
+
+    let themes_dir = get_user_themes_dir()
+        .ok_or_else(|| "Could not determine user themes directory".to_string())?;
+    let new_name = generate_unique_theme_name_from(&format!("{}-custom", source_name));
+    let new_dir = themes_dir.join(&new_name);
+
+    let result = copy_dir_recursive(&source_dir, &new_dir).and_then(|_| {
+        // The copy is no longer tied to the upstream repository
+        let _ = fs::remove_dir_all(new_dir.join(".git"));
+
+        // Mode lives in the light.mode marker file, not in omarchist.json
+        let light_mode_path = new_dir.join("light.mode");
+        if !light_mode_path.exists() && !is_dark_color(&read_colors_config(&new_dir)?.background) {
+            fs::write(&light_mode_path, "")
+                .map_err(|e| format!("Failed to create light.mode file: {}", e))?;
+        }
+
+        let theme = reverse_engineer_theme(&new_dir, &new_name)?;
+        let json = serde_json::to_string_pretty(&theme)
+            .map_err(|e| format!("Failed to serialize theme data: {}", e))?;
+        fs::write(new_dir.join("omarchist.json"), json)
+            .map_err(|e| format!("Failed to write omarchist.json: {}", e))
+    });
+
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&new_dir);
+        return Err(e);
+    }
+
+    Ok(new_name)
+}
+
+// Build an EditingTheme for a theme that has no omarchist.json. Every app config is
+// first derived from the palette, then replaced by whatever can be parsed from the
+// theme's own files so the editor starts from the theme's real values.
+fn reverse_engineer_theme(theme_dir: &Path, theme_name: &str) -> Result<EditingTheme, String> {
+    let colors = read_colors_config(theme_dir)?;
+    let is_light_theme = theme_dir.join("light.mode").exists();
+
+    let terminal = terminal_config_from_colors(&colors);
+
+    let palette = ColorPalette {
+        background: colors.background.clone(),
+        foreground: colors.foreground.clone(),
+        accent: colors.accent.clone(),
+        terminal: terminal.normal.clone(),
+        bright: terminal.bright.clone(),
+        is_light_theme,
+        image_type: ImageType::Chromatic,
+    };
+
+    let mut theme = build_theme_from_palette(&palette, theme_name)?;
+    theme.apps.terminal = Some(terminal);
+    theme.colors = colors;
+
+    read_app_configs_from_files(theme_dir, &mut theme);
+    read_editor_configs(theme_dir, &mut theme);
+
+    let now = Utc::now().to_rfc3339();
+    theme.created_at = now.clone();
+    theme.modified_at = now;
+
+    Ok(theme)
+}
+
+// Keep the theme's own editor themes as plugin configs, so saving rewrites them
+// unchanged rather than replacing them with generated ones
+fn read_editor_configs(theme_dir: &Path, theme: &mut EditingTheme) {
+    if let Ok(plugin_spec) = fs::read_to_string(theme_dir.join("neovim.lua")) {
+        theme.apps.neovim = Some(NeovimConfig {
+            mode: EditorThemeMode::Plugin,
+            plugin_spec,
+        });
+    }
+
+    if let Ok(content) = fs::read_to_string(theme_dir.join("vscode.json"))
+        && let Ok(config) = serde_json::from_str::<VscodeConfig>(&content)
+    {
+        theme.apps.vscode = Some(VscodeConfig {
+            mode: EditorThemeMode::Plugin,
+            ..config
+        });
+    }
+}
+
+fn read_colors_config(theme_dir: &Path) -> Result<ColorsConfig, String> {
+    let colors_path = theme_dir.join("colors.toml");
+    if colors_path.exists() {
+        let content = fs::read_to_string(&colors_path)
+            .map_err(|e| format!("Failed to read colors.toml: {}", e))?;
+        return Ok(parse_colors_config(&content));
+    }
+
+    let alacritty_path = theme_dir.join("alacritty.toml");
+    let parsed = parse_alacritty_toml(&alacritty_path)
+        .ok_or_else(|| "Theme has no colors.toml or alacritty.toml to convert".to_string())?;
+
+    let primary = parsed.primary;
+    let normal = parsed.terminal;
+
+    // alacritty.toml only gives us the normal palette; reuse it for the bright colors
+    Ok(ColorsConfig {
+        accent: normal.blue.clone(),
+        cursor: primary.foreground.clone(),
+        selection_foreground: primary.background.clone(),
+        selection_background: primary.foreground.clone(),
+        color8: normal.black.clone(),
+        color9: normal.red.clone(),
+        color10: normal.green.clone(),
+        color11: normal.yellow.clone(),
+        color12: normal.blue.clone(),
+        color13: normal.magenta.clone(),
+        color14: normal.cyan.clone(),
+        color15: normal.white.clone(),
+        foreground: primary.foreground,
+        background: primary.background,
+        color0: normal.black,
+        color1: normal.red,
+        color2: normal.green,
+        color3: normal.yellow,
+        color4: normal.blue,
+        color5: normal.magenta,
+        color6: normal.cyan,
+        color7: normal.white,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_engineered_editor_themes_stay_as_plugins() {
+        let theme_dir = std::env::temp_dir().join(format!(
+            "omarchist-convert-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&theme_dir).unwrap();
+        fs::write(theme_dir.join("colors.toml"), "background = \"#1e1e2e\"\n").unwrap();
+        fs::write(
+            theme_dir.join("neovim.lua"),
+            "return { \"catppuccin/nvim\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            theme_dir.join("vscode.json"),
+            r#"{ "name": "Catppuccin Mocha", "extension": "catppuccin.catppuccin-vsc" }"#,
+        )
+        .unwrap();
+
+        let theme = reverse_engineer_theme(&theme_dir, "mocha").unwrap();
+        let _ = fs::remove_dir_all(&theme_dir);
+
+        let neovim = theme.apps.neovim.unwrap();
+        assert_eq!(neovim.mode, EditorThemeMode::Plugin);
+        assert_eq!(neovim.plugin_spec, "return { \"catppuccin/nvim\" }\n");
+        let vscode = theme.apps.vscode.unwrap();
+        assert_eq!(vscode.mode, EditorThemeMode::Plugin);
+        assert_eq!(vscode.extension, "catppuccin.catppuccin-vsc");
+    }
+}
@@ -1,7 +1,9 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::system::themes::theme_management::vscode::unlink_vscode_extension;
+
 fn get_system_theme_path(theme_name: &str) -> Option<PathBuf> {
     dirs::home_dir().map(|h| {
         h.join(".local")
@@ -59,6 +61,9 @@ pub fn delete_theme(theme_name: &str, is_system: bool) -> Result<(), String> {
         return Err(format!("Theme folder does not exist: {}", path.display()));
     }
 
+    // A link to its generated VS Code extension would be left dangling
+    unlink_vscode_extension(theme_name)?;
+
     // Delete the directory and all its contents
     fs::remove_dir_all(&path).map_err(|e| format!("Failed to delete theme folder: {}", e))?;
 
@@ -180,6 +185,28 @@ pub fn open_backgrounds_folder(theme_name: &str, is_system: bool) -> Result<(),
     Ok(())
 }
 
+pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest)
+        .map_err(|e| format!("Failed to create '{}': {}", dest.display(), e))?;
+
+    let entries =
+        fs::read_dir(src).map_err(|e| format!("Failed to read '{}': {}", src.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let target = dest.join(entry.file_name());
+
+        if path.is_dir() {
+            copy_dir_recursive(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)
+                .map_err(|e| format!("Failed to copy '{}': {}", path.display(), e))?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn is_system_theme(theme_name: &str) -> bool {
     if let Some(path) = get_system_theme_path(theme_name) {
         path.exists()
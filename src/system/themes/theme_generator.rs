@@ -1,16 +1,14 @@
 use std::path::Path;
 
 use crate::system::themes::color_extractor::{
-    ColorPalette, copy_image_to_backgrounds, extract_palette,
+    ColorPalette, copy_image_to_backgrounds, extract_palette_with_mode,
 };
 use crate::system::themes::color_utils::{adjust_brightness, darken_color, hex_to_rgb};
-use crate::system::themes::theme_management::{
-    create_theme_from_defaults, save_theme_data, update_icons_theme,
-};
+use crate::system::themes::theme_management::{create_theme_from_defaults, save_theme_data};
 use crate::types::themes::{
-    BrowserConfig, BtopConfig, EditingTheme, HyprlandConfig, HyprlockConfig, MakoConfig,
-    SwayosdConfig, TerminalConfig, TerminalCursor, TerminalPrimary, TerminalSelection,
-    WalkerConfig, WaybarConfig,
+    BrowserConfig, BtopConfig, EditingTheme, EditorThemeMode, HyprlandConfig, HyprlockConfig,
+    MakoConfig, NeovimConfig, SwayosdConfig, TerminalConfig, TerminalCursor, TerminalPrimary,
+    TerminalSelection, VscodeConfig, WalkerConfig, WaybarConfig,
 };
 
 // Available icon themes mapped to their representative colors (RGB)
@@ -60,6 +58,17 @@ pub fn create_theme_from_image(
     theme_name: &str,
     progress: Option<ProgressCallback>,
 ) -> Result<String, String> {
+    generate_theme_from_image(image_path, theme_name, None, progress).map(|(name, _)| name)
+}
+
+// Create a complete theme from an image, optionally forcing light or dark mode.
+// Returns the created theme name together with the extracted palette.
+pub fn generate_theme_from_image(
+    image_path: &Path,
+    theme_name: &str,
+    force_light: Option<bool>,
+    progress: Option<ProgressCallback>,
+) -> Result<(String, ColorPalette), String> {
     let report = |msg: &str| {
         if let Some(ref cb) = progress {
             cb(msg);
@@ -69,7 +78,7 @@ pub fn create_theme_from_image(
     report("Analyzing image...");
 
     // Extract color palette
-    let palette = extract_palette(image_path)?;
+    let palette = extract_palette_with_mode(image_path, force_light)?;
 
     report("Creating theme structure...");
 
@@ -79,7 +88,17 @@ pub fn create_theme_from_image(
     report("Applying colors...");
 
     // Build complete theme with extracted colors
-    let editing_theme = build_theme_from_palette(&palette, theme_name)?;
+    let mut editing_theme = build_theme_from_palette(&palette, theme_name)?;
+
+    // There is no existing editor theme to keep, so generate both from the palette
+    editing_theme.apps.neovim = Some(NeovimConfig {
+        mode: EditorThemeMode::Generated,
+        ..NeovimConfig::default()
+    });
+    editing_theme.apps.vscode = Some(VscodeConfig {
+        mode: EditorThemeMode::Generated,
+        ..VscodeConfig::default()
+    });
 
     // Save all configs
     save_theme_data(theme_name, &editing_theme)?;
@@ -91,11 +110,11 @@ pub fn create_theme_from_image(
 
     report("Done!");
 
-    Ok(theme_name.to_string())
+    Ok((theme_name.to_string(), palette))
 }
 
 // Build a complete EditingTheme from a color palette
-fn build_theme_from_palette(
+pub(crate) fn build_theme_from_palette(
     palette: &ColorPalette,
     theme_name: &str,
 ) -> Result<EditingTheme, String> {
@@ -158,12 +177,10 @@ fn build_theme_from_palette(
         progress: adjust_brightness(&palette.foreground, -0.3),
     };
 
-    // Select the best matching icon theme based on accent color
+    // Select the best matching icon theme based on accent color.
+    // `save_theme_data` writes icons.theme from this config.
     let icon_theme_name = select_icon_theme(&palette.accent, palette.is_light_theme);
 
-    // Save the icons.theme file directly
-    let _ = update_icons_theme(theme_name, icon_theme_name);
-
     // Create icons config for the theme data
     let icons_config = serde_json::json!({
         "theme_name": icon_theme_name
@@ -211,8 +228,8 @@ fn build_theme_from_palette(
             mako: Some(mako_config),
             walker: Some(walker_config),
             swayosd: Some(swayosd_config),
-            neovim: None,
-            vscode: None,
+            neovim: Some(NeovimConfig::default()),
+            vscode: Some(VscodeConfig::default()),
             icons: Some(icons_config),
             ghostty: None,
             kitty: None,
@@ -0,0 +1,227 @@
+use chrono::{DateTime, Duration, Local};
+
+use super::theme_management::save_theme_data;
+use crate::types::themes::EditingTheme;
+
+const MAX_HISTORY_ENTRIES: usize = 100;
+// Saves from the same source closer together than this (e.g. dragging a color
+// picker) are merged into a single entry
+const COALESCE_WINDOW_MS: i64 = 1000;
+
+// One saved state of a theme and the change that produced it
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub label: String,
+    pub theme: EditingTheme,
+    pub recorded_at: DateTime<Local>,
+}
+
+// Linear undo/redo history of `EditingTheme` snapshots for one editing session.
+// The entry at `position` is the state currently saved on disk; entries after it
+// can be redone until a new change is recorded.
+#[derive(Debug, Clone)]
+pub struct ThemeHistory {
+    entries: Vec<HistoryEntry>,
+    position: usize,
+}
+
+impl ThemeHistory {
+    pub fn new(initial: EditingTheme) -> Self {
+        Self {
+            entries: vec![HistoryEntry {
+                label: "Opened theme".to_string(),
+                theme: initial,
+                recorded_at: Local::now(),
+            }],
+            position: 0,
+        }
+    }
+
+    // Record a new state. Returns false when it matches the current state.
+    pub fn record(&mut self, label: impl Into<String>, theme: EditingTheme) -> bool {
+        if same_content(&self.current().theme, &theme) {
+            return false;
+        }
+
+        let label = label.into();
+        let now = Local::now();
+
+        let current = self.current();
+        let is_latest = self.position + 1 == self.entries.len();
+        if self.position > 0
+            && is_latest
+            && current.label == label
+            && now - current.recorded_at < Duration::milliseconds(COALESCE_WINDOW_MS)
+        {
+            let current = &mut self.entries[self.position];
+            current.theme = theme;
+            current.recorded_at = now;
+            return true;
+        }
+
+        self.entries.truncate(self.position + 1);
+        self.entries.push(HistoryEntry {
+            label,
+            theme,
+            recorded_at: now,
+        });
+
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.position = self.entries.len() - 1;
+
+        true
+    }
+
+    pub fn current(&self) -> &HistoryEntry {
+        &self.entries[self.position]
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.position + 1 < self.entries.len()
+    }
+
+    pub fn undo(&mut self) -> Option<&EditingTheme> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.position -= 1;
+        Some(&self.current().theme)
+    }
+
+    pub fn redo(&mut self) -> Option<&EditingTheme> {
+        if !self.can_redo() {
+            return None;
+        }
+        self.position += 1;
+        Some(&self.current().theme)
+    }
+
+    // Move to any entry in the history without discarding the others
+    pub fn jump_to(&mut self, index: usize) -> Option<&EditingTheme> {
+        if index >= self.entries.len() || index == self.position {
+            return None;
+        }
+        self.position = index;
+        Some(&self.current().theme)
+    }
+}
+
+// Write a snapshot back to disk and regenerate every app config from it
+pub fn restore_theme_snapshot(theme_name: &str, theme: &EditingTheme) -> Result<(), String> {
+    save_theme_data(theme_name, theme)
+}
+
+// `modified_at` changes on every save, so it is not part of the comparison
+fn same_content(a: &EditingTheme, b: &EditingTheme) -> bool {
+    let strip = |theme: &EditingTheme| {
+        let mut value = serde_json::to_value(theme).unwrap_or_default();
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("modified_at");
+        }
+        value
+    };
+
+    a.is_light_theme == b.is_light_theme && strip(a) == strip(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme_with_accent(accent: &str) -> EditingTheme {
+        let mut theme = EditingTheme::default();
+        theme.colors.accent = accent.to_string();
+        theme
+    }
+
+    // Record as if the previous entry was made long enough ago not to be merged
+    fn record_later(history: &mut ThemeHistory, theme: EditingTheme) {
+        let position = history.position;
+        history.entries[position].recorded_at -= Duration::milliseconds(COALESCE_WINDOW_MS);
+        history.record("Accent", theme);
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut history = ThemeHistory::new(theme_with_accent("#000000"));
+        record_later(&mut history, theme_with_accent("#111111"));
+        record_later(&mut history, theme_with_accent("#222222"));
+
+        assert_eq!(history.undo().unwrap().colors.accent, "#111111");
+        assert_eq!(history.undo().unwrap().colors.accent, "#000000");
+        assert!(history.undo().is_none());
+
+        assert_eq!(history.redo().unwrap().colors.accent, "#111111");
+        assert_eq!(history.redo().unwrap().colors.accent, "#222222");
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn record_after_undo_discards_redo_entries() {
+        let mut history = ThemeHistory::new(theme_with_accent("#000000"));
+        record_later(&mut history, theme_with_accent("#111111"));
+        history.undo();
+        record_later(&mut history, theme_with_accent("#333333"));
+
+        assert!(!history.can_redo());
+        assert_eq!(history.entries().len(), 2);
+        assert_eq!(history.current().theme.colors.accent, "#333333");
+    }
+
+    #[test]
+    fn record_skips_unchanged_state() {
+        let mut history = ThemeHistory::new(theme_with_accent("#000000"));
+        let mut same = theme_with_accent("#000000");
+        same.modified_at = "later".to_string();
+
+        assert!(!history.record("Accent", same));
+        assert_eq!(history.entries().len(), 1);
+    }
+
+    #[test]
+    fn rapid_changes_from_same_source_are_merged() {
+        let mut history = ThemeHistory::new(theme_with_accent("#000000"));
+        history.record("Accent", theme_with_accent("#111111"));
+        history.record("Accent", theme_with_accent("#222222"));
+        history.record("Accent", theme_with_accent("#333333"));
+
+        assert_eq!(history.entries().len(), 2);
+        assert_eq!(history.undo().unwrap().colors.accent, "#000000");
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut history = ThemeHistory::new(theme_with_accent("#000000"));
+        for i in 1..=MAX_HISTORY_ENTRIES + 10 {
+            record_later(&mut history, theme_with_accent(&format!("#{:06x}", i)));
+        }
+
+        assert_eq!(history.entries().len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.position(), MAX_HISTORY_ENTRIES - 1);
+    }
+
+    #[test]
+    fn jump_to_keeps_later_entries() {
+        let mut history = ThemeHistory::new(theme_with_accent("#000000"));
+        record_later(&mut history, theme_with_accent("#111111"));
+        record_later(&mut history, theme_with_accent("#222222"));
+
+        assert_eq!(history.jump_to(0).unwrap().colors.accent, "#000000");
+        assert!(history.can_redo());
+        assert!(history.jump_to(5).is_none());
+    }
+}
@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use super::custom_themes::get_user_themes_dir;
+use super::parse_colors::{parse_alacritty_toml, parse_colors_toml};
+use super::preview_img::find_preview_image;
+use super::theme_file_ops::copy_dir_recursive;
+use super::utils::dir_to_title;
+use crate::shell::git_sh_commands::git_clone;
+use crate::types::themes::ThemeColors;
+
+// Where a community theme is installed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallSource {
+    Git(String),
+    Local(PathBuf),
+}
+
+impl InstallSource {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        if source.is_empty() {
+            return Err("No theme source given".to_string());
+        }
+
+        if source.contains("://") || source.starts_with("git@") {
+            return Ok(InstallSource::Git(source.to_string()));
+        }
+
+        let path = expand_home(source);
+        if !path.is_dir() {
+            return Err(format!("Theme source not found: {}", source));
+        }
+
+        // Local bare repositories are cloned rather than copied
+        if is_bare_repo(&path) {
+            Ok(InstallSource::Git(path.to_string_lossy().to_string()))
+        } else {
+            Ok(InstallSource::Local(path))
+        }
+    }
+
+    // Theme name derived the same way `omarchy-theme-install` does:
+    // `omarchy-nord-theme.git` becomes `nord`.
+    pub fn default_theme_name(&self) -> String {
+        let raw = match self {
+            InstallSource::Git(url) => url
+                .trim_end_matches('/')
+                .rsplit(['/', ':'])
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            InstallSource::Local(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+
+        let name = raw.strip_suffix(".git").unwrap_or(&raw);
+        let name = name.strip_prefix("omarchy-").unwrap_or(name);
+        let name = name.strip_suffix("-theme").unwrap_or(name);
+        name.to_lowercase().replace(' ', "-")
+    }
+}
+
+// A fetched theme waiting for confirmation. Nothing is written to the themes
+// directory until `confirm_theme_install` is called.
+#[derive(Debug, Clone)]
+pub struct ThemeInstallPreview {
+    pub name: String,
+    pub title: String,
+    pub colors: ThemeColors,
+    pub image: String,
+    staging_dir: PathBuf,
+}
+
+// Fetch a theme into a staging directory and validate it
+pub fn prepare_theme_install(
+    source: &str,
+    name: Option<&str>,
+) -> Result<ThemeInstallPreview, String> {
+    let source = InstallSource::parse(source)?;
+    let name = match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => source.default_theme_name(),
+    };
+
+    if name.is_empty() || name.contains('/') || name.starts_with('.') {
+        return Err(format!("Invalid theme name '{}'", name));
+    }
+
+    // Reject non-theme folders before copying anything
+    if let InstallSource::Local(ref path) = source {
+        read_theme_colors(path)?;
+    }
+
+    let staging_dir = get_staging_dir(&name)?;
+    let _ = fs::remove_dir_all(&staging_dir);
+
+    let fetched = match source {
+        InstallSource::Git(ref url) => git_clone(url, &staging_dir),
+        InstallSource::Local(ref path) => copy_dir_recursive(path, &staging_dir),
+    };
+
+    let colors = fetched.and_then(|_| read_theme_colors(&staging_dir));
+    let colors = match colors {
+        Ok(colors) => colors,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(e);
+        }
+    };
+
+    Ok(ThemeInstallPreview {
+        title: dir_to_title(&name),
+        image: find_preview_image(&staging_dir).unwrap_or_default(),
+        name,
+        colors,
+        staging_dir,
+    })
+}
+
+// Move a previewed theme into `~/.config/omarchy/themes/`. Returns the installed name.
+pub fn confirm_theme_install(preview: &ThemeInstallPreview) -> Result<String, String> {
+    let themes_dir = get_user_themes_dir()
+        .ok_or_else(|| "Could not determine user themes directory".to_string())?;
+    let dest = themes_dir.join(&preview.name);
+
+    if dest.exists() {
+        return Err(format!("Theme '{}' already exists", preview.name));
+    }
+
+    fs::create_dir_all(&themes_dir)
+        .map_err(|e| format!("Failed to create themes directory: {}", e))?;
+
+    // Staging lives under the cache dir which may be on another filesystem
+    if fs::rename(&preview.staging_dir, &dest).is_err() {
+        if let Err(e) = copy_dir_recursive(&preview.staging_dir, &dest) {
+            let _ = fs::remove_dir_all(&dest);
+            return Err(e);
+        }
+        let _ = fs::remove_dir_all(&preview.staging_dir);
+    }
+
+    Ok(preview.name.clone())
+}
+
+pub fn cancel_theme_install(preview: &ThemeInstallPreview) {
+    let _ = fs::remove_dir_all(&preview.staging_dir);
+}
+
+fn read_theme_colors(theme_dir: &Path) -> Result<ThemeColors, String> {
+    let colors_path = theme_dir.join("colors.toml");
+    let alacritty_path = theme_dir.join("alacritty.toml");
+
+    let colors = if colors_path.exists() {
+        parse_colors_toml(&colors_path)
+    } else if alacritty_path.exists() {
+        parse_alacritty_toml(&alacritty_path)
+    } else {
+        return Err("Not an Omarchy theme: no colors.toml or alacritty.toml found".to_string());
+    };
+
+    colors.ok_or_else(|| "Theme colors could not be parsed".to_string())
+}
+
+fn get_staging_dir(name: &str) -> Result<PathBuf, String> {
+    dirs::cache_dir()
+        .map(|d| {
+            d.join("omarchist").join("theme-install").join(format!(
+                "{}-{}",
+                name,
+                Utc::now().timestamp_millis()
+            ))
+        })
+        .ok_or_else(|| "Could not determine cache directory".to_string())
+}
+
+fn is_bare_repo(path: &Path) -> bool {
+    path.join("HEAD").is_file() && path.join("objects").is_dir() && path.join("refs").is_dir()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|h| h.join(rest))
+            .unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_https_url_is_git() {
+        let source = InstallSource::parse("https://github.com/user/omarchy-nord-theme").unwrap();
+        assert_eq!(
+            source,
+            InstallSource::Git("https://github.com/user/omarchy-nord-theme".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_ssh_url_is_git() {
+        let source = InstallSource::parse("git@github.com:user/omarchy-nord-theme.git").unwrap();
+        assert!(matches!(source, InstallSource::Git(_)));
+    }
+
+    #[test]
+    fn parse_missing_path_returns_err() {
+        assert!(InstallSource::parse("/__omarchist_nonexistent_theme__").is_err());
+    }
+
+    #[test]
+    fn parse_empty_returns_err() {
+        assert!(InstallSource::parse("   ").is_err());
+    }
+
+    #[test]
+    fn default_name_strips_omarchy_prefix_and_theme_suffix() {
+        let source = InstallSource::Git("https://github.com/user/omarchy-nord-theme.git".into());
+        assert_eq!(source.default_theme_name(), "nord");
+    }
+
+    #[test]
+    fn default_name_from_ssh_url() {
+        let source = InstallSource::Git("git@github.com:user/rose-pine.git".into());
+        assert_eq!(source.default_theme_name(), "rose-pine");
+    }
+
+    #[test]
+    fn default_name_ignores_trailing_slash() {
+        let source = InstallSource::Git("https://example.com/themes/Kanagawa/".into());
+        assert_eq!(source.default_theme_name(), "kanagawa");
+    }
+
+    #[test]
+    fn default_name_from_local_dir() {
+        let source = InstallSource::Local(PathBuf::from("/tmp/omarchy-tokyo-night-theme"));
+        assert_eq!(source.default_theme_name(), "tokyo-night");
+    }
+}
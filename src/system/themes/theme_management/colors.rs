@@ -1,6 +1,10 @@
+use std::collections::HashMap;
 use std::fs;
 
-use crate::types::themes::{ColorsConfig, TerminalConfig};
+use crate::types::themes::{
+    ColorsConfig, EditingTheme, TerminalConfig, TerminalCursor, TerminalPalette, TerminalPrimary,
+    TerminalSelection,
+};
 
 use super::paths::get_custom_themes_dir;
 
@@ -96,3 +100,159 @@ pub fn colors_config_from_terminal(terminal: &TerminalConfig, accent: &str) -> C
         color15: terminal.bright.white.clone(),
     }
 }
+
+// The palette generated editor themes are built from. The terminal config is the
+// source of truth for colors.toml, so it wins over the stored colors when present.
+pub fn theme_palette(theme: &EditingTheme) -> ColorsConfig {
+    match theme.apps.terminal {
+        Some(ref terminal) => colors_config_from_terminal(terminal, &theme.colors.accent),
+        None => theme.colors.clone(),
+    }
+}
+
+// Parse a `colors.toml` into a full ColorsConfig. Missing keys fall back to the
+// defaults, except `accent`, `cursor` and the selection colors which are derived
+// from the palette like Omarchy does.
+pub fn parse_colors_config(toml_content: &str) -> ColorsConfig {
+    let mut values: HashMap<&str, String> = HashMap::new();
+
+    for line in toml_content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            values.insert(key.trim(), value.trim().trim_matches('"').to_string());
+        }
+    }
+
+    let defaults = ColorsConfig::default();
+    let get = |key: &str, fallback: &str| {
+        values
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| fallback.to_string())
+    };
+
+    let foreground = get("foreground", &defaults.foreground);
+    let background = get("background", &defaults.background);
+    let color0 = get("color0", &defaults.color0);
+    let color1 = get("color1", &defaults.color1);
+    let color2 = get("color2", &defaults.color2);
+    let color3 = get("color3", &defaults.color3);
+    let color4 = get("color4", &defaults.color4);
+    let color5 = get("color5", &defaults.color5);
+    let color6 = get("color6", &defaults.color6);
+    let color7 = get("color7", &defaults.color7);
+
+    ColorsConfig {
+        accent: get("accent", &color4),
+        cursor: get("cursor", &foreground),
+        selection_foreground: get("selection_foreground", &background),
+        selection_background: get("selection_background", &foreground),
+        color8: get("color8", &color0),
+        color9: get("color9", &color1),
+        color10: get("color10", &color2),
+        color11: get("color11", &color3),
+        color12: get("color12", &color4),
+        color13: get("color13", &color5),
+        color14: get("color14", &color6),
+        color15: get("color15", &color7),
+        foreground,
+        background,
+        color0,
+        color1,
+        color2,
+        color3,
+        color4,
+        color5,
+        color6,
+        color7,
+    }
+}
+
+// Inverse of `colors_config_from_terminal`
+pub fn terminal_config_from_colors(colors: &ColorsConfig) -> TerminalConfig {
+    TerminalConfig {
+        primary: TerminalPrimary {
+            background: colors.background.clone(),
+            foreground: colors.foreground.clone(),
+        },
+        cursor: TerminalCursor {
+            cursor: colors.cursor.clone(),
+            text: colors.background.clone(),
+        },
+        selection: TerminalSelection {
+            background: colors.selection_background.clone(),
+            foreground: colors.selection_foreground.clone(),
+        },
+        normal: TerminalPalette {
+            black: colors.color0.clone(),
+            red: colors.color1.clone(),
+            green: colors.color2.clone(),
+            yellow: colors.color3.clone(),
+            blue: colors.color4.clone(),
+            magenta: colors.color5.clone(),
+            cyan: colors.color6.clone(),
+            white: colors.color7.clone(),
+        },
+        bright: TerminalPalette {
+            black: colors.color8.clone(),
+            red: colors.color9.clone(),
+            green: colors.color10.clone(),
+            yellow: colors.color11.clone(),
+            blue: colors.color12.clone(),
+            magenta: colors.color13.clone(),
+            cyan: colors.color14.clone(),
+            white: colors.color15.clone(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_colors_config_reads_all_keys() {
+        let content = r##"accent = "#111111"
+cursor = "#222222"
+foreground = "#333333"
+background = "#444444"
+selection_foreground = "#555555"
+selection_background = "#666666"
+
+color0 = "#000000"
+color9 = "#990000"
+"##;
+        let colors = parse_colors_config(content);
+        assert_eq!(colors.accent, "#111111");
+        assert_eq!(colors.cursor, "#222222");
+        assert_eq!(colors.foreground, "#333333");
+        assert_eq!(colors.background, "#444444");
+        assert_eq!(colors.selection_foreground, "#555555");
+        assert_eq!(colors.selection_background, "#666666");
+        assert_eq!(colors.color0, "#000000");
+        assert_eq!(colors.color9, "#990000");
+    }
+
+    #[test]
+    fn parse_colors_config_derives_missing_accent_and_brights() {
+        let content = "color1 = \"#ff0000\"\ncolor4 = \"#0000ff\"\n";
+        let colors = parse_colors_config(content);
+        assert_eq!(colors.accent, "#0000ff");
+        assert_eq!(colors.color9, "#ff0000");
+        assert_eq!(colors.color12, "#0000ff");
+    }
+
+    #[test]
+    fn terminal_config_round_trips_through_colors_config() {
+        let colors = ColorsConfig::default();
+        let terminal = terminal_config_from_colors(&colors);
+        let back = colors_config_from_terminal(&terminal, &colors.accent);
+        assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&colors).unwrap()
+        );
+    }
+}
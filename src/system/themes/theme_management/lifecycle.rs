@@ -13,12 +13,15 @@ use super::hyprland::{parse_hyprland_conf, update_hyprland_conf};
 use super::hyprlock::{parse_hyprlock_conf, update_hyprlock_conf};
 use super::icons::{parse_icons_theme, update_icons_theme};
 use super::mako::{parse_mako_ini, update_mako_ini};
+use super::neovim::update_neovim_lua;
 use super::paths::get_custom_themes_dir;
 use super::swayosd::{parse_swayosd_css, update_swayosd_css};
 use super::terminal::update_terminal_configs;
+use super::vscode::{unlink_vscode_extension, update_vscode_theme};
 use super::walker::update_walker_css;
 use super::waybar::{parse_waybar_css, update_waybar_css};
 use crate::assets::extract_default_dir;
+use crate::system::themes::theme_snapshots::{record_theme_snapshot, snapshot_limit_from_settings};
 
 pub fn generate_unique_theme_name() -> String {
     let themes_dir = match get_custom_themes_dir() {
@@ -46,6 +49,25 @@ pub fn generate_unique_theme_name() -> String {
     }
 }
 
+// Returns `base` if it is free, otherwise the first free `base-N` in the custom themes dir.
+pub fn generate_unique_theme_name_from(base: &str) -> String {
+    match get_custom_themes_dir() {
+        Some(dir) => first_free_name(base, |name| dir.join(name).exists()),
+        None => format!("{}-{}", base, Utc::now().timestamp()),
+    }
+}
+
+fn first_free_name(base: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    if !is_taken(base) {
+        return base.to_string();
+    }
+
+    (2..=1000)
+        .map(|counter| format!("{}-{}", base, counter))
+        .find(|name| !is_taken(name))
+        .unwrap_or_else(|| format!("{}-{}", base, Utc::now().timestamp()))
+}
+
 pub fn create_theme_from_defaults(theme_name: &str) -> Result<String, String> {
     let themes_dir = get_custom_themes_dir()
         .ok_or_else(|| "Could not determine custom themes directory".to_string())?;
@@ -106,6 +128,13 @@ pub fn load_theme_for_editing(theme_name: &str) -> Result<EditingTheme, String>
         EditingTheme::default()
     };
 
+    read_app_configs_from_files(&theme_dir, &mut editing_theme);
+
+    Ok(editing_theme)
+}
+
+// Overlay every app config that can be parsed back from the generated files in `theme_dir`
+pub fn read_app_configs_from_files(theme_dir: &Path, editing_theme: &mut EditingTheme) {
     editing_theme.is_light_theme = theme_dir.join("light.mode").exists();
 
     let waybar_css_path = theme_dir.join("waybar.css");
@@ -163,8 +192,6 @@ pub fn load_theme_for_editing(theme_name: &str) -> Result<EditingTheme, String>
     {
         editing_theme.apps.swayosd = Some(config);
     }
-
-    Ok(editing_theme)
 }
 
 pub fn save_theme_data(theme_name: &str, theme_data: &EditingTheme) -> Result<(), String> {
@@ -232,6 +259,21 @@ pub fn save_theme_data(theme_name: &str, theme_data: &EditingTheme) -> Result<()
         update_icons_theme(theme_name, theme_name_val)?;
     }
 
+    if let Some(ref neovim_config) = theme_data.apps.neovim {
+        update_neovim_lua(theme_name, neovim_config, theme_data)?;
+    }
+
+    if let Some(ref vscode_config) = theme_data.apps.vscode {
+        update_vscode_theme(theme_name, vscode_config, theme_data)?;
+    }
+
+    // A failed snapshot should never fail the save itself
+    if let Some(limit) = snapshot_limit_from_settings()
+        && let Err(e) = record_theme_snapshot(&theme_dir, limit, true)
+    {
+        eprintln!("Failed to record theme snapshot: {}", e);
+    }
+
     Ok(())
 }
 
@@ -251,6 +293,7 @@ pub fn rename_theme(old_name: &str, new_name: &str) -> Result<(), String> {
     }
 
     fs::rename(&old_path, &new_path).map_err(|e| format!("Failed to rename theme: {}", e))?;
+    unlink_vscode_extension(old_name)?;
 
     let json_path = new_path.join("omarchist.json");
     if json_path.exists() {
@@ -265,6 +308,11 @@ pub fn rename_theme(old_name: &str, new_name: &str) -> Result<(), String> {
             .map_err(|e| format!("Failed to serialize theme data: {}", e))?;
         fs::write(&json_path, updated_content)
             .map_err(|e| format!("Failed to write omarchist.json: {}", e))?;
+
+        // The generated extension's id comes from the theme name
+        if let Some(ref vscode_config) = theme.apps.vscode {
+            update_vscode_theme(new_name, vscode_config, &theme)?;
+        }
     }
 
     Ok(())
@@ -285,3 +333,22 @@ fn update_light_mode_file(theme_dir: &Path, is_light: bool) -> Result<(), String
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_free_name_returns_base_when_free() {
+        assert_eq!(first_free_name("nord", |_| false), "nord");
+    }
+
+    #[test]
+    fn first_free_name_appends_counter_on_conflict() {
+        let taken = ["nord", "nord-2"];
+        assert_eq!(
+            first_free_name("nord", |name| taken.contains(&name)),
+            "nord-3"
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use std::fs;
+
+use crate::system::themes::color_utils::adjust_lightness;
+use crate::types::themes::{ColorsConfig, EditingTheme, EditorThemeMode, NeovimConfig};
+
+use super::colors::theme_palette;
+use super::paths::get_custom_themes_dir;
+
+// One `vim.api.nvim_set_hl` call. A group either links to another group or sets colors.
+#[derive(Debug, Clone, Default)]
+struct Highlight {
+    fg: Option<String>,
+    bg: Option<String>,
+    sp: Option<String>,
+    link: Option<&'static str>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    undercurl: bool,
+}
+
+impl Highlight {
+    fn fg(color: &str) -> Self {
+        Self {
+            fg: Some(color.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn fg_bg(fg: &str, bg: &str) -> Self {
+        Self {
+            fg: Some(fg.to_string()),
+            bg: Some(bg.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn bg(color: &str) -> Self {
+        Self {
+            bg: Some(color.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn link(group: &'static str) -> Self {
+        Self {
+            link: Some(group),
+            ..Default::default()
+        }
+    }
+
+    fn undercurl(color: &str) -> Self {
+        Self {
+            sp: Some(color.to_string()),
+            undercurl: true,
+            ..Default::default()
+        }
+    }
+
+    fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    fn to_lua(&self) -> String {
+        if let Some(link) = self.link {
+            return format!("{{ link = \"{}\" }}", link);
+        }
+
+        let mut parts = Vec::new();
+        for (key, value) in [("fg", &self.fg), ("bg", &self.bg), ("sp", &self.sp)] {
+            if let Some(color) = value {
+                parts.push(format!("{} = \"{}\"", key, color));
+            }
+        }
+        for (key, enabled) in [
+            ("bold", self.bold),
+            ("italic", self.italic),
+            ("underline", self.underline),
+            ("undercurl", self.undercurl),
+        ] {
+            if enabled {
+                parts.push(format!("{} = true", key));
+            }
+        }
+
+        format!("{{ {} }}", parts.join(", "))
+    }
+}
+
+// Highlight groups for the editor UI, syntax, diagnostics and treesitter captures
+fn highlight_groups(c: &ColorsConfig, is_light: bool) -> Vec<(&'static str, Highlight)> {
+    // Floating windows and the status line sit slightly off the main background
+    let surface = adjust_lightness(&c.background, if is_light { -0.04 } else { 0.04 });
+    let subtle = adjust_lightness(&c.background, if is_light { -0.08 } else { 0.08 });
+
+    vec![
+        // Editor UI
+        ("Normal", Highlight::fg_bg(&c.foreground, &c.background)),
+        ("NormalNC", Highlight::fg_bg(&c.foreground, &c.background)),
+        ("NormalFloat", Highlight::fg_bg(&c.foreground, &surface)),
+        ("FloatBorder", Highlight::fg_bg(&c.color8, &surface)),
+        ("FloatTitle", Highlight::fg_bg(&c.accent, &surface).bold()),
+        ("Cursor", Highlight::fg_bg(&c.background, &c.cursor)),
+        ("CursorLine", Highlight::bg(&surface)),
+        ("CursorColumn", Highlight::bg(&surface)),
+        ("ColorColumn", Highlight::bg(&surface)),
+        ("LineNr", Highlight::fg(&c.color8)),
+        ("CursorLineNr", Highlight::fg(&c.accent).bold()),
+        ("SignColumn", Highlight::bg(&c.background)),
+        (
+            "Visual",
+            Highlight::fg_bg(&c.selection_foreground, &c.selection_background),
+        ),
+        ("Search", Highlight::fg_bg(&c.background, &c.color3)),
+        ("IncSearch", Highlight::fg_bg(&c.background, &c.accent)),
+        ("CurSearch", Highlight::link("IncSearch")),
+        ("MatchParen", Highlight::fg(&c.accent).bold()),
+        ("Pmenu", Highlight::fg_bg(&c.foreground, &surface)),
+        ("PmenuSel", Highlight::fg_bg(&c.background, &c.accent)),
+        ("PmenuSbar", Highlight::bg(&subtle)),
+        ("PmenuThumb", Highlight::bg(&c.color8)),
+        ("StatusLine", Highlight::fg_bg(&c.foreground, &surface)),
+        ("StatusLineNC", Highlight::fg_bg(&c.color8, &c.background)),
+        ("TabLine", Highlight::fg_bg(&c.color8, &surface)),
+        ("TabLineSel", Highlight::fg_bg(&c.background, &c.accent)),
+        ("TabLineFill", Highlight::bg(&c.background)),
+        ("WinSeparator", Highlight::fg(&subtle)),
+        ("VertSplit", Highlight::link("WinSeparator")),
+        ("Folded", Highlight::fg_bg(&c.color8, &surface)),
+        ("NonText", Highlight::fg(&subtle)),
+        ("Whitespace", Highlight::fg(&subtle)),
+        ("EndOfBuffer", Highlight::fg(&c.background)),
+        ("Directory", Highlight::fg(&c.color4)),
+        ("Title", Highlight::fg(&c.accent).bold()),
+        ("ErrorMsg", Highlight::fg(&c.color1)),
+        ("WarningMsg", Highlight::fg(&c.color3)),
+        ("MoreMsg", Highlight::fg(&c.color2)),
+        ("Question", Highlight::fg(&c.color4)),
+        ("DiffAdd", Highlight::fg(&c.color2)),
+        ("DiffChange", Highlight::fg(&c.color3)),
+        ("DiffDelete", Highlight::fg(&c.color1)),
+        ("DiffText", Highlight::fg_bg(&c.color3, &subtle)),
+        // Syntax
+        ("Comment", Highlight::fg(&c.color8).italic()),
+        ("Constant", Highlight::fg(&c.color3)),
+        ("String", Highlight::fg(&c.color2)),
+        ("Character", Highlight::fg(&c.color2)),
+        ("Number", Highlight::fg(&c.color3)),
+        ("Boolean", Highlight::fg(&c.color3)),
+        ("Float", Highlight::link("Number")),
+        ("Identifier", Highlight::fg(&c.foreground)),
+        ("Function", Highlight::fg(&c.color4)),
+        ("Statement", Highlight::fg(&c.color5)),
+        ("Keyword", Highlight::fg(&c.color5)),
+        ("Conditional", Highlight::fg(&c.color5)),
+        ("Repeat", Highlight::fg(&c.color5)),
+        ("Operator", Highlight::fg(&c.color6)),
+        ("PreProc", Highlight::fg(&c.color6)),
+        ("Type", Highlight::fg(&c.color6)),
+        ("Special", Highlight::fg(&c.accent)),
+        ("Delimiter", Highlight::fg(&c.color7)),
+        ("Underlined", Highlight::fg(&c.color4).underline()),
+        ("Error", Highlight::fg(&c.color1)),
+        ("Todo", Highlight::fg_bg(&c.background, &c.accent).bold()),
+        // Diagnostics
+        ("DiagnosticError", Highlight::fg(&c.color1)),
+        ("DiagnosticWarn", Highlight::fg(&c.color3)),
+        ("DiagnosticInfo", Highlight::fg(&c.color4)),
+        ("DiagnosticHint", Highlight::fg(&c.color6)),
+        ("DiagnosticOk", Highlight::fg(&c.color2)),
+        ("DiagnosticUnderlineError", Highlight::undercurl(&c.color1)),
+        ("DiagnosticUnderlineWarn", Highlight::undercurl(&c.color3)),
+        ("DiagnosticUnderlineInfo", Highlight::undercurl(&c.color4)),
+        ("DiagnosticUnderlineHint", Highlight::undercurl(&c.color6)),
+        // Treesitter
+        ("@comment", Highlight::link("Comment")),
+        ("@string", Highlight::link("String")),
+        ("@string.escape", Highlight::fg(&c.color6)),
+        ("@character", Highlight::link("Character")),
+        ("@number", Highlight::link("Number")),
+        ("@boolean", Highlight::link("Boolean")),
+        ("@constant", Highlight::link("Constant")),
+        ("@constant.builtin", Highlight::fg(&c.color11)),
+        ("@variable", Highlight::fg(&c.foreground)),
+        ("@variable.builtin", Highlight::fg(&c.color9)),
+        ("@variable.parameter", Highlight::fg(&c.color15).italic()),
+        ("@variable.member", Highlight::fg(&c.color12)),
+        ("@property", Highlight::fg(&c.color12)),
+        ("@function", Highlight::link("Function")),
+        ("@function.builtin", Highlight::fg(&c.color14)),
+        ("@function.method", Highlight::link("Function")),
+        ("@constructor", Highlight::fg(&c.color6)),
+        ("@keyword", Highlight::link("Keyword")),
+        ("@keyword.return", Highlight::fg(&c.color13)),
+        ("@operator", Highlight::link("Operator")),
+        ("@type", Highlight::link("Type")),
+        ("@type.builtin", Highlight::fg(&c.color14)),
+        ("@module", Highlight::fg(&c.color12)),
+        ("@punctuation", Highlight::link("Delimiter")),
+        ("@tag", Highlight::fg(&c.color5)),
+        ("@tag.attribute", Highlight::fg(&c.color4)),
+        ("@markup.heading", Highlight::link("Title")),
+        ("@markup.link", Highlight::fg(&c.color4).underline()),
+    ]
+}
+
+// A self-contained LazyVim spec that defines the colorscheme from the palette, so no
+// external plugin is needed.
+pub fn generate_neovim_colorscheme(
+    theme_name: &str,
+    colors: &ColorsConfig,
+    is_light: bool,
+) -> String {
+    let terminal_colors = [
+        &colors.color0,
+        &colors.color1,
+        &colors.color2,
+        &colors.color3,
+        &colors.color4,
+        &colors.color5,
+        &colors.color6,
+        &colors.color7,
+        &colors.color8,
+        &colors.color9,
+        &colors.color10,
+        &colors.color11,
+        &colors.color12,
+        &colors.color13,
+        &colors.color14,
+        &colors.color15,
+    ];
+
+    let mut lines = vec![
+        format!(
+            "-- Generated by Omarchist from the {} palette.",
+            lua_string(theme_name)
+        ),
+        "-- Switch the Neovim theme to plugin mode in Omarchist to edit this file by hand."
+            .to_string(),
+        "return {".to_string(),
+        "    {".to_string(),
+        "        \"LazyVim/LazyVim\",".to_string(),
+        "        opts = {".to_string(),
+        "            colorscheme = function()".to_string(),
+        "                vim.cmd(\"highlight clear\")".to_string(),
+        "                if vim.fn.exists(\"syntax_on\") == 1 then".to_string(),
+        "                    vim.cmd(\"syntax reset\")".to_string(),
+        "                end".to_string(),
+        format!(
+            "                vim.o.background = \"{}\"",
+            if is_light { "light" } else { "dark" }
+        ),
+        format!(
+            "                vim.g.colors_name = {}",
+            lua_string(&format!("omarchist-{}", theme_name))
+        ),
+        String::new(),
+        "                local hl = function(group, opts)".to_string(),
+        "                    vim.api.nvim_set_hl(0, group, opts)".to_string(),
+        "                end".to_string(),
+        String::new(),
+    ];
+
+    for (group, highlight) in highlight_groups(colors, is_light) {
+        lines.push(format!(
+            "                hl(\"{}\", {})",
+            group,
+            highlight.to_lua()
+        ));
+    }
+
+    lines.push(String::new());
+    for (index, color) in terminal_colors.iter().enumerate() {
+        lines.push(format!(
+            "                vim.g.terminal_color_{} = \"{}\"",
+            index, color
+        ));
+    }
+
+    lines.extend(["            end,", "        },", "    },", "}"].map(String::from));
+
+    lines.join("\n") + "\n"
+}
+
+// A double-quoted Lua string literal; escapes keep it on one line, so it also
+// stays inside a `--` comment
+fn lua_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => out.push_str(&format!("\\{:03}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn neovim_lua_content(theme_name: &str, config: &NeovimConfig, theme: &EditingTheme) -> String {
+    match config.mode {
+        EditorThemeMode::Plugin => config.plugin_spec.clone(),
+        EditorThemeMode::Generated => {
+            generate_neovim_colorscheme(theme_name, &theme_palette(theme), theme.is_light_theme)
+        }
+    }
+}
+
+pub fn update_neovim_lua(
+    theme_name: &str,
+    config: &NeovimConfig,
+    theme: &EditingTheme,
+) -> Result<(), String> {
+    let themes_dir = get_custom_themes_dir()
+        .ok_or_else(|| "Could not determine custom themes directory".to_string())?;
+
+    let theme_dir = themes_dir.join(theme_name);
+
+    if !theme_dir.exists() {
+        return Err(format!("Theme '{}' not found", theme_name));
+    }
+
+    let content = neovim_lua_content(theme_name, config, theme);
+
+    // An empty plugin spec means the theme never had one; leave any existing file alone
+    if content.trim().is_empty() {
+        return Ok(());
+    }
+
+    fs::write(theme_dir.join("neovim.lua"), content)
+        .map_err(|e| format!("Failed to write neovim.lua: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_colorscheme_sets_normal_from_palette() {
+        let colors = ColorsConfig::default();
+        let lua = generate_neovim_colorscheme("test", &colors, false);
+
+        assert!(lua.contains(&format!(
+            "hl(\"Normal\", {{ fg = \"{}\", bg = \"{}\" }})",
+            colors.foreground, colors.background
+        )));
+        assert!(lua.contains("vim.o.background = \"dark\""));
+        assert!(lua.contains("vim.g.colors_name = \"omarchist-test\""));
+    }
+
+    #[test]
+    fn generated_colorscheme_covers_treesitter_and_diagnostics() {
+        let lua = generate_neovim_colorscheme("test", &ColorsConfig::default(), true);
+
+        assert!(lua.contains("hl(\"@function\", { link = \"Function\" })"));
+        assert!(lua.contains("hl(\"DiagnosticUnderlineError\""));
+        assert!(lua.contains("undercurl = true"));
+        assert!(lua.contains("vim.g.terminal_color_15"));
+        assert!(lua.contains("vim.o.background = \"light\""));
+    }
+
+    #[test]
+    fn theme_names_are_escaped_in_lua_strings() {
+        let lua =
+            generate_neovim_colorscheme("My \"Best\"\\\ntheme", &ColorsConfig::default(), false);
+
+        assert!(lua.contains(r#"vim.g.colors_name = "omarchist-My \"Best\"\\\ntheme""#));
+        assert!(
+            lua.contains(r#"-- Generated by Omarchist from the "My \"Best\"\\\ntheme" palette."#)
+        );
+    }
+
+    #[test]
+    fn plugin_mode_writes_spec_verbatim() {
+        let config = NeovimConfig {
+            mode: EditorThemeMode::Plugin,
+            plugin_spec: "return {}".to_string(),
+        };
+
+        let content = neovim_lua_content("test", &config, &EditingTheme::default());
+        assert_eq!(content, "return {}");
+    }
+
+    #[test]
+    fn legacy_string_config_is_read_as_plugin_spec() {
+        let json = r#"{ "neovim": "return {}" }"#;
+        let apps: crate::types::themes::AppConfigs = serde_json::from_str(json).unwrap();
+        let neovim = apps.neovim.unwrap();
+
+        assert_eq!(neovim.mode, EditorThemeMode::Plugin);
+        assert_eq!(neovim.plugin_spec, "return {}");
+    }
+
+    #[test]
+    fn legacy_raw_config_object_is_read_as_plugin_spec() {
+        let json =
+            r#"{ "neovim": { "raw_config": "return {}", "theme_name": "x", "lazy": false } }"#;
+        let apps: crate::types::themes::AppConfigs = serde_json::from_str(json).unwrap();
+
+        assert_eq!(apps.neovim.unwrap().plugin_spec, "return {}");
+    }
+}
@@ -0,0 +1,565 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value, json};
+
+use crate::system::themes::color_utils::adjust_lightness;
+use crate::types::themes::{ColorsConfig, EditingTheme, EditorThemeMode, VscodeConfig};
+
+use super::colors::theme_palette;
+use super::paths::get_custom_themes_dir;
+
+// Folder inside the theme holding the generated extension. It has the layout of an
+// unpacked VSIX, so it can be linked into `~/.vscode/extensions` as-is.
+pub const VSCODE_EXTENSION_DIR: &str = "vscode-extension";
+
+const VSCODE_PUBLISHER: &str = "omarchist";
+
+// Lowercase, dash-separated form of the theme name usable in an extension id
+fn extension_slug(theme_name: &str) -> String {
+    let slug: String = theme_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn extension_name(theme_name: &str) -> String {
+    format!("omarchist-{}", extension_slug(theme_name))
+}
+
+// The id VS Code knows the generated extension by, e.g. `omarchist.omarchist-nord`
+pub fn generated_extension_id(theme_name: &str) -> String {
+    format!("{}.{}", VSCODE_PUBLISHER, extension_name(theme_name))
+}
+
+// Where VS Code looks for unpacked extensions, one `<id>-<version>` folder each
+fn vscode_extensions_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".vscode").join("extensions"))
+}
+
+/// Link the theme's generated extension into `~/.vscode/extensions`, so
+/// Omarchy finds it installed instead of looking the id up on the
+/// marketplace. Does nothing for themes without one.
+pub fn link_vscode_extension(theme_name: &str) -> Result<(), String> {
+    let themes_dir = get_custom_themes_dir()
+        .ok_or_else(|| "Could not determine custom themes directory".to_string())?;
+    let extension_dir = themes_dir.join(theme_name).join(VSCODE_EXTENSION_DIR);
+    if !extension_dir.exists() {
+        return Ok(());
+    }
+
+    let extensions_dir = vscode_extensions_dir()
+        .ok_or_else(|| "Could not determine VS Code extensions directory".to_string())?;
+    link_extension_into(&extensions_dir, &extension_dir, theme_name)
+}
+
+/// Remove the links `link_vscode_extension` made for this theme.
+pub fn unlink_vscode_extension(theme_name: &str) -> Result<(), String> {
+    match vscode_extensions_dir() {
+        Some(extensions_dir) => unlink_extension_from(&extensions_dir, theme_name),
+        None => Ok(()),
+    }
+}
+
+fn link_extension_into(
+    extensions_dir: &Path,
+    extension_dir: &Path,
+    theme_name: &str,
+) -> Result<(), String> {
+    let package_path = extension_dir.join("package.json");
+    let package: Value = fs::read_to_string(&package_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .ok_or_else(|| format!("Failed to read '{}'", package_path.display()))?;
+    let version = package["version"].as_str().unwrap_or("1.0.0");
+
+    // A link for an older version would be loaded alongside the new one
+    unlink_extension_from(extensions_dir, theme_name)?;
+
+    fs::create_dir_all(extensions_dir)
+        .map_err(|e| format!("Failed to create VS Code extensions directory: {}", e))?;
+    let link = extensions_dir.join(format!(
+        "{}-{}",
+        generated_extension_id(theme_name),
+        version
+    ));
+    std::os::unix::fs::symlink(extension_dir, &link)
+        .map_err(|e| format!("Failed to link VS Code extension: {}", e))
+}
+
+// Only our links are removed, never an extension VS Code installed itself
+fn unlink_extension_from(extensions_dir: &Path, theme_name: &str) -> Result<(), String> {
+    let Ok(entries) = fs::read_dir(extensions_dir) else {
+        return Ok(());
+    };
+
+    let prefix = format!("{}-", generated_extension_id(theme_name));
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        // `omarchist.omarchist-nord-1.0.0`, but not `omarchist.omarchist-nord-light-1.0.0`
+        let is_ours = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .is_some_and(|version| {
+                version.starts_with(|c: char| c.is_ascii_digit())
+                    && version.chars().all(|c| c.is_ascii_digit() || c == '.')
+            });
+        if is_ours && entry.file_type().is_ok_and(|t| t.is_symlink()) {
+            fs::remove_file(entry.path())
+                .map_err(|e| format!("Failed to unlink VS Code extension: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn workbench_colors(c: &ColorsConfig, is_light: bool) -> Map<String, Value> {
+    // Panels sit slightly off the editor background, like the Neovim floats
+    let surface = adjust_lightness(&c.background, if is_light { -0.04 } else { 0.04 });
+    let subtle = adjust_lightness(&c.background, if is_light { -0.08 } else { 0.08 });
+
+    let mut colors = Map::new();
+    let mut set = |key: &str, value: &str| {
+        colors.insert(key.to_string(), Value::String(value.to_string()));
+    };
+
+    // Editor
+    set("editor.background", &c.background);
+    set("editor.foreground", &c.foreground);
+    set("editorCursor.foreground", &c.cursor);
+    set("editor.selectionBackground", &c.selection_background);
+    set("editor.selectionForeground", &c.selection_foreground);
+    set("editor.lineHighlightBackground", &surface);
+    set("editor.findMatchBackground", &c.color3);
+    set("editorLineNumber.foreground", &c.color8);
+    set("editorLineNumber.activeForeground", &c.accent);
+    set("editorIndentGuide.background1", &subtle);
+    set("editorWhitespace.foreground", &subtle);
+    set("editorBracketMatch.border", &c.accent);
+    set("editorError.foreground", &c.color1);
+    set("editorWarning.foreground", &c.color3);
+    set("editorInfo.foreground", &c.color4);
+    set("editorHint.foreground", &c.color6);
+    set("editorGutter.addedBackground", &c.color2);
+    set("editorGutter.modifiedBackground", &c.color3);
+    set("editorGutter.deletedBackground", &c.color1);
+    set("editorWidget.background", &surface);
+    set("editorGroupHeader.tabsBackground", &surface);
+
+    // Workbench chrome
+    set("foreground", &c.foreground);
+    set("focusBorder", &c.accent);
+    set("activityBar.background", &surface);
+    set("activityBar.foreground", &c.foreground);
+    set("activityBar.inactiveForeground", &c.color8);
+    set("activityBarBadge.background", &c.accent);
+    set("activityBarBadge.foreground", &c.background);
+    set("sideBar.background", &surface);
+    set("sideBar.foreground", &c.foreground);
+    set("sideBarSectionHeader.background", &surface);
+    set("titleBar.activeBackground", &surface);
+    set("titleBar.activeForeground", &c.foreground);
+    set("titleBar.inactiveBackground", &surface);
+    set("titleBar.inactiveForeground", &c.color8);
+    set("statusBar.background", &surface);
+    set("statusBar.foreground", &c.foreground);
+    set("statusBarItem.remoteBackground", &c.accent);
+    set("statusBarItem.remoteForeground", &c.background);
+    set("tab.activeBackground", &c.background);
+    set("tab.activeForeground", &c.foreground);
+    set("tab.activeBorderTop", &c.accent);
+    set("tab.inactiveBackground", &surface);
+    set("tab.inactiveForeground", &c.color8);
+    set("panel.background", &surface);
+    set("panel.border", &subtle);
+    set("panelTitle.activeBorder", &c.accent);
+    set("button.background", &c.accent);
+    set("button.foreground", &c.background);
+    set("badge.background", &c.accent);
+    set("badge.foreground", &c.background);
+    set("input.background", &surface);
+    set("input.foreground", &c.foreground);
+    set("input.border", &subtle);
+    set("dropdown.background", &surface);
+    set("list.activeSelectionBackground", &c.selection_background);
+    set("list.activeSelectionForeground", &c.selection_foreground);
+    set("list.hoverBackground", &subtle);
+    set("list.highlightForeground", &c.accent);
+    set("progressBar.background", &c.accent);
+    set("textLink.foreground", &c.color4);
+    set("gitDecoration.addedResourceForeground", &c.color2);
+    set("gitDecoration.modifiedResourceForeground", &c.color3);
+    set("gitDecoration.deletedResourceForeground", &c.color1);
+    set("gitDecoration.untrackedResourceForeground", &c.color6);
+
+    // Integrated terminal
+    set("terminal.background", &c.background);
+    set("terminal.foreground", &c.foreground);
+    set("terminalCursor.foreground", &c.cursor);
+    let ansi = [
+        ("Black", &c.color0, &c.color8),
+        ("Red", &c.color1, &c.color9),
+        ("Green", &c.color2, &c.color10),
+        ("Yellow", &c.color3, &c.color11),
+        ("Blue", &c.color4, &c.color12),
+        ("Magenta", &c.color5, &c.color13),
+        ("Cyan", &c.color6, &c.color14),
+        ("White", &c.color7, &c.color15),
+    ];
+    for (name, normal, bright) in ansi {
+        set(&format!("terminal.ansi{}", name), normal);
+        set(&format!("terminal.ansiBright{}", name), bright);
+    }
+
+    colors
+}
+
+// TextMate scopes mirror the syntax groups of the generated Neovim colorscheme
+fn token_colors(c: &ColorsConfig) -> Vec<Value> {
+    let rule = |name: &str, scopes: &[&str], foreground: &str, font_style: Option<&str>| {
+        let mut settings = json!({ "foreground": foreground });
+        if let Some(style) = font_style {
+            settings["fontStyle"] = json!(style);
+        }
+        json!({ "name": name, "scope": scopes, "settings": settings })
+    };
+
+    vec![
+        rule(
+            "Comment",
+            &["comment", "punctuation.definition.comment"],
+            &c.color8,
+            Some("italic"),
+        ),
+        rule("String", &["string", "string.quoted"], &c.color2, None),
+        rule("Escape", &["constant.character.escape"], &c.color6, None),
+        rule(
+            "Number",
+            &["constant.numeric", "constant.language"],
+            &c.color3,
+            None,
+        ),
+        rule(
+            "Constant",
+            &["constant", "variable.other.constant"],
+            &c.color11,
+            None,
+        ),
+        rule(
+            "Keyword",
+            &["keyword", "storage", "storage.type", "storage.modifier"],
+            &c.color5,
+            None,
+        ),
+        rule("Operator", &["keyword.operator"], &c.color6, None),
+        rule(
+            "Function",
+            &[
+                "entity.name.function",
+                "support.function",
+                "meta.function-call",
+            ],
+            &c.color4,
+            None,
+        ),
+        rule(
+            "Type",
+            &[
+                "entity.name.type",
+                "entity.name.class",
+                "support.type",
+                "support.class",
+            ],
+            &c.color6,
+            None,
+        ),
+        rule(
+            "Variable",
+            &["variable", "meta.definition.variable"],
+            &c.foreground,
+            None,
+        ),
+        rule(
+            "Parameter",
+            &["variable.parameter"],
+            &c.color15,
+            Some("italic"),
+        ),
+        rule(
+            "Property",
+            &[
+                "variable.other.property",
+                "variable.other.object.property",
+                "support.variable.property",
+            ],
+            &c.color12,
+            None,
+        ),
+        rule(
+            "Builtin",
+            &["variable.language", "support.variable"],
+            &c.color9,
+            None,
+        ),
+        rule("Tag", &["entity.name.tag"], &c.color5, None),
+        rule(
+            "Attribute",
+            &["entity.other.attribute-name"],
+            &c.color4,
+            None,
+        ),
+        rule(
+            "Punctuation",
+            &["punctuation", "meta.brace"],
+            &c.color7,
+            None,
+        ),
+        rule(
+            "Heading",
+            &["markup.heading", "entity.name.section"],
+            &c.accent,
+            Some("bold"),
+        ),
+        rule(
+            "Link",
+            &["markup.underline.link"],
+            &c.color4,
+            Some("underline"),
+        ),
+        rule("Invalid", &["invalid", "invalid.illegal"], &c.color1, None),
+    ]
+}
+
+// The color theme JSON contributed by the generated extension
+pub fn generate_vscode_theme(theme_name: &str, colors: &ColorsConfig, is_light: bool) -> Value {
+    json!({
+        "name": theme_name,
+        "type": if is_light { "light" } else { "dark" },
+        "semanticHighlighting": true,
+        "colors": workbench_colors(colors, is_light),
+        "tokenColors": token_colors(colors),
+    })
+}
+
+fn theme_file_name(theme_name: &str) -> String {
+    format!("{}-color-theme.json", extension_slug(theme_name))
+}
+
+// The manifest of the generated extension
+pub fn generate_vscode_package_json(theme_name: &str, version: &str, is_light: bool) -> Value {
+    json!({
+        "name": extension_name(theme_name),
+        "displayName": theme_name,
+        "description": format!("{} color theme generated by Omarchist", theme_name),
+        "publisher": VSCODE_PUBLISHER,
+        "version": version,
+        "engines": { "vscode": "^1.70.0" },
+        "categories": ["Themes"],
+        "contributes": {
+            "themes": [{
+                "label": theme_name,
+                "uiTheme": if is_light { "vs" } else { "vs-dark" },
+                "path": format!("./themes/{}", theme_file_name(theme_name)),
+            }],
+        },
+    })
+}
+
+// Write vscode.json, plus the extension folder in generated mode
+pub fn update_vscode_theme(
+    theme_name: &str,
+    config: &VscodeConfig,
+    theme: &EditingTheme,
+) -> Result<(), String> {
+    let themes_dir = get_custom_themes_dir()
+        .ok_or_else(|| "Could not determine custom themes directory".to_string())?;
+
+    let theme_dir = themes_dir.join(theme_name);
+
+    if !theme_dir.exists() {
+        return Err(format!("Theme '{}' not found", theme_name));
+    }
+
+    let extension_dir = theme_dir.join(VSCODE_EXTENSION_DIR);
+
+    let (name, extension) = match config.mode {
+        EditorThemeMode::Plugin => {
+            unlink_vscode_extension(theme_name)?;
+
+            // A leftover generated extension would be picked up by theme bundles
+            if extension_dir.exists() {
+                fs::remove_dir_all(&extension_dir)
+                    .map_err(|e| format!("Failed to remove VS Code extension: {}", e))?;
+            }
+
+            // An empty plugin config means the theme never had one; leave any existing file alone
+            if config.name.is_empty() && config.extension.is_empty() {
+                return Ok(());
+            }
+
+            (config.name.clone(), config.extension.clone())
+        }
+        EditorThemeMode::Generated => {
+            write_vscode_extension(&extension_dir, theme_name, theme)?;
+            link_vscode_extension(theme_name)?;
+            (theme_name.to_string(), generated_extension_id(theme_name))
+        }
+    };
+
+    let vscode_json = json!({ "name": name, "extension": extension });
+    let content = serde_json::to_string_pretty(&vscode_json)
+        .map_err(|e| format!("Failed to serialize vscode.json: {}", e))?;
+    fs::write(theme_dir.join("vscode.json"), content)
+        .map_err(|e| format!("Failed to write vscode.json: {}", e))?;
+
+    Ok(())
+}
+
+fn write_vscode_extension(
+    extension_dir: &Path,
+    theme_name: &str,
+    theme: &EditingTheme,
+) -> Result<(), String> {
+    let themes_subdir = extension_dir.join("themes");
+    fs::create_dir_all(&themes_subdir)
+        .map_err(|e| format!("Failed to create VS Code extension directory: {}", e))?;
+
+    let write_json = |path: PathBuf, value: &Value| {
+        let content = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to serialize '{}': {}", path.display(), e))?;
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+    };
+
+    // VS Code requires a semver version; theme versions are free text
+    let version = if theme.version.split('.').count() == 3 {
+        theme.version.as_str()
+    } else {
+        "1.0.0"
+    };
+
+    write_json(
+        extension_dir.join("package.json"),
+        &generate_vscode_package_json(theme_name, version, theme.is_light_theme),
+    )?;
+    write_json(
+        themes_subdir.join(theme_file_name(theme_name)),
+        &generate_vscode_theme(theme_name, &theme_palette(theme), theme.is_light_theme),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_id_is_slugified() {
+        assert_eq!(extension_slug("My Theme_2"), "my-theme-2");
+        assert_eq!(
+            generated_extension_id("--Nord--"),
+            "omarchist.omarchist-nord"
+        );
+    }
+
+    #[test]
+    fn generated_theme_uses_palette() {
+        let colors = ColorsConfig::default();
+        let theme = generate_vscode_theme("test", &colors, false);
+
+        assert_eq!(theme["type"], "dark");
+        assert_eq!(
+            theme["colors"]["editor.background"],
+            colors.background.as_str()
+        );
+        assert_eq!(
+            theme["colors"]["terminal.ansiBrightRed"],
+            colors.color9.as_str()
+        );
+        assert!(
+            theme["tokenColors"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|rule| rule["settings"]["foreground"] == colors.color4.as_str())
+        );
+    }
+
+    #[test]
+    fn package_json_points_at_theme_file() {
+        let package = generate_vscode_package_json("Nord", "1.0.0", true);
+        let contributed = &package["contributes"]["themes"][0];
+
+        assert_eq!(package["name"], "omarchist-nord");
+        assert_eq!(contributed["uiTheme"], "vs");
+        assert_eq!(contributed["path"], "./themes/nord-color-theme.json");
+    }
+
+    #[test]
+    fn legacy_configs_are_read_as_plugin_mode() {
+        let json = r#"{ "vscode": { "raw_config": "{ \"name\": \"Nord\", \"extension\": \"arcticicestudio.nord-visual-studio-code\" }" } }"#;
+        let apps: crate::types::themes::AppConfigs = serde_json::from_str(json).unwrap();
+        let vscode = apps.vscode.unwrap();
+
+        assert_eq!(vscode.mode, EditorThemeMode::Plugin);
+        assert_eq!(vscode.name, "Nord");
+        assert_eq!(vscode.extension, "arcticicestudio.nord-visual-studio-code");
+
+        let json = r#"{ "vscode": { "mode": "generated" } }"#;
+        let apps: crate::types::themes::AppConfigs = serde_json::from_str(json).unwrap();
+        assert_eq!(apps.vscode.unwrap().mode, EditorThemeMode::Generated);
+    }
+
+    #[test]
+    fn extension_links_replace_older_versions_only() {
+        let dir =
+            std::env::temp_dir().join(format!("omarchist-vscode-link-{}", std::process::id()));
+        let extensions_dir = dir.join("extensions");
+        let extension_dir = dir.join(VSCODE_EXTENSION_DIR);
+        fs::create_dir_all(&extension_dir).unwrap();
+        fs::write(
+            extension_dir.join("package.json"),
+            generate_vscode_package_json("Nord", "1.2.0", false).to_string(),
+        )
+        .unwrap();
+
+        fs::create_dir_all(extensions_dir.join("omarchist.omarchist-nord-light-1.0.0")).unwrap();
+        link_extension_into(&extensions_dir, &extension_dir, "Nord").unwrap();
+        fs::write(
+            extension_dir.join("package.json"),
+            r#"{ "version": "1.3.0" }"#,
+        )
+        .unwrap();
+        link_extension_into(&extensions_dir, &extension_dir, "Nord").unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&extensions_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "omarchist.omarchist-nord-1.3.0",
+                "omarchist.omarchist-nord-light-1.0.0"
+            ]
+        );
+
+        unlink_extension_from(&extensions_dir, "Nord").unwrap();
+        assert_eq!(fs::read_dir(&extensions_dir).unwrap().count(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
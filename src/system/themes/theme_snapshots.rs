@@ -0,0 +1,434 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+
+use super::custom_themes::get_user_themes_dir;
+use super::theme_file_ops::copy_dir_recursive;
+use crate::system::config::config_setup::read_settings;
+use crate::types::themes::{SnapshotFieldChange, ThemeSnapshot};
+
+// Snapshots live inside the theme so they move with it on rename
+pub const SNAPSHOTS_DIR: &str = ".history";
+
+// Ids sort chronologically as plain strings
+const SNAPSHOT_ID_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+
+// Auto-saves closer together than this form a burst, of which only the first
+// and latest snapshots are kept, so dragging a color picker does not flood the log
+const SNAPSHOT_MERGE_SECS: i64 = 30;
+
+// Marks a snapshot recorded by an auto-save, which a later one may replace
+const AUTO_SAVE_MARKER: &str = ".autosave";
+
+// Not captured or restored: the history itself, and backgrounds, which are
+// large and are not generated by saves
+const SKIPPED_ENTRIES: &[&str] = &[SNAPSHOTS_DIR, "backgrounds", AUTO_SAVE_MARKER];
+
+// The snapshot retention limit when snapshots are enabled in settings
+pub fn snapshot_limit_from_settings() -> Option<usize> {
+    read_settings()
+        .ok()
+        .filter(|s| s.settings.theme_snapshots)
+        .map(|s| s.settings.theme_snapshot_limit.max(1))
+}
+
+// Record the current state of a theme directory. With `merge_recent` the snapshot
+// counts as an auto-save, and replaces the latest snapshot when both belong to
+// the same burst of auto-saves and it is not the first of that burst.
+pub fn record_theme_snapshot(
+    theme_dir: &Path,
+    limit: usize,
+    merge_recent: bool,
+) -> Result<ThemeSnapshot, String> {
+    let history_dir = theme_dir.join(SNAPSHOTS_DIR);
+    let now = Utc::now();
+
+    if merge_recent
+        && let [.., previous, latest] = snapshot_ids(&history_dir).as_slice()
+        && is_auto_save(&history_dir, previous)
+        && is_auto_save(&history_dir, latest)
+        && let (Some(previous_at), Some(latest_at)) =
+            (parse_snapshot_id(previous), parse_snapshot_id(latest))
+        && (latest_at - previous_at).num_seconds() < SNAPSHOT_MERGE_SECS
+        && (now - latest_at).num_seconds() < SNAPSHOT_MERGE_SECS
+    {
+        let _ = fs::remove_dir_all(history_dir.join(latest));
+    }
+
+    let id = now.format(SNAPSHOT_ID_FORMAT).to_string();
+    let snapshot_dir = history_dir.join(&id);
+    fs::create_dir_all(&snapshot_dir)
+        .map_err(|e| format!("Failed to create snapshot directory: {}", e))?;
+
+    copy_theme_tree(theme_dir, &snapshot_dir)?;
+    if merge_recent {
+        fs::write(snapshot_dir.join(AUTO_SAVE_MARKER), "")
+            .map_err(|e| format!("Failed to mark snapshot: {}", e))?;
+    }
+
+    prune_snapshots(&history_dir, limit);
+
+    Ok(read_snapshot_info(&snapshot_dir, &id))
+}
+
+// All snapshots of a user theme, newest first
+pub fn list_theme_snapshots(theme_name: &str) -> Result<Vec<ThemeSnapshot>, String> {
+    let history_dir = get_theme_dir(theme_name)?.join(SNAPSHOTS_DIR);
+
+    Ok(snapshot_ids(&history_dir)
+        .into_iter()
+        .rev()
+        .map(|id| read_snapshot_info(&history_dir.join(&id), &id))
+        .collect())
+}
+
+// Field-by-field differences between the `omarchist.json` of two snapshots
+pub fn diff_theme_snapshots(
+    theme_name: &str,
+    old_id: &str,
+    new_id: &str,
+) -> Result<Vec<SnapshotFieldChange>, String> {
+    let history_dir = get_theme_dir(theme_name)?.join(SNAPSHOTS_DIR);
+    let old = read_snapshot_json(&history_dir.join(old_id))?;
+    let new = read_snapshot_json(&history_dir.join(new_id))?;
+
+    Ok(diff_theme_json(&old, &new))
+}
+
+// Copy a snapshot's files back over the theme. The current state is recorded
+// first so the rollback itself can be undone.
+pub fn rollback_theme_snapshot(theme_name: &str, id: &str) -> Result<(), String> {
+    let theme_dir = get_theme_dir(theme_name)?;
+    let snapshot_dir = theme_dir.join(SNAPSHOTS_DIR).join(id);
+
+    if !snapshot_dir.join("omarchist.json").exists() {
+        return Err(format!("Snapshot '{}' not found", id));
+    }
+
+    let limit = snapshot_limit_from_settings().unwrap_or(usize::MAX);
+    record_theme_snapshot(&theme_dir, limit, false)?;
+
+    restore_snapshot(&snapshot_dir, &theme_dir)
+}
+
+fn restore_snapshot(snapshot_dir: &Path, theme_dir: &Path) -> Result<(), String> {
+    // Light mode is the presence of a marker file, so it has to be removed too
+    if !snapshot_dir.join("light.mode").exists() {
+        let _ = fs::remove_file(theme_dir.join("light.mode"));
+    }
+
+    // Generated folders such as `vscode-extension` are replaced wholesale, so
+    // one the snapshot did not have is dropped
+    for path in theme_entries(theme_dir)? {
+        if path.is_dir() {
+            fs::remove_dir_all(&path)
+                .map_err(|e| format!("Failed to remove '{}': {}", path.display(), e))?;
+        }
+    }
+
+    copy_theme_tree(snapshot_dir, theme_dir)
+}
+
+pub fn diff_theme_json(old: &Value, new: &Value) -> Vec<SnapshotFieldChange> {
+    let mut old_fields = BTreeMap::new();
+    let mut new_fields = BTreeMap::new();
+    flatten_json("", old, &mut old_fields);
+    flatten_json("", new, &mut new_fields);
+
+    // Timestamps change on every save and would drown out the real edits
+    for key in ["created_at", "modified_at"] {
+        old_fields.remove(key);
+        new_fields.remove(key);
+    }
+
+    let mut paths: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old_value = old_fields.get(path).cloned();
+            let new_value = new_fields.get(path).cloned();
+            (old_value != new_value).then(|| SnapshotFieldChange {
+                path: path.clone(),
+                old_value,
+                new_value,
+            })
+        })
+        .collect()
+}
+
+fn flatten_json(prefix: &str, value: &Value, fields: &mut BTreeMap<String, String>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten_json(&join(key), value, fields);
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                flatten_json(&join(&index.to_string()), value, fields);
+            }
+        }
+        Value::String(s) => {
+            fields.insert(prefix.to_string(), s.clone());
+        }
+        Value::Null => {}
+        other => {
+            fields.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+fn get_theme_dir(theme_name: &str) -> Result<PathBuf, String> {
+    let theme_dir = get_user_themes_dir()
+        .ok_or_else(|| "Could not determine user themes directory".to_string())?
+        .join(theme_name);
+
+    if !theme_dir.exists() {
+        return Err(format!("Theme '{}' not found", theme_name));
+    }
+
+    Ok(theme_dir)
+}
+
+// Files and folders of a theme or snapshot, except `SKIPPED_ENTRIES`
+fn theme_entries(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read '{}': {}", dir.display(), e))?;
+
+    Ok(entries
+        .flatten()
+        .filter(|entry| !SKIPPED_ENTRIES.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .map(|entry| entry.path())
+        .collect())
+}
+
+fn copy_theme_tree(src: &Path, dest: &Path) -> Result<(), String> {
+    for path in theme_entries(src)? {
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        if path.is_dir() {
+            copy_dir_recursive(&path, &dest.join(file_name))?;
+        } else {
+            fs::copy(&path, dest.join(file_name))
+                .map_err(|e| format!("Failed to copy '{}': {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+fn is_auto_save(history_dir: &Path, id: &str) -> bool {
+    history_dir.join(id).join(AUTO_SAVE_MARKER).exists()
+}
+
+// Snapshot ids, oldest first
+fn snapshot_ids(history_dir: &Path) -> Vec<String> {
+    let mut ids: Vec<String> = fs::read_dir(history_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|id| parse_snapshot_id(id).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ids.sort();
+    ids
+}
+
+fn prune_snapshots(history_dir: &Path, limit: usize) {
+    let ids = snapshot_ids(history_dir);
+    let excess = ids.len().saturating_sub(limit);
+
+    for id in &ids[..excess] {
+        let _ = fs::remove_dir_all(history_dir.join(id));
+    }
+}
+
+fn parse_snapshot_id(id: &str) -> Option<chrono::DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(id, SNAPSHOT_ID_FORMAT)
+        .ok()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+fn read_snapshot_json(snapshot_dir: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(snapshot_dir.join("omarchist.json"))
+        .map_err(|e| format!("Failed to read snapshot: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+fn read_snapshot_info(snapshot_dir: &Path, id: &str) -> ThemeSnapshot {
+    let json = read_snapshot_json(snapshot_dir).unwrap_or_default();
+    let field = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    ThemeSnapshot {
+        id: id.to_string(),
+        created_at: parse_snapshot_id(id)
+            .map(|t| t.with_timezone(&Local).to_rfc3339())
+            .unwrap_or_default(),
+        version: field("version"),
+        modified_at: field("modified_at"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_changed_added_and_removed_fields() {
+        let old = json!({
+            "name": "nord",
+            "colors": { "accent": "#000000", "color1": "#111111" },
+        });
+        let new = json!({
+            "name": "nord",
+            "colors": { "accent": "#ffffff", "color2": "#222222" },
+        });
+
+        let changes = diff_theme_json(&old, &new);
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+
+        assert_eq!(paths, ["colors.accent", "colors.color1", "colors.color2"]);
+        assert_eq!(changes[0].old_value.as_deref(), Some("#000000"));
+        assert_eq!(changes[0].new_value.as_deref(), Some("#ffffff"));
+        assert_eq!(changes[1].new_value, None);
+        assert_eq!(changes[2].old_value, None);
+    }
+
+    #[test]
+    fn diff_ignores_timestamps() {
+        let old = json!({ "modified_at": "2025-01-01", "created_at": "2025-01-01" });
+        let new = json!({ "modified_at": "2026-01-01", "created_at": "2026-01-01" });
+
+        assert!(diff_theme_json(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn diff_flattens_arrays_and_numbers() {
+        let old = json!({ "apps": { "btop": { "sizes": [1, 2] } } });
+        let new = json!({ "apps": { "btop": { "sizes": [1, 3] } } });
+
+        let changes = diff_theme_json(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "apps.btop.sizes.1");
+        assert_eq!(changes[0].new_value.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn snapshot_ids_round_trip() {
+        let now = Utc::now();
+        let id = now.format(SNAPSHOT_ID_FORMAT).to_string();
+        let parsed = parse_snapshot_id(&id).expect("generated id should parse");
+
+        assert_eq!(parsed.timestamp_millis(), now.timestamp_millis());
+        assert!(parse_snapshot_id("not-a-snapshot").is_none());
+    }
+
+    #[test]
+    fn record_and_prune_snapshots() {
+        let theme_dir = std::env::temp_dir().join(format!(
+            "omarchist-snapshot-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&theme_dir).unwrap();
+        fs::write(theme_dir.join("omarchist.json"), r#"{"version":"1"}"#).unwrap();
+
+        for _ in 0..3 {
+            record_theme_snapshot(&theme_dir, 2, false).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        let history_dir = theme_dir.join(SNAPSHOTS_DIR);
+        assert_eq!(snapshot_ids(&history_dir).len(), 2);
+
+        let latest = snapshot_ids(&history_dir).pop().unwrap();
+        assert!(history_dir.join(latest).join("omarchist.json").exists());
+
+        let _ = fs::remove_dir_all(&theme_dir);
+    }
+
+    #[test]
+    fn auto_saves_keep_the_first_of_a_burst() {
+        let theme_dir = std::env::temp_dir().join(format!(
+            "omarchist-snapshot-burst-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&theme_dir).unwrap();
+        fs::write(theme_dir.join("omarchist.json"), r#"{"version":"1"}"#).unwrap();
+        let history_dir = theme_dir.join(SNAPSHOTS_DIR);
+        let record = |merge| {
+            record_theme_snapshot(&theme_dir, 10, merge).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+
+        // An auto-save never replaces a snapshot that was not one
+        record(false);
+        record(true);
+        assert_eq!(snapshot_ids(&history_dir).len(), 2);
+
+        // The first auto-save of the burst stays, the intermediate ones are replaced
+        record(true);
+        let first = snapshot_ids(&history_dir)[1].clone();
+        record(true);
+        record(true);
+        let ids = snapshot_ids(&history_dir);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[1], first);
+
+        let _ = fs::remove_dir_all(&theme_dir);
+    }
+
+    #[test]
+    fn rollback_restores_subdirectories() {
+        let theme_dir = std::env::temp_dir().join(format!(
+            "omarchist-snapshot-tree-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let extension_dir = theme_dir.join("vscode-extension");
+        fs::create_dir_all(extension_dir.join("themes")).unwrap();
+        fs::create_dir_all(theme_dir.join("backgrounds")).unwrap();
+        fs::write(theme_dir.join("omarchist.json"), r#"{"version":"1"}"#).unwrap();
+        fs::write(extension_dir.join("themes").join("a.json"), "old").unwrap();
+        fs::write(theme_dir.join("backgrounds").join("1.png"), "").unwrap();
+
+        let snapshot = record_theme_snapshot(&theme_dir, 10, true).unwrap();
+        let snapshot_dir = theme_dir.join(SNAPSHOTS_DIR).join(&snapshot.id);
+        assert!(snapshot_dir.join("vscode-extension/themes/a.json").exists());
+        assert!(!snapshot_dir.join("backgrounds").exists());
+
+        fs::write(extension_dir.join("themes").join("a.json"), "new").unwrap();
+        fs::create_dir_all(theme_dir.join("extra")).unwrap();
+        restore_snapshot(&snapshot_dir, &theme_dir).unwrap();
+        assert!(!theme_dir.join("extra").exists());
+        assert!(theme_dir.join("backgrounds").join("1.png").exists());
+        assert_eq!(
+            fs::read_to_string(extension_dir.join("themes").join("a.json")).unwrap(),
+            "old"
+        );
+        assert!(!theme_dir.join(AUTO_SAVE_MARKER).exists());
+
+        let _ = fs::remove_dir_all(&theme_dir);
+    }
+}
@@ -1,21 +1,32 @@
 mod config;
+mod custom_module;
 mod icons;
 mod jsonc;
 mod library;
 mod paths;
+mod preview_data;
 mod profiles;
+mod render;
+mod style;
 mod types;
+mod validation;
 
 pub use config::{
-    add_module_to_zone, get_bar_settings, get_live_bar_settings, get_live_module_config,
-    get_module_config, load_live_waybar_config, load_waybar_config, replace_top_level_value,
-    save_waybar_config, set_bar_setting, set_module_config_field,
+    add_module_to_zone, add_waybar_bar, get_bar_settings, get_live_bar_settings,
+    get_live_module_config, get_module_config, load_live_waybar_config, load_waybar_config,
+    remove_bar_setting, remove_waybar_bar, replace_top_level_value, save_waybar_config,
+    set_bar_setting, set_module_config_field,
+};
+pub use custom_module::{
+    CustomModule, LIVE_SCRIPTS_DIR, add_custom_module, check_custom_output, run_custom_module_once,
 };
 pub use library::module_library;
 pub use paths::{
-    live_waybar_config_path, live_waybar_dir, omarchist_config_dir, waybar_current_profile_path,
-    waybar_profile_config_path, waybar_profiles_dir,
+    current_theme_waybar_css_path, live_waybar_config_path, live_waybar_dir, omarchist_config_dir,
+    waybar_current_profile_path, waybar_profile_config_path, waybar_profile_scripts_dir,
+    waybar_profile_style_path, waybar_profiles_dir,
 };
+pub use preview_data::{BatteryData, LiveSampler, PreviewData};
 pub use profiles::{
     CUSTOM_WAYBAR_PROFILE, OMARCHY_DEFAULT_PROFILE, UNKNOWN_MANAGED_PROFILE, adopt_live_waybar,
     apply_waybar_profile, create_waybar_profile, current_live_waybar_profile,
@@ -25,4 +36,13 @@ pub use profiles::{
     is_read_only_waybar_profile, list_waybar_profiles, rename_waybar_profile,
     start_with_omarchy_default_profile, unique_waybar_profile_name,
 };
-pub use types::{BarSettings, LibraryModule, WaybarConfig, WaybarModule, WaybarZone};
+pub use render::render_module;
+pub use style::{
+    COMMON_STYLE_SELECTORS, STYLE_PROPERTIES, StyleRule, THEME_IMPORT, WaybarStyle,
+    load_waybar_style, module_selector, module_style_selectors, parse_define_colors,
+    save_waybar_style, theme_colors,
+};
+pub use types::{BarSettings, LibraryModule, WaybarBar, WaybarConfig, WaybarModule, WaybarZone};
+pub use validation::{
+    Diagnostic, Severity, has_errors, validate_waybar_config, validate_waybar_profile,
+};
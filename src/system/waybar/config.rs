@@ -1,25 +1,38 @@
 use std::fs;
 
 use super::icons::new_module;
-use super::jsonc::{find_top_level_key, find_value_end, strip_jsonc_comments};
+use super::jsonc::{find_top_level_key, find_value_end, parse_jsonc, strip_jsonc_comments};
 use super::paths::{live_waybar_config_path, waybar_profile_config_path};
-use super::types::{BarSettings, WaybarConfig, WaybarModule, WaybarZone};
+use super::types::{BarSettings, WaybarBar, WaybarConfig, WaybarZone};
+
+// Added by "Add Bar". It has its own empty zones so every bar can be edited
+// on its own.
+const NEW_BAR: &str = r#"{
+  "layer": "top",
+  "position": "bottom",
+  "modules-left": [],
+  "modules-center": [],
+  "modules-right": []
+}"#;
 
-pub fn get_bar_settings(profile_name: &str) -> Option<BarSettings> {
+pub fn get_bar_settings(profile_name: &str, bar: usize) -> Option<BarSettings> {
     let config_path = waybar_profile_config_path(profile_name)?;
-    get_bar_settings_from_path(&config_path)
+    get_bar_settings_from_path(&config_path, bar)
 }
 
-pub fn get_live_bar_settings() -> Option<BarSettings> {
+pub fn get_live_bar_settings(bar: usize) -> Option<BarSettings> {
     let config_path = live_waybar_config_path()?;
-    get_bar_settings_from_path(&config_path)
+    get_bar_settings_from_path(&config_path, bar)
 }
 
-fn get_bar_settings_from_path(config_path: &std::path::Path) -> Option<BarSettings> {
-    let raw = fs::read_to_string(config_path).ok()?;
-    let stripped = strip_jsonc_comments(&raw);
-    let json: serde_json::Value = serde_json::from_str(&stripped).ok()?;
+fn get_bar_settings_from_path(config_path: &std::path::Path, bar: usize) -> Option<BarSettings> {
+    let json = read_config_json(config_path)?;
+    bar_values(&json)
+        .get(bar)
+        .map(|bar| parse_bar_settings(bar))
+}
 
+fn parse_bar_settings(json: &serde_json::Value) -> BarSettings {
     let str_field =
         |key: &str| -> Option<String> { json.get(key)?.as_str().map(|s| s.to_string()) };
     let u32_field = |key: &str| -> Option<u32> { json.get(key)?.as_u64().map(|v| v as u32) };
@@ -33,7 +46,7 @@ fn get_bar_settings_from_path(config_path: &std::path::Path) -> Option<BarSettin
         .and_then(|v| v.as_i64())
         .map(|v| v as i32);
 
-    Some(BarSettings {
+    BarSettings {
         position: str_field("position"),
         height: u32_field("height"),
         layer: str_field("layer"),
@@ -45,25 +58,28 @@ fn get_bar_settings_from_path(config_path: &std::path::Path) -> Option<BarSettin
         margin_right: i32_field("margin-right").or(unified_margin),
         margin_bottom: i32_field("margin-bottom").or(unified_margin),
         margin_left: i32_field("margin-left").or(unified_margin),
-    })
+    }
 }
 
 /// `value` is a JSON-serialized value (e.g. `"\"top\""`, `"26"`, `"true"`).
 pub fn set_bar_setting(
     profile_name: &str,
+    bar: usize,
     key: &str,
     value: &serde_json::Value,
 ) -> Result<(), String> {
-    let config_path = waybar_profile_config_path(profile_name)
-        .ok_or_else(|| "Could not determine home directory".to_string())?;
-
-    let raw =
-        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
-
-    let new_raw = replace_top_level_value(&raw, key, value)
-        .ok_or_else(|| format!("Could not locate or insert key \"{}\" in config", key))?;
+    edit_config(profile_name, |raw| {
+        edit_bar(&raw, bar, |src| replace_top_level_value(src, key, value))
+            .ok_or_else(|| format!("Could not locate or insert key \"{}\" in config", key))
+    })
+}
 
-    fs::write(&config_path, new_raw).map_err(|e| format!("Failed to write config: {}", e))
+/// Remove a bar setting so Waybar falls back to its default, e.g. dropping
+/// `output` puts the bar on every monitor.
+pub fn remove_bar_setting(profile_name: &str, bar: usize, key: &str) -> Result<(), String> {
+    edit_config(profile_name, |raw| {
+        Ok(edit_bar(&raw, bar, |src| remove_top_level_key(src, key)).unwrap_or(raw))
+    })
 }
 
 pub fn load_waybar_config(profile_name: &str) -> Option<WaybarConfig> {
@@ -80,143 +96,222 @@ fn load_waybar_config_from_path(
     profile_name: &str,
     config_path: &std::path::Path,
 ) -> Option<WaybarConfig> {
-    let raw = fs::read_to_string(config_path).ok()?;
-    let stripped = strip_jsonc_comments(&raw);
-    let json: serde_json::Value = serde_json::from_str(&stripped).ok()?;
+    let json = read_config_json(config_path)?;
+
+    Some(WaybarConfig {
+        profile_name: profile_name.to_string(),
+        bars: bar_values(&json).into_iter().map(parse_bar).collect(),
+    })
+}
 
-    let parse_modules = |list_key: &str, zone: WaybarZone| -> Vec<WaybarModule> {
-        json.get(list_key)
+fn parse_bar(json: &serde_json::Value) -> WaybarBar {
+    let parse_modules = |zone: WaybarZone| {
+        json.get(zone_key(&zone))
             .and_then(|v| v.as_array())
             .map(|arr| {
                 arr.iter()
                     .filter_map(|v| v.as_str())
-                    .map(|s| new_module(s, zone.clone(), &json))
+                    .map(|s| new_module(s, zone.clone(), json))
                     .collect()
             })
             .unwrap_or_default()
     };
 
-    Some(WaybarConfig {
-        profile_name: profile_name.to_string(),
-        modules_left: parse_modules("modules-left", WaybarZone::Left),
-        modules_center: parse_modules("modules-center", WaybarZone::Center),
-        modules_right: parse_modules("modules-right", WaybarZone::Right),
-    })
+    WaybarBar {
+        settings: parse_bar_settings(json),
+        modules_left: parse_modules(WaybarZone::Left),
+        modules_center: parse_modules(WaybarZone::Center),
+        modules_right: parse_modules(WaybarZone::Right),
+        config: json.clone(),
+    }
 }
 
 pub fn save_waybar_config(config: &WaybarConfig) -> Result<(), String> {
-    let config_path = waybar_profile_config_path(&config.profile_name)
-        .ok_or_else(|| "Could not determine home directory".to_string())?;
+    edit_config(&config.profile_name, |raw| {
+        write_bar_modules(&raw, &config.bars)
+    })
+}
 
-    let raw =
-        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
+// Rewrite the zone arrays that differ from `bars`, leaving the others as
+// they are. A bar without one of the arrays gets it added.
+fn write_bar_modules(raw: &str, bars: &[WaybarBar]) -> Result<String, String> {
+    let json = parse_jsonc(raw).map_err(|e| format!("Failed to parse config: {}", e))?;
+    let existing = bar_values(&json);
+
+    let mut result = raw.to_string();
+    for (index, bar) in bars.iter().enumerate() {
+        for zone in [WaybarZone::Left, WaybarZone::Center, WaybarZone::Right] {
+            let json_key = zone_key(&zone);
+            let module_keys: Vec<&str> =
+                bar.modules(&zone).iter().map(|m| m.key.as_str()).collect();
+
+            let current: Vec<&str> = existing
+                .get(index)
+                .and_then(|bar| bar.get(json_key))
+                .and_then(|modules| modules.as_array())
+                .map(|modules| modules.iter().filter_map(|m| m.as_str()).collect())
+                .unwrap_or_default();
+            if current == module_keys {
+                continue;
+            }
 
-    let keys_and_modules = [
-        (
-            "modules-left",
-            config
-                .modules_left
-                .iter()
-                .map(|m| m.key.as_str())
-                .collect::<Vec<_>>(),
-        ),
-        (
-            "modules-center",
-            config
-                .modules_center
-                .iter()
-                .map(|m| m.key.as_str())
-                .collect::<Vec<_>>(),
-        ),
-        (
-            "modules-right",
-            config
-                .modules_right
-                .iter()
-                .map(|m| m.key.as_str())
-                .collect::<Vec<_>>(),
-        ),
-    ];
-
-    let mut result = raw;
-    for (json_key, module_keys) in &keys_and_modules {
-        result = replace_module_array(&result, json_key, module_keys)
-            .ok_or_else(|| format!("Could not find \"{}\" in config", json_key))?;
-    }
-
-    fs::write(&config_path, result).map_err(|e| format!("Failed to write config: {}", e))
-}
-
-pub fn get_module_config(profile_name: &str, module_key: &str) -> serde_json::Value {
+            result = edit_bar(&result, index, |src| {
+                match find_top_level_key(src, json_key) {
+                    Some(_) => replace_module_array(src, json_key, &module_keys),
+                    None => replace_top_level_value(src, json_key, &serde_json::json!(module_keys)),
+                }
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Could not update \"{}\" in bar {} of config",
+                    json_key,
+                    index + 1
+                )
+            })?;
+        }
+    }
+    Ok(result)
+}
+
+/// Append a new bar to the config, turning a single-bar config into an
+/// array. Returns the new bar's index.
+pub fn add_waybar_bar(profile_name: &str) -> Result<usize, String> {
+    let mut index = 0;
+    edit_config(profile_name, |raw| {
+        index = bar_spans(&raw).len();
+        append_bar(&raw, NEW_BAR).ok_or_else(|| "Could not add a bar to config".to_string())
+    })?;
+    Ok(index)
+}
+
+pub fn remove_waybar_bar(profile_name: &str, bar: usize) -> Result<(), String> {
+    edit_config(profile_name, |raw| {
+        if bar_spans(&raw).len() < 2 {
+            return Err("The config needs at least one bar".to_string());
+        }
+        remove_bar(&raw, bar).ok_or_else(|| format!("Bar {} not found in config", bar + 1))
+    })
+}
+
+pub fn get_module_config(profile_name: &str, bar: usize, module_key: &str) -> serde_json::Value {
     let Some(config_path) = waybar_profile_config_path(profile_name) else {
         return serde_json::Value::Null;
     };
-    get_module_config_from_path(&config_path, module_key)
+    get_module_config_from_path(&config_path, bar, module_key)
 }
 
-pub fn get_live_module_config(module_key: &str) -> serde_json::Value {
+pub fn get_live_module_config(bar: usize, module_key: &str) -> serde_json::Value {
     let Some(config_path) = live_waybar_config_path() else {
         return serde_json::Value::Null;
     };
-    get_module_config_from_path(&config_path, module_key)
+    get_module_config_from_path(&config_path, bar, module_key)
 }
 
 fn get_module_config_from_path(
     config_path: &std::path::Path,
+    bar: usize,
     module_key: &str,
 ) -> serde_json::Value {
-    let Ok(raw) = fs::read_to_string(config_path) else {
-        return serde_json::Value::Null;
-    };
-    let stripped = strip_jsonc_comments(&raw);
-    let Ok(json) = serde_json::from_str::<serde_json::Value>(&stripped) else {
+    let Some(json) = read_config_json(config_path) else {
         return serde_json::Value::Null;
     };
 
-    json.get(module_key)
+    bar_values(&json)
+        .get(bar)
+        .and_then(|bar| bar.get(module_key))
         .cloned()
         .unwrap_or(serde_json::Value::Null)
 }
 
 pub fn set_module_config_field(
     profile_name: &str,
+    bar: usize,
     module_key: &str,
     field: &str,
     value: &serde_json::Value,
 ) -> Result<(), String> {
-    let config_path = waybar_profile_config_path(profile_name)
-        .ok_or_else(|| "Could not determine home directory".to_string())?;
+    edit_config(profile_name, |raw| {
+        let mut json = parse_jsonc(&raw).map_err(|e| format!("Failed to parse config: {}", e))?;
+
+        let bar_json = match &mut json {
+            serde_json::Value::Array(bars) => bars.iter_mut().filter(|b| b.is_object()).nth(bar),
+            root if bar == 0 => Some(root),
+            _ => None,
+        };
+        let block = bar_json
+            .and_then(|b| b.as_object_mut())
+            .ok_or_else(|| format!("Bar {} not found in config", bar + 1))?
+            .entry(module_key)
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+
+        if let serde_json::Value::Object(obj) = block {
+            obj.insert(field.to_string(), value.clone());
+        } else {
+            return Err(format!("Module \"{}\" config is not an object", module_key));
+        }
 
-    let raw =
-        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
+        serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("Failed to serialise config: {}", e))
+    })
+}
 
-    let stripped = strip_jsonc_comments(&raw);
-    let mut json: serde_json::Value =
-        serde_json::from_str(&stripped).map_err(|e| format!("Failed to parse config: {}", e))?;
+pub fn add_module_to_zone(
+    profile_name: &str,
+    bar: usize,
+    module_key: &str,
+    zone: &WaybarZone,
+    default_config: &str,
+) -> Result<(), String> {
+    edit_config(profile_name, |raw| {
+        add_module_to_bar(&raw, bar, module_key, zone, default_config)
+    })
+}
 
-    let block = json
-        .as_object_mut()
-        .ok_or_else(|| "Config is not a JSON object".to_string())?
-        .entry(module_key)
-        .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+// Append `module_key` to a zone of one bar, defining it with
+// `default_config` unless the bar already has a definition
+fn add_module_to_bar(
+    raw: &str,
+    bar: usize,
+    module_key: &str,
+    zone: &WaybarZone,
+    default_config: &str,
+) -> Result<String, String> {
+    let default_config: serde_json::Value = match default_config.trim() {
+        "" => serde_json::Value::Null,
+        text => serde_json::from_str(text)
+            .map_err(|e| format!("Invalid default config for \"{}\": {}", module_key, e))?,
+    };
 
-    if let serde_json::Value::Object(obj) = block {
-        obj.insert(field.to_string(), value.clone());
-    } else {
-        return Err(format!("Module \"{}\" config is not an object", module_key));
-    }
+    let zone_key = zone_key(zone);
+    edit_bar(raw, bar, |src| {
+        // Checked before appending, which adds the key to the zone array
+        let defined = find_top_level_key(src, module_key).is_some();
+        let src = append_to_zone_array(src, zone_key, module_key)?;
+        if default_config.is_null() || defined {
+            return Some(src);
+        }
+        replace_top_level_value(&src, module_key, &default_config)
+    })
+    .ok_or_else(|| format!("Could not find zone array \"{}\" in config", zone_key))
+}
 
-    let new_raw = serde_json::to_string_pretty(&json)
-        .map_err(|e| format!("Failed to serialise config: {}", e))?;
+fn zone_key(zone: &WaybarZone) -> &'static str {
+    match zone {
+        WaybarZone::Left => "modules-left",
+        WaybarZone::Center => "modules-center",
+        WaybarZone::Right => "modules-right",
+    }
+}
 
-    fs::write(&config_path, new_raw).map_err(|e| format!("Failed to write config: {}", e))
+fn read_config_json(config_path: &std::path::Path) -> Option<serde_json::Value> {
+    let raw = fs::read_to_string(config_path).ok()?;
+    parse_jsonc(&raw).ok()
 }
 
-pub fn add_module_to_zone(
+// Read a profile's config, rewrite its text with `edit`, and write it back
+fn edit_config(
     profile_name: &str,
-    module_key: &str,
-    zone: &WaybarZone,
-    default_config: &str,
+    edit: impl FnOnce(String) -> Result<String, String>,
 ) -> Result<(), String> {
     let config_path = waybar_profile_config_path(profile_name)
         .ok_or_else(|| "Could not determine home directory".to_string())?;
@@ -224,33 +319,131 @@ pub fn add_module_to_zone(
     let raw =
         fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
 
-    let zone_key = match zone {
-        WaybarZone::Left => "modules-left",
-        WaybarZone::Center => "modules-center",
-        WaybarZone::Right => "modules-right",
-    };
-    let raw = append_to_zone_array(&raw, zone_key, module_key)
-        .ok_or_else(|| format!("Could not find zone array \"{}\" in config", zone_key))?;
+    let new_raw = edit(raw)?;
 
-    let raw = if !default_config.is_empty() && default_config != "null" {
-        let key_pat = format!("\"{}\"", module_key);
-        if raw.contains(key_pat.as_str()) {
-            raw
-        } else {
-            let block = format!("  \"{}\": {},\n", module_key, default_config);
-            let close = raw
-                .rfind('}')
-                .ok_or_else(|| "Malformed JSON: no closing `}`".to_string())?;
-            let mut result = raw[..close].to_string();
-            result.push_str(&block);
-            result.push_str(&raw[close..]);
-            result
+    fs::write(&config_path, new_raw).map_err(|e| format!("Failed to write config: {}", e))
+}
+
+// The bar objects of a parsed config, in the same order as `bar_spans`
+fn bar_values(json: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match json {
+        serde_json::Value::Array(bars) => bars.iter().filter(|b| b.is_object()).collect(),
+        serde_json::Value::Object(_) => vec![json],
+        _ => Vec::new(),
+    }
+}
+
+/// Byte ranges of the bar objects in a Waybar config: the root object
+/// itself, or each object of a root array. Strings and comments are skipped.
+pub fn bar_spans(src: &str) -> Vec<(usize, usize)> {
+    let bytes = src.as_bytes();
+    let mut spans = Vec::new();
+    let mut depth = 0usize;
+    let mut bar_depth = 0usize;
+    let mut start = 0usize;
+    let mut in_str = false;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        let ch = bytes[i];
+        if in_str {
+            if ch == b'\\' {
+                i += 2;
+                continue;
+            } else if ch == b'"' {
+                in_str = false;
+            }
+            i += 1;
+            continue;
         }
+        match ch {
+            b'"' => in_str = true,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = src[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = src[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+                continue;
+            }
+            b'{' | b'[' => {
+                if depth == 0 {
+                    bar_depth = usize::from(ch == b'[');
+                }
+                if ch == b'{' && depth == bar_depth {
+                    start = i;
+                }
+                depth += 1;
+            }
+            b'}' | b']' if depth > 0 => {
+                depth -= 1;
+                if ch == b'}' && depth == bar_depth {
+                    spans.push((start, i + 1));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Rewrite the source of one bar object with `edit`, leaving the rest of
+/// the config untouched.
+pub fn edit_bar(
+    src: &str,
+    bar: usize,
+    edit: impl FnOnce(&str) -> Option<String>,
+) -> Option<String> {
+    let (start, end) = *bar_spans(src).get(bar)?;
+    let edited = edit(&src[start..end])?;
+
+    let mut result = String::with_capacity(src.len() + edited.len());
+    result.push_str(&src[..start]);
+    result.push_str(&edited);
+    result.push_str(&src[end..]);
+    Some(result)
+}
+
+/// Append `bar_src` as a new bar. A config with a single root object is
+/// wrapped in an array first.
+pub fn append_bar(src: &str, bar_src: &str) -> Option<String> {
+    let spans = bar_spans(src);
+    let (first_start, _) = *spans.first()?;
+    let (_, last_end) = *spans.last()?;
+    let is_array = strip_jsonc_comments(src).trim_start().starts_with('[');
+
+    let mut result = String::with_capacity(src.len() + bar_src.len() + 8);
+    if is_array {
+        result.push_str(&src[..last_end]);
+        result.push_str(",\n");
+        result.push_str(bar_src);
+        result.push_str(&src[last_end..]);
     } else {
-        raw
+        result.push_str(&src[..first_start]);
+        result.push_str("[\n");
+        result.push_str(&src[first_start..last_end]);
+        result.push_str(",\n");
+        result.push_str(bar_src);
+        result.push_str("\n]");
+        result.push_str(&src[last_end..]);
+    }
+    Some(result)
+}
+
+/// Remove one bar of an array config together with the comma separating it
+/// from its neighbour.
+pub fn remove_bar(src: &str, bar: usize) -> Option<String> {
+    let spans = bar_spans(src);
+    let (start, end) = match bar {
+        0 => (spans.first()?.0, spans.get(1)?.0),
+        _ => (spans.get(bar - 1)?.1, spans.get(bar)?.1),
     };
 
-    fs::write(&config_path, raw).map_err(|e| format!("Failed to write config: {}", e))
+    let mut result = String::with_capacity(src.len());
+    result.push_str(&src[..start]);
+    result.push_str(&src[end..]);
+    Some(result)
 }
 
 // 1. If the key already exists, replace its value in-place.
@@ -261,7 +454,7 @@ pub fn replace_top_level_value(src: &str, key: &str, value: &serde_json::Value)
     let key_pat = format!("\"{}\"", key);
 
     // Case 1: key already present
-    if let Some(key_pos) = src.find(key_pat.as_str()) {
+    if let Some(key_pos) = find_top_level_key(src, key) {
         let after_key = &src[key_pos + key_pat.len()..];
         let colon_offset = after_key.find(':')?;
         let after_colon_start = key_pos + key_pat.len() + colon_offset + 1;
@@ -286,19 +479,114 @@ pub fn replace_top_level_value(src: &str, key: &str, value: &serde_json::Value)
     // Case 2: key absent — insert before first `modules-` key or before closing `}`
     let insert_line = format!("  \"{}\": {},\n", key, value_str);
 
-    let anchor = if let Some(pos) = find_top_level_key(src, "modules-left") {
-        pos
+    if let Some(anchor) = find_top_level_key(src, "modules-left") {
+        let mut result = String::with_capacity(src.len() + insert_line.len());
+        result.push_str(&src[..anchor]);
+        result.push_str(&insert_line);
+        result.push_str(&src[anchor..]);
+        return Some(result);
+    }
+
+    // Appended as the last member, so the one before it needs a comma
+    let close = src.rfind('}')?;
+    let last = last_token_end(&src[..close]);
+    let needs_comma = !matches!(src[..last].chars().last(), Some('{' | ',') | None);
+
+    let mut result = String::with_capacity(src.len() + insert_line.len() + 2);
+    result.push_str(&src[..last]);
+    if needs_comma {
+        result.push(',');
+    }
+    result.push_str(src[last..close].trim_end());
+    result.push('\n');
+    result.push_str(insert_line.trim_end_matches([',', '\n']));
+    result.push('\n');
+    result.push_str(&src[close..]);
+    Some(result)
+}
+
+// Byte position just past the last character of `src` that is neither
+// whitespace nor part of a comment
+fn last_token_end(src: &str) -> usize {
+    let bytes = src.as_bytes();
+    let mut last = 0;
+    let mut in_str = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_str => {
+                i += 2;
+                last = i.min(bytes.len());
+                continue;
+            }
+            b'"' => in_str = !in_str,
+            _ if in_str => {}
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = src[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = src[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+                continue;
+            }
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+        last = i;
+    }
+    last
+}
+
+/// Remove a top-level key and its value, along with the comma that
+/// separated it from its neighbour and, when it had a line to itself, the
+/// rest of that line.
+pub fn remove_top_level_key(src: &str, key: &str) -> Option<String> {
+    let key_pos = find_top_level_key(src, key)?;
+    let colon = key_pos + src[key_pos..].find(':')? + 1;
+    let value_start = colon + whitespace_len(&src[colon..]);
+    let value_end = find_value_end(src, value_start)?;
+
+    let mut start = key_pos;
+    let mut end = value_end;
+    let after = &src[value_end..];
+    let ws = whitespace_len(after);
+    if after[ws..].starts_with(',') {
+        end += ws + 1;
     } else {
-        src.rfind('}')?
-    };
+        // The last key: drop the comma after the previous one instead
+        let before = src[..key_pos].trim_end();
+        if before.ends_with(',') {
+            start = before.len() - 1;
+        }
+    }
+
+    let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = src[end..].find('\n').map(|i| end + i + 1);
+    if let Some(line_end) = line_end
+        && src[line_start..start].trim().is_empty()
+        && src[end..line_end].trim().is_empty()
+    {
+        start = line_start;
+        end = line_end;
+    }
 
-    let mut result = String::with_capacity(src.len() + insert_line.len());
-    result.push_str(&src[..anchor]);
-    result.push_str(&insert_line);
-    result.push_str(&src[anchor..]);
+    let mut result = String::with_capacity(src.len());
+    result.push_str(&src[..start]);
+    result.push_str(&src[end..]);
     Some(result)
 }
 
+fn whitespace_len(s: &str) -> usize {
+    s.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| c.len_utf8())
+        .sum()
+}
+
 pub fn replace_module_array(src: &str, key: &str, values: &[&str]) -> Option<String> {
     let new_array = {
         let items: Vec<String> = values
@@ -530,4 +818,153 @@ mod tests {
             "inserted key should appear before modules-left"
         );
     }
+
+    #[test]
+    fn replace_top_level_value_appends_after_last_member_with_comma() {
+        let src = "{\n  \"tray\": {\"spacing\": 17} // icons\n}";
+        let result = replace_top_level_value(src, "height", &serde_json::json!(26))
+            .expect("inserting a new key should succeed");
+        assert_eq!(
+            result,
+            "{\n  \"tray\": {\"spacing\": 17}, // icons\n  \"height\": 26\n}"
+        );
+        parse(&result);
+    }
+
+    const OMARCHY_DEFAULT: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/defaults/omarchist/waybar/profiles/omarchy-default/config.jsonc"
+    ));
+
+    #[test]
+    fn add_module_to_zone_keeps_the_config_valid() {
+        let definition = r#"{"format": "{}", "exec": "date", "interval": 60}"#;
+        let result = add_module_to_bar(
+            OMARCHY_DEFAULT,
+            0,
+            "custom/date",
+            &WaybarZone::Right,
+            definition,
+        )
+        .expect("the module should be added");
+
+        let json = parse_jsonc(&result).expect("result should be valid JSONC");
+        assert_eq!(json["custom/date"]["exec"], "date");
+        assert!(
+            json["modules-right"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!("custom/date"))
+        );
+
+        // Without "modules-left" the definition goes after the last member
+        let bare = "{\n  \"modules-right\": [\"clock\"],\n  \"clock\": {}\n}";
+        let result =
+            add_module_to_bar(bare, 0, "custom/date", &WaybarZone::Right, definition).unwrap();
+        parse_jsonc(&result).expect("result should be valid JSONC");
+    }
+
+    const TWO_BARS: &str = r#"// One bar per monitor
+[
+  {
+    "output": "DP-1", // the {main} one
+    "modules-left": ["clock"]
+  },
+  {
+    "output": "HDMI-A-1",
+    "modules-left": ["tray"]
+  }
+]
+"#;
+
+    #[test]
+    fn write_bar_modules_only_touches_changed_zones() {
+        let json = parse(TWO_BARS);
+        let mut bars: Vec<WaybarBar> = bar_values(&json).into_iter().map(parse_bar).collect();
+        bars[1]
+            .modules_right
+            .push(new_module("clock", WaybarZone::Right, &json[1]));
+
+        let result = write_bar_modules(TWO_BARS, &bars).expect("the zones should be written");
+
+        let written = parse(&result);
+        assert_eq!(written[1]["modules-left"], serde_json::json!(["tray"]));
+        assert_eq!(written[1]["modules-right"], serde_json::json!(["clock"]));
+        assert!(written[0].get("modules-right").is_none());
+        assert!(written[1].get("modules-center").is_none());
+        // The first bar is left exactly as it was, comments included
+        assert!(result.contains("\"output\": \"DP-1\", // the {main} one"));
+    }
+
+    fn parse(src: &str) -> serde_json::Value {
+        serde_json::from_str(&strip_jsonc_comments(src)).expect("result should be valid JSON")
+    }
+
+    #[test]
+    fn bar_spans_finds_root_object_or_array_bars() {
+        let single = r#"{"height": 26, "clock": {"format": "{:%H}"}}"#;
+        assert_eq!(bar_spans(single), vec![(0, single.len())]);
+
+        let spans = bar_spans(TWO_BARS);
+        assert_eq!(spans.len(), 2);
+        assert!(TWO_BARS[spans[0].0..spans[0].1].contains("DP-1"));
+        assert!(TWO_BARS[spans[1].0..spans[1].1].contains("HDMI-A-1"));
+    }
+
+    #[test]
+    fn edit_bar_only_changes_the_selected_bar() {
+        let result = edit_bar(TWO_BARS, 1, |src| {
+            replace_module_array(src, "modules-left", &["clock", "tray"])
+        })
+        .expect("second bar should be editable");
+
+        let json = parse(&result);
+        assert_eq!(json[0]["modules-left"], serde_json::json!(["clock"]));
+        assert_eq!(
+            json[1]["modules-left"],
+            serde_json::json!(["clock", "tray"])
+        );
+        assert!(result.starts_with("// One bar per monitor"));
+    }
+
+    #[test]
+    fn append_bar_wraps_a_single_bar_config_in_an_array() {
+        let src = "{\n  \"modules-left\": [\"clock\"]\n}\n";
+        let result = append_bar(src, NEW_BAR).expect("append should succeed");
+
+        let json = parse(&result);
+        assert_eq!(json.as_array().map(|a| a.len()), Some(2));
+        assert_eq!(json[0]["modules-left"], serde_json::json!(["clock"]));
+
+        let result = append_bar(&result, NEW_BAR).expect("append to array should succeed");
+        assert_eq!(bar_spans(&result).len(), 3);
+    }
+
+    #[test]
+    fn remove_bar_drops_the_bar_and_its_separator() {
+        let first_removed = remove_bar(TWO_BARS, 0).expect("first bar should be removable");
+        assert_eq!(
+            parse(&first_removed),
+            serde_json::json!([{"output": "HDMI-A-1", "modules-left": ["tray"]}])
+        );
+
+        let last_removed = remove_bar(TWO_BARS, 1).expect("last bar should be removable");
+        assert_eq!(parse(&last_removed)[0]["output"], "DP-1");
+        assert_eq!(bar_spans(&last_removed).len(), 1);
+        assert!(remove_bar(TWO_BARS, 2).is_none());
+    }
+
+    #[test]
+    fn remove_top_level_key_drops_the_line_and_comma() {
+        let src = "{\n  \"output\": \"DP-1\",\n  \"height\": 26\n}";
+        assert_eq!(
+            remove_top_level_key(src, "output").as_deref(),
+            Some("{\n  \"height\": 26\n}")
+        );
+        assert_eq!(
+            remove_top_level_key(src, "height").as_deref(),
+            Some("{\n  \"output\": \"DP-1\"\n}")
+        );
+        assert!(remove_top_level_key(src, "layer").is_none());
+    }
 }
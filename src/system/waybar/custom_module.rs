@@ -0,0 +1,419 @@
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use smol::unblock;
+
+use super::config::{add_module_to_zone, load_waybar_config};
+use super::paths::waybar_profile_scripts_dir;
+use super::types::WaybarZone;
+
+/// Where Waybar finds a profile's scripts once it is applied, since applying
+/// copies the whole profile directory to `~/.config/waybar`.
+pub const LIVE_SCRIPTS_DIR: &str = "~/.config/waybar/scripts";
+
+// How long "run once" waits for the first line of output
+const RUN_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Keys Waybar reads from a line of `return-type: json` output
+const JSON_STRING_KEYS: &[&str] = &["text", "alt", "tooltip"];
+
+/// A `custom/<name>` module as entered in the custom module builder.
+#[derive(Debug, Clone, Default)]
+pub struct CustomModule {
+    pub name: String,
+    /// Command Waybar runs. Unused when `script` is set.
+    pub exec: String,
+    /// Script to store in the profile as `scripts/<name>.sh` and run
+    /// instead of `exec`.
+    pub script: Option<String>,
+    /// Whether the command prints JSON objects rather than plain text.
+    pub json: bool,
+    /// Seconds between runs. Without one, Waybar keeps the command running
+    /// and shows every line it prints.
+    pub interval: Option<u32>,
+    /// Refresh when Waybar receives `SIGRTMIN+<signal>`.
+    pub signal: Option<u32>,
+    pub on_click: Option<String>,
+    pub format: Option<String>,
+}
+
+impl CustomModule {
+    pub fn key(&self) -> String {
+        format!("custom/{}", self.name)
+    }
+
+    /// The command written to `exec`: the stored script once applied, or
+    /// the command as entered.
+    pub fn exec_command(&self) -> String {
+        if self.script.is_some() {
+            format!("{}/{}.sh", LIVE_SCRIPTS_DIR, self.name)
+        } else {
+            self.exec.trim().to_string()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("The module needs a name".to_string());
+        }
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err("Names may only use letters, digits, `-` and `_`".to_string());
+        }
+        if self.script.is_none() && self.exec.trim().is_empty() {
+            return Err("Enter a command or a script to run".to_string());
+        }
+        Ok(())
+    }
+
+    /// The module's definition block, with keys in the order they are
+    /// usually written.
+    pub fn definition(&self) -> String {
+        let mut fields: Vec<(&str, Value)> = Vec::new();
+        if let Some(format) = &self.format {
+            fields.push(("format", Value::from(format.as_str())));
+        }
+        fields.push(("exec", Value::from(self.exec_command())));
+        if self.json {
+            fields.push(("return-type", Value::from("json")));
+        }
+        if let Some(interval) = self.interval {
+            fields.push(("interval", Value::from(interval)));
+        }
+        if let Some(signal) = self.signal {
+            fields.push(("signal", Value::from(signal)));
+        }
+        if let Some(on_click) = &self.on_click {
+            fields.push(("on-click", Value::from(on_click.as_str())));
+        }
+
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\": {}", key, value))
+            .collect();
+        format!("{{{}}}", fields.join(", "))
+    }
+}
+
+/// Write `module` into a bar of the profile, storing its script in the
+/// profile directory. Returns the module's key.
+pub fn add_custom_module(
+    profile_name: &str,
+    bar: usize,
+    module: &CustomModule,
+    zone: &WaybarZone,
+) -> Result<String, String> {
+    module.validate()?;
+    let key = module.key();
+
+    let exists = load_waybar_config(profile_name)
+        .is_some_and(|config| config.bars.iter().any(|b| b.config.get(&key).is_some()));
+    if exists {
+        return Err(format!("\"{}\" is already defined in this profile", key));
+    }
+
+    if let Some(script) = &module.script {
+        let dir = waybar_profile_scripts_dir(profile_name)
+            .ok_or_else(|| "Could not determine home directory".to_string())?;
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        write_script(&dir.join(format!("{}.sh", module.name)), script)?;
+    }
+
+    add_module_to_zone(profile_name, bar, &key, zone, &module.definition())?;
+    Ok(key)
+}
+
+/// Run a custom module's command once, the way Waybar would, and return the
+/// first line it prints. The command and anything it started are stopped
+/// once that line arrives, or after `RUN_TIMEOUT` without one. Scripts run
+/// from a temporary copy, and commands pointing into
+/// `LIVE_SCRIPTS_DIR` run the profile's own copy, so nothing needs to be
+/// applied first.
+pub async fn run_custom_module_once(
+    profile_name: &str,
+    module: &CustomModule,
+) -> Result<String, String> {
+    let profile_name = profile_name.to_string();
+    let module = module.clone();
+    unblock(move || {
+        let Some(script) = &module.script else {
+            let command = profile_command(&profile_name, &module.exec);
+            return run_first_line(&command, RUN_TIMEOUT);
+        };
+
+        let path = create_temp_script(&module.name, script)?;
+        let result = run_first_line(&path.to_string_lossy(), RUN_TIMEOUT);
+        let _ = fs::remove_file(&path);
+        result
+    })
+    .await
+}
+
+/// Problems with one line of a custom module's output. For JSON output
+/// this checks the line against the object Waybar expects: string `text`,
+/// `alt` and `tooltip`, a string or list of strings for `class`, and a
+/// number for `percentage`.
+pub fn check_custom_output(line: &str, json: bool) -> Vec<String> {
+    if !json {
+        if line.trim_start().starts_with('{') {
+            return vec!["The output looks like JSON; set the return type to JSON".to_string()];
+        }
+        return Vec::new();
+    }
+
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => return vec![format!("The output is not valid JSON: {}", e)],
+    };
+    let Some(object) = value.as_object() else {
+        return vec!["The output must be a JSON object".to_string()];
+    };
+
+    let mut problems = Vec::new();
+    if !object.contains_key("text") {
+        problems.push("There is no \"text\", so the module shows nothing".to_string());
+    }
+    for (key, value) in object {
+        let valid = match key.as_str() {
+            k if JSON_STRING_KEYS.contains(&k) => value.is_string(),
+            "class" => {
+                value.is_string()
+                    || value
+                        .as_array()
+                        .is_some_and(|classes| classes.iter().all(Value::is_string))
+            }
+            "percentage" => value.is_number(),
+            _ => {
+                problems.push(format!("\"{}\" is not read by Waybar", key));
+                continue;
+            }
+        };
+        if !valid {
+            let expected = match key.as_str() {
+                "class" => "a string or a list of strings",
+                "percentage" => "a number",
+                _ => "a string",
+            };
+            problems.push(format!("\"{}\" should be {}", key, expected));
+        }
+    }
+    problems
+}
+
+// Waybar runs the file directly, so it needs an interpreter line
+fn script_content(script: &str) -> String {
+    if script.starts_with("#!") {
+        script.to_string()
+    } else {
+        format!("#!/bin/sh\n{}", script)
+    }
+}
+
+fn write_script(path: &Path, script: &str) -> Result<(), String> {
+    fs::write(path, script_content(script))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to make {} executable: {}", path.display(), e))
+}
+
+// A new file only we can read, rather than a predictable path another user
+// could have put something at first
+fn create_temp_script(name: &str, script: &str) -> Result<PathBuf, String> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    for attempt in 0..16u32 {
+        let path = std::env::temp_dir().join(format!(
+            "omarchist-{}-{}-{:x}.sh",
+            name,
+            std::process::id(),
+            nanos.wrapping_add(attempt)
+        ));
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o700)
+            .open(&path);
+        match file {
+            Ok(mut file) => {
+                return file
+                    .write_all(script_content(script).as_bytes())
+                    .map(|()| path.clone())
+                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e));
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+        }
+    }
+    Err("Failed to create a temporary script".to_string())
+}
+
+// Point commands at the profile's scripts rather than the live ones, which
+// only exist once the profile is applied
+fn profile_command(profile_name: &str, exec: &str) -> String {
+    match waybar_profile_scripts_dir(profile_name) {
+        Some(dir) => exec.replace(LIVE_SCRIPTS_DIR, &dir.to_string_lossy()),
+        None => exec.to_string(),
+    }
+}
+
+fn run_first_line(command: &str, timeout: Duration) -> Result<String, String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Its own group, so whatever it starts is stopped along with it
+        .process_group(0)
+        .spawn()
+        .map_err(|e| format!("Failed to run the command: {}", e))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to read the command's output".to_string())?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        let read = BufReader::new(stdout).read_line(&mut line).map(|_| line);
+        let _ = tx.send(read);
+    });
+
+    let result = rx.recv_timeout(timeout);
+    // Until it is waited on the group id can't be reused. With the whole group
+    // gone nothing holds the pipe open, so the reader thread sees the end.
+    let killed_group = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !killed_group {
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+
+    match result {
+        Ok(Ok(line)) if !line.is_empty() => Ok(line.trim_end_matches(['\n', '\r']).to_string()),
+        Ok(Ok(_)) => {
+            let mut stderr = String::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                let _ = pipe.read_to_string(&mut stderr);
+            }
+            match stderr.trim() {
+                "" => Err("The command printed nothing".to_string()),
+                error => Err(format!("The command printed nothing: {}", error)),
+            }
+        }
+        Ok(Err(e)) => Err(format!("Failed to read the command's output: {}", e)),
+        Err(_) => Err(format!(
+            "The command printed nothing within {} seconds",
+            timeout.as_secs()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definition_keeps_key_order_and_points_scripts_at_live_dir() {
+        let module = CustomModule {
+            name: "updates".to_string(),
+            script: Some("echo 1".to_string()),
+            json: true,
+            interval: Some(60),
+            signal: Some(8),
+            on_click: Some("omarchy-update".to_string()),
+            format: Some("{}".to_string()),
+            ..CustomModule::default()
+        };
+        assert_eq!(
+            module.definition(),
+            r#"{"format": "{}", "exec": "~/.config/waybar/scripts/updates.sh", "return-type": "json", "interval": 60, "signal": 8, "on-click": "omarchy-update"}"#
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_names_and_missing_command() {
+        let mut module = CustomModule {
+            name: "my mod".to_string(),
+            exec: "date".to_string(),
+            ..CustomModule::default()
+        };
+        assert!(module.validate().is_err());
+        module.name = "my-mod".to_string();
+        assert!(module.validate().is_ok());
+        module.exec = "  ".to_string();
+        assert!(module.validate().is_err());
+    }
+
+    #[test]
+    fn json_output_is_checked_against_waybar_schema() {
+        let ok = r#"{"text": "3", "alt": "updates", "tooltip": "3 updates", "class": ["a"], "percentage": 30}"#;
+        assert!(check_custom_output(ok, true).is_empty());
+
+        let problems = check_custom_output(r#"{"text": 3, "class": [1], "colour": "red"}"#, true);
+        assert_eq!(
+            problems,
+            vec![
+                "\"text\" should be a string".to_string(),
+                "\"class\" should be a string or a list of strings".to_string(),
+                "\"colour\" is not read by Waybar".to_string(),
+            ]
+        );
+
+        assert_eq!(check_custom_output("nope", true).len(), 1);
+        assert_eq!(check_custom_output("[1]", true).len(), 1);
+    }
+
+    #[test]
+    fn plain_output_hints_at_json() {
+        assert!(check_custom_output("3 updates", false).is_empty());
+        assert_eq!(check_custom_output(r#"{"text": "3"}"#, false).len(), 1);
+    }
+
+    #[test]
+    fn run_first_line_stops_continuous_commands() {
+        let line = run_first_line("echo first; echo second; sleep 5", RUN_TIMEOUT).unwrap();
+        assert_eq!(line, "first");
+        assert!(run_first_line("true", RUN_TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn run_first_line_stops_what_the_command_started() {
+        let marker =
+            std::env::temp_dir().join(format!("omarchist-run-once-{}", std::process::id()));
+        let command = format!("(sleep 1; touch '{}') & echo first; wait", marker.display());
+
+        assert_eq!(run_first_line(&command, RUN_TIMEOUT).unwrap(), "first");
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn temp_scripts_are_new_private_files() {
+        let first = create_temp_script("test", "echo 1").unwrap();
+        let second = create_temp_script("test", "echo 1").unwrap();
+        assert_ne!(first, second);
+
+        let mode = fs::metadata(&first).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "#!/bin/sh\necho 1");
+
+        let _ = fs::remove_file(&first);
+        let _ = fs::remove_file(&second);
+    }
+}
@@ -9,6 +9,9 @@ pub fn strip_jsonc_comments(src: &str) -> String {
             if ch == '*' && chars.peek() == Some(&'/') {
                 chars.next();
                 in_block_comment = false;
+            } else if ch == '\n' {
+                // Kept so line numbers still match the source
+                out.push('\n');
             }
             continue;
         }
@@ -54,6 +57,79 @@ pub fn strip_jsonc_comments(src: &str) -> String {
     out
 }
 
+/// Blank out commas directly before a closing `}` or `]`, which Waybar's
+/// parser accepts but serde_json does not. Expects comments to be stripped
+/// already; every other character keeps its position.
+pub fn strip_trailing_commas(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut in_string = false;
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((i, ch)) = chars.next() {
+        if in_string {
+            out.push(ch);
+            if ch == '\\' {
+                if let Some((_, next)) = chars.next() {
+                    out.push(next);
+                }
+            } else if ch == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => in_string = true,
+            ',' if matches!(src[i + 1..].trim_start().chars().next(), Some('}' | ']')) => {
+                out.push(' ');
+                continue;
+            }
+            _ => {}
+        }
+        out.push(ch);
+    }
+
+    out
+}
+
+/// A JSONC syntax error, located in the original source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsoncError {
+    /// 1-based.
+    pub line: usize,
+    /// 1-based.
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for JsoncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Parse JSONC, allowing trailing commas, and report syntax errors with the line and column they occur
+/// at in `src`.
+pub fn parse_jsonc(src: &str) -> Result<serde_json::Value, JsoncError> {
+    serde_json::from_str(&strip_trailing_commas(&strip_jsonc_comments(src))).map_err(|e| {
+        let message = e.to_string();
+        // serde_json appends the position, which is reported separately
+        let message = match message.rfind(" at line ") {
+            Some(pos) => message[..pos].to_string(),
+            None => message,
+        };
+        JsoncError {
+            line: e.line(),
+            column: e.column(),
+            message,
+        }
+    })
+}
+
 /// Find the byte position of `"key"` at depth 1 in the JSON source (i.e. a
 /// top-level key of the root object), skipping nested objects/arrays and
 /// string literals.
@@ -234,6 +310,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn strip_jsonc_comments_block_comment_preserves_newlines() {
+        let src = "{\n/* one\n two */\n\"a\": 1\n}";
+        let result = strip_jsonc_comments(src);
+        assert_eq!(result, "{\n\n\n\"a\": 1\n}");
+    }
+
+    #[test]
+    fn parse_jsonc_reports_line_and_column() {
+        let src = "{\n  // comment\n  \"a\": 1\n  \"b\": 2\n}";
+        let error = parse_jsonc(src).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 3));
+        assert_eq!(error.message, "expected `,` or `}`");
+        assert!(parse_jsonc("{\"a\": 1} // done").is_ok());
+    }
+
+    #[test]
+    fn parse_jsonc_accepts_trailing_commas() {
+        let src = "{\n  \"a\": [1, 2,],\n  \"b\": \",}\", // b\n}";
+        let json = parse_jsonc(src).unwrap();
+        assert_eq!(json["a"], serde_json::json!([1, 2]));
+        assert_eq!(json["b"], ",}");
+    }
+
     #[test]
     fn strip_jsonc_comments_empty_input_returns_empty() {
         assert_eq!(
@@ -47,3 +47,27 @@ pub fn waybar_profiles_dir() -> Option<PathBuf> {
 pub fn waybar_current_profile_path() -> Option<PathBuf> {
     omarchist_config_dir().map(|d| d.join("waybar").join("current-profile"))
 }
+
+// Returns `~/.config/omarchist/waybar/profiles/<profile_name>/style.css`,
+// or `None` if the home directory cannot be determined.
+pub fn waybar_profile_style_path(profile_name: &str) -> Option<PathBuf> {
+    waybar_profiles_dir().map(|d| d.join(profile_name).join("style.css"))
+}
+
+// Returns `~/.config/omarchist/waybar/profiles/<profile_name>/scripts`, where
+// custom modules' scripts are stored, or `None` if home dir is unavailable.
+pub fn waybar_profile_scripts_dir(profile_name: &str) -> Option<PathBuf> {
+    waybar_profiles_dir().map(|d| d.join(profile_name).join("scripts"))
+}
+
+// Returns the active Omarchy theme's `waybar.css`, which defines the theme's
+// `@define-color` names, or `None` if the home directory cannot be determined.
+pub fn current_theme_waybar_css_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| {
+        h.join(".config")
+            .join("omarchy")
+            .join("current")
+            .join("theme")
+            .join("waybar.css")
+    })
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Local};
+use sysinfo::{Components, Disks, System};
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// The values modules are rendered with in the status bar preview.
+#[derive(Debug, Clone)]
+pub struct PreviewData {
+    pub time: DateTime<Local>,
+    /// Percent, 0-100.
+    pub cpu_usage: f64,
+    /// GHz.
+    pub cpu_frequency: f64,
+    pub load: f64,
+    /// GiB.
+    pub memory_used: f64,
+    pub memory_total: f64,
+    pub swap_used: f64,
+    pub swap_total: f64,
+    pub disk_path: String,
+    pub disk_used: f64,
+    pub disk_total: f64,
+    /// `None` on machines without a battery.
+    pub battery: Option<BatteryData>,
+    /// °C.
+    pub temperature: f64,
+    pub volume: f64,
+    pub brightness: f64,
+    pub wifi_essid: String,
+    pub wifi_signal: f64,
+    pub window_title: String,
+    pub keyboard_layout: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatteryData {
+    /// Percent, 0-100.
+    pub capacity: f64,
+    /// Waybar's status name: `charging`, `discharging`, `full` or `plugged`.
+    pub status: &'static str,
+    pub time: String,
+    /// Watts.
+    pub power: f64,
+}
+
+impl PreviewData {
+    /// Plausible values for a laptop on Wi-Fi, with the current time.
+    pub fn sample() -> Self {
+        Self {
+            time: Local::now(),
+            cpu_usage: 12.0,
+            cpu_frequency: 3.2,
+            load: 0.8,
+            memory_used: 6.4,
+            memory_total: 16.0,
+            swap_used: 0.0,
+            swap_total: 8.0,
+            disk_path: "/".to_string(),
+            disk_used: 120.0,
+            disk_total: 512.0,
+            battery: Some(BatteryData {
+                capacity: 76.0,
+                status: "discharging",
+                time: "3 h 12 min".to_string(),
+                power: 8.5,
+            }),
+            temperature: 52.0,
+            volume: 45.0,
+            brightness: 70.0,
+            wifi_essid: "Home".to_string(),
+            wifi_signal: 72.0,
+            window_title: "Omarchist".to_string(),
+            keyboard_layout: "us".to_string(),
+        }
+    }
+}
+
+/// Reads the machine's own time, CPU, memory, disk, temperature and battery.
+/// Everything else keeps its sample value. CPU usage is measured between two
+/// calls, so keep the sampler around and call `sample` periodically.
+pub struct LiveSampler {
+    system: System,
+}
+
+impl LiveSampler {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_all();
+        Self { system }
+    }
+
+    pub fn sample(&mut self) -> PreviewData {
+        self.system.refresh_cpu_all();
+        self.system.refresh_memory();
+
+        let mut data = PreviewData::sample();
+        data.cpu_usage = self.system.global_cpu_usage() as f64;
+        if let Some(cpu) = self.system.cpus().first() {
+            data.cpu_frequency = cpu.frequency() as f64 / 1000.0;
+        }
+        data.load = System::load_average().one;
+        data.memory_used = self.system.used_memory() as f64 / GIB;
+        data.memory_total = self.system.total_memory() as f64 / GIB;
+        data.swap_used = self.system.used_swap() as f64 / GIB;
+        data.swap_total = self.system.total_swap() as f64 / GIB;
+
+        let disks = Disks::new_with_refreshed_list();
+        if let Some(disk) = disks
+            .iter()
+            .find(|d| d.mount_point() == std::path::Path::new("/"))
+        {
+            data.disk_total = disk.total_space() as f64 / GIB;
+            data.disk_used = (disk.total_space() - disk.available_space()) as f64 / GIB;
+        }
+
+        let components = Components::new_with_refreshed_list();
+        if let Some(temperature) = components
+            .iter()
+            .filter_map(|c| c.temperature())
+            .reduce(f32::max)
+        {
+            data.temperature = temperature as f64;
+        }
+
+        data.time = Local::now();
+        data.battery = read_battery();
+        data
+    }
+}
+
+impl Default for LiveSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_battery() -> Option<BatteryData> {
+    let manager = battery::Manager::new().ok()?;
+    let battery = manager.batteries().ok()?.flatten().next()?;
+
+    let state = battery.state();
+    let status = match state {
+        battery::State::Charging => "charging",
+        battery::State::Discharging => "discharging",
+        battery::State::Full => "full",
+        _ => "plugged",
+    };
+    let seconds = match state {
+        battery::State::Charging => battery.time_to_full().map(|t| t.value),
+        battery::State::Discharging => battery.time_to_empty().map(|t| t.value),
+        _ => None,
+    };
+    let time = seconds
+        .map(|s| {
+            let minutes = (s / 60.0) as u64;
+            format!("{} h {} min", minutes / 60, minutes % 60)
+        })
+        .unwrap_or_default();
+
+    Some(BatteryData {
+        capacity: (battery.state_of_charge().value * 100.0) as f64,
+        status,
+        time,
+        power: battery.energy_rate().value as f64,
+    })
+}
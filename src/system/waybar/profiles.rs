@@ -4,6 +4,7 @@ use super::paths::{
     live_waybar_config_path, live_waybar_dir, omarchist_config_dir, waybar_current_profile_path,
     waybar_profiles_dir,
 };
+use super::validation::{Severity, validate_waybar_profile};
 use crate::assets::extract_default_dir;
 
 pub const CUSTOM_WAYBAR_PROFILE: &str = "Custom Waybar";
@@ -282,9 +283,28 @@ pub fn is_read_only_waybar_profile(profile_name: &str) -> bool {
     profile_name == CUSTOM_WAYBAR_PROFILE
 }
 
+/// Copy a profile to `~/.config/waybar`. Refused when the profile's config
+/// has errors, so a typo can't leave the desktop without a bar.
 pub fn apply_waybar_profile(profile_name: &str) -> Result<(), String> {
     ensure_default_waybar_profile()?;
 
+    let errors: Vec<String> = validate_waybar_profile(profile_name)?
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(ToString::to_string)
+        .collect();
+    if !errors.is_empty() {
+        return Err(format!(
+            "The config has errors, so it was not applied:\n{}",
+            errors.join("\n")
+        ));
+    }
+
+    install_waybar_profile(profile_name)
+}
+
+// Copy a profile to `~/.config/waybar` without checking it
+fn install_waybar_profile(profile_name: &str) -> Result<(), String> {
     let home = dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
 
     let profile_dir = omarchist_config_dir()
@@ -349,7 +369,8 @@ pub fn start_with_omarchy_default_profile(profile_name: &str) -> Result<String,
 
 pub fn adopt_live_waybar(profile_name: &str) -> Result<String, String> {
     let imported = import_live_waybar_as_profile(profile_name)?;
-    apply_waybar_profile(&imported)?;
+    // Already what Waybar runs, so there is nothing to check
+    install_waybar_profile(&imported)?;
     Ok(imported)
 }
 
@@ -0,0 +1,537 @@
+use chrono::format::{Item, StrftimeItems};
+use serde_json::Value;
+
+use super::icons::strip_span_tags;
+use super::preview_data::PreviewData;
+
+// Workspaces shown by the preview; the first is the active one
+const PREVIEW_WORKSPACES: &[&str] = &["1", "2", "3", "4", "5"];
+
+/// Render what a module shows in the bar for `data`, following its `format`,
+/// `format-<status>`/`format-<state>` variants, `format-icons` and `states`.
+/// `bar` is the bar's JSON object holding the module blocks. Returns an empty
+/// string when the module would print nothing, e.g. a script-driven custom
+/// module or the tray.
+pub fn render_module(key: &str, bar: &Value, data: &PreviewData) -> String {
+    let block = bar.get(key);
+    let name = key.split('#').next().unwrap_or(key);
+
+    if name.starts_with("group/") {
+        return block
+            .and_then(|b| b.get("modules"))
+            .and_then(|v| v.as_array())
+            .map(|children| {
+                children
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|child| render_module(child, bar, data))
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+    }
+
+    if name == "hyprland/workspaces" {
+        return render_workspaces(block);
+    }
+
+    let values = module_values(name, data);
+    let state = values
+        .state_value
+        .and_then(|value| module_state(block, value, values.lesser));
+    let format = select_format(block, values.status, state.as_deref())
+        .unwrap_or_else(|| values.default_format.to_string());
+    let icon = format_icon(block, values.status, values.percentage);
+
+    let text = format_placeholders(&strip_span_tags(&format), &values.args, data, &icon);
+    text.trim().to_string()
+}
+
+// A placeholder value. Numbers honour format specs like `{used:0.1f}`.
+enum Arg {
+    Num(f64),
+    Text(String),
+}
+
+// What a module type reports, in terms of Waybar's format settings
+struct ModuleValues {
+    default_format: &'static str,
+    args: Vec<(&'static str, Arg)>,
+    // Picks the `{icon}` from a `format-icons` array
+    percentage: Option<f64>,
+    // Compared against `states` thresholds
+    state_value: Option<f64>,
+    // Battery states match values at or below their threshold
+    lesser: bool,
+    // Selects `format-<status>` and keys a `format-icons` object
+    status: Option<&'static str>,
+}
+
+impl ModuleValues {
+    fn new(default_format: &'static str) -> Self {
+        Self {
+            default_format,
+            args: Vec::new(),
+            percentage: None,
+            state_value: None,
+            lesser: false,
+            status: None,
+        }
+    }
+
+    fn percent(mut self, value: f64) -> Self {
+        self.percentage = Some(value);
+        self.state_value = Some(value);
+        self
+    }
+
+    fn num(mut self, name: &'static str, value: f64) -> Self {
+        self.args.push((name, Arg::Num(value)));
+        self
+    }
+
+    fn text(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.args.push((name, Arg::Text(value.into())));
+        self
+    }
+}
+
+fn module_values(name: &str, data: &PreviewData) -> ModuleValues {
+    let round = |v: f64| v.round();
+    let percent_of = |part: f64, total: f64| {
+        if total > 0.0 {
+            (part / total * 100.0).round()
+        } else {
+            0.0
+        }
+    };
+
+    match name {
+        // The clock's `{:%H:%M}` placeholders are handled by chrono
+        "clock" => ModuleValues::new("{:%H:%M}"),
+        "cpu" => ModuleValues::new("{usage}%")
+            .percent(round(data.cpu_usage))
+            .num("usage", round(data.cpu_usage))
+            .num("load", data.load)
+            .num("avg_frequency", data.cpu_frequency)
+            .num("max_frequency", data.cpu_frequency)
+            .num("min_frequency", data.cpu_frequency),
+        "memory" => {
+            let percentage = percent_of(data.memory_used, data.memory_total);
+            ModuleValues::new("{percentage}%")
+                .percent(percentage)
+                .num("percentage", percentage)
+                .num("used", data.memory_used)
+                .num("total", data.memory_total)
+                .num("avail", data.memory_total - data.memory_used)
+                .num(
+                    "swapPercentage",
+                    percent_of(data.swap_used, data.swap_total),
+                )
+                .num("swapUsed", data.swap_used)
+                .num("swapTotal", data.swap_total)
+                .num("swapAvail", data.swap_total - data.swap_used)
+        }
+        "disk" => {
+            let used = percent_of(data.disk_used, data.disk_total);
+            let free = data.disk_total - data.disk_used;
+            ModuleValues::new("{percentage_used}%")
+                .percent(used)
+                .num("percentage_used", used)
+                .num("percentage_free", 100.0 - used)
+                .text("used", format!("{:.1}GiB", data.disk_used))
+                .text("free", format!("{:.1}GiB", free))
+                .text("total", format!("{:.1}GiB", data.disk_total))
+                .text("path", data.disk_path.clone())
+        }
+        "battery" => match &data.battery {
+            Some(battery) => {
+                let mut values = ModuleValues::new("{capacity}%")
+                    .percent(round(battery.capacity))
+                    .num("capacity", round(battery.capacity))
+                    .num("power", battery.power)
+                    .text("time", battery.time.clone());
+                values.lesser = true;
+                values.status = Some(battery.status);
+                values
+            }
+            // Waybar hides the battery module on machines without one
+            None => ModuleValues::new(""),
+        },
+        "temperature" => {
+            let mut values = ModuleValues::new("{temperatureC}°C")
+                .num("temperatureC", round(data.temperature))
+                .num("temperatureF", round(data.temperature * 9.0 / 5.0 + 32.0))
+                .num("temperatureK", round(data.temperature + 273.15));
+            values.percentage = Some(percent_of(data.temperature, 80.0).min(100.0));
+            values.state_value = Some(round(data.temperature));
+            values
+        }
+        "backlight" => ModuleValues::new("{percent}%")
+            .percent(round(data.brightness))
+            .num("percent", round(data.brightness)),
+        "pulseaudio" | "wireplumber" => ModuleValues::new("{volume}%")
+            .percent(round(data.volume))
+            .num("volume", round(data.volume))
+            .text("desc", "Speakers")
+            .text("node_name", "Speakers")
+            .text("format_source", ""),
+        "network" => {
+            let mut values = ModuleValues::new("{ifname}")
+                .percent(round(data.wifi_signal))
+                .num("signalStrength", round(data.wifi_signal))
+                .text("essid", data.wifi_essid.clone())
+                .text("ifname", "wlan0")
+                .text("ipaddr", "192.168.1.20")
+                .text("gwaddr", "192.168.1.1")
+                .num("cidr", 24.0)
+                .num("frequency", 5.2)
+                .text("bandwidthDownBits", "1.2Mb/s")
+                .text("bandwidthUpBits", "240kb/s")
+                .text("bandwidthDownBytes", "150kB/s")
+                .text("bandwidthUpBytes", "30kB/s");
+            values.status = Some("wifi");
+            values
+        }
+        "bluetooth" => {
+            let mut values = ModuleValues::new(" {status}")
+                .text("status", "on")
+                .num("num_connections", 0.0)
+                .text("controller_alias", "Bluetooth");
+            values.status = Some("on");
+            values
+        }
+        "hyprland/window" => ModuleValues::new("{}")
+            .text("", data.window_title.clone())
+            .text("title", data.window_title.clone())
+            .text("initialTitle", data.window_title.clone())
+            .text("class", "omarchist"),
+        "hyprland/language" => ModuleValues::new("{}")
+            .text("", data.keyboard_layout.clone())
+            .text("short", data.keyboard_layout.clone())
+            .text("long", data.keyboard_layout.clone()),
+        "idle_inhibitor" => {
+            let mut values = ModuleValues::new("{status}").text("status", "deactivated");
+            values.status = Some("deactivated");
+            values
+        }
+        // Custom modules print whatever their script outputs, so only their
+        // static text and icon can be shown
+        _ => ModuleValues::new("{}").text("", "").text("text", ""),
+    }
+}
+
+fn render_workspaces(block: Option<&Value>) -> String {
+    let format = block
+        .and_then(|b| b.get("format"))
+        .and_then(|v| v.as_str())
+        .map(strip_span_tags)
+        .unwrap_or_else(|| "{name}".to_string());
+    let icons = block.and_then(|b| b.get("format-icons"));
+
+    PREVIEW_WORKSPACES
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let icon = icons
+                .and_then(|icons| {
+                    let lookup = |k: &str| icons.get(k).and_then(|v| v.as_str());
+                    // Like Waybar, an `active` icon wins over the workspace's own
+                    (index == 0)
+                        .then(|| lookup("active"))
+                        .flatten()
+                        .or_else(|| lookup(name))
+                        .or_else(|| lookup("default"))
+                })
+                .map(strip_span_tags)
+                .unwrap_or_else(|| name.to_string());
+            format
+                .replace("{name}", name)
+                .replace("{id}", name)
+                .replace("{icon}", &icon)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim()
+        .to_string()
+}
+
+// The state whose threshold `value` reaches, like Waybar: the highest
+// threshold at or below `value`, or for `lesser` modules the lowest at or
+// above it
+fn module_state(block: Option<&Value>, value: f64, lesser: bool) -> Option<String> {
+    let states = block?.get("states")?.as_object()?;
+    let mut thresholds: Vec<(&String, f64)> = states
+        .iter()
+        .filter_map(|(name, v)| v.as_f64().map(|t| (name, t)))
+        .collect();
+    if lesser {
+        thresholds.sort_by(|a, b| a.1.total_cmp(&b.1));
+    } else {
+        thresholds.sort_by(|a, b| b.1.total_cmp(&a.1));
+    }
+    thresholds
+        .into_iter()
+        .find(|(_, t)| if lesser { value <= *t } else { value >= *t })
+        .map(|(name, _)| name.clone())
+}
+
+// Most specific format the module defines: `format-<status>-<state>`,
+// `format-<status>`, `format-<state>`, then `format`
+fn select_format(
+    block: Option<&Value>,
+    status: Option<&str>,
+    state: Option<&str>,
+) -> Option<String> {
+    let block = block?;
+    let mut candidates = Vec::new();
+    if let (Some(status), Some(state)) = (status, state) {
+        candidates.push(format!("format-{}-{}", status, state));
+    }
+    if let Some(status) = status {
+        candidates.push(format!("format-{}", status));
+    }
+    if let Some(state) = state {
+        candidates.push(format!("format-{}", state));
+    }
+    candidates.push("format".to_string());
+
+    candidates
+        .iter()
+        .find_map(|key| block.get(key).and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
+// The `{icon}` for a module: from a `format-icons` array by percentage, or
+// from an object keyed by status with a `default` fallback
+fn format_icon(block: Option<&Value>, status: Option<&str>, percentage: Option<f64>) -> String {
+    let Some(icons) = block.and_then(|b| b.get("format-icons")) else {
+        return String::new();
+    };
+    let icons = match icons.as_object() {
+        Some(map) => match status
+            .and_then(|s| map.get(s))
+            .or_else(|| map.get("default"))
+        {
+            Some(v) => v,
+            None => return String::new(),
+        },
+        None => icons,
+    };
+
+    let icon = match icons {
+        Value::Array(list) if !list.is_empty() => {
+            // Waybar's integer steps: 60% of five icons is the fourth
+            let step = (100 / list.len()).max(1);
+            let index = percentage.map_or(0, |p| (p.max(0.0) as usize / step).min(list.len() - 1));
+            list[index].as_str()
+        }
+        Value::String(s) => Some(s.as_str()),
+        _ => None,
+    };
+    icon.map(strip_span_tags).unwrap_or_default()
+}
+
+fn format_placeholders(
+    format: &str,
+    args: &[(&str, Arg)],
+    data: &PreviewData,
+    icon: &str,
+) -> String {
+    let mut out = String::new();
+    let mut rest = format;
+    while let Some(open) = rest.find('{') {
+        // `{{` is a literal brace
+        if rest[open + 1..].starts_with('{') {
+            out.push_str(&rest[..=open]);
+            rest = &rest[open + 2..];
+            continue;
+        }
+        let Some(len) = rest[open..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..open]);
+        let placeholder = &rest[open + 1..open + len];
+        let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+
+        let value = if name == "icon" {
+            Some(icon.to_string())
+        } else if name.is_empty() && spec.contains('%') {
+            format_time(data, spec)
+        } else {
+            args.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, arg)| format_arg(arg, spec))
+        };
+        match value {
+            Some(value) => out.push_str(&value),
+            // Unknown placeholders are printed as written, like Waybar
+            None => out.push_str(&rest[open..=open + len]),
+        }
+        rest = &rest[open + len + 1..];
+    }
+    out.push_str(rest);
+    out.replace("}}", "}")
+}
+
+fn format_time(data: &PreviewData, spec: &str) -> Option<String> {
+    // `L` asks Waybar to use the locale; the preview always uses the default
+    let spec = spec.strip_prefix('L').unwrap_or(spec);
+    let items: Vec<Item> = StrftimeItems::new(spec).collect();
+    if items.contains(&Item::Error) {
+        return None;
+    }
+    Some(data.time.format_with_items(items.into_iter()).to_string())
+}
+
+// Apply a `[<^>][width][.precision][f]` spec
+fn format_arg(arg: &Arg, spec: &str) -> String {
+    let (align, spec) = match spec.chars().next() {
+        Some(c @ ('<' | '^' | '>')) => (Some(c), &spec[1..]),
+        _ => (None, spec),
+    };
+    let spec = spec.trim_end_matches('f');
+    let (width, precision) = match spec.split_once('.') {
+        Some((w, p)) => (w, p.parse::<usize>().ok()),
+        None => (spec, None),
+    };
+    let width = width.parse::<usize>().unwrap_or(0);
+
+    let (text, default_align) = match arg {
+        Arg::Num(value) => {
+            let text = match precision {
+                Some(precision) => format!("{:.*}", precision, value),
+                None if value.fract() == 0.0 => format!("{}", *value as i64),
+                None => format!("{:.1}", value),
+            };
+            (text, '>')
+        }
+        Arg::Text(text) => (text.clone(), '<'),
+    };
+    match align.unwrap_or(default_align) {
+        '<' => format!("{:<width$}", text),
+        '^' => format!("{:^width$}", text),
+        _ => format!("{:>width$}", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+    use serde_json::json;
+
+    fn data() -> PreviewData {
+        PreviewData {
+            time: Local.with_ymd_and_hms(2026, 3, 9, 14, 5, 0).unwrap(),
+            ..PreviewData::sample()
+        }
+    }
+
+    #[test]
+    fn clock_formats_time_with_chrono() {
+        let bar = json!({ "clock": { "format": "{:L%A %H:%M}" } });
+        assert_eq!(render_module("clock", &bar, &data()), "Monday 14:05");
+        assert_eq!(render_module("clock", &json!({}), &data()), "14:05");
+    }
+
+    #[test]
+    fn number_specs_pad_and_round() {
+        let bar = json!({ "memory": { "format": "{used:0.1f}G/{total}G {percentage:3}%" } });
+        assert_eq!(render_module("memory", &bar, &data()), "6.4G/16G  40%");
+    }
+
+    #[test]
+    fn battery_picks_state_format_and_status_icon() {
+        let bar = json!({
+            "battery": {
+                "format": "{capacity}% {icon}",
+                "format-discharging-warning": "low {capacity}%",
+                "format-charging": "+{capacity}% {icon}",
+                "format-warning": "warn {capacity}%",
+                "format-icons": { "charging": ["c"], "default": ["0", "1", "2", "3", "4"] },
+                "states": { "warning": 20, "critical": 10 }
+            }
+        });
+        let mut data = data();
+        assert_eq!(render_module("battery", &bar, &data), "76% 3");
+
+        data.battery.as_mut().unwrap().capacity = 15.0;
+        assert_eq!(render_module("battery", &bar, &data), "low 15%");
+
+        let battery = data.battery.as_mut().unwrap();
+        battery.capacity = 50.0;
+        battery.status = "charging";
+        assert_eq!(render_module("battery", &bar, &data), "+50% c");
+
+        // The status format wins over the state one
+        data.battery.as_mut().unwrap().capacity = 15.0;
+        assert_eq!(render_module("battery", &bar, &data), "+15% c");
+    }
+
+    #[test]
+    fn icons_step_like_waybar() {
+        let bar = json!({
+            "battery": {
+                "format": "{icon}",
+                "format-icons": ["0", "1", "2", "3", "4"]
+            }
+        });
+        let icon = |capacity: f64| {
+            let mut data = data();
+            data.battery.as_mut().unwrap().capacity = capacity;
+            render_module("battery", &bar, &data)
+        };
+        // Rounding over four steps would give "2" at 60%
+        assert_eq!(icon(60.0), "3");
+        assert_eq!(icon(59.0), "2");
+        assert_eq!(icon(100.0), "4");
+        assert_eq!(icon(0.0), "0");
+    }
+
+    #[test]
+    fn states_pick_highest_reached_threshold() {
+        let bar = json!({
+            "cpu": {
+                "format": "{usage}%",
+                "format-busy": "busy",
+                "states": { "busy": 10, "critical": 90 }
+            }
+        });
+        assert_eq!(render_module("cpu", &bar, &data()), "busy");
+    }
+
+    #[test]
+    fn groups_render_children_and_custom_modules_show_their_icon() {
+        let bar = json!({
+            "group/tray-expander": { "modules": ["custom/expand-icon", "network"] },
+            "custom/expand-icon": { "format": "<span>\u{f053}</span>{}" },
+            "network": { "format-wifi": "{icon}", "format-icons": ["a", "b", "c", "d", "e"] }
+        });
+        assert_eq!(
+            render_module("group/tray-expander", &bar, &data()),
+            "\u{f053} d"
+        );
+    }
+
+    #[test]
+    fn workspaces_use_active_and_named_icons() {
+        let bar = json!({
+            "hyprland/workspaces": {
+                "format": "{icon}",
+                "format-icons": { "active": "*", "2": "two", "default": "." }
+            }
+        });
+        assert_eq!(
+            render_module("hyprland/workspaces", &bar, &data()),
+            "* two . . ."
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders_are_kept() {
+        let bar = json!({ "cpu": { "format": "{nope} {{x}}" } });
+        assert_eq!(render_module("cpu", &bar, &data()), "{nope} {x}");
+    }
+}
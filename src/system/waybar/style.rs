@@ -0,0 +1,558 @@
+use std::fs;
+
+use super::paths::{current_theme_waybar_css_path, waybar_profile_style_path};
+
+/// Makes the active Omarchy theme's colors, such as `@foreground` and
+/// `@background`, available to a profile's `style.css`. The path is relative
+/// to `~/.config/waybar/`, where profiles are applied.
+pub const THEME_IMPORT: &str = "@import \"../omarchy/current/theme/waybar.css\";";
+const THEME_IMPORT_TARGET: &str = "omarchy/current/theme/waybar.css";
+
+/// Selectors offered for every profile, as `(selector, label)`.
+pub const COMMON_STYLE_SELECTORS: &[(&str, &str)] = &[
+    ("*", "Everything"),
+    ("window#waybar", "Bar"),
+    ("#workspaces button", "Workspace buttons"),
+    ("#workspaces button.active", "Active workspace"),
+    ("#workspaces button.empty", "Empty workspaces"),
+    ("tooltip", "Tooltips"),
+];
+
+/// The properties the style editor has controls for, as `(property, label)`.
+pub const STYLE_PROPERTIES: &[(&str, &str)] = &[
+    ("font-family", "Font"),
+    ("font-size", "Font Size"),
+    ("padding", "Padding"),
+    ("border-radius", "Radius"),
+    ("color", "Text Color"),
+    ("background-color", "Background"),
+    ("border", "Border"),
+];
+
+// State classes Waybar adds to some modules, e.g. `#battery.critical`
+const MODULE_STATES: &[(&str, &[&str])] = &[
+    ("battery", &["charging", "warning", "critical"]),
+    ("network", &["disconnected"]),
+    ("pulseaudio", &["muted"]),
+    ("wireplumber", &["muted"]),
+    ("bluetooth", &["off", "disabled"]),
+    ("temperature", &["critical"]),
+];
+
+/// A rule with a plain list of declarations, which the editor can change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleRule {
+    /// The selector list, normalised to e.g. `#cpu, #memory`.
+    pub selector: String,
+    /// `(property, value)` pairs in source order.
+    pub declarations: Vec<(String, String)>,
+    // The original text, written back as-is until the rule is edited
+    source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum StyleItem {
+    Rule(StyleRule),
+    // Kept verbatim: whitespace, comments, at-rules such as `@import` and
+    // `@define-color`, and rules with nested blocks or comments
+    Raw(String),
+}
+
+/// A Waybar `style.css`. Simple rules are parsed so their properties can be
+/// edited; everything else, and every rule that is not edited, is written
+/// back exactly as it was read.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WaybarStyle {
+    items: Vec<StyleItem>,
+}
+
+impl WaybarStyle {
+    pub fn parse(src: &str) -> Self {
+        let bytes = src.as_bytes();
+        let mut items = Vec::new();
+        let mut raw_start = 0usize;
+        let mut i = 0usize;
+
+        while i < bytes.len() {
+            if bytes[i].is_ascii_whitespace() {
+                i += 1;
+                continue;
+            }
+            if src[i..].starts_with("/*") {
+                i = src[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+                continue;
+            }
+
+            // An unterminated statement is left as raw text
+            let Some(end) = statement_end(src, i) else {
+                break;
+            };
+            if let Some(rule) = parse_rule(&src[i..end]) {
+                if raw_start < i {
+                    items.push(StyleItem::Raw(src[raw_start..i].to_string()));
+                }
+                items.push(StyleItem::Rule(rule));
+                raw_start = end;
+            }
+            i = end;
+        }
+
+        if raw_start < src.len() {
+            items.push(StyleItem::Raw(src[raw_start..].to_string()));
+        }
+
+        Self { items }
+    }
+
+    pub fn to_css(&self) -> String {
+        let mut out = String::new();
+        for item in &self.items {
+            match item {
+                StyleItem::Raw(text) => out.push_str(text),
+                StyleItem::Rule(rule) => match &rule.source {
+                    Some(source) => out.push_str(source),
+                    None => out.push_str(&format_rule(rule)),
+                },
+            }
+        }
+        out
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &StyleRule> {
+        self.items.iter().filter_map(|item| match item {
+            StyleItem::Rule(rule) => Some(rule),
+            StyleItem::Raw(_) => None,
+        })
+    }
+
+    /// The value of `property` in the rules for exactly `selector`. Rules that
+    /// group it with other selectors are not considered.
+    pub fn property(&self, selector: &str, property: &str) -> Option<&str> {
+        let selector = normalize_selector(selector);
+        self.rules()
+            .filter(|rule| rule.selector == selector)
+            .flat_map(|rule| rule.declarations.iter())
+            .filter(|(p, _)| p == property)
+            .map(|(_, v)| v.as_str())
+            .last()
+    }
+
+    /// Set `property` in the last rule for exactly `selector`, adding a rule at
+    /// the end if there is none. `None` removes the property from every rule
+    /// for `selector`.
+    pub fn set_property(&mut self, selector: &str, property: &str, value: Option<&str>) {
+        let selector = normalize_selector(selector);
+
+        let Some(value) = value else {
+            for rule in self.rules_mut().filter(|rule| rule.selector == selector) {
+                if rule.declarations.iter().any(|(p, _)| p == property) {
+                    rule.declarations.retain(|(p, _)| p != property);
+                    rule.source = None;
+                }
+            }
+            return;
+        };
+
+        if let Some(rule) = self
+            .rules_mut()
+            .filter(|rule| rule.selector == selector)
+            .last()
+        {
+            rule.source = None;
+            match rule
+                .declarations
+                .iter_mut()
+                .rev()
+                .find(|(p, _)| p == property)
+            {
+                Some(declaration) => declaration.1 = value.to_string(),
+                None => rule
+                    .declarations
+                    .push((property.to_string(), value.to_string())),
+            }
+            return;
+        }
+
+        let css = self.to_css();
+        let separator = if css.is_empty() || css.ends_with("\n\n") {
+            ""
+        } else if css.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        self.items.push(StyleItem::Raw(separator.to_string()));
+        self.items.push(StyleItem::Rule(StyleRule {
+            selector,
+            declarations: vec![(property.to_string(), value.to_string())],
+            source: None,
+        }));
+        self.items.push(StyleItem::Raw("\n".to_string()));
+    }
+
+    pub fn imports_theme(&self) -> bool {
+        self.raw_text().any(|text| {
+            text.lines()
+                .any(|line| line.contains("@import") && line.contains(THEME_IMPORT_TARGET))
+        })
+    }
+
+    pub fn set_theme_import(&mut self, enabled: bool) {
+        if enabled == self.imports_theme() {
+            return;
+        }
+
+        if enabled {
+            // `@import` has to come before any rule
+            self.items
+                .insert(0, StyleItem::Raw(format!("{}\n\n", THEME_IMPORT)));
+            return;
+        }
+
+        for item in &mut self.items {
+            if let StyleItem::Raw(text) = item {
+                *text = text
+                    .split_inclusive('\n')
+                    .filter(|line| {
+                        !(line.contains("@import") && line.contains(THEME_IMPORT_TARGET))
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    /// Names of the colors defined in this file with `@define-color`.
+    pub fn defined_colors(&self) -> Vec<(String, String)> {
+        self.raw_text().flat_map(parse_define_colors).collect()
+    }
+
+    /// Selectors to offer in the editor: the common ones, those of
+    /// `module_keys`, then every other rule already in the file.
+    pub fn selectors(&self, module_keys: &[String]) -> Vec<String> {
+        let mut selectors: Vec<String> = COMMON_STYLE_SELECTORS
+            .iter()
+            .map(|(selector, _)| selector.to_string())
+            .collect();
+
+        let candidates = module_keys
+            .iter()
+            .flat_map(|key| module_style_selectors(key))
+            .chain(self.rules().map(|rule| rule.selector.clone()));
+        for selector in candidates {
+            if !selectors.contains(&selector) {
+                selectors.push(selector);
+            }
+        }
+        selectors
+    }
+
+    fn rules_mut(&mut self) -> impl Iterator<Item = &mut StyleRule> {
+        self.items.iter_mut().filter_map(|item| match item {
+            StyleItem::Rule(rule) => Some(rule),
+            StyleItem::Raw(_) => None,
+        })
+    }
+
+    fn raw_text(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().filter_map(|item| match item {
+            StyleItem::Raw(text) => Some(text.as_str()),
+            StyleItem::Rule(_) => None,
+        })
+    }
+}
+
+/// The selector Waybar gives a module: `#custom-weather` for
+/// `custom/weather`, `#workspaces` for `hyprland/workspaces` and
+/// `#battery.bat2` for `battery#bat2`.
+pub fn module_selector(module_key: &str) -> String {
+    let (name, id) = match module_key.split_once('#') {
+        Some((name, id)) => (name, Some(id)),
+        None => (module_key, None),
+    };
+    let name = match name.split_once('/') {
+        Some(("custom", rest)) => format!("custom-{}", rest),
+        Some((_, rest)) => rest.to_string(),
+        None => name.to_string(),
+    };
+    match id {
+        Some(id) => format!("#{}.{}", name, id),
+        None => format!("#{}", name),
+    }
+}
+
+/// The module's selector followed by its state selectors, such as
+/// `#battery.critical`.
+pub fn module_style_selectors(module_key: &str) -> Vec<String> {
+    let selector = module_selector(module_key);
+    let name = module_key.split('#').next().unwrap_or(module_key);
+    let states = MODULE_STATES
+        .iter()
+        .find(|(module, _)| *module == name)
+        .map(|(_, states)| *states)
+        .unwrap_or_default();
+
+    std::iter::once(selector.clone())
+        .chain(states.iter().map(|state| format!("{}.{}", selector, state)))
+        .collect()
+}
+
+/// `(name, value)` of every `@define-color` line in `css`.
+pub fn parse_define_colors(css: &str) -> Vec<(String, String)> {
+    css.lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("@define-color")?;
+            let (name, value) = rest.trim().split_once(char::is_whitespace)?;
+            let value = value.trim().trim_end_matches(';').trim();
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// The colors defined by the active Omarchy theme's `waybar.css`.
+pub fn theme_colors() -> Vec<(String, String)> {
+    current_theme_waybar_css_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|css| parse_define_colors(&css))
+        .unwrap_or_default()
+}
+
+/// A profile's `style.css`. A profile without one gets an empty style, which
+/// is created on save.
+pub fn load_waybar_style(profile_name: &str) -> Option<WaybarStyle> {
+    let style_path = waybar_profile_style_path(profile_name)?;
+    let css = fs::read_to_string(style_path).unwrap_or_default();
+    Some(WaybarStyle::parse(&css))
+}
+
+pub fn save_waybar_style(profile_name: &str, style: &WaybarStyle) -> Result<(), String> {
+    let style_path = waybar_profile_style_path(profile_name)
+        .ok_or_else(|| "Could not determine home directory".to_string())?;
+
+    fs::write(&style_path, style.to_css()).map_err(|e| format!("Failed to write style: {}", e))
+}
+
+// End of the statement starting at `start`: just past the `;` of an at-rule
+// like `@import`, or past the `}` closing a block
+fn statement_end(src: &str, start: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut i = start;
+
+    while i < bytes.len() {
+        let ch = bytes[i];
+        if let Some(q) = quote {
+            if ch == b'\\' {
+                i += 2;
+                continue;
+            } else if ch == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+        match ch {
+            b'"' | b'\'' => quote = Some(ch),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = src[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+                continue;
+            }
+            b';' if depth == 0 => return Some(i + 1),
+            b'{' => depth += 1,
+            b'}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+// A plain `selector { property: value; ... }` rule, or `None` for at-rules
+// and anything with nested blocks or comments
+fn parse_rule(statement: &str) -> Option<StyleRule> {
+    if statement.starts_with('@') {
+        return None;
+    }
+    let open = statement.find('{')?;
+    let body = statement[open + 1..].strip_suffix('}')?;
+    if body.contains('{') || body.contains("/*") {
+        return None;
+    }
+
+    let selector = normalize_selector(&statement[..open]);
+    if selector.is_empty() {
+        return None;
+    }
+
+    Some(StyleRule {
+        selector,
+        declarations: parse_declarations(body)?,
+        source: Some(statement.to_string()),
+    })
+}
+
+fn parse_declarations(body: &str) -> Option<Vec<(String, String)>> {
+    let mut declarations = Vec::new();
+    let mut push = |text: &str| -> Option<()> {
+        let text = text.trim();
+        if !text.is_empty() {
+            let (property, value) = text.split_once(':')?;
+            declarations.push((property.trim().to_string(), value.trim().to_string()));
+        }
+        Some(())
+    };
+
+    let mut parens = 0usize;
+    let mut quote = None;
+    let mut start = 0usize;
+    for (i, ch) in body.char_indices() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => quote = Some(ch),
+            '(' => parens += 1,
+            ')' => parens = parens.saturating_sub(1),
+            ';' if parens == 0 => {
+                push(&body[start..i])?;
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    push(&body[start..])?;
+
+    Some(declarations)
+}
+
+fn normalize_selector(selector: &str) -> String {
+    selector
+        .split(',')
+        .map(|part| part.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_rule(rule: &StyleRule) -> String {
+    let mut out = format!("{} {{\n", rule.selector.replace(", ", ",\n"));
+    for (property, value) in &rule.declarations {
+        out.push_str(&format!("  {}: {};\n", property, value));
+    }
+    out.push('}');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLE: &str = r#"@import "../omarchy/current/theme/waybar.css";
+
+* {
+  font-family: 'JetBrainsMono Nerd Font';
+  font-size: 12px;
+}
+
+/* keep me */
+#cpu,
+#battery {
+  margin: 0 7.5px;
+}
+
+@keyframes blink {
+  to { color: #ffffff; }
+}
+
+#clock {
+  margin-left: 8.75px;
+}
+"#;
+
+    #[test]
+    fn unedited_style_is_written_back_unchanged() {
+        let style = WaybarStyle::parse(STYLE);
+        assert_eq!(style.to_css(), STYLE);
+        assert_eq!(
+            style
+                .rules()
+                .map(|r| r.selector.as_str())
+                .collect::<Vec<_>>(),
+            vec!["*", "#cpu, #battery", "#clock"]
+        );
+        assert_eq!(style.property("*", "font-size"), Some("12px"));
+    }
+
+    #[test]
+    fn set_property_only_rewrites_the_edited_rule() {
+        let mut style = WaybarStyle::parse(STYLE);
+        style.set_property("#clock", "padding", Some("0 4px"));
+        style.set_property("#battery.critical", "color", Some("@red"));
+        style.set_property("*", "font-size", None);
+
+        let css = style.to_css();
+        assert!(css.contains("#clock {\n  margin-left: 8.75px;\n  padding: 0 4px;\n}"));
+        assert!(css.ends_with("}\n\n#battery.critical {\n  color: @red;\n}\n"));
+        assert!(css.contains("* {\n  font-family: 'JetBrainsMono Nerd Font';\n}"));
+        assert!(css.contains("/* keep me */\n#cpu,\n#battery {"));
+        assert!(css.contains("@keyframes blink {\n  to { color: #ffffff; }\n}"));
+
+        let reparsed = WaybarStyle::parse(&css);
+        assert_eq!(
+            reparsed.property("#battery.critical", "color"),
+            Some("@red")
+        );
+        assert_eq!(reparsed.property("*", "font-size"), None);
+    }
+
+    #[test]
+    fn theme_import_can_be_toggled() {
+        let mut style = WaybarStyle::parse(STYLE);
+        assert!(style.imports_theme());
+
+        style.set_theme_import(false);
+        assert!(!style.imports_theme());
+        assert!(style.to_css().starts_with("\n* {"));
+
+        style.set_theme_import(true);
+        assert!(style.to_css().starts_with(THEME_IMPORT));
+        assert_eq!(style.to_css().matches("@import").count(), 1);
+    }
+
+    #[test]
+    fn module_selectors_follow_waybar_naming() {
+        assert_eq!(module_selector("clock"), "#clock");
+        assert_eq!(module_selector("hyprland/workspaces"), "#workspaces");
+        assert_eq!(module_selector("custom/omarchy"), "#custom-omarchy");
+        assert_eq!(module_selector("battery#bat2"), "#battery.bat2");
+        assert_eq!(
+            module_style_selectors("battery"),
+            vec![
+                "#battery",
+                "#battery.charging",
+                "#battery.warning",
+                "#battery.critical"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_define_colors_reads_names_and_values() {
+        let css = "@define-color background #1a1b26;\n@define-color foreground #a9b1d6;\n";
+        assert_eq!(
+            parse_define_colors(css),
+            vec![
+                ("background".to_string(), "#1a1b26".to_string()),
+                ("foreground".to_string(), "#a9b1d6".to_string()),
+            ]
+        );
+    }
+}
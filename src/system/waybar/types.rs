@@ -33,15 +33,19 @@ pub struct WaybarModule {
     pub zone: WaybarZone,
 }
 
-#[derive(Debug, Clone)]
-pub struct WaybarConfig {
-    pub profile_name: String,
+/// One bar of a Waybar config. A config file holds either a single bar
+/// object or an array of them, e.g. one bar per monitor.
+#[derive(Debug, Clone, Default)]
+pub struct WaybarBar {
+    pub settings: BarSettings,
     pub modules_left: Vec<WaybarModule>,
     pub modules_center: Vec<WaybarModule>,
     pub modules_right: Vec<WaybarModule>,
+    /// The bar's JSON object, for looking up module blocks.
+    pub config: serde_json::Value,
 }
 
-impl WaybarConfig {
+impl WaybarBar {
     pub fn all_modules(&self) -> Vec<&WaybarModule> {
         self.modules_left
             .iter()
@@ -49,6 +53,28 @@ impl WaybarConfig {
             .chain(self.modules_right.iter())
             .collect()
     }
+
+    pub fn modules(&self, zone: &WaybarZone) -> &Vec<WaybarModule> {
+        match zone {
+            WaybarZone::Left => &self.modules_left,
+            WaybarZone::Center => &self.modules_center,
+            WaybarZone::Right => &self.modules_right,
+        }
+    }
+
+    pub fn modules_mut(&mut self, zone: &WaybarZone) -> &mut Vec<WaybarModule> {
+        match zone {
+            WaybarZone::Left => &mut self.modules_left,
+            WaybarZone::Center => &mut self.modules_center,
+            WaybarZone::Right => &mut self.modules_right,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WaybarConfig {
+    pub profile_name: String,
+    pub bars: Vec<WaybarBar>,
 }
 
 #[derive(Debug, Clone)]
@@ -73,16 +99,17 @@ mod tests {
             icon: String::new(),
             zone,
         };
-        let config = WaybarConfig {
-            profile_name: "test".to_string(),
+        let bar = WaybarBar {
+            settings: BarSettings::default(),
             modules_left: vec![make_module("clock", WaybarZone::Left)],
             modules_center: vec![make_module("cpu", WaybarZone::Center)],
             modules_right: vec![
                 make_module("tray", WaybarZone::Right),
                 make_module("memory", WaybarZone::Right),
             ],
+            ..WaybarBar::default()
         };
-        let all = config.all_modules();
+        let all = bar.all_modules();
         assert_eq!(all.len(), 4, "all_modules should return all four modules");
         assert_eq!(all[0].key, "clock");
         assert_eq!(all[1].key, "cpu");
@@ -91,16 +118,11 @@ mod tests {
     }
 
     #[test]
-    fn all_modules_empty_config_returns_empty_vec() {
-        let config = WaybarConfig {
-            profile_name: "empty".to_string(),
-            modules_left: vec![],
-            modules_center: vec![],
-            modules_right: vec![],
-        };
+    fn all_modules_empty_bar_returns_empty_vec() {
+        let bar = WaybarBar::default();
         assert!(
-            config.all_modules().is_empty(),
-            "all_modules on an empty config should return an empty vec"
+            bar.all_modules().is_empty(),
+            "all_modules on an empty bar should return an empty vec"
         );
     }
 }
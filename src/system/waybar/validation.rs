@@ -0,0 +1,599 @@
+use std::fs;
+
+use serde_json::Value;
+
+use super::config::bar_spans;
+use super::jsonc::{find_top_level_key, find_value_end, parse_jsonc};
+use super::paths::waybar_profile_config_path;
+
+const ZONE_KEYS: &[&str] = &["modules-left", "modules-center", "modules-right"];
+
+// Bar keys Waybar reads as whole numbers
+const UNSIGNED_BAR_KEYS: &[&str] = &["height", "spacing"];
+
+// Module types built into Waybar, besides `custom/*` and `group/*`
+const KNOWN_MODULES: &[&str] = &[
+    "backlight",
+    "backlight/slider",
+    "battery",
+    "bluetooth",
+    "cava",
+    "cffi",
+    "clock",
+    "cpu",
+    "disk",
+    "dwl/tags",
+    "dwl/window",
+    "ext/workspaces",
+    "gamemode",
+    "hyprland/language",
+    "hyprland/submap",
+    "hyprland/window",
+    "hyprland/windowcount",
+    "hyprland/workspaces",
+    "idle_inhibitor",
+    "image",
+    "inhibitor",
+    "jack",
+    "keyboard-state",
+    "load",
+    "memory",
+    "mpd",
+    "mpris",
+    "network",
+    "niri/language",
+    "niri/window",
+    "niri/workspaces",
+    "power-profiles-daemon",
+    "privacy",
+    "pulseaudio",
+    "pulseaudio/slider",
+    "river/layout",
+    "river/mode",
+    "river/tags",
+    "river/window",
+    "sndio",
+    "sway/language",
+    "sway/mode",
+    "sway/scratchpad",
+    "sway/window",
+    "sway/workspaces",
+    "systemd-failed-units",
+    "temperature",
+    "tray",
+    "upower",
+    "user",
+    "wireplumber",
+    "wlr/taskbar",
+    "wlr/workspaces",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Waybar would fail to start or crash; the profile is not applied.
+    Error,
+    /// Waybar would skip or ignore something.
+    Warning,
+}
+
+/// One problem found in a Waybar config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based line in the config file, when the problem can be located.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {}: {}", line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+/// Check a profile's config file. See `validate_waybar_config`.
+pub fn validate_waybar_profile(profile_name: &str) -> Result<Vec<Diagnostic>, String> {
+    let config_path = waybar_profile_config_path(profile_name)
+        .ok_or_else(|| "Could not determine home directory".to_string())?;
+    let src =
+        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
+    Ok(validate_waybar_config(&src))
+}
+
+/// Check a Waybar config for JSONC syntax errors, zones listing custom or
+/// group modules that are never defined, unknown module types, wrongly
+/// typed `height`, `spacing` and `interval` values, and `format` strings
+/// with unbalanced braces or placeholders the module doesn't provide.
+pub fn validate_waybar_config(src: &str) -> Vec<Diagnostic> {
+    let json = match parse_jsonc(src) {
+        Ok(json) => json,
+        Err(e) => {
+            return vec![Diagnostic {
+                severity: Severity::Error,
+                line: Some(e.line),
+                column: Some(e.column),
+                message: e.message,
+            }];
+        }
+    };
+
+    let bars: Vec<&Value> = match &json {
+        Value::Object(_) => vec![&json],
+        Value::Array(items) => items.iter().collect(),
+        _ => Vec::new(),
+    };
+    let objects: Vec<&Value> = bars.iter().copied().filter(|b| b.is_object()).collect();
+    if objects.is_empty() || objects.len() != bars.len() {
+        return vec![Diagnostic {
+            severity: Severity::Error,
+            line: None,
+            column: None,
+            message: "The config must be a bar object or a list of bar objects".to_string(),
+        }];
+    }
+
+    let spans = bar_spans(src);
+    let mut diagnostics = Vec::new();
+    for (index, bar) in objects.iter().enumerate() {
+        let (start, end) = spans.get(index).copied().unwrap_or((0, 0));
+        let source = BarSource {
+            src,
+            start,
+            text: &src[start..end],
+            prefix: if objects.len() > 1 {
+                format!("Bar {}: ", index + 1)
+            } else {
+                String::new()
+            },
+        };
+        validate_bar(bar, &source, &mut diagnostics);
+    }
+    diagnostics
+}
+
+// Where a bar sits in the config source, for locating its problems
+struct BarSource<'a> {
+    src: &'a str,
+    start: usize,
+    text: &'a str,
+    // Names the bar in messages when the config has several
+    prefix: String,
+}
+
+impl BarSource<'_> {
+    fn push(
+        &self,
+        diagnostics: &mut Vec<Diagnostic>,
+        severity: Severity,
+        offset: Option<usize>,
+        message: String,
+    ) {
+        let position = offset.map(|offset| {
+            let before = &self.src[..self.start + offset];
+            let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+            (
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )
+        });
+        diagnostics.push(Diagnostic {
+            severity,
+            line: position.map(|p| p.0),
+            column: position.map(|p| p.1),
+            message: format!("{}{}", self.prefix, message),
+        });
+    }
+
+    fn key(&self, key: &str) -> Option<usize> {
+        find_top_level_key(self.text, key)
+    }
+
+    // A key inside a module's block, falling back to the block itself
+    fn module_key(&self, module: &str, key: &str) -> Option<usize> {
+        let block_start = self.key(module)?;
+        let colon = block_start + self.text[block_start..].find(':')? + 1;
+        let value_start = colon + self.text[colon..].len() - self.text[colon..].trim_start().len();
+        let block_end = find_value_end(self.text, value_start)?;
+        let inner = find_top_level_key(&self.text[block_start..block_end], key);
+        Some(inner.map_or(block_start, |offset| block_start + offset))
+    }
+
+    // A module's entry in a zone array
+    fn zone_entry(&self, zone: &str, module: &str) -> Option<usize> {
+        let zone_start = self.key(zone)?;
+        let pattern = format!("\"{}\"", module);
+        self.text[zone_start..]
+            .find(&pattern)
+            .map(|offset| zone_start + offset)
+    }
+}
+
+fn validate_bar(bar: &Value, source: &BarSource, diagnostics: &mut Vec<Diagnostic>) {
+    for key in UNSIGNED_BAR_KEYS {
+        if let Some(value) = bar.get(key)
+            && !value.is_u64()
+        {
+            source.push(
+                diagnostics,
+                Severity::Error,
+                source.key(key),
+                format!("\"{}\" should be a whole number, not {}", key, value),
+            );
+        }
+    }
+
+    // Modules listed in the zones, with where they are listed
+    let mut listed: Vec<(String, Option<usize>)> = Vec::new();
+    for zone in ZONE_KEYS {
+        let Some(value) = bar.get(zone) else {
+            continue;
+        };
+        let Some(entries) = value.as_array() else {
+            source.push(
+                diagnostics,
+                Severity::Error,
+                source.key(zone),
+                format!("\"{}\" should be a list of module names", zone),
+            );
+            continue;
+        };
+        for entry in entries {
+            match entry.as_str() {
+                Some(module) => {
+                    if !listed.iter().any(|(m, _)| m == module) {
+                        listed.push((module.to_string(), source.zone_entry(zone, module)));
+                    }
+                }
+                None => source.push(
+                    diagnostics,
+                    Severity::Error,
+                    source.key(zone),
+                    format!("\"{}\" should only list module names, not {}", zone, entry),
+                ),
+            }
+        }
+    }
+
+    // Group members count as listed too
+    let mut index = 0;
+    while index < listed.len() {
+        let module = listed[index].0.clone();
+        if module.starts_with("group/")
+            && let Some(members) = bar
+                .get(&module)
+                .and_then(|b| b.get("modules"))
+                .and_then(|v| v.as_array())
+        {
+            for member in members.iter().filter_map(|v| v.as_str()) {
+                if !listed.iter().any(|(m, _)| m == member) {
+                    listed.push((member.to_string(), source.module_key(&module, "modules")));
+                }
+            }
+        }
+        index += 1;
+    }
+
+    for (module, offset) in &listed {
+        validate_module(bar, module, *offset, source, diagnostics);
+    }
+}
+
+fn validate_module(
+    bar: &Value,
+    module: &str,
+    listed_at: Option<usize>,
+    source: &BarSource,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let module_type = module.split('#').next().unwrap_or(module);
+    let block = bar.get(module);
+    let is_custom = module_type.starts_with("custom/");
+    let is_group = module_type.starts_with("group/");
+
+    if (is_custom || is_group) && block.is_none() {
+        source.push(
+            diagnostics,
+            Severity::Error,
+            listed_at,
+            format!("\"{}\" is listed but never defined", module),
+        );
+        return;
+    }
+    if is_group {
+        if block
+            .and_then(|b| b.get("modules"))
+            .is_none_or(|v| !v.is_array())
+        {
+            source.push(
+                diagnostics,
+                Severity::Error,
+                source.key(module),
+                format!("\"{}\" needs a \"modules\" list", module),
+            );
+        }
+        return;
+    }
+    if !is_custom && !KNOWN_MODULES.contains(&module_type) {
+        source.push(
+            diagnostics,
+            Severity::Warning,
+            listed_at,
+            format!(
+                "\"{}\" is not a Waybar module type, Waybar will skip it",
+                module_type
+            ),
+        );
+        return;
+    }
+
+    let Some(block) = block.and_then(|b| b.as_object()) else {
+        return;
+    };
+
+    if let Some(interval) = block.get("interval") {
+        let valid = interval.as_f64().is_some_and(|n| n >= 0.0)
+            || (is_custom && interval.as_str() == Some("once"));
+        if !valid {
+            let expected = if is_custom {
+                "a number of seconds or \"once\""
+            } else {
+                "a number of seconds"
+            };
+            source.push(
+                diagnostics,
+                Severity::Error,
+                source.module_key(module, "interval"),
+                format!(
+                    "\"{}\" interval should be {}, not {}",
+                    module, expected, interval
+                ),
+            );
+        }
+    }
+
+    let placeholder_type = if is_custom { "custom" } else { module_type };
+    for (key, value) in block {
+        let is_format = key == "format" || (key.starts_with("format-") && key != "format-icons");
+        let Some(format) = value.as_str().filter(|_| is_format) else {
+            continue;
+        };
+        if let Err(problem) = check_format(placeholder_type, format) {
+            source.push(
+                diagnostics,
+                Severity::Error,
+                source.module_key(module, key),
+                format!("\"{}\" {}: {}", module, key, problem),
+            );
+        }
+    }
+}
+
+// Check a format string's braces and, for modules whose placeholders are
+// known, its placeholder names. Waybar aborts on either mistake.
+fn check_format(module_type: &str, format: &str) -> Result<(), String> {
+    let known = known_placeholders(module_type);
+    let mut rest = format;
+    while let Some(pos) = rest.find(['{', '}']) {
+        let after = &rest[pos + 1..];
+        if rest[pos..].starts_with('}') {
+            let Some(escaped) = after.strip_prefix('}') else {
+                return Err("`}` without a matching `{` (write `}}` for a literal brace)".into());
+            };
+            rest = escaped;
+            continue;
+        }
+        if let Some(escaped) = after.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+
+        let Some(close) = after
+            .find(['{', '}'])
+            .filter(|&i| after[i..].starts_with('}'))
+        else {
+            return Err("`{` is never closed (write `{{` for a literal brace)".into());
+        };
+        let placeholder = &after[..close];
+        let name = placeholder.split(':').next().unwrap_or_default();
+        // Per-core values like `{usage0}` share their base name's rules
+        let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        if let Some(known) = known
+            && !name.is_empty()
+            && !known.contains(&name)
+            && !known.contains(&base)
+        {
+            return Err(format!("unknown placeholder {{{}}}", name));
+        }
+        rest = &after[close + 1..];
+    }
+    Ok(())
+}
+
+// Placeholders a module type fills in, for the types the validator knows
+fn known_placeholders(module_type: &str) -> Option<&'static [&'static str]> {
+    Some(match module_type {
+        "battery" => &[
+            "capacity", "power", "icon", "time", "timeTo", "cycles", "health",
+        ],
+        "backlight" => &["percent", "icon"],
+        "bluetooth" => &[
+            "status",
+            "num_connections",
+            "controller_address",
+            "controller_address_type",
+            "controller_alias",
+            "device_address",
+            "device_address_type",
+            "device_alias",
+            "device_enumerate",
+            "device_battery_percentage",
+            "icon",
+        ],
+        "clock" => &["calendar", "tz_list", "ordinal_date"],
+        "cpu" => &[
+            "load",
+            "usage",
+            "avg_frequency",
+            "max_frequency",
+            "min_frequency",
+            "icon",
+        ],
+        "custom" => &["text", "alt", "percentage", "icon"],
+        "disk" => &[
+            "percentage_used",
+            "percentage_free",
+            "total",
+            "used",
+            "free",
+            "path",
+            "specific_total",
+            "specific_used",
+            "specific_free",
+        ],
+        "hyprland/language" => &["short", "shortDescription", "long", "variant"],
+        "hyprland/window" => &["title", "initialTitle", "class", "initialClass"],
+        "hyprland/workspaces" => &["id", "name", "icon", "windows"],
+        "idle_inhibitor" => &["status", "icon"],
+        "memory" => &[
+            "percentage",
+            "swapPercentage",
+            "total",
+            "swapTotal",
+            "used",
+            "swapUsed",
+            "avail",
+            "swapAvail",
+            "icon",
+        ],
+        "network" => &[
+            "ifname",
+            "ipaddr",
+            "ipaddr6",
+            "gwaddr",
+            "netmask",
+            "netmask6",
+            "cidr",
+            "cidr6",
+            "essid",
+            "bssid",
+            "signalStrength",
+            "signaldBm",
+            "frequency",
+            "bandwidthUpBits",
+            "bandwidthDownBits",
+            "bandwidthTotalBits",
+            "bandwidthUpOctets",
+            "bandwidthDownOctets",
+            "bandwidthTotalOctets",
+            "bandwidthUpBytes",
+            "bandwidthDownBytes",
+            "bandwidthTotalBytes",
+            "icon",
+        ],
+        "pulseaudio" => &["volume", "icon", "format_source", "desc"],
+        "temperature" => &["temperatureC", "temperatureF", "temperatureK", "icon"],
+        "wireplumber" => &["volume", "node_name", "icon"],
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(src: &str) -> Vec<String> {
+        validate_waybar_config(src)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn valid_config_has_no_diagnostics() {
+        let src = r#"{
+  // Omarchy's bar
+  "height": 26,
+  "modules-left": ["hyprland/workspaces"],
+  "modules-center": ["clock"],
+  "modules-right": ["group/tray", "battery"],
+  "group/tray": { "modules": ["custom/expand", "tray"] },
+  "custom/expand": { "format": "{{x}}", "interval": "once" },
+  "clock": { "format": "{:%H:%M}", "interval": 1 },
+  "battery": { "format": "{capacity}% {icon}", "format-charging": "{capacity}%" },
+  "cpu": { "format": "{usage0} {icon1}" }
+}"#;
+        assert!(validate_waybar_config(src).is_empty());
+    }
+
+    #[test]
+    fn syntax_errors_are_located() {
+        let src = "{\n  \"height\": 26\n  \"spacing\": 4\n}";
+        assert_eq!(
+            messages(src),
+            vec!["line 3, column 3: expected `,` or `}`".to_string()]
+        );
+    }
+
+    #[test]
+    fn listed_but_undefined_and_unknown_modules() {
+        let src = r#"{
+  "modules-left": ["custom/missing", "clok"]
+}"#;
+        let diagnostics = validate_waybar_config(src);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "line 2, column 20: \"custom/missing\" is listed but never defined"
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert!(!has_errors(&diagnostics[1..]));
+    }
+
+    #[test]
+    fn wrong_value_types_are_errors() {
+        let src = r#"[
+  { "modules-left": [] },
+  {
+    "height": "30",
+    "modules-left": ["cpu"],
+    "cpu": { "interval": "5" }
+  }
+]"#;
+        assert_eq!(
+            messages(src),
+            vec![
+                "line 4, column 5: Bar 2: \"height\" should be a whole number, not \"30\""
+                    .to_string(),
+                "line 6, column 14: Bar 2: \"cpu\" interval should be a number of seconds, not \"5\""
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_commas_are_accepted() {
+        let src = "{\n  \"modules-left\": [\"clock\",],\n  \"a\": 1,\n}";
+        assert!(validate_waybar_config(src).is_empty());
+    }
+
+    #[test]
+    fn format_placeholders_are_checked() {
+        assert!(check_format("memory", "{used:0.1f}G {percentage}%").is_ok());
+        assert!(check_format("memory", "{usage}%").is_err());
+        assert!(check_format("memory", "{used").is_err());
+        assert!(check_format("memory", "used}").is_err());
+        assert!(check_format("mpris", "{anything}").is_ok());
+    }
+}
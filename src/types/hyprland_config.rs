@@ -17,6 +17,9 @@ pub struct HyprlandConfig {
     pub ecosystem: EcosystemConfig,
     pub quirks: QuirksConfig,
     pub debug: DebugConfig,
+    // `device { name = ... }` blocks, written after the sections
+    #[serde(default)]
+    pub devices: Vec<DeviceConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,6 +201,10 @@ impl Default for ShadowConfig {
 pub struct AnimationsConfig {
     pub enabled: bool,
     pub workspace_wraparound: bool,
+    #[serde(default)]
+    pub beziers: Vec<BezierCurve>,
+    #[serde(default)]
+    pub animations: Vec<Animation>,
 }
 
 impl Default for AnimationsConfig {
@@ -205,10 +212,34 @@ impl Default for AnimationsConfig {
         Self {
             enabled: true,
             workspace_wraparound: false,
+            beziers: Vec::new(),
+            animations: Vec::new(),
         }
     }
 }
 
+// `bezier = name, x1, y1, x2, y2`. The curve runs from (0, 0) to (1, 1) with
+// the two control points in between.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BezierCurve {
+    pub name: String,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+// `animation = name, onoff, speed, curve[, style]`. Speed is in deciseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Animation {
+    pub name: String,
+    pub enabled: bool,
+    pub speed: f64,
+    // Empty for `animation = name, 0`, which only turns it off
+    pub curve: String,
+    pub style: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InputConfig {
     pub kb_model: String,
@@ -315,6 +346,39 @@ impl Default for TouchpadConfig {
     }
 }
 
+// A `device { name = ... }` block, overriding input settings for one device.
+// Only the options that are set are written, the rest follow `input`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceConfig {
+    pub name: String,
+    pub enabled: Option<bool>,
+    pub sensitivity: Option<f64>,
+    pub accel_profile: Option<String>,
+    pub natural_scroll: Option<bool>,
+    pub left_handed: Option<bool>,
+    pub scroll_factor: Option<f64>,
+    pub kb_layout: Option<String>,
+    pub kb_variant: Option<String>,
+    pub kb_options: Option<String>,
+    pub repeat_rate: Option<i32>,
+    pub repeat_delay: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputDeviceKind {
+    Mouse,
+    Keyboard,
+    Tablet,
+    Touch,
+}
+
+// A connected input device as reported by `hyprctl devices -j`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputDevice {
+    pub name: String,
+    pub kind: InputDeviceKind,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GesturesConfig {
     pub workspace_swipe_distance: i32,
@@ -731,3 +795,246 @@ pub struct KeyboardOption {
     pub name: String,
     pub description: String,
 }
+
+// One entry of a comma-separated `kb_layout` list, with the `kb_variant` entry
+// at the same position. An empty variant is the layout's default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyboardLayoutChoice {
+    pub layout: String,
+    pub variant: String,
+}
+
+// Where a keybind was read from. Omarchy and user binds are changed by writing
+// overrides, never by editing their files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeybindSource {
+    Omarchy,
+    User,
+    Omarchist,
+}
+
+// The single-letter flags after `bind`, e.g. `bindel` is repeat + locked
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeybindFlags {
+    pub locked: bool,
+    pub release: bool,
+    pub repeat: bool,
+    pub non_consuming: bool,
+    pub mouse: bool,
+    pub transparent: bool,
+    pub ignore_mods: bool,
+    pub separate: bool,
+    // Flags Omarchist has no switch for, such as `o` (long press) or `c`
+    // (click), kept as written so the bind survives a save
+    #[serde(default)]
+    pub other: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keybind {
+    pub flags: KeybindFlags,
+    pub mods: Vec<String>,
+    pub key: String,
+    // Only present on `bindd` binds
+    pub description: Option<String>,
+    pub dispatcher: String,
+    pub args: String,
+    pub source: KeybindSource,
+}
+
+impl Keybind {
+    // Key names are matched case-insensitively by Hyprland
+    pub fn combo(&self) -> KeyCombo {
+        KeyCombo {
+            mods: self.mods.clone(),
+            key: self.key.to_uppercase(),
+        }
+    }
+}
+
+// Normalized mods + key. Two binds with the same combo fire together.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct KeyCombo {
+    pub mods: Vec<String>,
+    pub key: String,
+}
+
+// Everything Omarchist writes to its keybinds file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeybindOverrides {
+    pub unbinds: Vec<KeyCombo>,
+    pub binds: Vec<Keybind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeybindConflict {
+    pub combo: KeyCombo,
+    // Indices into the list the conflict was detected in
+    pub indices: Vec<usize>,
+}
+
+// A resolution and refresh rate an output supports, e.g. `2560x1440@165.00Hz`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonitorMode {
+    pub width: i32,
+    pub height: i32,
+    pub refresh_rate: f64,
+}
+
+impl MonitorMode {
+    // Refresh rates are reported with two decimals, so compare at that precision
+    pub fn matches(&self, other: &MonitorMode) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && (self.refresh_rate - other.refresh_rate).abs() < 0.01
+    }
+}
+
+// A connected output as reported by `hyprctl monitors all -j`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorInfo {
+    pub name: String,
+    pub description: String,
+    pub mode: MonitorMode,
+    pub x: i32,
+    pub y: i32,
+    pub scale: f64,
+    pub transform: i32,
+    pub mirror_of: Option<String>,
+    pub disabled: bool,
+    pub available_modes: Vec<MonitorMode>,
+}
+
+// The settings Omarchist writes as one `monitor =` line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorSettings {
+    pub name: String,
+    pub mode: MonitorMode,
+    pub x: i32,
+    pub y: i32,
+    pub scale: f64,
+    // wl_output transform: 0-3 rotate by 90° steps, 4-7 flip first
+    pub transform: i32,
+    pub mirror: Option<String>,
+    pub enabled: bool,
+}
+
+impl MonitorSettings {
+    // Size in layout coordinates, which is what positions are measured in
+    pub fn logical_size(&self) -> (i32, i32) {
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        let width = (self.mode.width as f64 / scale).round() as i32;
+        let height = (self.mode.height as f64 / scale).round() as i32;
+
+        if self.transform % 2 == 1 {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    // Disabled and mirrored outputs take no space in the layout
+    pub fn is_arranged(&self) -> bool {
+        self.enabled && self.mirror.is_none()
+    }
+}
+
+impl From<&MonitorInfo> for MonitorSettings {
+    fn from(info: &MonitorInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            mode: info.mode,
+            x: info.x,
+            y: info.y,
+            scale: info.scale,
+            transform: info.transform,
+            mirror: info.mirror_of.clone(),
+            enabled: !info.disabled,
+        }
+    }
+}
+
+// `windowrulev2` is an alias of `windowrule` on current Hyprland, the keyword
+// is kept so rules are written back the way they were read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowRuleKeyword {
+    #[default]
+    WindowRule,
+    WindowRuleV2,
+}
+
+// What a window rule does, the part before the first comma
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowRuleAction {
+    Float,
+    Tile,
+    Fullscreen,
+    Maximize,
+    Pin,
+    Center,
+    // Sizes and positions keep Hyprland's own units, e.g. `50%` or `100%-w`
+    Size { width: String, height: String },
+    Move { x: String, y: String },
+    // Active, inactive and fullscreen opacities, e.g. `0.9 0.8`
+    Opacity(String),
+    Workspace { workspace: String, silent: bool },
+    Other(String),
+}
+
+// Which windows a rule applies to. Regexes are stored as written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowMatcher {
+    Class(String),
+    Title(String),
+    InitialClass(String),
+    InitialTitle(String),
+    Floating(bool),
+    XWayland(bool),
+    Fullscreen(bool),
+    Pinned(bool),
+    Workspace(String),
+    // Anything else, or the bare regex of a legacy `windowrule` when `key` is empty
+    Other { key: String, value: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowRule {
+    pub keyword: WindowRuleKeyword,
+    pub action: WindowRuleAction,
+    pub matchers: Vec<WindowMatcher>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkspaceRuleOption {
+    Monitor(String),
+    Default(bool),
+    Persistent(bool),
+    OnCreatedEmpty(String),
+    Other { key: String, value: String },
+}
+
+// `workspace = 1, monitor:DP-1, default:true`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceRule {
+    pub workspace: String,
+    pub options: Vec<WorkspaceRuleOption>,
+}
+
+// Everything Omarchist writes to its rules file, in file order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSet {
+    pub window_rules: Vec<WindowRule>,
+    pub workspace_rules: Vec<WorkspaceRule>,
+}
+
+// An open window as reported by `hyprctl clients -j`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientInfo {
+    pub class: String,
+    pub title: String,
+    pub initial_class: String,
+    pub initial_title: String,
+    pub floating: bool,
+    pub xwayland: bool,
+    pub workspace: String,
+}
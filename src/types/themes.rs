@@ -511,8 +511,10 @@ pub struct AppConfigs {
     pub mako: Option<MakoConfig>,
     pub walker: Option<WalkerConfig>,
     pub swayosd: Option<SwayosdConfig>,
-    pub neovim: Option<serde_json::Value>,
-    pub vscode: Option<serde_json::Value>,
+    #[serde(default, deserialize_with = "deserialize_neovim_config")]
+    pub neovim: Option<NeovimConfig>,
+    #[serde(default, deserialize_with = "deserialize_vscode_config")]
+    pub vscode: Option<VscodeConfig>,
     pub icons: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ghostty: Option<serde_json::Value>,
@@ -521,6 +523,119 @@ pub struct AppConfigs {
     pub terminal: Option<TerminalConfig>,
 }
 
+// How an editor theme is provided: an external plugin/extension, or a colorscheme
+// Omarchist generates from the theme palette
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditorThemeMode {
+    #[default]
+    Plugin,
+    Generated,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NeovimConfig {
+    #[serde(default)]
+    pub mode: EditorThemeMode,
+    // lazy.nvim spec written to neovim.lua as-is in plugin mode
+    #[serde(default, alias = "raw_config")]
+    pub plugin_spec: String,
+}
+
+// Older themes stored neovim.lua as a plain string or as `{ raw_config, ... }`
+fn deserialize_neovim_config<'de, D>(deserializer: D) -> Result<Option<NeovimConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+
+    Ok(match value {
+        Some(serde_json::Value::String(plugin_spec)) => Some(NeovimConfig {
+            mode: EditorThemeMode::Plugin,
+            plugin_spec,
+        }),
+        Some(value @ serde_json::Value::Object(_)) => serde_json::from_value(value).ok(),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VscodeConfig {
+    #[serde(default)]
+    pub mode: EditorThemeMode,
+    // Marketplace theme label and extension id written to vscode.json in plugin mode
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub extension: String,
+}
+
+// Older themes stored the vscode.json text as a plain string or as `{ raw_config }`
+fn deserialize_vscode_config<'de, D>(deserializer: D) -> Result<Option<VscodeConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+
+    // Legacy configs hold the vscode.json text itself, which has the same shape
+    let value = match value {
+        Some(serde_json::Value::String(raw)) => serde_json::from_str(&raw).ok(),
+        Some(serde_json::Value::Object(map)) => {
+            match map.get("raw_config").and_then(|v| v.as_str()) {
+                Some(raw) => serde_json::from_str(raw).ok(),
+                None => Some(serde_json::Value::Object(map)),
+            }
+        }
+        _ => None,
+    };
+
+    Ok(value.and_then(|v| serde_json::from_value(v).ok()))
+}
+
+// Portable `.omarchist-theme` bundle: a manifest plus every file of the theme directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeBundle {
+    pub manifest: ThemeBundleManifest,
+    pub files: Vec<ThemeBundleFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeBundleManifest {
+    pub format: String,
+    pub format_version: u32,
+    pub name: String,
+    pub author: Option<String>,
+    pub exported_at: String,
+    pub exported_by: String,
+    pub is_light_theme: bool,
+    pub file_count: usize,
+}
+
+// Path is relative to the theme directory and always uses `/` separators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeBundleFile {
+    pub path: String,
+    pub data: String, // base64
+}
+
+// A saved revision of a theme directory under `<theme>/.history/<id>/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeSnapshot {
+    pub id: String,
+    pub created_at: String,
+    pub version: String,
+    pub modified_at: String,
+}
+
+// One `omarchist.json` field that differs between two snapshots. Paths are
+// dotted, e.g. `apps.terminal.normal.red`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFieldChange {
+    pub path: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThemeEditTab {
     General,
@@ -536,6 +651,7 @@ pub enum ThemeEditTab {
     Btop,
     Swayosd,
     Backgrounds,
+    Snapshots,
 }
 
 impl ThemeEditTab {
@@ -554,6 +670,7 @@ impl ThemeEditTab {
             ThemeEditTab::Btop => "Btop",
             ThemeEditTab::Swayosd => "SwayOSD",
             ThemeEditTab::Backgrounds => "Backgrounds",
+            ThemeEditTab::Snapshots => "Snapshots",
         }
     }
 
@@ -572,6 +689,7 @@ impl ThemeEditTab {
             ThemeEditTab::Btop,
             ThemeEditTab::Swayosd,
             ThemeEditTab::Backgrounds,
+            ThemeEditTab::Snapshots,
         ]
     }
 }
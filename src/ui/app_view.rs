@@ -280,6 +280,15 @@ impl MainWindowView {
             }
         }
 
+        let edited_theme = match &page {
+            ActivePage::ThemeEdit(theme_name) => Some(theme_name.clone()),
+            _ => None,
+        };
+        self.title_bar.update(cx, |title_bar, cx| {
+            title_bar.set_edited_theme(edited_theme);
+            cx.notify();
+        });
+
         self.active_page = page;
 
         // Transfer GPUI focus to the newly active page so its key_context
@@ -1,2 +1,9 @@
+pub mod animations_panel;
 pub mod config_view;
+pub mod devices_panel;
+pub mod keybindings_panel;
+pub mod keyboard_layout_panel;
+pub mod monitors_panel;
+pub mod rules_panel;
+pub mod saving;
 pub use config_view::ConfigView;
@@ -0,0 +1,783 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputState},
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::system::hyprland_config::HyprlandConfigManager;
+use crate::types::hyprland_config::{Animation, AnimationsConfig, BezierCurve};
+use crate::ui::config_page::saving::save_config_later;
+
+// Pixels per unit of the curve editor. X runs from 0 to 1, Y leaves room
+// above and below for curves that overshoot.
+const EDITOR_UNIT: f32 = 200.;
+const Y_MIN: f64 = -0.5;
+const Y_MAX: f64 = 1.5;
+const HANDLE_SIZE: f32 = 12.;
+
+const SPEED_STEP: f64 = 0.5;
+
+const PRESETS: [(&str, [f64; 4]); 8] = [
+    ("easeOutQuint", [0.23, 1.0, 0.32, 1.0]),
+    ("easeInOutCubic", [0.65, 0.05, 0.36, 1.0]),
+    ("easeOutBack", [0.34, 1.56, 0.64, 1.0]),
+    ("easeInBack", [0.36, 0.0, 0.66, -0.56]),
+    ("almostLinear", [0.5, 0.5, 0.75, 1.0]),
+    ("quick", [0.15, 0.0, 0.1, 1.0]),
+    ("overshot", [0.05, 0.9, 0.1, 1.1]),
+    ("linear", [0.0, 0.0, 1.0, 1.0]),
+];
+
+// Animation tree leaves and branches, see the Hyprland wiki
+const ANIMATION_NAMES: [&str; 27] = [
+    "global",
+    "windows",
+    "windowsIn",
+    "windowsOut",
+    "windowsMove",
+    "layers",
+    "layersIn",
+    "layersOut",
+    "fade",
+    "fadeIn",
+    "fadeOut",
+    "fadeSwitch",
+    "fadeShadow",
+    "fadeDim",
+    "fadeLayers",
+    "fadeLayersIn",
+    "fadeLayersOut",
+    "fadePopups",
+    "border",
+    "borderangle",
+    "workspaces",
+    "workspacesIn",
+    "workspacesOut",
+    "specialWorkspace",
+    "specialWorkspaceIn",
+    "specialWorkspaceOut",
+    "zoomFactor",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum ControlPoint {
+    First,
+    Second,
+}
+
+pub struct AnimationsPanel {
+    config_manager: Rc<RefCell<HyprlandConfigManager>>,
+    selected_curve: Option<usize>,
+    dragging: Option<ControlPoint>,
+    // Written during prepaint so mouse positions can be mapped onto the curve
+    editor_bounds: Rc<Cell<Bounds<Pixels>>>,
+    name_input: Entity<InputState>,
+    error_message: Option<String>,
+    // The pending save; replacing it drops that save for the newer one
+    save_task: Option<Task<()>>,
+}
+
+impl AnimationsPanel {
+    pub fn new(
+        config_manager: Rc<RefCell<HyprlandConfigManager>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let has_curves = !config_manager.borrow().get().animations.beziers.is_empty();
+        let name = config_manager
+            .borrow()
+            .get()
+            .animations
+            .beziers
+            .first()
+            .map(|b| b.name.clone())
+            .unwrap_or_default();
+        let name_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(name)
+                .placeholder("Curve name")
+        });
+
+        Self {
+            config_manager,
+            selected_curve: has_curves.then_some(0),
+            dragging: None,
+            editor_bounds: Rc::new(Cell::new(Bounds::default())),
+            name_input,
+            error_message: None,
+            save_task: None,
+        }
+    }
+
+    /// Drop a save that hasn't run yet, e.g. before the files are replaced.
+    pub fn cancel_save(&mut self) {
+        self.save_task = None;
+    }
+
+    fn animations(&self) -> AnimationsConfig {
+        self.config_manager.borrow().get().animations.clone()
+    }
+
+    fn update<F>(&mut self, cx: &mut Context<Self>, f: F)
+    where
+        F: FnOnce(&mut AnimationsConfig),
+    {
+        self.config_manager
+            .borrow_mut()
+            .update(|config| f(&mut config.animations));
+        self.save(cx);
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        self.save_task = Some(save_config_later(
+            &self.config_manager,
+            cx,
+            |this, result, _cx| {
+                this.error_message = result
+                    .err()
+                    .map(|e| format!("Failed to save animations: {}", e));
+            },
+        ));
+        cx.notify();
+    }
+
+    fn select_curve(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self
+            .animations()
+            .beziers
+            .get(index)
+            .map(|b| b.name.clone())
+            .unwrap_or_default();
+        self.selected_curve = Some(index);
+        self.error_message = None;
+        self.name_input
+            .update(cx, |input, cx| input.set_value(name, window, cx));
+        cx.notify();
+    }
+
+    fn add_curve(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let beziers = self.animations().beziers;
+        let name = (1..)
+            .map(|n| format!("curve{}", n))
+            .find(|name| !beziers.iter().any(|b| &b.name == name))
+            .unwrap_or_default();
+
+        // CSS `ease` as a starting point
+        self.update(cx, |animations| {
+            animations.beziers.push(BezierCurve {
+                name,
+                x1: 0.25,
+                y1: 0.1,
+                x2: 0.25,
+                y2: 1.0,
+            })
+        });
+        self.select_curve(beziers.len(), window, cx);
+    }
+
+    // Sets the selected curve to a preset, or adds the preset when nothing is selected
+    fn apply_preset(&mut self, preset: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let (name, [x1, y1, x2, y2]) = PRESETS[preset];
+        let beziers = self.animations().beziers;
+
+        match self.selected_curve.filter(|i| *i < beziers.len()) {
+            Some(index) => self.update(cx, |animations| {
+                let bezier = &mut animations.beziers[index];
+                (bezier.x1, bezier.y1, bezier.x2, bezier.y2) = (x1, y1, x2, y2);
+            }),
+            None => {
+                let index = match beziers.iter().position(|b| b.name == name) {
+                    Some(index) => index,
+                    None => {
+                        self.update(cx, |animations| {
+                            animations.beziers.push(BezierCurve {
+                                name: name.to_string(),
+                                x1,
+                                y1,
+                                x2,
+                                y2,
+                            })
+                        });
+                        beziers.len()
+                    }
+                };
+                self.select_curve(index, window, cx);
+            }
+        }
+    }
+
+    fn rename_curve(&mut self, cx: &mut Context<Self>) {
+        let Some(index) = self.selected_curve else {
+            return;
+        };
+        let new_name = self.name_input.read(cx).value().trim().to_string();
+        let beziers = self.animations().beziers;
+        let Some(old_name) = beziers.get(index).map(|b| b.name.clone()) else {
+            return;
+        };
+
+        if new_name.is_empty() || new_name.contains(|c: char| c == ',' || c.is_whitespace()) {
+            self.error_message =
+                Some("Curve names can not be empty or contain commas or spaces".to_string());
+        } else if new_name != old_name && beziers.iter().any(|b| b.name == new_name) {
+            self.error_message = Some(format!("A curve named {} already exists", new_name));
+        } else {
+            self.update(cx, |animations| {
+                animations.beziers[index].name = new_name.clone();
+                for animation in &mut animations.animations {
+                    if animation.curve == old_name {
+                        animation.curve = new_name.clone();
+                    }
+                }
+            });
+            return;
+        }
+        cx.notify();
+    }
+
+    // Animations using a deleted curve go back to Hyprland's default one
+    fn delete_curve(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(index) = self.selected_curve else {
+            return;
+        };
+
+        self.update(cx, |animations| {
+            if index < animations.beziers.len() {
+                let removed = animations.beziers.remove(index);
+                for animation in &mut animations.animations {
+                    if animation.curve == removed.name {
+                        animation.curve = "default".to_string();
+                    }
+                }
+            }
+        });
+
+        if self.animations().beziers.is_empty() {
+            self.selected_curve = None;
+        } else {
+            self.select_curve(index.saturating_sub(1), window, cx);
+        }
+    }
+
+    fn drag_to(&mut self, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        let (Some(point), Some(index)) = (self.dragging, self.selected_curve) else {
+            return;
+        };
+        if event.pressed_button != Some(MouseButton::Left) {
+            return;
+        }
+
+        let (x, y) = from_editor(event.position, self.editor_bounds.get());
+        self.config_manager.borrow_mut().update(|config| {
+            if let Some(bezier) = config.animations.beziers.get_mut(index) {
+                match point {
+                    ControlPoint::First => (bezier.x1, bezier.y1) = (x, y),
+                    ControlPoint::Second => (bezier.x2, bezier.y2) = (x, y),
+                }
+            }
+        });
+        cx.notify();
+    }
+
+    // Saving reloads Hyprland, so it only happens once the handle is let go
+    fn end_drag(&mut self, cx: &mut Context<Self>) {
+        if self.dragging.take().is_some() {
+            self.save(cx);
+        }
+    }
+
+    fn render_editor(&self, bezier: &BezierCurve, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let curve_color = theme.primary;
+        let guide_color = theme.border;
+        let handle_color = theme.muted_foreground;
+        let bounds_cell = self.editor_bounds.clone();
+        let points = [bezier.x1, bezier.y1, bezier.x2, bezier.y2];
+
+        let handle = |point: ControlPoint, x: f64, y: f64| {
+            let (left, top) = editor_offset(x, y);
+            div()
+                .absolute()
+                .left(px(left - HANDLE_SIZE / 2.))
+                .top(px(top - HANDLE_SIZE / 2.))
+                .size(px(HANDLE_SIZE))
+                .rounded_full()
+                .bg(curve_color)
+                .border_2()
+                .border_color(theme.background)
+                .cursor_grab()
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
+                        this.dragging = Some(point);
+                        cx.notify();
+                    }),
+                )
+        };
+
+        div()
+            .relative()
+            .flex_shrink_0()
+            .w(px(EDITOR_UNIT))
+            .h(px(EDITOR_UNIT * (Y_MAX - Y_MIN) as f32))
+            .child(
+                canvas(
+                    move |bounds, _window, _cx| bounds_cell.set(bounds),
+                    move |bounds, _, window, _cx| {
+                        paint_curve(
+                            bounds,
+                            points,
+                            curve_color,
+                            guide_color,
+                            handle_color,
+                            window,
+                        )
+                    },
+                )
+                .size_full(),
+            )
+            .child(handle(ControlPoint::First, bezier.x1, bezier.y1))
+            .child(handle(ControlPoint::Second, bezier.x2, bezier.y2))
+            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                this.drag_to(event, cx);
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| this.end_drag(cx)),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| this.end_drag(cx)),
+            )
+    }
+
+    fn render_curves(&self, animations: &AnimationsConfig, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let panel = cx.entity();
+        let selected = self
+            .selected_curve
+            .and_then(|index| animations.beziers.get(index));
+
+        let curve_buttons: Vec<AnyElement> = animations
+            .beziers
+            .iter()
+            .enumerate()
+            .map(|(index, bezier)| {
+                Button::new(("select-curve", index))
+                    .label(bezier.name.clone())
+                    .small()
+                    .when(Some(index) == self.selected_curve, |this| this.primary())
+                    .when(Some(index) != self.selected_curve, |this| this.ghost())
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_curve(index, window, cx);
+                    }))
+                    .into_any_element()
+            })
+            .collect();
+
+        let presets = Button::new("curve-presets")
+            .label("Apply Preset")
+            .small()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu;
+                for (index, (name, _)) in PRESETS.iter().enumerate() {
+                    let panel = panel.clone();
+                    menu = menu.item(PopupMenuItem::new(*name).on_click(
+                        move |_event, window, cx| {
+                            panel.update(cx, |this, cx| this.apply_preset(index, window, cx));
+                        },
+                    ));
+                }
+                menu
+            });
+
+        v_flex()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .flex_wrap()
+                    .child(div().text_sm().font_semibold().child("Curves"))
+                    .children(curve_buttons)
+                    .child(div().flex_1())
+                    .child(presets)
+                    .child(
+                        Button::new("add-curve")
+                            .label("New Curve")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, window, cx| this.add_curve(window, cx))),
+                    ),
+            )
+            .children(selected.map(|bezier| {
+                h_flex()
+                    .gap_4()
+                    .items_start()
+                    .child(self.render_editor(bezier, cx))
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .gap_2()
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(div().flex_1().child(Input::new(&self.name_input).small()))
+                                    .child(
+                                        Button::new("rename-curve")
+                                            .label("Rename")
+                                            .small()
+                                            .on_click(cx.listener(|this, _, _window, cx| {
+                                                this.rename_curve(cx)
+                                            })),
+                                    )
+                                    .child(
+                                        Button::new("delete-curve")
+                                            .label("Delete")
+                                            .small()
+                                            .ghost()
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.delete_curve(window, cx)
+                                            })),
+                                    ),
+                            )
+                            .child(div().text_sm().child(format!(
+                                "cubic-bezier({}, {}, {}, {})",
+                                bezier.x1, bezier.y1, bezier.x2, bezier.y2
+                            )))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.muted_foreground)
+                                    .child("Drag the two handles to shape the curve. X stays between 0 and 1, Y may overshoot."),
+                            ),
+                    )
+            }))
+    }
+
+    fn render_animation_row(
+        &self,
+        index: usize,
+        animation: &Animation,
+        curves: &[String],
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let theme = cx.theme();
+        let panel = cx.entity();
+
+        let curve_panel = panel.clone();
+        let curve_options = curves.to_vec();
+        let current_curve = animation.curve.clone();
+        let curve_menu = Button::new(("animation-curve", index))
+            .label(if animation.curve.is_empty() {
+                "default".to_string()
+            } else {
+                animation.curve.clone()
+            })
+            .xsmall()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu;
+                for curve in curve_options.iter().cloned() {
+                    let panel = curve_panel.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(curve.clone())
+                            .checked(curve == current_curve)
+                            .on_click(move |_event, _window, cx| {
+                                let curve = curve.clone();
+                                panel.update(cx, |this, cx| {
+                                    this.update(cx, |a| a.animations[index].curve = curve);
+                                });
+                            }),
+                    );
+                }
+                menu
+            });
+
+        let styles = animation_styles(&animation.name);
+        let style_panel = panel.clone();
+        let current_style = animation.style.clone();
+        let style_menu = Button::new(("animation-style", index))
+            .label(
+                animation
+                    .style
+                    .clone()
+                    .unwrap_or_else(|| "Default style".to_string()),
+            )
+            .xsmall()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu;
+                let options =
+                    std::iter::once(None).chain(styles.iter().map(|s| Some(s.to_string())));
+                for style in options {
+                    let panel = style_panel.clone();
+                    let label = style.clone().unwrap_or_else(|| "Default style".to_string());
+                    menu = menu.item(
+                        PopupMenuItem::new(label)
+                            .checked(style == current_style)
+                            .on_click(move |_event, _window, cx| {
+                                let style = style.clone();
+                                panel.update(cx, |this, cx| {
+                                    this.update(cx, |a| a.animations[index].style = style);
+                                });
+                            }),
+                    );
+                }
+                menu
+            });
+
+        let speed = animation.speed;
+        h_flex()
+            .gap_3()
+            .py_1p5()
+            .w_full()
+            .items_center()
+            .border_b_1()
+            .border_color(theme.border.opacity(0.3))
+            .child(
+                Checkbox::new(("animation-enabled", index))
+                    .checked(animation.enabled)
+                    .on_click(cx.listener(move |this, checked: &bool, _window, cx| {
+                        let enabled = *checked;
+                        this.update(cx, |a| {
+                            let animation = &mut a.animations[index];
+                            animation.enabled = enabled;
+                            // `name, 0` has no speed or curve to turn back on with
+                            if enabled && animation.curve.is_empty() {
+                                animation.speed = 5.0;
+                                animation.curve = "default".to_string();
+                            }
+                        });
+                    })),
+            )
+            .child(
+                div()
+                    .w(px(180.))
+                    .flex_shrink_0()
+                    .text_sm()
+                    .font_semibold()
+                    .child(animation.name.clone()),
+            )
+            .when(animation.enabled, |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(
+                            Button::new(("animation-slower", index))
+                                .label("−")
+                                .xsmall()
+                                .ghost()
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    this.update(cx, |a| {
+                                        a.animations[index].speed =
+                                            (speed - SPEED_STEP).max(SPEED_STEP)
+                                    });
+                                })),
+                        )
+                        .child(
+                            div()
+                                .w(px(64.))
+                                .text_sm()
+                                .text_center()
+                                .child(format!("{:.2} ds", speed)),
+                        )
+                        .child(
+                            Button::new(("animation-faster", index))
+                                .label("+")
+                                .xsmall()
+                                .ghost()
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    this.update(cx, |a| {
+                                        a.animations[index].speed = speed + SPEED_STEP
+                                    });
+                                })),
+                        ),
+                )
+                .child(curve_menu)
+                .when(!animation_styles(&animation.name).is_empty(), |this| {
+                    this.child(style_menu)
+                })
+            })
+            .child(div().flex_1())
+            .child(
+                Button::new(("remove-animation", index))
+                    .label("Remove")
+                    .xsmall()
+                    .ghost()
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.update(cx, |a| {
+                            a.animations.remove(index);
+                        });
+                    })),
+            )
+            .into_any_element()
+    }
+}
+
+// Styles Hyprland accepts for an animation, by the branch of the tree it is in
+fn animation_styles(name: &str) -> &'static [&'static str] {
+    if name.starts_with("windows") {
+        &["slide", "popin", "popin 80%", "gnomed"]
+    } else if name.starts_with("layers") {
+        &["slide", "popin", "fade"]
+    } else if name.starts_with("workspaces") || name.starts_with("specialWorkspace") {
+        &["slide", "slidevert", "fade", "slidefade", "slidefadevert"]
+    } else if name == "borderangle" {
+        &["once", "loop"]
+    } else {
+        &[]
+    }
+}
+
+// Offset of a curve point from the editor's top-left corner
+fn editor_offset(x: f64, y: f64) -> (f32, f32) {
+    (x as f32 * EDITOR_UNIT, (Y_MAX - y) as f32 * EDITOR_UNIT)
+}
+
+fn to_editor(x: f64, y: f64, bounds: Bounds<Pixels>) -> Point<Pixels> {
+    let (left, top) = editor_offset(x, y);
+    point(bounds.origin.x + px(left), bounds.origin.y + px(top))
+}
+
+// Map a mouse position back to curve coordinates, rounded to two decimals
+fn from_editor(position: Point<Pixels>, bounds: Bounds<Pixels>) -> (f64, f64) {
+    let left = f32::from(position.x - bounds.origin.x) as f64;
+    let top = f32::from(position.y - bounds.origin.y) as f64;
+    let unit = EDITOR_UNIT as f64;
+
+    let x = (left / unit).clamp(0.0, 1.0);
+    let y = (Y_MAX - top / unit).clamp(Y_MIN, Y_MAX);
+    ((x * 100.0).round() / 100.0, (y * 100.0).round() / 100.0)
+}
+
+fn paint_curve(
+    bounds: Bounds<Pixels>,
+    [x1, y1, x2, y2]: [f64; 4],
+    curve_color: Hsla,
+    guide_color: Hsla,
+    handle_color: Hsla,
+    window: &mut Window,
+) {
+    let start = to_editor(0.0, 0.0, bounds);
+    let end = to_editor(1.0, 1.0, bounds);
+    let first = to_editor(x1, y1, bounds);
+    let second = to_editor(x2, y2, bounds);
+
+    // The unit square the curve travels through
+    let mut square = PathBuilder::stroke(px(1.));
+    square.move_to(start);
+    square.line_to(to_editor(1.0, 0.0, bounds));
+    square.line_to(end);
+    square.line_to(to_editor(0.0, 1.0, bounds));
+    square.line_to(start);
+    if let Ok(path) = square.build() {
+        window.paint_path(path, guide_color);
+    }
+
+    let mut handles = PathBuilder::stroke(px(1.));
+    handles.move_to(start);
+    handles.line_to(first);
+    handles.move_to(end);
+    handles.line_to(second);
+    if let Ok(path) = handles.build() {
+        window.paint_path(path, handle_color);
+    }
+
+    let mut curve = PathBuilder::stroke(px(2.));
+    curve.move_to(start);
+    curve.cubic_bezier_to(end, first, second);
+    if let Ok(path) = curve.build() {
+        window.paint_path(path, curve_color);
+    }
+}
+
+impl Render for AnimationsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let animations = self.animations();
+        let panel = cx.entity();
+
+        let curves: Vec<String> = std::iter::once("default".to_string())
+            .chain(animations.beziers.iter().map(|b| b.name.clone()))
+            .collect();
+
+        let rows: Vec<AnyElement> = animations
+            .animations
+            .iter()
+            .enumerate()
+            .map(|(index, animation)| self.render_animation_row(index, animation, &curves, cx))
+            .collect();
+
+        let missing: Vec<&'static str> = ANIMATION_NAMES
+            .iter()
+            .copied()
+            .filter(|name| !animations.animations.iter().any(|a| a.name == *name))
+            .collect();
+
+        v_flex()
+            .w_full()
+            .gap_4()
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| div().text_sm().text_color(theme.danger).child(msg.clone())),
+            )
+            .child(self.render_curves(&animations, cx))
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(div().flex_1().text_sm().font_semibold().child("Animations"))
+                            .child(
+                                Button::new("add-animation")
+                                    .label("Add Animation")
+                                    .small()
+                                    .primary()
+                                    .dropdown_menu(move |menu, _window, _cx| {
+                                        let mut menu = menu.scrollable(true).max_h(px(320.));
+                                        for name in missing.iter().copied() {
+                                            let panel = panel.clone();
+                                            menu = menu.item(PopupMenuItem::new(name).on_click(
+                                                move |_event, _window, cx| {
+                                                    panel.update(cx, |this, cx| {
+                                                        this.update(cx, |a| {
+                                                            a.animations.push(Animation {
+                                                                name: name.to_string(),
+                                                                enabled: true,
+                                                                speed: 5.0,
+                                                                curve: "default".to_string(),
+                                                                style: None,
+                                                            })
+                                                        });
+                                                    });
+                                                },
+                                            ));
+                                        }
+                                        menu
+                                    }),
+                            ),
+                    )
+                    .when(rows.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(theme.muted_foreground)
+                                .child("No animations are overridden. Hyprland's defaults apply."),
+                        )
+                    })
+                    .children(rows),
+            )
+    }
+}
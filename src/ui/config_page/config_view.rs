@@ -1,37 +1,43 @@
 use gpui::*;
 use gpui_component::{
-    IndexPath, Sizable as _, Size,
-    select::{SearchableVec, Select, SelectEvent, SelectItem, SelectState},
+    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable as _, Size,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    select::{Select, SelectEvent, SelectState},
     setting::{NumberFieldOptions, SettingField, SettingGroup, SettingItem, SettingPage, Settings},
 };
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::system::hyprland_config::HyprlandConfigManager;
+use crate::system::hyprland_config::validation::{reload_hyprland, supersede_writes};
+use crate::system::hyprland_config::{HyprlandConfigManager, HyprlandProfiles};
 use crate::types::hyprland_config::*;
-
-#[derive(Clone, Debug)]
-struct KeyboardLayoutItem {
-    value: SharedString,
-    // The human-readable label
-    label: SharedString,
-}
-
-impl SelectItem for KeyboardLayoutItem {
-    type Value = SharedString;
-
-    fn title(&self) -> SharedString {
-        self.label.clone()
-    }
-
-    fn value(&self) -> &SharedString {
-        &self.value
-    }
-}
+use crate::ui::config_page::animations_panel::AnimationsPanel;
+use crate::ui::config_page::devices_panel::DevicesPanel;
+use crate::ui::config_page::keybindings_panel::KeybindingsPanel;
+use crate::ui::config_page::keyboard_layout_panel::KeyboardLayoutPanel;
+use crate::ui::config_page::monitors_panel::MonitorsPanel;
+use crate::ui::config_page::rules_panel::RulesPanel;
+use crate::ui::config_page::saving::{reset_config_later, save_config_later};
+use crate::ui::dialogs::hyprland_profile_dialogs::{
+    OnProfileChanged, ProfileNameAction, open_delete_hyprland_profile_dialog,
+    open_hyprland_profile_name_dialog,
+};
 
 pub struct ConfigView {
     config_manager: Rc<RefCell<HyprlandConfigManager>>,
-    keyboard_layout_select: Entity<SelectState<SearchableVec<KeyboardLayoutItem>>>,
+    keyboard_layout_panel: Entity<KeyboardLayoutPanel>,
+    animations_panel: Entity<AnimationsPanel>,
+    devices_panel: Entity<DevicesPanel>,
+    keybindings_panel: Entity<KeybindingsPanel>,
+    monitors_panel: Entity<MonitorsPanel>,
+    rules_panel: Entity<RulesPanel>,
+    profile_select: Entity<SelectState<Vec<SharedString>>>,
+    profile_names: Vec<String>,
+    // Why the last save was rolled back, as reported by Hyprland
+    error_message: Option<String>,
+    // The pending save; replacing it drops that save for the newer one
+    save_task: Option<Task<()>>,
     pub focus_handle: FocusHandle,
 }
 
@@ -45,55 +51,56 @@ impl ConfigView {
             }
         };
 
-        // Build keyboard layout items
-        let catalog = crate::system::hyprland_config::keyboard::load_keyboard_catalog();
-        let mut layout_items: Vec<KeyboardLayoutItem> = match catalog {
-            Ok(c) => c
-                .layouts
-                .into_iter()
-                .map(|l| KeyboardLayoutItem {
-                    value: l.name.clone().into(),
-                    label: l.description.clone().into(),
-                })
-                .collect(),
+        let config_manager_rc = Rc::new(RefCell::new(config_manager));
+
+        let (profile_names, current_profile) = match HyprlandProfiles::open() {
+            Ok(profiles) => (profiles.list(), profiles.current()),
             Err(e) => {
-                eprintln!("Failed to load keyboard catalog: {}", e);
-                vec![KeyboardLayoutItem {
-                    value: "us".into(),
-                    label: "English (US)".into(),
-                }]
+                eprintln!("Failed to load Hyprland profiles: {}", e);
+                (Vec::new(), None)
             }
         };
-        layout_items.sort_by(|a, b| a.label.cmp(&b.label));
-
-        // Find the index of the currently active layout
-        let current_kb = config_manager.get().input.kb_layout.clone();
-        let initial_index = layout_items
-            .iter()
-            .position(|item| item.value.as_ref() == current_kb.as_str())
-            .map(|i| IndexPath::default().row(i));
-
-        let delegate = SearchableVec::new(layout_items);
-        let keyboard_layout_select =
-            cx.new(|cx| SelectState::new(delegate, initial_index, window, cx).searchable(true));
-
-        let config_manager_rc = Rc::new(RefCell::new(config_manager));
+        let profile_items: Vec<SharedString> =
+            profile_names.iter().map(|n| n.clone().into()).collect();
+        let initial_profile = current_profile
+            .and_then(|current| profile_names.iter().position(|n| *n == current))
+            .map(IndexPath::new);
+        let profile_select =
+            cx.new(|cx| SelectState::new(profile_items, initial_profile, window, cx));
 
         cx.subscribe_in(
-            &keyboard_layout_select,
+            &profile_select,
             window,
-            |this, _select, event: &SelectEvent<SearchableVec<KeyboardLayoutItem>>, _window, cx| {
+            |this, _select, event: &SelectEvent<Vec<SharedString>>, window, cx| {
                 if let SelectEvent::Confirm(Some(value)) = event {
-                    let layout = value.to_string();
-                    this.update_config(cx, |c| c.input.kb_layout = layout);
+                    let profile = value.to_string();
+                    this.switch_profile(&profile, window, cx);
                 }
             },
         )
         .detach();
 
+        let animations_panel =
+            cx.new(|cx| AnimationsPanel::new(config_manager_rc.clone(), window, cx));
+        let devices_panel = cx.new(|cx| DevicesPanel::new(config_manager_rc.clone(), window, cx));
+        let keyboard_layout_panel =
+            cx.new(|cx| KeyboardLayoutPanel::new(config_manager_rc.clone(), window, cx));
+        let keybindings_panel = cx.new(|cx| KeybindingsPanel::new(window, cx));
+        let monitors_panel = cx.new(|cx| MonitorsPanel::new(window, cx));
+        let rules_panel = cx.new(|cx| RulesPanel::new(window, cx));
+
         Self {
             config_manager: config_manager_rc,
-            keyboard_layout_select,
+            keyboard_layout_panel,
+            animations_panel,
+            devices_panel,
+            keybindings_panel,
+            monitors_panel,
+            rules_panel,
+            profile_select,
+            profile_names,
+            error_message: None,
+            save_task: None,
             focus_handle: cx.focus_handle(),
         }
     }
@@ -103,9 +110,245 @@ impl ConfigView {
         F: FnOnce(&mut HyprlandConfig),
     {
         self.config_manager.borrow_mut().update(f);
-        let _ = self.config_manager.borrow().save();
+        self.save_task = Some(save_config_later(
+            &self.config_manager,
+            cx,
+            |this, result, _cx| this.error_message = result.err(),
+        ));
         cx.notify();
     }
+
+    fn reset_option(&mut self, id: &str, cx: &mut Context<Self>) {
+        // Takes over from the pending save, its changes are written along
+        // with the reset
+        self.save_task = Some(reset_config_later(
+            &self.config_manager,
+            &[id],
+            cx,
+            |this, result, _cx| this.error_message = result.err(),
+        ));
+        cx.notify();
+    }
+
+    // Put a profile in place and reload everything on the page from it
+    fn switch_profile(&mut self, profile: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel_saves(cx);
+
+        let profile = profile.to_string();
+        cx.spawn_in(window, async move |this, cx| {
+            // Saves already on their way would write the old profile's
+            // settings over the new one
+            let result = smol::unblock(move || {
+                supersede_writes(|| {
+                    let applied =
+                        HyprlandProfiles::open().and_then(|profiles| profiles.apply(&profile));
+                    if applied.is_ok() {
+                        reload_hyprland();
+                    }
+                    applied
+                })
+            })
+            .await;
+
+            let _ = this.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok(()) => {
+                        this.error_message = None;
+                        this.reload_config(window, cx);
+                    }
+                    Err(e) => this.error_message = Some(e),
+                }
+
+                this.refresh_profiles(window, cx);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn cancel_saves(&mut self, cx: &mut Context<Self>) {
+        self.save_task = None;
+        self.keyboard_layout_panel
+            .update(cx, |panel, _| panel.cancel_save());
+        self.animations_panel
+            .update(cx, |panel, _| panel.cancel_save());
+        self.devices_panel
+            .update(cx, |panel, _| panel.cancel_save());
+        self.keybindings_panel
+            .update(cx, |panel, _| panel.cancel_save());
+        self.monitors_panel
+            .update(cx, |panel, _| panel.cancel_save());
+        self.rules_panel.update(cx, |panel, _| panel.cancel_save());
+    }
+
+    fn reload_config(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match HyprlandConfigManager::load() {
+            Ok(manager) => *self.config_manager.borrow_mut() = manager,
+            Err(e) => self.error_message = Some(e),
+        }
+
+        let config_manager = self.config_manager.clone();
+        self.animations_panel =
+            cx.new(|cx| AnimationsPanel::new(config_manager.clone(), window, cx));
+        self.devices_panel = cx.new(|cx| DevicesPanel::new(config_manager.clone(), window, cx));
+        self.keyboard_layout_panel =
+            cx.new(|cx| KeyboardLayoutPanel::new(config_manager, window, cx));
+        self.keybindings_panel = cx.new(|cx| KeybindingsPanel::new(window, cx));
+        self.monitors_panel = cx.new(|cx| MonitorsPanel::new(window, cx));
+        self.rules_panel = cx.new(|cx| RulesPanel::new(window, cx));
+    }
+
+    fn refresh_profiles(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Ok(profiles) = HyprlandProfiles::open() else {
+            return;
+        };
+        self.profile_names = profiles.list();
+
+        let items: Vec<SharedString> = self
+            .profile_names
+            .iter()
+            .map(|n| n.clone().into())
+            .collect();
+        let current: Option<SharedString> = profiles.current().map(Into::into);
+        self.profile_select.update(cx, |select, cx| {
+            select.set_items(items, window, cx);
+            if let Some(current) = current {
+                select.set_selected_value(&current, window, cx);
+            }
+        });
+    }
+
+    fn current_profile(&self, cx: &App) -> String {
+        self.profile_select
+            .read(cx)
+            .selected_value()
+            .map(|s| s.to_string())
+            .unwrap_or_default()
+    }
+
+    fn render_profile_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let current = self.current_profile(cx);
+        let has_profile = !current.is_empty();
+        let can_delete = has_profile && self.profile_names.len() > 1;
+
+        let view = cx.entity().downgrade();
+        let on_changed: OnProfileChanged = Rc::new(move |profile, window, cx| {
+            let _ = view.update(cx, |this, cx| this.switch_profile(&profile, window, cx));
+        });
+
+        let name_button = |id: &'static str, icon: IconName, tooltip: &'static str, action| {
+            let profile = current.clone();
+            let on_changed = on_changed.clone();
+            Button::new(id)
+                .icon(Icon::new(icon))
+                .ghost()
+                .small()
+                .tooltip(tooltip)
+                .disabled(action != ProfileNameAction::Create && !has_profile)
+                .on_click(move |_, window, cx| {
+                    open_hyprland_profile_name_dialog(
+                        action,
+                        profile.clone(),
+                        on_changed.clone(),
+                        window,
+                        cx,
+                    );
+                })
+        };
+
+        let delete_profile = current.clone();
+        let delete_on_changed = on_changed.clone();
+
+        h_flex()
+            .w_full()
+            .p_4()
+            .gap_2()
+            .items_center()
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .w(px(200.))
+                    .child(Select::new(&self.profile_select).small()),
+            )
+            .child(name_button(
+                "add-hyprland-profile",
+                IconName::Plus,
+                "Add Profile",
+                ProfileNameAction::Create,
+            ))
+            .child(name_button(
+                "rename-hyprland-profile",
+                IconName::Replace,
+                "Rename Profile",
+                ProfileNameAction::Rename,
+            ))
+            .child(name_button(
+                "duplicate-hyprland-profile",
+                IconName::Copy,
+                "Duplicate Profile",
+                ProfileNameAction::Duplicate,
+            ))
+            .child(
+                Button::new("delete-hyprland-profile")
+                    .icon(Icon::new(IconName::Delete))
+                    .ghost()
+                    .small()
+                    .tooltip("Delete Profile")
+                    .disabled(!can_delete)
+                    .on_click(move |_, window, cx| {
+                        if can_delete {
+                            open_delete_hyprland_profile_dialog(
+                                delete_profile.clone(),
+                                delete_on_changed.clone(),
+                                window,
+                                cx,
+                            );
+                        }
+                    }),
+            )
+    }
+
+    // An option set in Omarchist's file gets a row below it saying so, with a
+    // button to hand it back to Omarchy
+    fn overridable(
+        &self,
+        view: &Entity<Self>,
+        id: &'static str,
+        item: SettingItem,
+    ) -> Vec<SettingItem> {
+        if !self.config_manager.borrow().is_overridden(id) {
+            return vec![item];
+        }
+
+        let view = view.clone();
+        let indicator = SettingItem::render(move |_opts, _window, cx| {
+            let view = view.clone();
+            h_flex()
+                .w_full()
+                .justify_end()
+                .items_center()
+                .gap_2()
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Overridden by Omarchist"),
+                )
+                .child(
+                    Button::new(SharedString::from(format!("reset-{}", id)))
+                        .label("Reset to Omarchy value")
+                        .xsmall()
+                        .ghost()
+                        .on_click(move |_, _window, cx| {
+                            view.update(cx, |this, cx| this.reset_option(id, cx));
+                        }),
+                )
+        });
+
+        vec![item, indicator]
+    }
 }
 
 impl Render for ConfigView {
@@ -116,17 +359,34 @@ impl Render for ConfigView {
             .id("config-view-root")
             .track_focus(&self.focus_handle)
             .size_full()
+            .flex()
+            .flex_col()
+            .child(self.render_profile_header(cx))
+            .children(self.error_message.as_ref().map(|msg| {
+                div()
+                    .px_4()
+                    .py_2()
+                    .text_sm()
+                    .text_color(cx.theme().danger)
+                    .child(msg.clone())
+            }))
             .child(
-                Settings::new("hyprland-config")
-                    .sidebar_width(px(220.0))
-                    .with_group_variant(gpui_component::group_box::GroupBoxVariant::Normal)
-                    .with_size(Size::default())
-                    .pages(vec![
-                        self.create_general_page(&view),
-                        self.create_appearance_page(&view),
-                        self.create_input_page(&view),
-                        self.create_misc_page(&view),
-                    ]),
+                div().flex_1().min_h_0().child(
+                    Settings::new("hyprland-config")
+                        .sidebar_width(px(220.0))
+                        .with_group_variant(gpui_component::group_box::GroupBoxVariant::Normal)
+                        .with_size(Size::default())
+                        .pages(vec![
+                            self.create_general_page(&view),
+                            self.create_appearance_page(&view),
+                            self.create_animations_page(&view),
+                            self.create_input_page(&view),
+                            self.create_misc_page(&view),
+                            self.create_keybindings_page(),
+                            self.create_monitors_page(),
+                            self.create_rules_page(),
+                        ]),
+                ),
             )
     }
 }
@@ -140,143 +400,169 @@ impl ConfigView {
             .group(
                 SettingGroup::new()
                     .title("Window Borders")
-                    .item(
-                        SettingItem::new("Border Size", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 0.0,
-                                    max: 10.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().general.border_size as f64,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.general.border_size = value as i32
+                    .items(
+                        self.overridable(
+                            view,
+                            "general:border_size",
+                            SettingItem::new("Border Size", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 0.0,
+                                        max: 10.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().general.border_size as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.general.border_size = value as i32
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(2.0)
-                        })
-                        .description("Size of the border around windows"),
+                                    },
+                                )
+                                .default_value(2.0)
+                            })
+                            .description("Size of the border around windows"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Resize on Border", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::switch(
-                                move |_cx| cm.borrow().get().general.resize_on_border,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.general.resize_on_border = value
+                    .items(
+                        self.overridable(
+                            view,
+                            "general:resize_on_border",
+                            SettingItem::new("Resize on Border", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| cm.borrow().get().general.resize_on_border,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.general.resize_on_border = value
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(false)
-                        })
-                        .description("Enable resizing windows by clicking and dragging on borders"),
+                                    },
+                                )
+                                .default_value(false)
+                            })
+                            .description(
+                                "Enable resizing windows by clicking and dragging on borders",
+                            ),
+                        ),
                     ),
             )
             .group(
                 SettingGroup::new()
                     .title("Gaps")
-                    .item(
-                        SettingItem::new("Gaps In", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 0.0,
-                                    max: 100.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().general.gaps_in as f64,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.general.gaps_in = value as i32
+                    .items(
+                        self.overridable(
+                            view,
+                            "general:gaps_in",
+                            SettingItem::new("Gaps In", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 0.0,
+                                        max: 100.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().general.gaps_in as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.general.gaps_in = value as i32
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(5.0)
-                        })
-                        .description("Gaps between windows"),
+                                    },
+                                )
+                                .default_value(5.0)
+                            })
+                            .description("Gaps between windows"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Gaps Out", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 0.0,
-                                    max: 100.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().general.gaps_out as f64,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.general.gaps_out = value as i32
+                    .items(
+                        self.overridable(
+                            view,
+                            "general:gaps_out",
+                            SettingItem::new("Gaps Out", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 0.0,
+                                        max: 100.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().general.gaps_out as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.general.gaps_out = value as i32
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(10.0)
-                        })
-                        .description("Gaps between windows and monitor edges"),
+                                    },
+                                )
+                                .default_value(10.0)
+                            })
+                            .description("Gaps between windows and monitor edges"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Gaps Workspaces", {
+                    .items(
+                        self.overridable(
+                            view,
+                            "general:gaps_workspaces",
+                            SettingItem::new("Gaps Workspaces", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 0.0,
+                                        max: 100.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().general.gaps_workspaces as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.general.gaps_workspaces = value as i32
+                                            });
+                                        });
+                                    },
+                                )
+                                .default_value(0.0)
+                            })
+                            .description("Gaps between workspaces. Stacks with gaps out"),
+                        ),
+                    ),
+            )
+            .group(
+                SettingGroup::new().title("Layout").items(
+                    self.overridable(
+                        view,
+                        "general:layout",
+                        SettingItem::new("Layout", {
                             let cm = cm.clone();
                             let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 0.0,
-                                    max: 100.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().general.gaps_workspaces as f64,
+                            SettingField::dropdown(
+                                vec![
+                                    ("dwindle".into(), "Dwindle".into()),
+                                    ("master".into(), "Master".into()),
+                                ],
+                                move |_cx| cm.borrow().get().general.layout.clone().into(),
                                 move |value, cx| {
                                     view.update(cx, |this, cx| {
                                         this.update_config(cx, |c| {
-                                            c.general.gaps_workspaces = value as i32
+                                            c.general.layout = value.to_string()
                                         });
                                     });
                                 },
                             )
-                            .default_value(0.0)
+                            .default_value("dwindle")
                         })
-                        .description("Gaps between workspaces. Stacks with gaps out"),
+                        .description("Window layout algorithm"),
                     ),
-            )
-            .group(
-                SettingGroup::new().title("Layout").item(
-                    SettingItem::new("Layout", {
-                        let cm = cm.clone();
-                        let view = view.clone();
-                        SettingField::dropdown(
-                            vec![
-                                ("dwindle".into(), "Dwindle".into()),
-                                ("master".into(), "Master".into()),
-                            ],
-                            move |_cx| cm.borrow().get().general.layout.clone().into(),
-                            move |value, cx| {
-                                view.update(cx, |this, cx| {
-                                    this.update_config(cx, |c| {
-                                        c.general.layout = value.to_string()
-                                    });
-                                });
-                            },
-                        )
-                        .default_value("dwindle")
-                    })
-                    .description("Window layout algorithm"),
                 ),
             )
     }
@@ -287,147 +573,222 @@ impl ConfigView {
         SettingPage::new("Appearance")
             .description("Visual appearance and effects")
             .group(
-                SettingGroup::new().title("Rounding").item(
-                    SettingItem::new("Rounding", {
-                        let cm = cm.clone();
-                        let view = view.clone();
-                        SettingField::number_input(
-                            NumberFieldOptions {
-                                min: 0.0,
-                                max: 50.0,
-                                step: 1.0,
-                            },
-                            move |_cx| cm.borrow().get().decoration.rounding as f64,
-                            move |value, cx| {
-                                view.update(cx, |this, cx| {
-                                    this.update_config(cx, |c| {
-                                        c.decoration.rounding = value as i32
-                                    });
-                                });
-                            },
-                        )
-                        .default_value(0.0)
-                    })
-                    .description("Rounded corners radius in pixels"),
-                ),
-            )
-            .group(
-                SettingGroup::new()
-                    .title("Opacity")
-                    .item(
-                        SettingItem::new("Active Opacity", {
+                SettingGroup::new().title("Rounding").items(
+                    self.overridable(
+                        view,
+                        "decoration:rounding",
+                        SettingItem::new("Rounding", {
                             let cm = cm.clone();
                             let view = view.clone();
                             SettingField::number_input(
                                 NumberFieldOptions {
                                     min: 0.0,
-                                    max: 1.0,
-                                    step: 0.1,
+                                    max: 50.0,
+                                    step: 1.0,
                                 },
-                                move |_cx| cm.borrow().get().decoration.active_opacity,
+                                move |_cx| cm.borrow().get().decoration.rounding as f64,
                                 move |value, cx| {
                                     view.update(cx, |this, cx| {
                                         this.update_config(cx, |c| {
-                                            c.decoration.active_opacity = value
+                                            c.decoration.rounding = value as i32
                                         });
                                     });
                                 },
                             )
-                            .default_value(1.0)
+                            .default_value(0.0)
                         })
-                        .description("Opacity of active windows"),
+                        .description("Rounded corners radius in pixels"),
+                    ),
+                ),
+            )
+            .group(
+                SettingGroup::new()
+                    .title("Opacity")
+                    .items(
+                        self.overridable(
+                            view,
+                            "decoration:active_opacity",
+                            SettingItem::new("Active Opacity", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 0.0,
+                                        max: 1.0,
+                                        step: 0.1,
+                                    },
+                                    move |_cx| cm.borrow().get().decoration.active_opacity,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.decoration.active_opacity = value
+                                            });
+                                        });
+                                    },
+                                )
+                                .default_value(1.0)
+                            })
+                            .description("Opacity of active windows"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Inactive Opacity", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 0.0,
-                                    max: 1.0,
-                                    step: 0.1,
-                                },
-                                move |_cx| cm.borrow().get().decoration.inactive_opacity,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.decoration.inactive_opacity = value
+                    .items(
+                        self.overridable(
+                            view,
+                            "decoration:inactive_opacity",
+                            SettingItem::new("Inactive Opacity", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 0.0,
+                                        max: 1.0,
+                                        step: 0.1,
+                                    },
+                                    move |_cx| cm.borrow().get().decoration.inactive_opacity,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.decoration.inactive_opacity = value
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(1.0)
-                        })
-                        .description("Opacity of inactive windows"),
+                                    },
+                                )
+                                .default_value(1.0)
+                            })
+                            .description("Opacity of inactive windows"),
+                        ),
                     ),
             )
             .group(
                 SettingGroup::new()
                     .title("Blur")
-                    .item(
-                        SettingItem::new("Enable Blur", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::switch(
-                                move |_cx| cm.borrow().get().decoration.blur.enabled,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.decoration.blur.enabled = value
+                    .items(
+                        self.overridable(
+                            view,
+                            "decoration:blur:enabled",
+                            SettingItem::new("Enable Blur", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| cm.borrow().get().decoration.blur.enabled,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.decoration.blur.enabled = value
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(true)
-                        })
-                        .description("Enable window background blur"),
+                                    },
+                                )
+                                .default_value(true)
+                            })
+                            .description("Enable window background blur"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Blur Size", {
+                    .items(
+                        self.overridable(
+                            view,
+                            "decoration:blur:size",
+                            SettingItem::new("Blur Size", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 1.0,
+                                        max: 20.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().decoration.blur.size as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.decoration.blur.size = value as i32
+                                            });
+                                        });
+                                    },
+                                )
+                                .default_value(8.0)
+                            })
+                            .description("Blur size/distance"),
+                        ),
+                    )
+                    .items(
+                        self.overridable(
+                            view,
+                            "decoration:blur:passes",
+                            SettingItem::new("Blur Passes", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 1.0,
+                                        max: 5.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().decoration.blur.passes as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.decoration.blur.passes = value as i32
+                                            });
+                                        });
+                                    },
+                                )
+                                .default_value(1.0)
+                            })
+                            .description("Number of blur passes"),
+                        ),
+                    ),
+            )
+    }
+
+    fn create_animations_page(&self, view: &Entity<Self>) -> SettingPage {
+        let cm = self.config_manager.clone();
+        let panel = self.animations_panel.clone();
+
+        SettingPage::new("Animations")
+            .description("Animation curves and timings")
+            .group(
+                SettingGroup::new()
+                    .title("General")
+                    .items(self.overridable(view, "animations:enabled", SettingItem::new("Enable Animations", {
                             let cm = cm.clone();
                             let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 1.0,
-                                    max: 20.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().decoration.blur.size as f64,
+                            SettingField::switch(
+                                move |_cx| cm.borrow().get().animations.enabled,
                                 move |value, cx| {
                                     view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.decoration.blur.size = value as i32
-                                        });
+                                        this.update_config(cx, |c| c.animations.enabled = value);
                                     });
                                 },
                             )
-                            .default_value(8.0)
+                            .default_value(true)
                         })
-                        .description("Blur size/distance"),
-                    )
-                    .item(
-                        SettingItem::new("Blur Passes", {
+                        .description("Turn all animations on or off")))
+                    .items(self.overridable(view, "animations:workspace_wraparound", SettingItem::new("Workspace Wraparound", {
                             let cm = cm.clone();
                             let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 1.0,
-                                    max: 5.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().decoration.blur.passes as f64,
+                            SettingField::switch(
+                                move |_cx| cm.borrow().get().animations.workspace_wraparound,
                                 move |value, cx| {
                                     view.update(cx, |this, cx| {
                                         this.update_config(cx, |c| {
-                                            c.decoration.blur.passes = value as i32
+                                            c.animations.workspace_wraparound = value
                                         });
                                     });
                                 },
                             )
-                            .default_value(1.0)
+                            .default_value(false)
                         })
-                        .description("Number of blur passes"),
-                    ),
+                        .description(
+                            "Animate between the first and last workspace as if they were adjacent",
+                        ))),
+            )
+            .group(
+                SettingGroup::new()
+                    .title("Curves & Animations")
+                    .item(SettingItem::render(move |_opts, _window, _cx| {
+                        panel.clone()
+                    })),
             )
     }
 
@@ -439,203 +800,273 @@ impl ConfigView {
             .group(
                 SettingGroup::new()
                     .title("Keyboard")
-                    .item(
-                        SettingItem::new("Keyboard Layout", {
-                            let select_entity = self.keyboard_layout_select.clone();
-                            SettingField::render(move |_opts, _window, _cx| {
-                                Select::new(&select_entity)
-                                    .search_placeholder("Search layouts...")
-                                    .small()
-                            })
-                        })
-                        .description("Keyboard layout (e.g., us, de, fr)"),
-                    )
-                    .item(
-                        SettingItem::new("Repeat Rate", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 1.0,
-                                    max: 100.0,
-                                    step: 1.0,
-                                },
-                                move |_cx| cm.borrow().get().input.repeat_rate as f64,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.input.repeat_rate = value as i32
+                    .item({
+                        let panel = self.keyboard_layout_panel.clone();
+                        SettingItem::render(move |_opts, _window, _cx| panel.clone())
+                    })
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:repeat_rate",
+                            SettingItem::new("Repeat Rate", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 1.0,
+                                        max: 100.0,
+                                        step: 1.0,
+                                    },
+                                    move |_cx| cm.borrow().get().input.repeat_rate as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.input.repeat_rate = value as i32
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(25.0)
-                        })
-                        .description("Repeat rate for held-down keys (repeats per second)"),
+                                    },
+                                )
+                                .default_value(25.0)
+                            })
+                            .description("Repeat rate for held-down keys (repeats per second)"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Repeat Delay", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: 100.0,
-                                    max: 2000.0,
-                                    step: 50.0,
-                                },
-                                move |_cx| cm.borrow().get().input.repeat_delay as f64,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.input.repeat_delay = value as i32
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:repeat_delay",
+                            SettingItem::new("Repeat Delay", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: 100.0,
+                                        max: 2000.0,
+                                        step: 50.0,
+                                    },
+                                    move |_cx| cm.borrow().get().input.repeat_delay as f64,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.input.repeat_delay = value as i32
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(600.0)
-                        })
-                        .description("Delay before key repeat starts (milliseconds)"),
+                                    },
+                                )
+                                .default_value(600.0)
+                            })
+                            .description("Delay before key repeat starts (milliseconds)"),
+                        ),
                     ),
             )
             .group(
                 SettingGroup::new()
                     .title("Mouse")
-                    .item(
-                        SettingItem::new("Sensitivity", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::number_input(
-                                NumberFieldOptions {
-                                    min: -1.0,
-                                    max: 1.0,
-                                    step: 0.1,
-                                },
-                                move |_cx| cm.borrow().get().input.sensitivity,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| c.input.sensitivity = value);
-                                    });
-                                },
-                            )
-                            .default_value(0.0)
-                        })
-                        .description("Mouse sensitivity (-1.0 to 1.0)"),
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:sensitivity",
+                            SettingItem::new("Sensitivity", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::number_input(
+                                    NumberFieldOptions {
+                                        min: -1.0,
+                                        max: 1.0,
+                                        step: 0.1,
+                                    },
+                                    move |_cx| cm.borrow().get().input.sensitivity,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| c.input.sensitivity = value);
+                                        });
+                                    },
+                                )
+                                .default_value(0.0)
+                            })
+                            .description("Mouse sensitivity (-1.0 to 1.0)"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Natural Scroll", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::switch(
-                                move |_cx| cm.borrow().get().input.natural_scroll,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| c.input.natural_scroll = value);
-                                    });
-                                },
-                            )
-                            .default_value(false)
-                        })
-                        .description("Invert scrolling direction"),
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:natural_scroll",
+                            SettingItem::new("Natural Scroll", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| cm.borrow().get().input.natural_scroll,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.input.natural_scroll = value
+                                            });
+                                        });
+                                    },
+                                )
+                                .default_value(false)
+                            })
+                            .description("Invert scrolling direction"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Left Handed", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::switch(
-                                move |_cx| cm.borrow().get().input.left_handed,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| c.input.left_handed = value);
-                                    });
-                                },
-                            )
-                            .default_value(false)
-                        })
-                        .description("Switch left and right mouse buttons"),
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:left_handed",
+                            SettingItem::new("Left Handed", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| cm.borrow().get().input.left_handed,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| c.input.left_handed = value);
+                                        });
+                                    },
+                                )
+                                .default_value(false)
+                            })
+                            .description("Switch left and right mouse buttons"),
+                        ),
                     ),
             )
             .group(
                 SettingGroup::new()
                     .title("Touchpad")
-                    .item(
-                        SettingItem::new("Disable While Typing", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::switch(
-                                move |_cx| cm.borrow().get().input.touchpad.disable_while_typing,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.input.touchpad.disable_while_typing = value
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:touchpad:disable_while_typing",
+                            SettingItem::new("Disable While Typing", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| {
+                                        cm.borrow().get().input.touchpad.disable_while_typing
+                                    },
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.input.touchpad.disable_while_typing = value
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(true)
-                        })
-                        .description("Disable touchpad while typing"),
+                                    },
+                                )
+                                .default_value(true)
+                            })
+                            .description("Disable touchpad while typing"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Tap to Click", {
-                            let cm = cm.clone();
-                            let view = view.clone();
-                            SettingField::switch(
-                                move |_cx| cm.borrow().get().input.touchpad.tap_to_click,
-                                move |value, cx| {
-                                    view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.input.touchpad.tap_to_click = value
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:touchpad:tap_to_click",
+                            SettingItem::new("Tap to Click", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| cm.borrow().get().input.touchpad.tap_to_click,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.input.touchpad.tap_to_click = value
+                                            });
                                         });
-                                    });
-                                },
-                            )
-                            .default_value(true)
-                        })
-                        .description("Tap on touchpad to click"),
+                                    },
+                                )
+                                .default_value(true)
+                            })
+                            .description("Tap on touchpad to click"),
+                        ),
                     )
-                    .item(
-                        SettingItem::new("Natural Scroll", {
+                    .items(
+                        self.overridable(
+                            view,
+                            "input:touchpad:natural_scroll",
+                            SettingItem::new("Natural Scroll", {
+                                let cm = cm.clone();
+                                let view = view.clone();
+                                SettingField::switch(
+                                    move |_cx| cm.borrow().get().input.touchpad.natural_scroll,
+                                    move |value, cx| {
+                                        view.update(cx, |this, cx| {
+                                            this.update_config(cx, |c| {
+                                                c.input.touchpad.natural_scroll = value
+                                            });
+                                        });
+                                    },
+                                )
+                                .default_value(false)
+                            })
+                            .description("Invert touchpad scrolling direction"),
+                        ),
+                    ),
+            )
+            .group(SettingGroup::new().title("Devices").item({
+                let panel = self.devices_panel.clone();
+                SettingItem::render(move |_opts, _window, _cx| panel.clone())
+            }))
+    }
+
+    fn create_misc_page(&self, view: &Entity<Self>) -> SettingPage {
+        let cm = self.config_manager.clone();
+
+        SettingPage::new("Miscellaneous")
+            .description("Miscellaneous settings")
+            .group(
+                SettingGroup::new().title("General").items(
+                    self.overridable(
+                        view,
+                        "misc:vfr",
+                        SettingItem::new("VFR", {
                             let cm = cm.clone();
                             let view = view.clone();
                             SettingField::switch(
-                                move |_cx| cm.borrow().get().input.touchpad.natural_scroll,
+                                move |_cx| cm.borrow().get().misc.vfr,
                                 move |value, cx| {
                                     view.update(cx, |this, cx| {
-                                        this.update_config(cx, |c| {
-                                            c.input.touchpad.natural_scroll = value
-                                        });
+                                        this.update_config(cx, |c| c.misc.vfr = value);
                                     });
                                 },
                             )
-                            .default_value(false)
+                            .default_value(true)
                         })
-                        .description("Invert touchpad scrolling direction"),
+                        .description("Variable refresh rate (saves battery)"),
                     ),
+                ),
             )
     }
 
-    fn create_misc_page(&self, view: &Entity<Self>) -> SettingPage {
-        let cm = self.config_manager.clone();
+    fn create_keybindings_page(&self) -> SettingPage {
+        let panel = self.keybindings_panel.clone();
 
-        SettingPage::new("Miscellaneous")
-            .description("Miscellaneous settings")
+        SettingPage::new("Keybindings")
+            .description("Omarchy and user keybinds. Changes are written as overrides to ~/.config/omarchist/hyprland/keybinds.conf")
             .group(
-                SettingGroup::new().title("General").item(
-                    SettingItem::new("VFR", {
-                        let cm = cm.clone();
-                        let view = view.clone();
-                        SettingField::switch(
-                            move |_cx| cm.borrow().get().misc.vfr,
-                            move |value, cx| {
-                                view.update(cx, |this, cx| {
-                                    this.update_config(cx, |c| c.misc.vfr = value);
-                                });
-                            },
-                        )
-                        .default_value(true)
-                    })
-                    .description("Variable refresh rate (saves battery)"),
-                ),
+                SettingGroup::new()
+                    .item(SettingItem::render(move |_opts, _window, _cx| panel.clone())),
+            )
+    }
+
+    fn create_monitors_page(&self) -> SettingPage {
+        let panel = self.monitors_panel.clone();
+
+        SettingPage::new("Monitors")
+            .description("Arrange connected outputs. Changes are written to ~/.config/omarchist/hyprland/monitors.conf")
+            .group(
+                SettingGroup::new()
+                    .item(SettingItem::render(move |_opts, _window, _cx| panel.clone())),
+            )
+    }
+
+    fn create_rules_page(&self) -> SettingPage {
+        let panel = self.rules_panel.clone();
+
+        SettingPage::new("Rules")
+            .description("Window and workspace rules. Later rules win. Changes are written to ~/.config/omarchist/hyprland/rules.conf")
+            .group(
+                SettingGroup::new()
+                    .item(SettingItem::render(move |_opts, _window, _cx| panel.clone())),
             )
     }
 }
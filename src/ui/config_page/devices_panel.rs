@@ -0,0 +1,598 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::system::hyprland_config::HyprlandConfigManager;
+use crate::system::hyprland_config::devices::read_devices;
+use crate::system::hyprland_config::keyboard::load_keyboard_catalog;
+use crate::types::hyprland_config::{DeviceConfig, InputConfig, InputDevice, InputDeviceKind};
+use crate::ui::config_page::saving::save_config_later;
+
+const SENSITIVITY_STEP: f64 = 0.1;
+const SCROLL_FACTOR_STEP: f64 = 0.1;
+const REPEAT_RATE_STEP: i32 = 5;
+const REPEAT_DELAY_STEP: i32 = 50;
+
+const ACCEL_PROFILES: [&str; 2] = ["adaptive", "flat"];
+
+pub struct DevicesPanel {
+    config_manager: Rc<RefCell<HyprlandConfigManager>>,
+    // Connected devices, to offer in the Add menu and to know what kind each is
+    connected: Vec<InputDevice>,
+    // `(name, description)` of every layout, for keyboards
+    layouts: Vec<(String, String)>,
+    error_message: Option<String>,
+    // The pending save; replacing it drops that save for the newer one
+    save_task: Option<Task<()>>,
+}
+
+impl DevicesPanel {
+    pub fn new(
+        config_manager: Rc<RefCell<HyprlandConfigManager>>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Self {
+        let (connected, error_message) = match read_devices() {
+            Ok(devices) => (devices, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+
+        let mut layouts: Vec<(String, String)> = load_keyboard_catalog()
+            .map(|catalog| {
+                catalog
+                    .layouts
+                    .into_iter()
+                    .map(|l| (l.name, l.description))
+                    .collect()
+            })
+            .unwrap_or_default();
+        layouts.sort_by(|a, b| a.1.cmp(&b.1));
+
+        Self {
+            config_manager,
+            connected,
+            layouts,
+            error_message,
+            save_task: None,
+        }
+    }
+
+    /// Drop a save that hasn't run yet, e.g. before the files are replaced.
+    pub fn cancel_save(&mut self) {
+        self.save_task = None;
+    }
+
+    fn update<F>(&mut self, index: usize, cx: &mut Context<Self>, f: F)
+    where
+        F: FnOnce(&mut DeviceConfig),
+    {
+        self.config_manager.borrow_mut().update(|config| {
+            if let Some(device) = config.devices.get_mut(index) {
+                f(device);
+            }
+        });
+        self.save(cx);
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        self.save_task = Some(save_config_later(
+            &self.config_manager,
+            cx,
+            |this, result, _cx| {
+                this.error_message = result
+                    .err()
+                    .map(|e| format!("Failed to save devices: {}", e));
+            },
+        ));
+        cx.notify();
+    }
+
+    fn add_device(&mut self, name: String, cx: &mut Context<Self>) {
+        // A block needs at least one setting to be written, and `enabled`
+        // is what a device has anyway
+        self.config_manager.borrow_mut().update(|config| {
+            config.devices.push(DeviceConfig {
+                name,
+                enabled: Some(true),
+                ..DeviceConfig::default()
+            })
+        });
+        self.save(cx);
+    }
+
+    fn remove_device(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.config_manager.borrow_mut().update(|config| {
+            if index < config.devices.len() {
+                config.devices.remove(index);
+            }
+        });
+        self.save(cx);
+    }
+
+    fn kind_of(&self, name: &str) -> Option<InputDeviceKind> {
+        self.connected
+            .iter()
+            .find(|device| device.name == name)
+            .map(|device| device.kind)
+    }
+
+    fn render_device(
+        &self,
+        index: usize,
+        device: &DeviceConfig,
+        input: &InputConfig,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let theme = cx.theme();
+        let kind = self.kind_of(&device.name);
+        // Devices that are not connected right now show every option
+        let pointer = kind != Some(InputDeviceKind::Keyboard);
+        let keyboard = matches!(kind, Some(InputDeviceKind::Keyboard) | None);
+
+        let status = match kind {
+            Some(InputDeviceKind::Mouse) => "Mouse",
+            Some(InputDeviceKind::Keyboard) => "Keyboard",
+            Some(InputDeviceKind::Tablet) => "Tablet",
+            Some(InputDeviceKind::Touch) => "Touchscreen",
+            None => "Not connected",
+        };
+
+        let mut rows: Vec<AnyElement> = vec![
+            self.render_option(
+                ("device-enabled", index),
+                "Enabled",
+                None::<fn(&ClickEvent, &mut Window, &mut App)>,
+                Checkbox::new(("device-enabled-value", index))
+                    .checked(device.enabled.unwrap_or(true))
+                    .on_click(cx.listener(move |this, checked: &bool, _window, cx| {
+                        let checked = *checked;
+                        this.update(index, cx, |d| d.enabled = Some(checked));
+                    }))
+                    .into_any_element(),
+                cx,
+            ),
+        ];
+
+        if pointer {
+            let sensitivity = device.sensitivity.unwrap_or(input.sensitivity);
+            rows.push(
+                self.render_option(
+                    ("device-sensitivity", index),
+                    "Sensitivity",
+                    device.sensitivity.is_some().then_some(cx.listener(
+                        move |this, _, _window, cx| {
+                            this.update(index, cx, |d| d.sensitivity = None)
+                        },
+                    )),
+                    self.stepper(
+                        ("device-sensitivity-value", index),
+                        format!("{:.1}", sensitivity),
+                        cx.listener(move |this, _, _window, cx| {
+                            this.update(index, cx, |d| {
+                                d.sensitivity =
+                                    Some(round_tenth(sensitivity - SENSITIVITY_STEP).max(-1.0))
+                            })
+                        }),
+                        cx.listener(move |this, _, _window, cx| {
+                            this.update(index, cx, |d| {
+                                d.sensitivity =
+                                    Some(round_tenth(sensitivity + SENSITIVITY_STEP).min(1.0))
+                            })
+                        }),
+                    ),
+                    cx,
+                ),
+            );
+
+            let panel = cx.entity();
+            let current = device.accel_profile.clone();
+            let label = device
+                .accel_profile
+                .clone()
+                .unwrap_or_else(|| accel_label(&input.accel_profile));
+            rows.push(
+                self.render_option(
+                    ("device-accel", index),
+                    "Acceleration",
+                    device.accel_profile.is_some().then_some(cx.listener(
+                        move |this, _, _window, cx| {
+                            this.update(index, cx, |d| d.accel_profile = None)
+                        },
+                    )),
+                    Button::new(("device-accel-value", index))
+                        .label(label)
+                        .xsmall()
+                        .dropdown_menu(move |menu, _window, _cx| {
+                            let mut menu = menu;
+                            for profile in ACCEL_PROFILES {
+                                let panel = panel.clone();
+                                menu = menu.item(
+                                    PopupMenuItem::new(profile)
+                                        .checked(current.as_deref() == Some(profile))
+                                        .on_click(move |_event, _window, cx| {
+                                            panel.update(cx, |this, cx| {
+                                                this.update(index, cx, |d| {
+                                                    d.accel_profile = Some(profile.to_string())
+                                                });
+                                            });
+                                        }),
+                                );
+                            }
+                            menu
+                        })
+                        .into_any_element(),
+                    cx,
+                ),
+            );
+
+            let natural_scroll = device.natural_scroll.unwrap_or(input.natural_scroll);
+            rows.push(
+                self.render_option(
+                    ("device-natural-scroll", index),
+                    "Natural Scroll",
+                    device.natural_scroll.is_some().then_some(cx.listener(
+                        move |this, _, _window, cx| {
+                            this.update(index, cx, |d| d.natural_scroll = None)
+                        },
+                    )),
+                    Checkbox::new(("device-natural-scroll-value", index))
+                        .checked(natural_scroll)
+                        .on_click(cx.listener(move |this, checked: &bool, _window, cx| {
+                            let checked = *checked;
+                            this.update(index, cx, |d| d.natural_scroll = Some(checked));
+                        }))
+                        .into_any_element(),
+                    cx,
+                ),
+            );
+
+            let left_handed = device.left_handed.unwrap_or(input.left_handed);
+            rows.push(
+                self.render_option(
+                    ("device-left-handed", index),
+                    "Left Handed",
+                    device.left_handed.is_some().then_some(cx.listener(
+                        move |this, _, _window, cx| {
+                            this.update(index, cx, |d| d.left_handed = None)
+                        },
+                    )),
+                    Checkbox::new(("device-left-handed-value", index))
+                        .checked(left_handed)
+                        .on_click(cx.listener(move |this, checked: &bool, _window, cx| {
+                            let checked = *checked;
+                            this.update(index, cx, |d| d.left_handed = Some(checked));
+                        }))
+                        .into_any_element(),
+                    cx,
+                ),
+            );
+
+            let scroll_factor = device.scroll_factor.unwrap_or(input.scroll_factor);
+            rows.push(self.render_option(
+                ("device-scroll-factor", index),
+                "Scroll Factor",
+                device.scroll_factor.is_some().then_some(cx.listener(
+                    move |this, _, _window, cx| this.update(index, cx, |d| d.scroll_factor = None),
+                )),
+                self.stepper(
+                    ("device-scroll-factor-value", index),
+                    format!("{:.1}", scroll_factor),
+                    cx.listener(move |this, _, _window, cx| {
+                        this.update(index, cx, |d| {
+                            d.scroll_factor =
+                                Some(round_tenth(scroll_factor - SCROLL_FACTOR_STEP).max(0.1))
+                        })
+                    }),
+                    cx.listener(move |this, _, _window, cx| {
+                        this.update(index, cx, |d| {
+                            d.scroll_factor = Some(round_tenth(scroll_factor + SCROLL_FACTOR_STEP))
+                        })
+                    }),
+                ),
+                cx,
+            ));
+        }
+
+        if keyboard {
+            let panel = cx.entity();
+            let layouts = self.layouts.clone();
+            let current = device.kb_layout.clone();
+            let layout = device
+                .kb_layout
+                .clone()
+                .unwrap_or_else(|| input.kb_layout.clone());
+            rows.push(
+                self.render_option(
+                    ("device-layout", index),
+                    "Keyboard Layout",
+                    device.kb_layout.is_some().then_some(cx.listener(
+                        move |this, _, _window, cx| this.update(index, cx, |d| d.kb_layout = None),
+                    )),
+                    Button::new(("device-layout-value", index))
+                        .label(layout)
+                        .xsmall()
+                        .dropdown_menu(move |menu, _window, _cx| {
+                            let mut menu = menu.scrollable(true).max_h(px(320.));
+                            for (name, description) in layouts.iter() {
+                                let panel = panel.clone();
+                                let name = name.clone();
+                                menu = menu.item(
+                                    PopupMenuItem::new(format!("{} ({})", description, name))
+                                        .checked(current.as_deref() == Some(name.as_str()))
+                                        .on_click(move |_event, _window, cx| {
+                                            let name = name.clone();
+                                            panel.update(cx, |this, cx| {
+                                                this.update(index, cx, |d| {
+                                                    d.kb_layout = Some(name)
+                                                });
+                                            });
+                                        }),
+                                );
+                            }
+                            menu
+                        })
+                        .into_any_element(),
+                    cx,
+                ),
+            );
+
+            let repeat_rate = device.repeat_rate.unwrap_or(input.repeat_rate);
+            rows.push(
+                self.render_option(
+                    ("device-repeat-rate", index),
+                    "Repeat Rate",
+                    device.repeat_rate.is_some().then_some(cx.listener(
+                        move |this, _, _window, cx| {
+                            this.update(index, cx, |d| d.repeat_rate = None)
+                        },
+                    )),
+                    self.stepper(
+                        ("device-repeat-rate-value", index),
+                        format!("{}/s", repeat_rate),
+                        cx.listener(move |this, _, _window, cx| {
+                            this.update(index, cx, |d| {
+                                d.repeat_rate = Some((repeat_rate - REPEAT_RATE_STEP).max(1))
+                            })
+                        }),
+                        cx.listener(move |this, _, _window, cx| {
+                            this.update(index, cx, |d| {
+                                d.repeat_rate = Some(repeat_rate + REPEAT_RATE_STEP)
+                            })
+                        }),
+                    ),
+                    cx,
+                ),
+            );
+
+            let repeat_delay = device.repeat_delay.unwrap_or(input.repeat_delay);
+            rows.push(self.render_option(
+                ("device-repeat-delay", index),
+                "Repeat Delay",
+                device.repeat_delay.is_some().then_some(cx.listener(
+                    move |this, _, _window, cx| this.update(index, cx, |d| d.repeat_delay = None),
+                )),
+                self.stepper(
+                    ("device-repeat-delay-value", index),
+                    format!("{} ms", repeat_delay),
+                    cx.listener(move |this, _, _window, cx| {
+                        this.update(index, cx, |d| {
+                            d.repeat_delay = Some((repeat_delay - REPEAT_DELAY_STEP).max(100))
+                        })
+                    }),
+                    cx.listener(move |this, _, _window, cx| {
+                        this.update(index, cx, |d| {
+                            d.repeat_delay = Some(repeat_delay + REPEAT_DELAY_STEP)
+                        })
+                    }),
+                ),
+                cx,
+            ));
+        }
+
+        v_flex()
+            .gap_1()
+            .p_3()
+            .w_full()
+            .rounded(theme.radius)
+            .border_1()
+            .border_color(theme.border)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .font_semibold()
+                            .child(device.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child(status),
+                    )
+                    .child(
+                        Button::new(("remove-device", index))
+                            .label("Remove")
+                            .xsmall()
+                            .ghost()
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.remove_device(index, cx)
+                            })),
+                    ),
+            )
+            .children(rows)
+            .into_any_element()
+    }
+
+    // One option of a device. Options with a value set get a Reset button
+    // that makes the device follow the Input settings again.
+    fn render_option(
+        &self,
+        id: impl Into<ElementId>,
+        label: &'static str,
+        on_reset: Option<impl Fn(&ClickEvent, &mut Window, &mut App) + 'static>,
+        control: AnyElement,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let theme = cx.theme();
+        let overridden = on_reset.is_some();
+
+        h_flex()
+            .gap_3()
+            .py_1()
+            .w_full()
+            .items_center()
+            .child(div().w(px(140.)).flex_shrink_0().text_sm().child(label))
+            .child(control)
+            .child(div().flex_1())
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(theme.muted_foreground)
+                    .child(if overridden { "Device" } else { "From Input" }),
+            )
+            .when_some(on_reset, |this, on_reset| {
+                this.child(
+                    Button::new(id)
+                        .label("Reset")
+                        .xsmall()
+                        .ghost()
+                        .on_click(on_reset),
+                )
+            })
+            .into_any_element()
+    }
+
+    fn stepper(
+        &self,
+        id: (&'static str, usize),
+        value: String,
+        on_decrease: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+        on_increase: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> AnyElement {
+        let (name, index) = id;
+        h_flex()
+            .gap_1()
+            .items_center()
+            .child(
+                Button::new(ElementId::NamedInteger(
+                    format!("{}-down", name).into(),
+                    index as u64,
+                ))
+                .label("−")
+                .xsmall()
+                .ghost()
+                .on_click(on_decrease),
+            )
+            .child(div().w(px(64.)).text_sm().text_center().child(value))
+            .child(
+                Button::new(ElementId::NamedInteger(
+                    format!("{}-up", name).into(),
+                    index as u64,
+                ))
+                .label("+")
+                .xsmall()
+                .ghost()
+                .on_click(on_increase),
+            )
+            .into_any_element()
+    }
+}
+
+fn accel_label(profile: &str) -> String {
+    if profile.is_empty() {
+        "adaptive".to_string()
+    } else {
+        profile.to_string()
+    }
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+impl Render for DevicesPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let config = self.config_manager.borrow().get().clone();
+        let panel = cx.entity();
+
+        let cards: Vec<AnyElement> = config
+            .devices
+            .iter()
+            .enumerate()
+            .map(|(index, device)| self.render_device(index, device, &config.input, cx))
+            .collect();
+
+        let available: Vec<InputDevice> = self
+            .connected
+            .iter()
+            .filter(|device| !config.devices.iter().any(|d| d.name == device.name))
+            .cloned()
+            .collect();
+        let nothing_to_add = available.is_empty();
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| div().text_sm().text_color(theme.danger).child(msg.clone())),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .text_color(theme.muted_foreground)
+                            .child("Settings for one device override the ones above for that device only."),
+                    )
+                    .child(
+                        Button::new("add-device")
+                            .label("Add Device")
+                            .small()
+                            .primary()
+                            .when(nothing_to_add, |button| button.label("No Other Devices"))
+                            .dropdown_menu(move |menu, _window, _cx| {
+                                let mut menu = menu;
+                                for device in available.iter().cloned() {
+                                    let panel = panel.clone();
+                                    menu = menu.item(PopupMenuItem::new(device.name.clone()).on_click(
+                                        move |_event, _window, cx| {
+                                            let name = device.name.clone();
+                                            panel.update(cx, |this, cx| this.add_device(name, cx));
+                                        },
+                                    ));
+                                }
+                                menu
+                            }),
+                    ),
+            )
+            .when(cards.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.muted_foreground)
+                        .child("No devices have their own settings."),
+                )
+            })
+            .children(cards)
+    }
+}
@@ -0,0 +1,466 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    v_flex,
+};
+
+use crate::system::hyprland_config::keybind_parser::parse_mods;
+use crate::system::hyprland_config::{KeybindManager, find_conflicts};
+use crate::types::hyprland_config::{Keybind, KeybindFlags, KeybindSource};
+use crate::ui::config_page::saving::run_config_write;
+
+// Form state for adding a bind or editing an existing one
+struct KeybindForm {
+    original: Option<Keybind>,
+    flags: KeybindFlags,
+    mods_input: Entity<InputState>,
+    key_input: Entity<InputState>,
+    description_input: Entity<InputState>,
+    dispatcher_input: Entity<InputState>,
+    args_input: Entity<InputState>,
+}
+
+impl KeybindForm {
+    fn new(original: Option<Keybind>, window: &mut Window, cx: &mut App) -> Self {
+        let input = |value: String, placeholder: &str, window: &mut Window, cx: &mut App| {
+            let placeholder = placeholder.to_string();
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .default_value(value)
+                    .placeholder(placeholder)
+            })
+        };
+
+        let bind = original.as_ref();
+        let mods = bind.map(|b| b.mods.join(" ")).unwrap_or_default();
+        let key = bind.map(|b| b.key.clone()).unwrap_or_default();
+        let description = bind.and_then(|b| b.description.clone()).unwrap_or_default();
+        let dispatcher = bind
+            .map(|b| b.dispatcher.clone())
+            .unwrap_or_else(|| "exec".to_string());
+        let args = bind.map(|b| b.args.clone()).unwrap_or_default();
+
+        Self {
+            flags: bind.map(|b| b.flags.clone()).unwrap_or_default(),
+            mods_input: input(mods, "SUPER SHIFT", window, cx),
+            key_input: input(key, "RETURN", window, cx),
+            description_input: input(description, "Optional", window, cx),
+            dispatcher_input: input(dispatcher, "exec", window, cx),
+            args_input: input(args, "alacritty", window, cx),
+            original,
+        }
+    }
+
+    fn to_keybind(&self, cx: &App) -> Result<Keybind, String> {
+        let value = |input: &Entity<InputState>| input.read(cx).value().trim().to_string();
+
+        let key = value(&self.key_input);
+        let dispatcher = value(&self.dispatcher_input);
+        let description = value(&self.description_input);
+
+        if key.is_empty() {
+            return Err("Key is required".to_string());
+        }
+        if dispatcher.is_empty() {
+            return Err("Dispatcher is required".to_string());
+        }
+        // Commas separate the fields of a bind line
+        if key.contains(',') || dispatcher.contains(',') || description.contains(',') {
+            return Err("Key, description and dispatcher can not contain commas".to_string());
+        }
+
+        Ok(Keybind {
+            flags: self.flags.clone(),
+            mods: parse_mods(&value(&self.mods_input)),
+            key,
+            description: (!description.is_empty()).then_some(description),
+            dispatcher,
+            args: value(&self.args_input),
+            source: KeybindSource::Omarchist,
+        })
+    }
+}
+
+pub struct KeybindingsPanel {
+    manager: Option<KeybindManager>,
+    search_input: Entity<InputState>,
+    form: Option<KeybindForm>,
+    error_message: Option<String>,
+    // The save in flight; dropping it drops its result
+    save_task: Option<Task<()>>,
+}
+
+impl KeybindingsPanel {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let (manager, error_message) = match KeybindManager::load() {
+            Ok(manager) => (Some(manager), None),
+            Err(e) => (None, Some(format!("Failed to load keybindings: {}", e))),
+        };
+
+        let search_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Search keybindings..."));
+
+        cx.subscribe(&search_input, |_this, _input, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        })
+        .detach();
+
+        Self {
+            manager,
+            search_input,
+            form: None,
+            error_message,
+            save_task: None,
+        }
+    }
+
+    /// Drop a save that hasn't run yet, e.g. before the files are replaced.
+    pub fn cancel_save(&mut self) {
+        self.save_task = None;
+    }
+
+    fn open_form(
+        &mut self,
+        original: Option<Keybind>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.form = Some(KeybindForm::new(original, window, cx));
+        self.error_message = None;
+        cx.notify();
+    }
+
+    fn submit_form(&mut self, cx: &mut Context<Self>) {
+        let (Some(manager), Some(form)) = (self.manager.as_mut(), self.form.as_ref()) else {
+            return;
+        };
+
+        match form.to_keybind(cx) {
+            Ok(keybind) => {
+                manager.set_keybind(form.original.as_ref(), keybind);
+                self.form = None;
+                self.save(cx);
+            }
+            Err(e) => {
+                self.error_message = Some(e);
+                cx.notify();
+            }
+        }
+    }
+
+    fn remove(&mut self, keybind: &Keybind, cx: &mut Context<Self>) {
+        if let Some(manager) = self.manager.as_mut() {
+            manager.remove_keybind(keybind);
+            self.save(cx);
+        }
+    }
+
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        if let Some(manager) = self.manager.as_mut() {
+            manager.reset();
+            self.form = None;
+            self.save(cx);
+        }
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        if let Some(manager) = self.manager.as_ref() {
+            self.save_task = Some(run_config_write(
+                manager.save_write(),
+                cx,
+                |this, result, _cx| {
+                    this.error_message = result
+                        .err()
+                        .map(|e| format!("Failed to save keybindings: {}", e));
+                },
+            ));
+        }
+        cx.notify();
+    }
+
+    fn matches_query(keybind: &Keybind, query: &str) -> bool {
+        if query.is_empty() {
+            return true;
+        }
+
+        [
+            format_combo(keybind),
+            keybind.description.clone().unwrap_or_default(),
+            keybind.dispatcher.clone(),
+            keybind.args.clone(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(query))
+    }
+
+    fn render_form(&self, form: &KeybindForm, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let field = |label: &'static str, input: &Entity<InputState>| {
+            v_flex()
+                .flex_1()
+                .gap_1()
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child(label),
+                )
+                .child(Input::new(input).small())
+        };
+
+        let flag_checkbox = |id: &'static str,
+                             label: &'static str,
+                             checked: bool,
+                             set: fn(&mut KeybindFlags, bool)| {
+            Checkbox::new(id)
+                .label(label)
+                .checked(checked)
+                .on_click(cx.listener(move |this, checked: &bool, _window, cx| {
+                    if let Some(form) = this.form.as_mut() {
+                        set(&mut form.flags, *checked);
+                        cx.notify();
+                    }
+                }))
+        };
+
+        v_flex()
+            .gap_3()
+            .p_3()
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .child(
+                div()
+                    .text_sm()
+                    .font_semibold()
+                    .child(if form.original.is_some() {
+                        "Edit Keybind"
+                    } else {
+                        "Add Keybind"
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(field("Modifiers", &form.mods_input))
+                    .child(field("Key", &form.key_input))
+                    .child(field("Description", &form.description_input)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(field("Dispatcher", &form.dispatcher_input))
+                    .child(field("Arguments", &form.args_input)),
+            )
+            .child(
+                h_flex()
+                    .gap_4()
+                    .child(flag_checkbox(
+                        "keybind-flag-locked",
+                        "Works when locked",
+                        form.flags.locked,
+                        |flags, value| flags.locked = value,
+                    ))
+                    .child(flag_checkbox(
+                        "keybind-flag-repeat",
+                        "Repeat while held",
+                        form.flags.repeat,
+                        |flags, value| flags.repeat = value,
+                    ))
+                    .child(flag_checkbox(
+                        "keybind-flag-release",
+                        "Trigger on release",
+                        form.flags.release,
+                        |flags, value| flags.release = value,
+                    )),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("keybind-form-cancel")
+                            .label("Cancel")
+                            .small()
+                            .ghost()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.form = None;
+                                this.error_message = None;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("keybind-form-save")
+                            .label("Save")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.submit_form(cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+// e.g. `SUPER + SHIFT + RETURN`
+fn format_combo(keybind: &Keybind) -> String {
+    keybind
+        .mods
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(keybind.key.as_str()))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+fn source_label(source: KeybindSource) -> &'static str {
+    match source {
+        KeybindSource::Omarchy => "Omarchy",
+        KeybindSource::User => "User",
+        KeybindSource::Omarchist => "Omarchist",
+    }
+}
+
+impl Render for KeybindingsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let query = self.search_input.read(cx).value().trim().to_lowercase();
+
+        let keybinds = self
+            .manager
+            .as_ref()
+            .map(|m| m.keybinds())
+            .unwrap_or_default();
+        let has_overrides = self.manager.as_ref().is_some_and(|m| {
+            let overrides = m.overrides();
+            !overrides.binds.is_empty() || !overrides.unbinds.is_empty()
+        });
+
+        let conflicts = find_conflicts(&keybinds);
+        let conflicting: Vec<usize> = conflicts
+            .iter()
+            .flat_map(|c| c.indices.iter().copied())
+            .collect();
+
+        let rows: Vec<AnyElement> = keybinds
+            .iter()
+            .enumerate()
+            .filter(|(_, bind)| Self::matches_query(bind, &query))
+            .map(|(index, bind)| {
+                let is_conflict = conflicting.contains(&index);
+                let edit_bind = bind.clone();
+                let remove_bind = bind.clone();
+                let summary = match bind.description {
+                    Some(ref description) => description.clone(),
+                    None => format!("{} {}", bind.dispatcher, bind.args),
+                };
+
+                h_flex()
+                    .gap_3()
+                    .py_1p5()
+                    .w_full()
+                    .items_center()
+                    .border_b_1()
+                    .border_color(theme.border.opacity(0.3))
+                    .child(
+                        div()
+                            .w(px(220.))
+                            .flex_shrink_0()
+                            .text_sm()
+                            .font_semibold()
+                            .when(is_conflict, |this| this.text_color(theme.warning))
+                            .child(format_combo(bind)),
+                    )
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .min_w_0()
+                            .child(div().text_sm().truncate().child(summary))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.muted_foreground)
+                                    .truncate()
+                                    .child(if is_conflict {
+                                        format!(
+                                            "{} · Conflicts with another bind",
+                                            source_label(bind.source)
+                                        )
+                                    } else {
+                                        source_label(bind.source).to_string()
+                                    }),
+                            ),
+                    )
+                    .child(
+                        Button::new(("edit-keybind", index))
+                            .label("Edit")
+                            .xsmall()
+                            .ghost()
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.open_form(Some(edit_bind.clone()), window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(("remove-keybind", index))
+                            .label("Remove")
+                            .xsmall()
+                            .ghost()
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.remove(&remove_bind, cx);
+                            })),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().flex_1().child(Input::new(&self.search_input).small()))
+                    .child(
+                        Button::new("add-keybind")
+                            .label("Add Keybind")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_form(None, window, cx);
+                            })),
+                    )
+                    .when(has_overrides, |this| {
+                        this.child(
+                            Button::new("reset-keybinds")
+                                .label("Reset")
+                                .small()
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.reset(cx);
+                                })),
+                        )
+                    }),
+            )
+            .when(!conflicts.is_empty(), |this| {
+                this.child(div().text_sm().text_color(theme.warning).child(format!(
+                    "{} key combination{} bound more than once",
+                    conflicts.len(),
+                    if conflicts.len() == 1 { " is" } else { "s are" }
+                )))
+            })
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| div().text_sm().text_color(theme.danger).child(msg.clone())),
+            )
+            .children(self.form.as_ref().map(|form| self.render_form(form, cx)))
+            .child(v_flex().w_full().children(rows))
+    }
+}
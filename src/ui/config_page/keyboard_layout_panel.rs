@@ -0,0 +1,542 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    menu::{DropdownMenu, PopupMenuItem},
+    select::{SearchableVec, Select, SelectEvent, SelectItem, SelectState},
+    v_flex,
+};
+
+use crate::system::hyprland_config::HyprlandConfigManager;
+use crate::system::hyprland_config::keyboard::{
+    format_layout_list, format_option_list, load_keyboard_catalog, option_code, parse_layout_list,
+    parse_option_list, search_options,
+};
+use crate::types::hyprland_config::{KeyboardCatalog, KeyboardLayoutChoice};
+use crate::ui::config_page::saving::{reset_config_later, save_config_later};
+
+// The options in Omarchist's file this panel writes
+const KEYBOARD_OPTION_IDS: [&str; 3] = ["input:kb_layout", "input:kb_variant", "input:kb_options"];
+
+// Enough to find an option, the search narrows it down from there
+const MAX_OPTION_RESULTS: usize = 40;
+
+#[derive(Clone, Debug)]
+struct KeyboardLayoutItem {
+    value: SharedString,
+    // The human-readable label
+    label: SharedString,
+}
+
+impl SelectItem for KeyboardLayoutItem {
+    type Value = SharedString;
+
+    fn title(&self) -> SharedString {
+        self.label.clone()
+    }
+
+    fn value(&self) -> &SharedString {
+        &self.value
+    }
+}
+
+/// Builds `kb_layout`, `kb_variant` and `kb_options` from the XKB catalog.
+/// Edits go to a draft that is previewed and only written on Apply.
+pub struct KeyboardLayoutPanel {
+    config_manager: Rc<RefCell<HyprlandConfigManager>>,
+    catalog: KeyboardCatalog,
+    layouts: Vec<KeyboardLayoutChoice>,
+    options: Vec<String>,
+    add_layout_select: Entity<SelectState<SearchableVec<KeyboardLayoutItem>>>,
+    option_search: Entity<InputState>,
+    error_message: Option<String>,
+    // The pending save; replacing it drops that save for the newer one
+    save_task: Option<Task<()>>,
+}
+
+impl KeyboardLayoutPanel {
+    pub fn new(
+        config_manager: Rc<RefCell<HyprlandConfigManager>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (catalog, error_message) = match load_keyboard_catalog() {
+            Ok(catalog) => (catalog, None),
+            Err(e) => (
+                KeyboardCatalog::default(),
+                Some(format!("Failed to load keyboard catalog: {}", e)),
+            ),
+        };
+
+        let mut layout_items: Vec<KeyboardLayoutItem> = catalog
+            .layouts
+            .iter()
+            .map(|l| KeyboardLayoutItem {
+                value: l.name.clone().into(),
+                label: format!("{} ({})", l.description, l.name).into(),
+            })
+            .collect();
+        layout_items.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let add_layout_select = cx.new(|cx| {
+            SelectState::new(SearchableVec::new(layout_items), None, window, cx).searchable(true)
+        });
+        cx.subscribe_in(
+            &add_layout_select,
+            window,
+            |this, select, event: &SelectEvent<SearchableVec<KeyboardLayoutItem>>, window, cx| {
+                if let SelectEvent::Confirm(Some(value)) = event {
+                    this.add_layout(value.to_string(), cx);
+                    // The picker is only for adding, it doesn't keep a selection
+                    select.update(cx, |select, cx| select.set_selected_index(None, window, cx));
+                }
+            },
+        )
+        .detach();
+
+        let option_search = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Search options, e.g. caps or grp...")
+        });
+        cx.subscribe(&option_search, |_this, _input, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        })
+        .detach();
+
+        let mut panel = Self {
+            config_manager,
+            catalog,
+            layouts: Vec::new(),
+            options: Vec::new(),
+            add_layout_select,
+            option_search,
+            error_message,
+            save_task: None,
+        };
+        panel.load_draft();
+        panel
+    }
+
+    /// Drop a save that hasn't run yet, e.g. before the files are replaced.
+    pub fn cancel_save(&mut self) {
+        self.save_task = None;
+    }
+
+    // Start the draft over from the values in effect
+    fn load_draft(&mut self) {
+        let manager = self.config_manager.borrow();
+        let input = &manager.get().input;
+        self.layouts = parse_layout_list(&input.kb_layout, &input.kb_variant);
+        self.options = parse_option_list(&input.kb_options);
+    }
+
+    fn draft_strings(&self) -> (String, String, String) {
+        let (kb_layout, kb_variant) = format_layout_list(&self.layouts);
+        (kb_layout, kb_variant, format_option_list(&self.options))
+    }
+
+    fn is_changed(&self) -> bool {
+        let manager = self.config_manager.borrow();
+        let input = &manager.get().input;
+        self.draft_strings()
+            != (
+                input.kb_layout.clone(),
+                input.kb_variant.clone(),
+                input.kb_options.clone(),
+            )
+    }
+
+    fn apply(&mut self, cx: &mut Context<Self>) {
+        let (kb_layout, kb_variant, kb_options) = self.draft_strings();
+        self.config_manager.borrow_mut().update(|config| {
+            config.input.kb_layout = kb_layout;
+            config.input.kb_variant = kb_variant;
+            config.input.kb_options = kb_options;
+        });
+        self.save_task = Some(save_config_later(
+            &self.config_manager,
+            cx,
+            |this, result, _cx| {
+                this.error_message = result.err();
+                // A rejected change was rolled back, show what is in effect
+                if this.error_message.is_some() {
+                    this.load_draft();
+                }
+            },
+        ));
+        cx.notify();
+    }
+
+    fn revert(&mut self, cx: &mut Context<Self>) {
+        self.load_draft();
+        self.error_message = None;
+        cx.notify();
+    }
+
+    fn is_overridden(&self) -> bool {
+        let manager = self.config_manager.borrow();
+        KEYBOARD_OPTION_IDS
+            .iter()
+            .any(|id| manager.is_overridden(id))
+    }
+
+    // Hand all three options back to Omarchy, they only make sense together
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        let ids: Vec<&str> = {
+            let manager = self.config_manager.borrow();
+            KEYBOARD_OPTION_IDS
+                .into_iter()
+                .filter(|id| manager.is_overridden(id))
+                .collect()
+        };
+
+        self.error_message = None;
+        // Takes over from the pending save, see `ConfigView::reset_option`
+        self.save_task = Some(reset_config_later(
+            &self.config_manager,
+            &ids,
+            cx,
+            |this, result, _cx| {
+                this.error_message = result.err();
+                this.load_draft();
+            },
+        ));
+        cx.notify();
+    }
+
+    fn add_layout(&mut self, layout: String, cx: &mut Context<Self>) {
+        if !self.layouts.iter().any(|choice| choice.layout == layout) {
+            self.layouts.push(KeyboardLayoutChoice {
+                layout,
+                variant: String::new(),
+            });
+        }
+        cx.notify();
+    }
+
+    fn move_layout(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from < self.layouts.len() && to < self.layouts.len() {
+            let choice = self.layouts.remove(from);
+            self.layouts.insert(to, choice);
+        }
+        cx.notify();
+    }
+
+    fn toggle_option(&mut self, code: String, cx: &mut Context<Self>) {
+        match self.options.iter().position(|option| *option == code) {
+            Some(index) => {
+                self.options.remove(index);
+            }
+            None => self.options.push(code),
+        }
+        cx.notify();
+    }
+
+    fn layout_description(&self, layout: &str) -> String {
+        self.catalog
+            .layouts
+            .iter()
+            .find(|l| l.name == layout)
+            .map(|l| l.description.clone())
+            .unwrap_or_else(|| layout.to_string())
+    }
+
+    fn render_layout_row(
+        &self,
+        index: usize,
+        choice: &KeyboardLayoutChoice,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let theme = cx.theme();
+        let panel = cx.entity();
+        let count = self.layouts.len();
+
+        let variants: Vec<(String, String)> = self
+            .catalog
+            .layouts
+            .iter()
+            .find(|l| l.name == choice.layout)
+            .map(|l| {
+                l.variants
+                    .iter()
+                    .map(|v| (v.name.clone(), v.description.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let has_variants = !variants.is_empty();
+        let current_variant = choice.variant.clone();
+
+        let variant_menu = Button::new(("keyboard-variant", index))
+            .label(if choice.variant.is_empty() {
+                "Default variant".to_string()
+            } else {
+                choice.variant.clone()
+            })
+            .xsmall()
+            .disabled(!has_variants)
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu.scrollable(true).max_h(px(320.));
+                let options = std::iter::once((String::new(), "Default variant".to_string()))
+                    .chain(variants.iter().cloned());
+                for (name, description) in options {
+                    let panel = panel.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(description)
+                            .checked(name == current_variant)
+                            .on_click(move |_event, _window, cx| {
+                                let name = name.clone();
+                                panel.update(cx, |this, cx| {
+                                    if let Some(choice) = this.layouts.get_mut(index) {
+                                        choice.variant = name;
+                                    }
+                                    cx.notify();
+                                });
+                            }),
+                    );
+                }
+                menu
+            });
+
+        h_flex()
+            .gap_2()
+            .py_1()
+            .w_full()
+            .items_center()
+            .border_b_1()
+            .border_color(theme.border.opacity(0.3))
+            .child(
+                div()
+                    .w(px(24.))
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child(format!("{}.", index + 1)),
+            )
+            .child(div().flex_1().text_sm().child(format!(
+                "{} ({})",
+                self.layout_description(&choice.layout),
+                choice.layout
+            )))
+            .child(variant_menu)
+            .child(
+                Button::new(("keyboard-layout-up", index))
+                    .label("Up")
+                    .xsmall()
+                    .ghost()
+                    .disabled(index == 0)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.move_layout(index, index.saturating_sub(1), cx)
+                    })),
+            )
+            .child(
+                Button::new(("keyboard-layout-down", index))
+                    .label("Down")
+                    .xsmall()
+                    .ghost()
+                    .disabled(index + 1 >= count)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.move_layout(index, index + 1, cx)
+                    })),
+            )
+            .child(
+                Button::new(("keyboard-layout-remove", index))
+                    .label("Remove")
+                    .xsmall()
+                    .ghost()
+                    .disabled(count <= 1)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.layouts.remove(index);
+                        cx.notify();
+                    })),
+            )
+            .into_any_element()
+    }
+
+    fn render_options(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let query = self.option_search.read(cx).value().to_string();
+        let results = search_options(&self.catalog, &query);
+        let total = results.len();
+
+        let chips: Vec<AnyElement> =
+            self.options
+                .iter()
+                .enumerate()
+                .map(|(index, code)| {
+                    let code = code.clone();
+                    Button::new(("keyboard-option-chip", index))
+                        .label(format!("{} ×", code))
+                        .xsmall()
+                        .on_click(cx.listener(move |this, _, _window, cx| {
+                            this.toggle_option(code.clone(), cx)
+                        }))
+                        .into_any_element()
+                })
+                .collect();
+
+        let rows: Vec<AnyElement> = results
+            .into_iter()
+            .take(MAX_OPTION_RESULTS)
+            .enumerate()
+            .map(|(index, (group, option))| {
+                let code = option_code(group, option);
+                let checked = self.options.contains(&code);
+                h_flex()
+                    .gap_2()
+                    .py_0p5()
+                    .items_center()
+                    .child(
+                        Checkbox::new(("keyboard-option", index))
+                            .checked(checked)
+                            .label(option.description.clone())
+                            .on_click(cx.listener({
+                                let code = code.clone();
+                                move |this, _: &bool, _window, cx| {
+                                    this.toggle_option(code.clone(), cx)
+                                }
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child(code),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        v_flex()
+            .gap_2()
+            .child(div().text_sm().font_semibold().child("Options"))
+            .when(chips.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(theme.muted_foreground)
+                        .child("No options. Search below to add one."),
+                )
+            })
+            .when(!chips.is_empty(), |this| {
+                this.child(h_flex().flex_wrap().gap_1().children(chips))
+            })
+            .child(Input::new(&self.option_search).small())
+            .children(rows)
+            .when(total > MAX_OPTION_RESULTS, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child(format!(
+                            "{} more, refine the search to see them.",
+                            total - MAX_OPTION_RESULTS
+                        )),
+                )
+            })
+    }
+}
+
+impl Render for KeyboardLayoutPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let (kb_layout, kb_variant, kb_options) = self.draft_strings();
+        let changed = self.is_changed();
+        let overridden = self.is_overridden();
+
+        // Without a grp: option there is no key to switch between layouts
+        let needs_switch =
+            self.layouts.len() > 1 && !self.options.iter().any(|option| option.starts_with("grp:"));
+
+        let rows: Vec<AnyElement> = self
+            .layouts
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| self.render_layout_row(index, choice, cx))
+            .collect();
+
+        v_flex()
+            .w_full()
+            .gap_4()
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| div().text_sm().text_color(theme.danger).child(msg.clone())),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(div().text_sm().font_semibold().child("Layouts"))
+                    .children(rows)
+                    .child(
+                        div().pt_1().w(px(320.)).child(
+                            Select::new(&self.add_layout_select)
+                                .placeholder("Add layout...")
+                                .search_placeholder("Search layouts...")
+                                .small(),
+                        ),
+                    )
+                    .when(needs_switch, |this| {
+                        this.child(div().text_xs().text_color(theme.warning).child(
+                            "Add a grp: option, such as grp:alt_shift_toggle, to switch between layouts.",
+                        ))
+                    }),
+            )
+            .child(self.render_options(cx))
+            .child(
+                v_flex()
+                    .gap_1()
+                    .p_3()
+                    .rounded(theme.radius)
+                    .bg(theme.muted)
+                    .text_sm()
+                    .font_family("monospace")
+                    .child(format!("kb_layout = {}", kb_layout))
+                    .child(format!("kb_variant = {}", kb_variant))
+                    .child(format!("kb_options = {}", kb_options)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .when(overridden, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(theme.muted_foreground)
+                                .child("Overridden by Omarchist"),
+                        )
+                        .child(
+                            Button::new("reset-keyboard-layout")
+                                .label("Reset to Omarchy value")
+                                .xsmall()
+                                .ghost()
+                                .on_click(cx.listener(|this, _, _window, cx| this.reset(cx))),
+                        )
+                    })
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("revert-keyboard-layout")
+                            .label("Revert")
+                            .small()
+                            .ghost()
+                            .disabled(!changed)
+                            .on_click(cx.listener(|this, _, _window, cx| this.revert(cx))),
+                    )
+                    .child(
+                        Button::new("apply-keyboard-layout")
+                            .label("Apply")
+                            .small()
+                            .primary()
+                            .disabled(!changed || self.layouts.is_empty())
+                            .on_click(cx.listener(|this, _, _window, cx| this.apply(cx))),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,610 @@
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::system::hyprland_config::MonitorManager;
+use crate::system::hyprland_config::monitor_rules::format_scale;
+use crate::types::hyprland_config::{MonitorMode, MonitorSettings};
+use crate::ui::config_page::saving::run_config_write;
+
+const CANVAS_WIDTH: f32 = 560.;
+const CANVAS_HEIGHT: f32 = 260.;
+const CANVAS_PADDING: f32 = 24.;
+
+const SCALES: [f64; 7] = [1.0, 1.25, 1.5, 5.0 / 3.0, 2.0, 2.5, 3.0];
+
+const TRANSFORMS: [&str; 8] = [
+    "Normal",
+    "90°",
+    "180°",
+    "270°",
+    "Flipped",
+    "Flipped 90°",
+    "Flipped 180°",
+    "Flipped 270°",
+];
+
+// Maps layout coordinates onto the canvas. Kept fixed while dragging so the
+// view does not rescale under the cursor.
+#[derive(Clone, Copy)]
+struct CanvasLayout {
+    factor: f32,
+    origin_x: i32,
+    origin_y: i32,
+    offset_x: f32,
+    offset_y: f32,
+}
+
+impl CanvasLayout {
+    fn fit(monitors: &[&MonitorSettings]) -> Self {
+        let min_x = monitors.iter().map(|m| m.x).min().unwrap_or(0);
+        let min_y = monitors.iter().map(|m| m.y).min().unwrap_or(0);
+        let max_x = monitors
+            .iter()
+            .map(|m| m.x + m.logical_size().0)
+            .max()
+            .unwrap_or(1920);
+        let max_y = monitors
+            .iter()
+            .map(|m| m.y + m.logical_size().1)
+            .max()
+            .unwrap_or(1080);
+
+        let width = (max_x - min_x).max(1) as f32;
+        let height = (max_y - min_y).max(1) as f32;
+        let factor = ((CANVAS_WIDTH - CANVAS_PADDING * 2.) / width)
+            .min((CANVAS_HEIGHT - CANVAS_PADDING * 2.) / height);
+
+        Self {
+            factor,
+            origin_x: min_x,
+            origin_y: min_y,
+            offset_x: (CANVAS_WIDTH - width * factor) / 2.,
+            offset_y: (CANVAS_HEIGHT - height * factor) / 2.,
+        }
+    }
+}
+
+// Applies the option picked from a dropdown, by index, to the selected output
+type ApplyChoice = Rc<dyn Fn(&mut MonitorSettings, usize)>;
+
+struct DragState {
+    index: usize,
+    start_mouse: Point<Pixels>,
+    start_x: i32,
+    start_y: i32,
+    layout: CanvasLayout,
+}
+
+pub struct MonitorsPanel {
+    manager: Option<MonitorManager>,
+    selected: usize,
+    drag: Option<DragState>,
+    error_message: Option<String>,
+    // The save in flight; dropping it drops its result
+    save_task: Option<Task<()>>,
+}
+
+impl MonitorsPanel {
+    pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
+        let mut panel = Self {
+            manager: None,
+            selected: 0,
+            drag: None,
+            error_message: None,
+            save_task: None,
+        };
+        panel.load();
+        panel
+    }
+
+    /// Drop a save that hasn't run yet, e.g. before the files are replaced.
+    pub fn cancel_save(&mut self) {
+        self.save_task = None;
+    }
+
+    fn load(&mut self) {
+        match MonitorManager::load() {
+            Ok(manager) => {
+                self.manager = Some(manager);
+                self.error_message = None;
+            }
+            Err(e) => {
+                self.manager = None;
+                self.error_message = Some(format!("Failed to load monitors: {}", e));
+            }
+        }
+        self.drag = None;
+        self.selected = 0;
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        if let Some(manager) = self.manager.as_ref() {
+            self.save_task = Some(run_config_write(
+                manager.save_write(),
+                cx,
+                |this, result, _cx| {
+                    this.error_message = result
+                        .err()
+                        .map(|e| format!("Failed to save monitors: {}", e));
+                },
+            ));
+        }
+        cx.notify();
+    }
+
+    fn update_selected<F>(&mut self, cx: &mut Context<Self>, f: F)
+    where
+        F: FnOnce(&mut MonitorSettings),
+    {
+        if let Some(manager) = self.manager.as_mut() {
+            manager.update(self.selected, f);
+            self.save(cx);
+        }
+    }
+
+    fn set_enabled(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        let Some(manager) = self.manager.as_mut() else {
+            return;
+        };
+
+        // Turning off the last output leaves nothing to turn it back on from
+        let others_enabled = manager
+            .settings()
+            .iter()
+            .enumerate()
+            .any(|(index, settings)| index != self.selected && settings.enabled);
+        if !enabled && !others_enabled {
+            self.error_message = Some("At least one monitor has to stay enabled".to_string());
+            cx.notify();
+            return;
+        }
+
+        manager.set_enabled(self.selected, enabled);
+        self.save(cx);
+    }
+
+    fn is_connected(&self, name: &str) -> bool {
+        self.manager
+            .as_ref()
+            .is_some_and(|m| m.monitors().iter().any(|info| info.name == name))
+    }
+
+    // Indices of the outputs drawn on the canvas
+    fn arranged(&self) -> Vec<usize> {
+        let Some(manager) = self.manager.as_ref() else {
+            return Vec::new();
+        };
+
+        manager
+            .settings()
+            .iter()
+            .enumerate()
+            .filter(|(_, settings)| settings.is_arranged() && self.is_connected(&settings.name))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn canvas_layout(&self) -> CanvasLayout {
+        if let Some(ref drag) = self.drag {
+            return drag.layout;
+        }
+
+        let settings = self.manager.as_ref().map(|m| m.settings()).unwrap_or(&[]);
+        let arranged: Vec<&MonitorSettings> =
+            self.arranged().into_iter().map(|i| &settings[i]).collect();
+        CanvasLayout::fit(&arranged)
+    }
+
+    fn start_drag(&mut self, index: usize, event: &MouseDownEvent, cx: &mut Context<Self>) {
+        let layout = self.canvas_layout();
+        let Some(settings) = self.manager.as_ref().and_then(|m| m.settings().get(index)) else {
+            return;
+        };
+
+        self.selected = index;
+        self.drag = Some(DragState {
+            index,
+            start_mouse: event.position,
+            start_x: settings.x,
+            start_y: settings.y,
+            layout,
+        });
+        cx.notify();
+    }
+
+    fn drag_to(&mut self, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        let (Some(drag), Some(manager)) = (self.drag.as_ref(), self.manager.as_mut()) else {
+            return;
+        };
+        if event.pressed_button != Some(MouseButton::Left) {
+            return;
+        }
+
+        let delta = event.position - drag.start_mouse;
+        let x = drag.start_x + (f32::from(delta.x) / drag.layout.factor).round() as i32;
+        let y = drag.start_y + (f32::from(delta.y) / drag.layout.factor).round() as i32;
+        manager.move_to(drag.index, x, y);
+        cx.notify();
+    }
+
+    fn end_drag(&mut self, cx: &mut Context<Self>) {
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+
+        // A click without moving only selects the output
+        let moved = self
+            .manager
+            .as_ref()
+            .and_then(|m| m.settings().get(drag.index))
+            .is_some_and(|s| (s.x, s.y) != (drag.start_x, drag.start_y));
+        if moved {
+            self.save(cx);
+        } else {
+            cx.notify();
+        }
+    }
+
+    fn render_canvas(&self, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let layout = self.canvas_layout();
+        let settings = self.manager.as_ref().map(|m| m.settings()).unwrap_or(&[]);
+
+        let outputs: Vec<AnyElement> = self
+            .arranged()
+            .into_iter()
+            .map(|index| {
+                let monitor = &settings[index];
+                let (width, height) = monitor.logical_size();
+                let is_selected = index == self.selected;
+
+                div()
+                    .id(("monitor-rect", index))
+                    .absolute()
+                    .left(px(
+                        layout.offset_x + (monitor.x - layout.origin_x) as f32 * layout.factor
+                    ))
+                    .top(px(
+                        layout.offset_y + (monitor.y - layout.origin_y) as f32 * layout.factor
+                    ))
+                    .w(px(width as f32 * layout.factor))
+                    .h(px(height as f32 * layout.factor))
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .justify_center()
+                    .overflow_hidden()
+                    .bg(theme.secondary)
+                    .border_2()
+                    .border_color(if is_selected {
+                        theme.primary
+                    } else {
+                        theme.border
+                    })
+                    .rounded(theme.radius)
+                    .cursor_move()
+                    .child(div().text_sm().font_semibold().child(monitor.name.clone()))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .child(format!("{}x{}", monitor.mode.width, monitor.mode.height)),
+                    )
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                            this.start_drag(index, event, cx);
+                        }),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        div()
+            .relative()
+            .w(px(CANVAS_WIDTH))
+            .h(px(CANVAS_HEIGHT))
+            .flex_shrink_0()
+            .bg(theme.muted)
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .children(outputs)
+            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                this.drag_to(event, cx);
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
+                    this.end_drag(cx);
+                }),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
+                    this.end_drag(cx);
+                }),
+            )
+    }
+
+    fn render_choice(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        current: String,
+        options: Vec<(String, bool)>,
+        apply: ApplyChoice,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let panel = cx.entity();
+
+        v_flex()
+            .gap_1()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(label),
+            )
+            .child(Button::new(id).label(current).small().dropdown_menu(
+                move |menu, _window, _cx| {
+                    let mut menu = menu.scrollable(true).max_h(px(320.));
+                    for (index, (option, checked)) in options.iter().cloned().enumerate() {
+                        let panel = panel.clone();
+                        let apply = apply.clone();
+                        menu = menu.item(PopupMenuItem::new(option).checked(checked).on_click(
+                            move |_event, _window, cx| {
+                                let apply = apply.clone();
+                                panel.update(cx, |this, cx| {
+                                    this.update_selected(cx, |settings| apply(settings, index));
+                                });
+                            },
+                        ));
+                    }
+                    menu
+                },
+            ))
+    }
+
+    fn render_settings(&self, settings: &MonitorSettings, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let manager = self.manager.as_ref();
+        let info = manager.and_then(|m| m.monitors().iter().find(|i| i.name == settings.name));
+
+        // Modes
+        let mut modes: Vec<MonitorMode> =
+            info.map(|i| i.available_modes.clone()).unwrap_or_default();
+        if !modes.iter().any(|mode| mode.matches(&settings.mode)) {
+            modes.insert(0, settings.mode);
+        }
+        let mode_options = modes
+            .iter()
+            .map(|mode| (format_mode_label(mode), mode.matches(&settings.mode)))
+            .collect();
+
+        // Scales
+        let mut scales = SCALES.to_vec();
+        if !scales.iter().any(|s| (s - settings.scale).abs() < 0.001) {
+            scales.push(settings.scale);
+            scales.sort_by(f64::total_cmp);
+        }
+        let scale_options = scales
+            .iter()
+            .map(|s| (format_scale_label(*s), (s - settings.scale).abs() < 0.001))
+            .collect();
+
+        // Transforms
+        let transform_options = TRANSFORMS
+            .iter()
+            .enumerate()
+            .map(|(index, label)| (label.to_string(), index as i32 == settings.transform))
+            .collect();
+
+        // Mirrors: any other enabled output that is not itself a mirror
+        let mirror_targets: Vec<String> = manager
+            .map(|m| m.settings())
+            .unwrap_or(&[])
+            .iter()
+            .filter(|other| other.name != settings.name && other.is_arranged())
+            .map(|other| other.name.clone())
+            .collect();
+        let mut mirror_options = vec![("None".to_string(), settings.mirror.is_none())];
+        mirror_options.extend(
+            mirror_targets
+                .iter()
+                .map(|name| (name.clone(), settings.mirror.as_ref() == Some(name))),
+        );
+
+        let description = info
+            .map(|i| i.description.clone())
+            .unwrap_or_else(|| "Not connected".to_string());
+
+        v_flex()
+            .gap_3()
+            .p_3()
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().text_sm().font_semibold().child(settings.name.clone()))
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_xs()
+                            .text_color(theme.muted_foreground)
+                            .truncate()
+                            .child(description),
+                    )
+                    .child(
+                        Checkbox::new("monitor-enabled")
+                            .label("Enabled")
+                            .checked(settings.enabled)
+                            .on_click(cx.listener(|this, checked: &bool, _window, cx| {
+                                this.set_enabled(*checked, cx);
+                            })),
+                    ),
+            )
+            .when(settings.enabled, |this| {
+                this.child(
+                    h_flex()
+                        .gap_3()
+                        .flex_wrap()
+                        .child(self.render_choice(
+                            "monitor-mode",
+                            "Resolution & Refresh",
+                            format_mode_label(&settings.mode),
+                            mode_options,
+                            Rc::new(move |settings, index| settings.mode = modes[index]),
+                            cx,
+                        ))
+                        .child(self.render_choice(
+                            "monitor-scale",
+                            "Scale",
+                            format_scale_label(settings.scale),
+                            scale_options,
+                            Rc::new(move |settings, index| settings.scale = scales[index]),
+                            cx,
+                        ))
+                        .child(
+                            self.render_choice(
+                                "monitor-transform",
+                                "Transform",
+                                TRANSFORMS
+                                    .get(settings.transform as usize)
+                                    .unwrap_or(&TRANSFORMS[0])
+                                    .to_string(),
+                                transform_options,
+                                Rc::new(|settings, index| settings.transform = index as i32),
+                                cx,
+                            ),
+                        )
+                        .child(
+                            self.render_choice(
+                                "monitor-mirror",
+                                "Mirror",
+                                settings
+                                    .mirror
+                                    .clone()
+                                    .unwrap_or_else(|| "None".to_string()),
+                                mirror_options,
+                                Rc::new(move |settings, index| {
+                                    settings.mirror = index
+                                        .checked_sub(1)
+                                        .and_then(|i| mirror_targets.get(i).cloned());
+                                }),
+                                cx,
+                            ),
+                        ),
+                )
+                .child(div().text_xs().text_color(theme.muted_foreground).child(
+                    if settings.mirror.is_some() {
+                        "Mirrored outputs show the same picture and are left out of the layout"
+                            .to_string()
+                    } else {
+                        format!(
+                            "Position {}x{}. Drag the monitor on the canvas to move it.",
+                            settings.x, settings.y
+                        )
+                    },
+                ))
+            })
+    }
+}
+
+// e.g. `2560x1440 @ 165.00 Hz`
+fn format_mode_label(mode: &MonitorMode) -> String {
+    format!(
+        "{}x{} @ {:.2} Hz",
+        mode.width, mode.height, mode.refresh_rate
+    )
+}
+
+// e.g. `1.5 (150%)`
+fn format_scale_label(scale: f64) -> String {
+    format!(
+        "{} ({:.0}%)",
+        format_scale((scale * 100.).round() / 100.),
+        scale * 100.
+    )
+}
+
+impl Render for MonitorsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let settings = self.manager.as_ref().map(|m| m.settings()).unwrap_or(&[]);
+
+        let outputs: Vec<AnyElement> = settings
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| {
+                let status = if !self.is_connected(&monitor.name) {
+                    " (not connected)"
+                } else if !monitor.enabled {
+                    " (disabled)"
+                } else if monitor.mirror.is_some() {
+                    " (mirror)"
+                } else {
+                    ""
+                };
+
+                Button::new(("select-monitor", index))
+                    .label(format!("{}{}", monitor.name, status))
+                    .small()
+                    .when(index == self.selected, |this| this.primary())
+                    .when(index != self.selected, |this| this.ghost())
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.selected = index;
+                        cx.notify();
+                    }))
+                    .into_any_element()
+            })
+            .collect();
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .flex_wrap()
+                    .children(outputs)
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("detect-monitors")
+                            .label("Detect Monitors")
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.load();
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| div().text_sm().text_color(theme.danger).child(msg.clone())),
+            )
+            .when(self.manager.is_some(), |this| {
+                this.child(self.render_canvas(cx))
+            })
+            .children(
+                settings
+                    .get(self.selected)
+                    .map(|selected| self.render_settings(selected, cx)),
+            )
+    }
+}
@@ -0,0 +1,945 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputState},
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::system::hyprland_config::RuleManager;
+use crate::system::hyprland_config::rule_parser::{
+    parse_action, parse_matcher, parse_workspace_option,
+};
+use crate::system::hyprland_config::rule_writer::{
+    format_action, format_matcher, format_workspace_option,
+};
+use crate::system::hyprland_config::rules::{read_clients, rule_from_client};
+use crate::types::hyprland_config::{
+    WindowMatcher, WindowRule, WindowRuleAction, WindowRuleKeyword, WorkspaceRule,
+    WorkspaceRuleOption,
+};
+use crate::ui::config_page::saving::run_config_write;
+
+// Actions offered in the form. The arguments input is appended to the name and
+// parsed, so `size` + `800 600` becomes a typed size rule.
+const ACTIONS: [&str; 11] = [
+    "float",
+    "tile",
+    "fullscreen",
+    "maximize",
+    "pin",
+    "center",
+    "size",
+    "move",
+    "opacity",
+    "workspace",
+    "custom",
+];
+
+fn new_input(
+    value: String,
+    placeholder: &str,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<InputState> {
+    let placeholder = placeholder.to_string();
+    cx.new(|cx| {
+        InputState::new(window, cx)
+            .default_value(value)
+            .placeholder(placeholder)
+    })
+}
+
+// Splits a comma separated input into its non-empty fields
+fn split_fields(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|f| !f.is_empty())
+}
+
+// Form state for adding or editing a window rule
+struct WindowRuleForm {
+    index: Option<usize>,
+    keyword: WindowRuleKeyword,
+    action: &'static str,
+    floating: Option<bool>,
+    xwayland: Option<bool>,
+    args_input: Entity<InputState>,
+    class_input: Entity<InputState>,
+    title_input: Entity<InputState>,
+    initial_class_input: Entity<InputState>,
+    initial_title_input: Entity<InputState>,
+    other_input: Entity<InputState>,
+}
+
+impl WindowRuleForm {
+    fn new(
+        index: Option<usize>,
+        rule: Option<WindowRule>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let rule = rule.unwrap_or_else(|| WindowRule {
+            keyword: Default::default(),
+            action: WindowRuleAction::Float,
+            matchers: Vec::new(),
+        });
+
+        let (action, args) = match rule.action {
+            WindowRuleAction::Float => ("float", String::new()),
+            WindowRuleAction::Tile => ("tile", String::new()),
+            WindowRuleAction::Fullscreen => ("fullscreen", String::new()),
+            WindowRuleAction::Maximize => ("maximize", String::new()),
+            WindowRuleAction::Pin => ("pin", String::new()),
+            WindowRuleAction::Center => ("center", String::new()),
+            WindowRuleAction::Other(ref action) => ("custom", action.clone()),
+            ref action => {
+                let formatted = format_action(action);
+                let (name, args) = formatted.split_once(' ').unwrap_or((&formatted, ""));
+                let name = ACTIONS
+                    .iter()
+                    .find(|a| **a == name)
+                    .copied()
+                    .unwrap_or("custom");
+                (name, args.to_string())
+            }
+        };
+
+        let mut class = String::new();
+        let mut title = String::new();
+        let mut initial_class = String::new();
+        let mut initial_title = String::new();
+        let mut floating = None;
+        let mut xwayland = None;
+        let mut other = Vec::new();
+        for matcher in &rule.matchers {
+            match matcher {
+                WindowMatcher::Class(regex) => class = regex.clone(),
+                WindowMatcher::Title(regex) => title = regex.clone(),
+                WindowMatcher::InitialClass(regex) => initial_class = regex.clone(),
+                WindowMatcher::InitialTitle(regex) => initial_title = regex.clone(),
+                WindowMatcher::Floating(value) => floating = Some(*value),
+                WindowMatcher::XWayland(value) => xwayland = Some(*value),
+                matcher => other.push(format_matcher(matcher)),
+            }
+        }
+
+        Self {
+            index,
+            keyword: rule.keyword,
+            action,
+            floating,
+            xwayland,
+            args_input: new_input(args, "e.g. 800 600", window, cx),
+            class_input: new_input(class, "^(kitty)$", window, cx),
+            title_input: new_input(title, "Optional", window, cx),
+            initial_class_input: new_input(initial_class, "Optional", window, cx),
+            initial_title_input: new_input(initial_title, "Optional", window, cx),
+            other_input: new_input(other.join(", "), "e.g. fullscreen:1, tag:video", window, cx),
+        }
+    }
+
+    fn to_rule(&self, cx: &App) -> Result<WindowRule, String> {
+        let value = |input: &Entity<InputState>| input.read(cx).value().trim().to_string();
+
+        let args = value(&self.args_input);
+        let action_text = match self.action {
+            "custom" => args,
+            action => format!("{} {}", action, args),
+        };
+        if action_text.contains(',') {
+            return Err("Action arguments can not contain commas".to_string());
+        }
+        let action = parse_action(&action_text).ok_or("Action is required")?;
+
+        let mut matchers = Vec::new();
+        for (input, matcher) in [
+            (
+                &self.class_input,
+                WindowMatcher::Class as fn(String) -> WindowMatcher,
+            ),
+            (&self.title_input, WindowMatcher::Title),
+            (&self.initial_class_input, WindowMatcher::InitialClass),
+            (&self.initial_title_input, WindowMatcher::InitialTitle),
+        ] {
+            let regex = value(input);
+            if regex.contains(',') {
+                return Err("Regexes can not contain commas, use . instead".to_string());
+            }
+            if !regex.is_empty() {
+                matchers.push(matcher(regex));
+            }
+        }
+        matchers.extend(self.floating.map(WindowMatcher::Floating));
+        matchers.extend(self.xwayland.map(WindowMatcher::XWayland));
+        matchers.extend(split_fields(&value(&self.other_input)).map(parse_matcher));
+
+        if matchers.is_empty() {
+            return Err("A rule needs at least one matcher".to_string());
+        }
+
+        Ok(WindowRule {
+            keyword: self.keyword,
+            action,
+            matchers,
+        })
+    }
+}
+
+// Form state for adding or editing a workspace rule
+struct WorkspaceRuleForm {
+    index: Option<usize>,
+    default: bool,
+    persistent: bool,
+    workspace_input: Entity<InputState>,
+    monitor_input: Entity<InputState>,
+    on_created_empty_input: Entity<InputState>,
+    other_input: Entity<InputState>,
+}
+
+impl WorkspaceRuleForm {
+    fn new(
+        index: Option<usize>,
+        rule: Option<WorkspaceRule>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let rule = rule.unwrap_or_else(|| WorkspaceRule {
+            workspace: String::new(),
+            options: Vec::new(),
+        });
+
+        let mut monitor = String::new();
+        let mut on_created_empty = String::new();
+        let mut default = false;
+        let mut persistent = false;
+        let mut other = Vec::new();
+        for option in &rule.options {
+            match option {
+                WorkspaceRuleOption::Monitor(value) => monitor = value.clone(),
+                WorkspaceRuleOption::OnCreatedEmpty(value) => on_created_empty = value.clone(),
+                WorkspaceRuleOption::Default(value) => default = *value,
+                WorkspaceRuleOption::Persistent(value) => persistent = *value,
+                option => other.push(format_workspace_option(option)),
+            }
+        }
+
+        Self {
+            index,
+            default,
+            persistent,
+            workspace_input: new_input(rule.workspace, "1, special:scratch, r[1-5]", window, cx),
+            monitor_input: new_input(monitor, "Optional, e.g. DP-1", window, cx),
+            on_created_empty_input: new_input(on_created_empty, "Optional command", window, cx),
+            other_input: new_input(
+                other.join(", "),
+                "e.g. gapsout:0, rounding:false",
+                window,
+                cx,
+            ),
+        }
+    }
+
+    fn to_rule(&self, cx: &App) -> Result<WorkspaceRule, String> {
+        let value = |input: &Entity<InputState>| input.read(cx).value().trim().to_string();
+
+        let workspace = value(&self.workspace_input);
+        let monitor = value(&self.monitor_input);
+        let on_created_empty = value(&self.on_created_empty_input);
+
+        if workspace.is_empty() {
+            return Err("Workspace is required".to_string());
+        }
+        if [&workspace, &monitor, &on_created_empty]
+            .iter()
+            .any(|v| v.contains(','))
+        {
+            return Err("Workspace, monitor and command can not contain commas".to_string());
+        }
+
+        let mut options = Vec::new();
+        if !monitor.is_empty() {
+            options.push(WorkspaceRuleOption::Monitor(monitor));
+        }
+        if self.default {
+            options.push(WorkspaceRuleOption::Default(true));
+        }
+        if self.persistent {
+            options.push(WorkspaceRuleOption::Persistent(true));
+        }
+        if !on_created_empty.is_empty() {
+            options.push(WorkspaceRuleOption::OnCreatedEmpty(on_created_empty));
+        }
+        options.extend(split_fields(&value(&self.other_input)).map(parse_workspace_option));
+
+        Ok(WorkspaceRule { workspace, options })
+    }
+}
+
+pub struct RulesPanel {
+    manager: Option<RuleManager>,
+    window_form: Option<WindowRuleForm>,
+    workspace_form: Option<WorkspaceRuleForm>,
+    error_message: Option<String>,
+    // The save in flight; dropping it drops its result
+    save_task: Option<Task<()>>,
+}
+
+impl RulesPanel {
+    pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
+        let (manager, error_message) = match RuleManager::load() {
+            Ok(manager) => (Some(manager), None),
+            Err(e) => (None, Some(format!("Failed to load rules: {}", e))),
+        };
+
+        Self {
+            manager,
+            window_form: None,
+            workspace_form: None,
+            error_message,
+            save_task: None,
+        }
+    }
+
+    /// Drop a save that hasn't run yet, e.g. before the files are replaced.
+    pub fn cancel_save(&mut self) {
+        self.save_task = None;
+    }
+
+    fn open_window_form(
+        &mut self,
+        index: Option<usize>,
+        rule: Option<WindowRule>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.window_form = Some(WindowRuleForm::new(index, rule, window, cx));
+        self.workspace_form = None;
+        self.error_message = None;
+        cx.notify();
+    }
+
+    fn open_workspace_form(
+        &mut self,
+        index: Option<usize>,
+        rule: Option<WorkspaceRule>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.workspace_form = Some(WorkspaceRuleForm::new(index, rule, window, cx));
+        self.window_form = None;
+        self.error_message = None;
+        cx.notify();
+    }
+
+    fn close_forms(&mut self, cx: &mut Context<Self>) {
+        self.window_form = None;
+        self.workspace_form = None;
+        self.error_message = None;
+        cx.notify();
+    }
+
+    fn submit_window_form(&mut self, cx: &mut Context<Self>) {
+        let (Some(manager), Some(form)) = (self.manager.as_mut(), self.window_form.as_ref()) else {
+            return;
+        };
+
+        match form.to_rule(cx) {
+            Ok(rule) => {
+                manager.set_window_rule(form.index, rule);
+                self.window_form = None;
+                self.save(cx);
+            }
+            Err(e) => {
+                self.error_message = Some(e);
+                cx.notify();
+            }
+        }
+    }
+
+    fn submit_workspace_form(&mut self, cx: &mut Context<Self>) {
+        let (Some(manager), Some(form)) = (self.manager.as_mut(), self.workspace_form.as_ref())
+        else {
+            return;
+        };
+
+        match form.to_rule(cx) {
+            Ok(rule) => {
+                manager.set_workspace_rule(form.index, rule);
+                self.workspace_form = None;
+                self.save(cx);
+            }
+            Err(e) => {
+                self.error_message = Some(e);
+                cx.notify();
+            }
+        }
+    }
+
+    fn edit_manager<F>(&mut self, cx: &mut Context<Self>, f: F)
+    where
+        F: FnOnce(&mut RuleManager),
+    {
+        if let Some(manager) = self.manager.as_mut() {
+            f(manager);
+            // Indices in an open form may no longer point at the same rule
+            self.window_form = None;
+            self.workspace_form = None;
+            self.save(cx);
+        }
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        if let Some(manager) = self.manager.as_ref() {
+            self.save_task = Some(run_config_write(
+                manager.save_write(),
+                cx,
+                |this, result, _cx| {
+                    this.error_message =
+                        result.err().map(|e| format!("Failed to save rules: {}", e));
+                },
+            ));
+        }
+        cx.notify();
+    }
+
+    fn render_field(
+        label: &'static str,
+        input: &Entity<InputState>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .gap_1()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(label),
+            )
+            .child(Input::new(input).small())
+    }
+
+    fn render_form_buttons(
+        id: &'static str,
+        cx: &Context<Self>,
+        submit: fn(&mut Self, &mut Context<Self>),
+    ) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .justify_end()
+            .child(
+                Button::new((id, 0usize))
+                    .label("Cancel")
+                    .small()
+                    .ghost()
+                    .on_click(cx.listener(|this, _, _window, cx| this.close_forms(cx))),
+            )
+            .child(
+                Button::new((id, 1usize))
+                    .label("Save")
+                    .small()
+                    .primary()
+                    .on_click(cx.listener(move |this, _, _window, cx| submit(this, cx))),
+            )
+    }
+
+    fn render_window_form(&self, form: &WindowRuleForm, cx: &Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let panel = cx.entity();
+
+        let action_panel = panel.clone();
+        let action_menu = Button::new("window-rule-action")
+            .label(form.action)
+            .small()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu;
+                for action in ACTIONS {
+                    let panel = action_panel.clone();
+                    menu = menu.item(PopupMenuItem::new(action).on_click(
+                        move |_event, _window, cx| {
+                            panel.update(cx, |this, cx| {
+                                if let Some(form) = this.window_form.as_mut() {
+                                    form.action = action;
+                                    cx.notify();
+                                }
+                            });
+                        },
+                    ));
+                }
+                menu
+            });
+
+        let flag_menu = |id: &'static str,
+                         label: &'static str,
+                         value: Option<bool>,
+                         set: fn(&mut WindowRuleForm, Option<bool>)| {
+            let panel = panel.clone();
+            v_flex()
+                .gap_1()
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child(label),
+                )
+                .child(
+                    Button::new(id)
+                        .label(flag_label(value))
+                        .small()
+                        .dropdown_menu(move |menu, _window, _cx| {
+                            let mut menu = menu;
+                            for option in [None, Some(true), Some(false)] {
+                                let panel = panel.clone();
+                                menu = menu.item(
+                                    PopupMenuItem::new(flag_label(option))
+                                        .checked(option == value)
+                                        .on_click(move |_event, _window, cx| {
+                                            panel.update(cx, |this, cx| {
+                                                if let Some(form) = this.window_form.as_mut() {
+                                                    set(form, option);
+                                                    cx.notify();
+                                                }
+                                            });
+                                        }),
+                                );
+                            }
+                            menu
+                        }),
+                )
+        };
+
+        v_flex()
+            .gap_3()
+            .p_3()
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .child(
+                div()
+                    .text_sm()
+                    .font_semibold()
+                    .child(if form.index.is_some() {
+                        "Edit Window Rule"
+                    } else {
+                        "Add Window Rule"
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_end()
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.muted_foreground)
+                                    .child("Action"),
+                            )
+                            .child(action_menu),
+                    )
+                    .child(Self::render_field("Arguments", &form.args_input, cx)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Self::render_field("Class", &form.class_input, cx))
+                    .child(Self::render_field("Title", &form.title_input, cx)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Self::render_field(
+                        "Initial Class",
+                        &form.initial_class_input,
+                        cx,
+                    ))
+                    .child(Self::render_field(
+                        "Initial Title",
+                        &form.initial_title_input,
+                        cx,
+                    )),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_end()
+                    .child(flag_menu(
+                        "window-rule-floating",
+                        "Floating",
+                        form.floating,
+                        |form, value| form.floating = value,
+                    ))
+                    .child(flag_menu(
+                        "window-rule-xwayland",
+                        "XWayland",
+                        form.xwayland,
+                        |form, value| form.xwayland = value,
+                    ))
+                    .child(Self::render_field("Other Matchers", &form.other_input, cx)),
+            )
+            .child(Self::render_form_buttons(
+                "window-rule-form",
+                cx,
+                Self::submit_window_form,
+            ))
+    }
+
+    fn render_workspace_form(
+        &self,
+        form: &WorkspaceRuleForm,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let theme = cx.theme();
+
+        v_flex()
+            .gap_3()
+            .p_3()
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .child(
+                div()
+                    .text_sm()
+                    .font_semibold()
+                    .child(if form.index.is_some() {
+                        "Edit Workspace Rule"
+                    } else {
+                        "Add Workspace Rule"
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Self::render_field("Workspace", &form.workspace_input, cx))
+                    .child(Self::render_field("Monitor", &form.monitor_input, cx)),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Self::render_field(
+                        "On Created Empty",
+                        &form.on_created_empty_input,
+                        cx,
+                    ))
+                    .child(Self::render_field("Other Options", &form.other_input, cx)),
+            )
+            .child(
+                h_flex()
+                    .gap_4()
+                    .child(
+                        Checkbox::new("workspace-rule-default")
+                            .label("Default for monitor")
+                            .checked(form.default)
+                            .on_click(cx.listener(|this, checked: &bool, _window, cx| {
+                                if let Some(form) = this.workspace_form.as_mut() {
+                                    form.default = *checked;
+                                    cx.notify();
+                                }
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("workspace-rule-persistent")
+                            .label("Persistent")
+                            .checked(form.persistent)
+                            .on_click(cx.listener(|this, checked: &bool, _window, cx| {
+                                if let Some(form) = this.workspace_form.as_mut() {
+                                    form.persistent = *checked;
+                                    cx.notify();
+                                }
+                            })),
+                    ),
+            )
+            .child(Self::render_form_buttons(
+                "workspace-rule-form",
+                cx,
+                Self::submit_workspace_form,
+            ))
+    }
+
+    fn render_from_window_button(&self, cx: &Context<Self>) -> impl IntoElement {
+        let panel = cx.entity();
+
+        Button::new("rule-from-window")
+            .label("From Window")
+            .small()
+            .dropdown_menu(move |menu, _window, _cx| {
+                // Read when the menu opens so it lists the windows open right now
+                let clients = read_clients().unwrap_or_default();
+                if clients.is_empty() {
+                    return menu.item(PopupMenuItem::new("No open windows").disabled(true));
+                }
+
+                let mut menu = menu.scrollable(true).max_h(px(320.));
+                for client in clients {
+                    let panel = panel.clone();
+                    let label = format!("{} — {}", client.class, client.title);
+                    menu = menu.item(PopupMenuItem::new(label).on_click(
+                        move |_event, window, cx| {
+                            let rule = rule_from_client(&client);
+                            panel.update(cx, |this, cx| {
+                                this.open_window_form(None, Some(rule), window, cx);
+                            });
+                        },
+                    ));
+                }
+                menu
+            })
+    }
+}
+
+fn flag_label(value: Option<bool>) -> &'static str {
+    match value {
+        None => "Any",
+        Some(true) => "Yes",
+        Some(false) => "No",
+    }
+}
+
+// How rows of one of the two rule lists reorder and remove their rule
+struct RuleList {
+    id: &'static str,
+    move_rule: fn(&mut RuleManager, usize, usize),
+    remove_rule: fn(&mut RuleManager, usize),
+}
+
+const WINDOW_RULES: RuleList = RuleList {
+    id: "window-rule",
+    move_rule: RuleManager::move_window_rule,
+    remove_rule: RuleManager::remove_window_rule,
+};
+
+const WORKSPACE_RULES: RuleList = RuleList {
+    id: "workspace-rule",
+    move_rule: RuleManager::move_workspace_rule,
+    remove_rule: RuleManager::remove_workspace_rule,
+};
+
+// One line in a rule list with reorder, edit and remove buttons
+fn render_rule_row(
+    list: &RuleList,
+    index: usize,
+    count: usize,
+    title: String,
+    detail: String,
+    cx: &Context<RulesPanel>,
+    on_edit: impl Fn(&mut RulesPanel, &mut Window, &mut Context<RulesPanel>) + 'static,
+) -> AnyElement {
+    let theme = cx.theme();
+    let (id, on_move, on_remove) = (list.id, list.move_rule, list.remove_rule);
+
+    h_flex()
+        .gap_3()
+        .py_1p5()
+        .w_full()
+        .items_center()
+        .border_b_1()
+        .border_color(theme.border.opacity(0.3))
+        .child(
+            div()
+                .w(px(220.))
+                .flex_shrink_0()
+                .text_sm()
+                .font_semibold()
+                .truncate()
+                .child(title),
+        )
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .text_sm()
+                .text_color(theme.muted_foreground)
+                .truncate()
+                .child(detail),
+        )
+        .child(
+            Button::new((id, index * 4))
+                .label("Up")
+                .xsmall()
+                .ghost()
+                .disabled(index == 0)
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.edit_manager(cx, |m| on_move(m, index, index - 1));
+                })),
+        )
+        .child(
+            Button::new((id, index * 4 + 1))
+                .label("Down")
+                .xsmall()
+                .ghost()
+                .disabled(index + 1 >= count)
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.edit_manager(cx, |m| on_move(m, index, index + 1));
+                })),
+        )
+        .child(
+            Button::new((id, index * 4 + 2))
+                .label("Edit")
+                .xsmall()
+                .ghost()
+                .on_click(cx.listener(move |this, _, window, cx| on_edit(this, window, cx))),
+        )
+        .child(
+            Button::new((id, index * 4 + 3))
+                .label("Remove")
+                .xsmall()
+                .ghost()
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.edit_manager(cx, |m| on_remove(m, index));
+                })),
+        )
+        .into_any_element()
+}
+
+impl Render for RulesPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let rules = self
+            .manager
+            .as_ref()
+            .map(|m| m.rules().clone())
+            .unwrap_or_default();
+
+        let window_count = rules.window_rules.len();
+        let window_rows: Vec<AnyElement> = rules
+            .window_rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let edit_rule = rule.clone();
+                render_rule_row(
+                    &WINDOW_RULES,
+                    index,
+                    window_count,
+                    format_action(&rule.action),
+                    rule.matchers
+                        .iter()
+                        .map(format_matcher)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    cx,
+                    move |this, window, cx| {
+                        this.open_window_form(Some(index), Some(edit_rule.clone()), window, cx)
+                    },
+                )
+            })
+            .collect();
+
+        let workspace_count = rules.workspace_rules.len();
+        let workspace_rows: Vec<AnyElement> = rules
+            .workspace_rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let edit_rule = rule.clone();
+                render_rule_row(
+                    &WORKSPACE_RULES,
+                    index,
+                    workspace_count,
+                    format!("Workspace {}", rule.workspace),
+                    rule.options
+                        .iter()
+                        .map(format_workspace_option)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    cx,
+                    move |this, window, cx| {
+                        this.open_workspace_form(Some(index), Some(edit_rule.clone()), window, cx)
+                    },
+                )
+            })
+            .collect();
+
+        let empty = |text: &'static str| {
+            div()
+                .py_2()
+                .text_sm()
+                .text_color(theme.muted_foreground)
+                .child(text)
+        };
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| div().text_sm().text_color(theme.danger).child(msg.clone())),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .font_semibold()
+                            .child("Window Rules"),
+                    )
+                    .child(self.render_from_window_button(cx))
+                    .child(
+                        Button::new("add-window-rule")
+                            .label("Add Window Rule")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_window_form(None, None, window, cx);
+                            })),
+                    ),
+            )
+            .children(
+                self.window_form
+                    .as_ref()
+                    .map(|form| self.render_window_form(form, cx)),
+            )
+            .child(
+                v_flex()
+                    .w_full()
+                    .when(window_rows.is_empty(), |this| {
+                        this.child(empty("No window rules yet"))
+                    })
+                    .children(window_rows),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .pt_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .font_semibold()
+                            .child("Workspace Rules"),
+                    )
+                    .child(
+                        Button::new("add-workspace-rule")
+                            .label("Add Workspace Rule")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_workspace_form(None, None, window, cx);
+                            })),
+                    ),
+            )
+            .children(
+                self.workspace_form
+                    .as_ref()
+                    .map(|form| self.render_workspace_form(form, cx)),
+            )
+            .child(
+                v_flex()
+                    .w_full()
+                    .when(workspace_rows.is_empty(), |this| {
+                        this.child(empty("No workspace rules yet"))
+                    })
+                    .children(workspace_rows),
+            )
+    }
+}
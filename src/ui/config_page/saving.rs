@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gpui::*;
+
+use crate::system::hyprland_config::HyprlandConfigManager;
+use crate::system::hyprland_config::hyprctl_reader::read_from_hyprctl;
+use crate::system::hyprland_config::validation::ConfigWrite;
+
+// Quiet time after the last change before saving, so dragging a slider
+// reloads Hyprland once rather than on every step
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Save the Hyprland config once changes settle, reloading and validating
+/// off the UI thread, then hand the result to `done`. Keep the task until it
+/// finishes; replacing it drops that save in favour of the newer one.
+pub fn save_config_later<V: 'static>(
+    manager: &Rc<RefCell<HyprlandConfigManager>>,
+    cx: &mut Context<V>,
+    done: impl FnOnce(&mut V, Result<(), String>, &mut Context<V>) + 'static,
+) -> Task<()> {
+    let manager = manager.clone();
+    cx.spawn(async move |this, cx| {
+        smol::Timer::after(SAVE_DEBOUNCE).await;
+
+        let pending = manager.borrow().begin_save();
+        let write = pending.write.clone();
+        let result = smol::unblock(move || write.run()).await;
+        let result = manager.borrow_mut().finish_save(pending, result);
+
+        let _ = this.update(cx, |view, cx| {
+            done(view, result, cx);
+            cx.notify();
+        });
+    })
+}
+
+/// Hand the options `ids` back to Omarchy and read the values that take
+/// their place, off the UI thread, then pass the result to `done`.
+pub fn reset_config_later<V: 'static>(
+    manager: &Rc<RefCell<HyprlandConfigManager>>,
+    ids: &[&str],
+    cx: &mut Context<V>,
+    done: impl FnOnce(&mut V, Result<(), String>, &mut Context<V>) + 'static,
+) -> Task<()> {
+    let manager = manager.clone();
+    let pending = manager.borrow().begin_reset(ids);
+    cx.spawn(async move |this, cx| {
+        let write = pending.write.clone();
+        let result = smol::unblock(move || write.run().map(|()| read_from_hyprctl())).await;
+        let result = manager.borrow_mut().finish_reset(pending, result);
+
+        let _ = this.update(cx, |view, cx| {
+            done(view, result, cx);
+            cx.notify();
+        });
+    })
+}
+
+/// Run `write` off the UI thread and hand the result to `done`.
+pub fn run_config_write<V: 'static>(
+    write: ConfigWrite,
+    cx: &mut Context<V>,
+    done: impl FnOnce(&mut V, Result<(), String>, &mut Context<V>) + 'static,
+) -> Task<()> {
+    cx.spawn(async move |this, cx| {
+        let result = smol::unblock(move || write.run()).await;
+        let _ = this.update(cx, |view, cx| {
+            done(view, result, cx);
+            cx.notify();
+        });
+    })
+}
@@ -1,6 +1,9 @@
 pub mod create_theme_dialog;
 pub mod create_waybar_profile_dialog;
+pub mod hyprland_profile_dialogs;
+pub mod install_theme_dialog;
 pub mod manage_waybar_profile_dialogs;
+pub mod theme_bundle_dialogs;
 pub mod theme_creation_progress_dialog;
 
 pub use theme_creation_progress_dialog::open_theme_creation_progress_dialog;
@@ -0,0 +1,272 @@
+use std::rc::Rc;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    input::{Input, InputState},
+    v_flex,
+};
+
+use crate::system::hyprland_config::HyprlandProfiles;
+
+// Called with the profile that should be active once a dialog succeeds
+pub type OnProfileChanged = Rc<dyn Fn(String, &mut Window, &mut App)>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileNameAction {
+    Create,
+    Rename,
+    Duplicate,
+}
+
+impl ProfileNameAction {
+    fn title(self) -> &'static str {
+        match self {
+            ProfileNameAction::Create => "New Hyprland Profile",
+            ProfileNameAction::Rename => "Rename Profile",
+            ProfileNameAction::Duplicate => "Duplicate Profile",
+        }
+    }
+
+    fn confirm_label(self) -> &'static str {
+        match self {
+            ProfileNameAction::Create => "Create",
+            ProfileNameAction::Rename => "Rename",
+            ProfileNameAction::Duplicate => "Duplicate",
+        }
+    }
+
+    fn notification(self, name: &str) -> String {
+        match self {
+            ProfileNameAction::Create => format!("Created profile \"{}\"", name),
+            ProfileNameAction::Rename => format!("Renamed profile to \"{}\"", name),
+            ProfileNameAction::Duplicate => format!("Duplicated profile as \"{}\"", name),
+        }
+    }
+}
+
+/// Ask for a profile name to create a profile, or to rename or duplicate
+/// `profile`.
+pub fn open_hyprland_profile_name_dialog(
+    action: ProfileNameAction,
+    profile: String,
+    on_changed: OnProfileChanged,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let default_value = match action {
+        ProfileNameAction::Create => String::new(),
+        ProfileNameAction::Rename => profile.clone(),
+        ProfileNameAction::Duplicate => format!("{}-copy", profile),
+    };
+    let name_input = cx.new(|cx| {
+        InputState::new(window, cx)
+            .default_value(default_value)
+            .placeholder("e.g. laptop, docked, presentation...")
+    });
+    let error: Entity<Option<String>> = cx.new(|_| None);
+    let focus = name_input.focus_handle(cx);
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        dialog
+            .title(action.title())
+            .w(px(420.))
+            .overlay(true)
+            .keyboard(true)
+            .close_button(true)
+            .overlay_closable(true)
+            .child(ProfileNameForm {
+                action,
+                profile: profile.clone(),
+                name_input: name_input.clone(),
+                error: error.clone(),
+                on_changed: on_changed.clone(),
+            })
+    });
+
+    focus.focus(window);
+}
+
+#[derive(IntoElement)]
+struct ProfileNameForm {
+    action: ProfileNameAction,
+    profile: String,
+    name_input: Entity<InputState>,
+    error: Entity<Option<String>>,
+    on_changed: OnProfileChanged,
+}
+
+impl RenderOnce for ProfileNameForm {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let value = self.name_input.read(cx).value().trim().to_string();
+        let is_empty = value.is_empty();
+        let unchanged = self.action == ProfileNameAction::Rename && value == self.profile;
+        let error_text = self.error.read(cx).clone();
+
+        let action = self.action;
+        let profile = self.profile.clone();
+        let name_input = self.name_input.clone();
+        let error_entity = self.error.clone();
+        let on_changed = self.on_changed.clone();
+
+        v_flex()
+            .p_4()
+            .gap_4()
+            .w_full()
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.muted_foreground)
+                            .child("Profile name"),
+                    )
+                    .child(Input::new(&self.name_input))
+                    .when(error_text.is_some(), |this: gpui::Div| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(theme.danger)
+                                .child(error_text.unwrap_or_default()),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-hyprland-profile")
+                            .label("Cancel")
+                            .ghost()
+                            .on_click(|_, window, cx| {
+                                window.close_dialog(cx);
+                            }),
+                    )
+                    .child(
+                        Button::new("confirm-hyprland-profile")
+                            .label(action.confirm_label())
+                            .primary()
+                            .disabled(is_empty || unchanged)
+                            .on_click(move |_, window: &mut Window, cx| {
+                                let new_name = name_input.read(cx).value().trim().to_string();
+
+                                error_entity.update(cx, |err, cx| {
+                                    *err = None;
+                                    cx.notify();
+                                });
+
+                                let result =
+                                    HyprlandProfiles::open().and_then(|profiles| match action {
+                                        ProfileNameAction::Create => profiles.create(&new_name),
+                                        ProfileNameAction::Rename => {
+                                            profiles.rename(&profile, &new_name)
+                                        }
+                                        ProfileNameAction::Duplicate => {
+                                            profiles.duplicate(&profile, &new_name)
+                                        }
+                                    });
+
+                                match result {
+                                    Ok(name) => {
+                                        window.close_dialog(cx);
+                                        window.push_notification(action.notification(&name), cx);
+                                        on_changed(name, window, cx);
+                                    }
+                                    Err(e) => {
+                                        error_entity.update(cx, |err, cx| {
+                                            *err = Some(e);
+                                            cx.notify();
+                                        });
+                                    }
+                                }
+                            }),
+                    ),
+            )
+    }
+}
+
+pub fn open_delete_hyprland_profile_dialog(
+    profile: String,
+    on_changed: OnProfileChanged,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    window.open_dialog(cx, move |dialog, _, _| {
+        dialog
+            .title("Delete Profile")
+            .w(px(420.))
+            .overlay(true)
+            .keyboard(true)
+            .close_button(true)
+            .overlay_closable(true)
+            .child(DeleteProfileConfirm {
+                profile: profile.clone(),
+                on_changed: on_changed.clone(),
+            })
+    });
+}
+
+#[derive(IntoElement)]
+struct DeleteProfileConfirm {
+    profile: String,
+    on_changed: OnProfileChanged,
+}
+
+impl RenderOnce for DeleteProfileConfirm {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let name = self.profile.clone();
+        let on_changed = self.on_changed.clone();
+
+        v_flex()
+            .p_4()
+            .gap_4()
+            .w_full()
+            .child(div().text_sm().text_color(theme.foreground).child(format!(
+                "Delete profile \"{}\"? This cannot be undone.",
+                self.profile
+            )))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-delete-hyprland-profile")
+                            .label("Cancel")
+                            .ghost()
+                            .on_click(|_, window, cx| {
+                                window.close_dialog(cx);
+                            }),
+                    )
+                    .child(
+                        Button::new("confirm-delete-hyprland-profile")
+                            .label("Delete")
+                            .danger()
+                            .on_click(move |_, window: &mut Window, cx| {
+                                window.close_dialog(cx);
+                                match HyprlandProfiles::open().and_then(|p| p.delete(&name)) {
+                                    Ok(active) => {
+                                        window.push_notification(
+                                            format!("Deleted profile \"{}\"", name),
+                                            cx,
+                                        );
+                                        on_changed(active, window, cx);
+                                    }
+                                    Err(e) => {
+                                        window.push_notification(
+                                            format!("Could not delete: {}", e),
+                                            cx,
+                                        );
+                                    }
+                                }
+                            }),
+                    ),
+            )
+    }
+}
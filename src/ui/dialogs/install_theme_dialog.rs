@@ -0,0 +1,258 @@
+use std::path::PathBuf;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    input::{Input, InputState},
+    v_flex,
+};
+use smol;
+
+use crate::system::themes::theme_install::{
+    ThemeInstallPreview, cancel_theme_install, confirm_theme_install, prepare_theme_install,
+};
+use crate::ui::color_utils::hex_to_hsla;
+use crate::ui::dialogs::create_theme_dialog::PENDING_REFRESH_THEMES;
+
+pub struct InstallThemeDialog {
+    source_input: Entity<InputState>,
+    preview: Option<ThemeInstallPreview>,
+    is_fetching: bool,
+    // Set once the dialog is dismissed, so a preview fetched afterwards is
+    // discarded rather than kept
+    closed: bool,
+    error_message: Option<String>,
+}
+
+impl InstallThemeDialog {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let source_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("https://github.com/user/omarchy-theme or ~/path/to/theme")
+        });
+
+        Self {
+            source_input,
+            preview: None,
+            is_fetching: false,
+            closed: false,
+            error_message: None,
+        }
+    }
+
+    fn fetch(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let source = self.source_input.read(cx).value().trim().to_string();
+        if source.is_empty() {
+            return;
+        }
+
+        self.discard_preview();
+        self.is_fetching = true;
+        self.error_message = None;
+        cx.notify();
+
+        let window_handle = window.window_handle();
+
+        cx.spawn(async move |this, cx| {
+            let result = smol::unblock(move || prepare_theme_install(&source, None)).await;
+
+            let mut result = Some(result);
+            let _ = window_handle.update(cx, |_view, _window, cx| {
+                let _ = this.update(cx, |this, cx| {
+                    this.is_fetching = false;
+                    match result.take() {
+                        Some(Ok(preview)) if this.closed => cancel_theme_install(&preview),
+                        Some(Ok(preview)) => this.preview = Some(preview),
+                        Some(Err(e)) => this.error_message = Some(e),
+                        None => {}
+                    }
+                    cx.notify();
+                });
+            });
+
+            // The dialog is gone, nothing will install or cancel the download
+            if let Some(Ok(preview)) = result {
+                cancel_theme_install(&preview);
+            }
+
+            Ok::<_, anyhow::Error>(())
+        })
+        .detach();
+    }
+
+    fn install(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(preview) = self.preview.as_ref() else {
+            return;
+        };
+
+        match confirm_theme_install(preview) {
+            Ok(name) => {
+                self.preview = None;
+                PENDING_REFRESH_THEMES.with(|flag| {
+                    *flag.borrow_mut() = true;
+                });
+                window.close_dialog(cx);
+                window.push_notification(format!("Installed theme \"{}\"", name), cx);
+                cx.refresh_windows();
+            }
+            Err(e) => {
+                self.error_message = Some(e);
+                cx.notify();
+            }
+        }
+    }
+
+    // Closing by the overlay, Escape or the close button skips the Cancel button
+    fn close(&mut self) {
+        self.closed = true;
+        self.discard_preview();
+    }
+
+    fn discard_preview(&mut self) {
+        if let Some(preview) = self.preview.take() {
+            cancel_theme_install(&preview);
+        }
+    }
+
+    fn render_preview(&self, preview: &ThemeInstallPreview, cx: &Context<Self>) -> Div {
+        let theme = cx.theme();
+        let colors = &preview.colors;
+        let swatches = [
+            &colors.primary.background,
+            &colors.terminal.black,
+            &colors.terminal.red,
+            &colors.terminal.green,
+            &colors.terminal.yellow,
+            &colors.terminal.blue,
+            &colors.terminal.magenta,
+            &colors.terminal.cyan,
+            &colors.terminal.white,
+            &colors.primary.foreground,
+        ];
+
+        v_flex()
+            .gap_2()
+            .p_3()
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(theme.foreground)
+                    .child(preview.title.clone()),
+            )
+            .when(!preview.image.is_empty(), |this| {
+                let path = PathBuf::from(&preview.image);
+                this.child(
+                    div()
+                        .w_full()
+                        .h(px(180.))
+                        .overflow_hidden()
+                        .child(img(path).w_full().h_full().object_fit(ObjectFit::Cover)),
+                )
+            })
+            .child(
+                h_flex().gap_1().children(
+                    swatches
+                        .iter()
+                        .filter_map(|hex| hex_to_hsla(hex))
+                        .map(|color| div().flex_1().h(px(24.)).bg(color)),
+                ),
+            )
+    }
+}
+
+impl Render for InstallThemeDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let is_empty = self.source_input.read(cx).value().trim().is_empty();
+        let has_preview = self.preview.is_some();
+        let preview = self.preview.as_ref().map(|p| self.render_preview(p, cx));
+
+        v_flex()
+            .p_4()
+            .gap_4()
+            .w_full()
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.muted_foreground)
+                            .child("Git URL or local folder"),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(div().flex_1().child(Input::new(&self.source_input)))
+                            .child(
+                                Button::new("fetch-theme")
+                                    .label(if self.is_fetching {
+                                        "Fetching..."
+                                    } else {
+                                        "Preview"
+                                    })
+                                    .disabled(is_empty || self.is_fetching)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.fetch(window, cx);
+                                    })),
+                            ),
+                    )
+                    .when_some(self.error_message.clone(), |this, error| {
+                        this.child(div().text_xs().text_color(theme.danger).child(error))
+                    }),
+            )
+            .children(preview)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-install")
+                            .label("Cancel")
+                            .ghost()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.close();
+                                window.close_dialog(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("confirm-install")
+                            .label("Install")
+                            .primary()
+                            .disabled(!has_preview)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.install(window, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+pub fn open_install_theme_dialog(window: &mut Window, cx: &mut App) {
+    let dialog = cx.new(|cx| InstallThemeDialog::new(window, cx));
+    let focus = dialog.read(cx).source_input.focus_handle(cx);
+
+    window.open_dialog(cx, move |dialog_builder, _, _| {
+        dialog_builder
+            .title("Install Theme")
+            .w(px(560.))
+            .overlay(true)
+            .keyboard(true)
+            .close_button(true)
+            .overlay_closable(true)
+            .on_close({
+                let dialog = dialog.clone();
+                move |_, _, cx| dialog.update(cx, |dialog, _| dialog.close())
+            })
+            .child(dialog.clone())
+    });
+
+    focus.focus(window);
+}
@@ -0,0 +1,70 @@
+use gpui::*;
+use gpui_component::WindowExt;
+use smol;
+
+use crate::system::themes::theme_bundle::{
+    BUNDLE_EXTENSION, export_theme_bundle, import_theme_bundle,
+};
+use crate::ui::dialogs::create_theme_dialog::PENDING_REFRESH_THEMES;
+
+pub fn open_import_theme_picker(window: &mut Window, cx: &mut App) {
+    window
+        .spawn(cx, async move |cx| {
+            // Run the blocking file dialog in a background thread
+            let result = smol::unblock(|| {
+                rfd::FileDialog::new()
+                    .add_filter("Omarchist Theme", &[BUNDLE_EXTENSION])
+                    .set_title("Import Theme")
+                    .pick_file()
+            })
+            .await;
+
+            let Some(path) = result else {
+                return Ok(());
+            };
+
+            match smol::unblock(move || import_theme_bundle(&path)).await {
+                Ok(_) => {
+                    PENDING_REFRESH_THEMES.with(|flag| {
+                        *flag.borrow_mut() = true;
+                    });
+                    cx.update(|_window, cx| cx.refresh_windows())?;
+                }
+                Err(e) => cx.update(|window, cx| {
+                    window.push_notification(format!("Failed to import theme: {}", e), cx)
+                })?,
+            }
+
+            Ok::<_, anyhow::Error>(())
+        })
+        .detach();
+}
+
+pub fn open_export_theme_picker(theme_name: String, window: &mut Window, cx: &mut App) {
+    window
+        .spawn(cx, async move |cx| {
+            let file_name = format!("{}.{}", theme_name, BUNDLE_EXTENSION);
+            let result = smol::unblock(move || {
+                rfd::FileDialog::new()
+                    .add_filter("Omarchist Theme", &[BUNDLE_EXTENSION])
+                    .set_title("Export Theme")
+                    .set_file_name(file_name)
+                    .save_file()
+            })
+            .await;
+
+            let Some(path) = result else {
+                return Ok(());
+            };
+
+            let name = theme_name.clone();
+            let message = match smol::unblock(move || export_theme_bundle(&name, &path)).await {
+                Ok(path) => format!("Exported \"{}\" to {}", theme_name, path.display()),
+                Err(e) => format!("Failed to export theme: {}", e),
+            };
+            cx.update(|window, cx| window.push_notification(message, cx))?;
+
+            Ok::<_, anyhow::Error>(())
+        })
+        .detach();
+}
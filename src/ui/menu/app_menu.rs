@@ -29,6 +29,8 @@ actions!(
         // Theme edit actions
         ThemeEditNextTab,
         ThemeEditPrevTab,
+        ThemeEditUndo,
+        ThemeEditRedo,
         NavigateBack,
     ]
 );
@@ -11,6 +11,8 @@ use crate::ui::menu::app_menu::SelectFont;
 
 pub struct MainTitleBar {
     omarchy_update_available: Option<bool>,
+    // The theme open in the editor, which "Export Theme..." exports
+    edited_theme: Option<String>,
 }
 
 impl Default for MainTitleBar {
@@ -23,16 +25,22 @@ impl MainTitleBar {
     pub fn new() -> Self {
         Self {
             omarchy_update_available: None,
+            edited_theme: None,
         }
     }
 
     pub fn set_omarchy_update_available(&mut self, available: bool) {
         self.omarchy_update_available = Some(available);
     }
+
+    pub fn set_edited_theme(&mut self, theme_name: Option<String>) {
+        self.edited_theme = theme_name;
+    }
 }
 
 impl Render for MainTitleBar {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let edited_theme = self.edited_theme.clone();
         TitleBar::new()
             .child(
                 h_flex()
@@ -66,7 +74,8 @@ impl Render for MainTitleBar {
                             .compact()
                             .ghost()
                             .cursor_pointer()
-                            .dropdown_menu(|menu: PopupMenu, _, _| {
+                            .dropdown_menu(move |menu: PopupMenu, _, _| {
+                                let export_theme = edited_theme.clone();
                                 menu.item(PopupMenuItem::new("Create New Theme")
                                         .on_click(|_, window, cx| {
                                             crate::ui::dialogs::create_theme_dialog::open_create_theme_dialog(window, cx);
@@ -75,8 +84,24 @@ impl Render for MainTitleBar {
                                 .separator()
                                 .menu("Refresh Theme", Box::new(super::app_menu::RefreshTheme))
                                 .separator()
-                                .item(PopupMenuItem::new("Import Theme...").disabled(true))
-                                .item(PopupMenuItem::new("Export Theme...").disabled(true))
+                                .item(PopupMenuItem::new("Install Theme...")
+                                        .on_click(|_, window, cx| {
+                                            crate::ui::dialogs::install_theme_dialog::open_install_theme_dialog(window, cx);
+                                        }),
+                                )
+                                .item(PopupMenuItem::new("Import Theme...")
+                                        .on_click(|_, window, cx| {
+                                            crate::ui::dialogs::theme_bundle_dialogs::open_import_theme_picker(window, cx);
+                                        }),
+                                )
+                                .item(PopupMenuItem::new("Export Theme...")
+                                        .disabled(export_theme.is_none())
+                                        .on_click(move |_, window, cx| {
+                                            if let Some(theme_name) = export_theme.clone() {
+                                                crate::ui::dialogs::theme_bundle_dialogs::open_export_theme_picker(theme_name, window, cx);
+                                            }
+                                        }),
+                                )
                             }),
                     ),
             )
@@ -5,11 +5,12 @@ use crate::system::config::config_setup::{read_settings, save_settings};
 use crate::ui::menu::app_menu;
 
 const KEY_CONTEXT: &str = "SettingsPage";
-/// Number of keyboard-navigable settings rows (currently just one).
-const SETTINGS_ITEM_COUNT: usize = 1;
+/// Number of keyboard-navigable settings rows.
+const SETTINGS_ITEM_COUNT: usize = 2;
 
 pub struct SettingsView {
     auto_apply_theme: bool,
+    theme_snapshots: bool,
     pub focus_handle: FocusHandle,
     /// Which settings row currently has keyboard focus (`None` = none).
     focused_index: Option<usize>,
@@ -18,12 +19,19 @@ pub struct SettingsView {
 impl SettingsView {
     /// Constructor intended to be passed directly to `cx.new(...)`.
     pub fn new(cx: &mut Context<Self>) -> Self {
-        let auto_apply_theme = read_settings()
+        let settings = read_settings().ok();
+        let auto_apply_theme = settings
+            .as_ref()
             .map(|s| s.settings.auto_apply_theme)
             .unwrap_or(false);
+        let theme_snapshots = settings
+            .as_ref()
+            .map(|s| s.settings.theme_snapshots)
+            .unwrap_or(false);
 
         Self {
             auto_apply_theme,
+            theme_snapshots,
             focus_handle: cx.focus_handle(),
             focused_index: None,
         }
@@ -44,10 +52,25 @@ impl SettingsView {
         cx.notify();
     }
 
+    fn toggle_theme_snapshots(
+        &mut self,
+        checked: bool,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.theme_snapshots = checked;
+
+        if let Err(e) = save_theme_snapshots(checked) {
+            eprintln!("Failed to save theme_snapshots: {}", e);
+        }
+
+        cx.notify();
+    }
+
     fn handle_next_focus(&mut self, cx: &mut Context<Self>) {
         self.focused_index = Some(match self.focused_index {
             None => 0,
-            Some(_i) => SETTINGS_ITEM_COUNT - 1,
+            Some(i) => (i + 1).min(SETTINGS_ITEM_COUNT - 1),
         });
         cx.notify();
     }
@@ -61,9 +84,16 @@ impl SettingsView {
     }
 
     fn handle_activate(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        if let Some(0) = self.focused_index {
-            let new_val = !self.auto_apply_theme;
-            self.toggle_auto_apply_theme(new_val, window, cx);
+        match self.focused_index {
+            Some(0) => {
+                let new_val = !self.auto_apply_theme;
+                self.toggle_auto_apply_theme(new_val, window, cx);
+            }
+            Some(1) => {
+                let new_val = !self.theme_snapshots;
+                self.toggle_theme_snapshots(new_val, window, cx);
+            }
+            _ => {}
         }
     }
 
@@ -79,11 +109,19 @@ fn save_auto_apply_theme(value: bool) -> Result<(), String> {
     save_settings(&settings)
 }
 
+fn save_theme_snapshots(value: bool) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    settings.settings.theme_snapshots = value;
+    save_settings(&settings)
+}
+
 impl Render for SettingsView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
         let auto_apply_theme = self.auto_apply_theme;
         let row_focused = self.focused_index == Some(0);
+        let theme_snapshots = self.theme_snapshots;
+        let snapshots_row_focused = self.focused_index == Some(1);
 
         v_flex()
             .id("settings-page")
@@ -169,6 +207,43 @@ impl Render for SettingsView {
                                         this.toggle_auto_apply_theme(*checked, window, cx);
                                     })),
                             ),
+                    )
+                    .child(
+                        // theme_snapshots row — keyboard focus ring when focused_index == 1
+                        h_flex()
+                            .gap_3()
+                            .items_center()
+                            .justify_between()
+                            .p_4()
+                            .rounded(theme.radius)
+                            .border_1()
+                            .border_color(if snapshots_row_focused {
+                                theme.ring
+                            } else {
+                                theme.border
+                            })
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .flex_1()
+                                    .child(
+                                        Label::new("Keep theme snapshots")
+                                            .font_weight(FontWeight::MEDIUM),
+                                    )
+                                    .child(
+                                        div().text_sm().text_color(theme.muted_foreground).child(
+                                            "Save a revision of the theme files on every edit so it can be compared and restored",
+                                        ),
+                                    ),
+                            )
+                            .child(
+                                Switch::new("theme-snapshots")
+                                    .checked(theme_snapshots)
+                                    .cursor_pointer()
+                                    .on_click(cx.listener(|this, checked, window, cx| {
+                                        this.toggle_theme_snapshots(*checked, window, cx);
+                                    })),
+                            ),
                     ),
             )
     }
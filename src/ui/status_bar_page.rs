@@ -1,9 +1,11 @@
 pub mod bar_settings;
+pub mod custom_module_builder;
 pub mod design_area;
 pub mod header;
 pub mod module_editor;
 pub mod module_library;
 pub mod shared;
 pub mod status_bar_view;
+pub mod style_editor;
 pub mod waybar_item;
 pub mod waybar_preview;
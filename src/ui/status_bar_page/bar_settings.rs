@@ -8,11 +8,12 @@ use gpui_component::{
     v_flex,
 };
 
-use crate::system::waybar::{BarSettings, get_bar_settings, set_bar_setting};
+use crate::system::waybar::{BarSettings, get_bar_settings, remove_bar_setting, set_bar_setting};
 use crate::ui::status_bar_page::shared::labeled_input;
 
 pub struct BarSettingsPanel {
     profile_name: String,
+    bar: usize,
     is_read_only: bool,
     settings: BarSettings,
     expanded: bool,
@@ -40,11 +41,12 @@ const LAYERS: &[&str] = &["top", "bottom", "overlay", "background"];
 impl BarSettingsPanel {
     pub fn new(
         profile_name: &str,
+        bar: usize,
         is_read_only: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        let settings = load_settings(profile_name, is_read_only).unwrap_or_default();
+        let settings = load_settings(profile_name, bar, is_read_only).unwrap_or_default();
 
         let mk_input = |val: String, placeholder: &str, window: &mut Window, cx: &mut App| {
             cx.new(|cx| {
@@ -129,6 +131,7 @@ impl BarSettingsPanel {
 
         let subscriptions = Self::build_subscriptions(
             profile_name,
+            bar,
             &height_input,
             &spacing_input,
             &output_input,
@@ -144,6 +147,7 @@ impl BarSettingsPanel {
 
         Self {
             profile_name: profile_name.to_string(),
+            bar,
             is_read_only,
             settings,
             expanded: false,
@@ -163,6 +167,7 @@ impl BarSettingsPanel {
     #[allow(clippy::too_many_arguments)]
     fn build_subscriptions(
         profile_name: &str,
+        bar: usize,
         height_input: &Entity<InputState>,
         spacing_input: &Entity<InputState>,
         output_input: &Entity<InputState>,
@@ -189,7 +194,13 @@ impl BarSettingsPanel {
                         if matches!(event, InputEvent::Change) {
                             let val = input_ref.read(cx).value().to_string();
                             if let Some(v) = $parse(&val) {
-                                if let Err(e) = set_bar_setting(&p, $key, &v) {
+                                // Null removes the key so Waybar uses its default
+                                let result = if v.is_null() {
+                                    remove_bar_setting(&p, bar, $key)
+                                } else {
+                                    set_bar_setting(&p, bar, $key, &v)
+                                };
+                                if let Err(e) = result {
                                     eprintln!("Bar settings save error: {}", e);
                                 }
                             }
@@ -209,7 +220,7 @@ impl BarSettingsPanel {
                     move |_this, _select, event: &SelectEvent<Vec<SharedString>>, _window, _cx| {
                         if let SelectEvent::Confirm(Some(val)) = event {
                             let v = serde_json::Value::from(val.to_string());
-                            if let Err(e) = set_bar_setting(&p, $key, &v) {
+                            if let Err(e) = set_bar_setting(&p, bar, $key, &v) {
                                 eprintln!("Bar settings save error: {}", e);
                             }
                         }
@@ -272,7 +283,7 @@ impl BarSettingsPanel {
             sub!(output_input, "output", |s: &str| {
                 let t = s.trim();
                 if t.is_empty() {
-                    None
+                    Some(serde_json::Value::Null)
                 } else {
                     Some(serde_json::Value::from(t.to_string()))
                 }
@@ -301,13 +312,15 @@ impl BarSettingsPanel {
     pub fn reload(
         &mut self,
         profile_name: &str,
+        bar: usize,
         is_read_only: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         self.profile_name = profile_name.to_string();
+        self.bar = bar;
         self.is_read_only = is_read_only;
-        let settings = load_settings(profile_name, is_read_only).unwrap_or_default();
+        let settings = load_settings(profile_name, bar, is_read_only).unwrap_or_default();
 
         macro_rules! update_input {
             ($field:expr, $val:expr) => {
@@ -378,9 +391,10 @@ impl BarSettingsPanel {
 
         self.settings = settings;
 
-        // Rebuild subscriptions so they point at the new profile name
+        // Rebuild subscriptions so they point at the new profile and bar
         self._subscriptions = Self::build_subscriptions(
             profile_name,
+            bar,
             &self.height_input,
             &self.spacing_input,
             &self.output_input,
@@ -599,7 +613,7 @@ impl Render for BarSettingsPanel {
     }
 }
 
-fn load_settings(profile_name: &str, is_read_only: bool) -> Option<BarSettings> {
+fn load_settings(profile_name: &str, bar: usize, is_read_only: bool) -> Option<BarSettings> {
     let _ = is_read_only;
-    get_bar_settings(profile_name)
+    get_bar_settings(profile_name, bar)
 }
@@ -0,0 +1,350 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::system::waybar::{
+    CustomModule, WaybarZone, add_custom_module, check_custom_output, run_custom_module_once,
+};
+use crate::ui::status_bar_page::shared::{labeled_input, labeled_input_wide};
+use crate::ui::status_bar_page::waybar_preview::WaybarPreview;
+
+const ZONES: &[(WaybarZone, &str)] = &[
+    (WaybarZone::Left, "Left"),
+    (WaybarZone::Center, "Center"),
+    (WaybarZone::Right, "Right"),
+];
+
+// Form for creating a `custom/<name>` module, shown in the module library
+pub struct CustomModuleBuilder {
+    profile_name: String,
+    bar: usize,
+    preview: Entity<WaybarPreview>,
+    name_input: Entity<InputState>,
+    format_input: Entity<InputState>,
+    exec_input: Entity<InputState>,
+    script_input: Entity<InputState>,
+    interval_input: Entity<InputState>,
+    signal_input: Entity<InputState>,
+    on_click_input: Entity<InputState>,
+    json: bool,
+    zone: WaybarZone,
+    running: bool,
+    // Outcome of the last "Run Once": the line printed and its problems
+    test_result: Option<Result<(String, Vec<String>), String>>,
+    error_message: Option<String>,
+}
+
+impl CustomModuleBuilder {
+    pub fn new(
+        profile_name: &str,
+        preview: Entity<WaybarPreview>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut input = |placeholder: &str| {
+            let placeholder = placeholder.to_string();
+            cx.new(|cx| InputState::new(window, cx).placeholder(placeholder))
+        };
+        let name_input = input("e.g. updates");
+        let format_input = input("e.g. {icon} {}");
+        let exec_input = input("e.g. checkupdates | wc -l");
+        let interval_input = input("Runs continuously");
+        let signal_input = input("e.g. 8");
+        let on_click_input = input("e.g. omarchy-update");
+        let script_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(6)
+                .placeholder("Optional. Stored in the profile and run instead of the command.")
+        });
+
+        Self {
+            profile_name: profile_name.to_string(),
+            bar: 0,
+            preview,
+            name_input,
+            format_input,
+            exec_input,
+            script_input,
+            interval_input,
+            signal_input,
+            on_click_input,
+            json: false,
+            zone: WaybarZone::Right,
+            running: false,
+            test_result: None,
+            error_message: None,
+        }
+    }
+
+    pub fn switch_profile(&mut self, profile_name: &str) {
+        self.profile_name = profile_name.to_string();
+        self.bar = 0;
+    }
+
+    pub fn select_bar(&mut self, bar: usize) {
+        self.bar = bar;
+    }
+
+    // The module as currently entered
+    fn module(&self, cx: &App) -> Result<CustomModule, String> {
+        let text = |input: &Entity<InputState>| input.read(cx).value().trim().to_string();
+        let optional = |input: &Entity<InputState>| Some(text(input)).filter(|s| !s.is_empty());
+        let number = |input: &Entity<InputState>, field: &str| {
+            optional(input)
+                .map(|s| {
+                    s.parse::<u32>()
+                        .map_err(|_| format!("{} must be a whole number", field))
+                })
+                .transpose()
+        };
+
+        Ok(CustomModule {
+            name: text(&self.name_input),
+            exec: text(&self.exec_input),
+            script: optional(&self.script_input),
+            json: self.json,
+            interval: number(&self.interval_input, "Interval")?,
+            signal: number(&self.signal_input, "Signal")?,
+            on_click: optional(&self.on_click_input),
+            format: optional(&self.format_input),
+        })
+    }
+
+    fn run_once(&mut self, cx: &mut Context<Self>) {
+        let module = match self.module(cx) {
+            Ok(module) => module,
+            Err(e) => {
+                self.test_result = Some(Err(e));
+                cx.notify();
+                return;
+            }
+        };
+        if module.script.is_none() && module.exec.is_empty() {
+            self.test_result = Some(Err("Enter a command or a script to run".to_string()));
+            cx.notify();
+            return;
+        }
+
+        self.running = true;
+        self.test_result = None;
+        cx.notify();
+
+        let profile_name = self.profile_name.clone();
+        cx.spawn(async move |this, cx| {
+            let result = run_custom_module_once(&profile_name, &module).await;
+            let _ = this.update(cx, |this, cx| {
+                this.running = false;
+                this.test_result = Some(result.map(|line| {
+                    let problems = check_custom_output(&line, module.json);
+                    (line, problems)
+                }));
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn add(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let result = self.module(cx).and_then(|module| {
+            add_custom_module(&self.profile_name, self.bar, &module, &self.zone)
+        });
+        match result {
+            Ok(key) => {
+                let profile_name = self.profile_name.clone();
+                self.preview.update(cx, |preview, cx| {
+                    preview.reload(&profile_name, false);
+                    cx.notify();
+                });
+                window.push_notification(format!("Added {} to bar", key), cx);
+                self.clear(window, cx);
+            }
+            Err(e) => self.error_message = Some(e),
+        }
+        cx.notify();
+    }
+
+    fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for input in [
+            &self.name_input,
+            &self.format_input,
+            &self.exec_input,
+            &self.script_input,
+            &self.interval_input,
+            &self.signal_input,
+            &self.on_click_input,
+        ] {
+            input.update(cx, |state, cx| state.set_value("", window, cx));
+        }
+        self.json = false;
+        self.test_result = None;
+        self.error_message = None;
+    }
+
+    fn render_zone_menu(&self, cx: &mut Context<Self>) -> AnyElement {
+        let current = self.zone.clone();
+        let label = ZONES
+            .iter()
+            .find(|(zone, _)| *zone == current)
+            .map_or("Left", |(_, label)| label);
+        let builder = cx.entity();
+
+        Button::new("custom-module-zone")
+            .label(format!("Zone: {}", label))
+            .small()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu;
+                for (zone, label) in ZONES {
+                    let builder = builder.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(*label)
+                            .checked(*zone == current)
+                            .on_click(move |_event, _window, cx| {
+                                builder.update(cx, |this, cx| {
+                                    this.zone = zone.clone();
+                                    cx.notify();
+                                });
+                            }),
+                    );
+                }
+                menu
+            })
+            .into_any_element()
+    }
+
+    fn render_test_result(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = cx.theme();
+        let Some(result) = &self.test_result else {
+            return div().into_any_element();
+        };
+
+        match result {
+            Err(error) => div()
+                .text_xs()
+                .text_color(theme.danger)
+                .child(error.clone())
+                .into_any_element(),
+            Ok((line, problems)) => v_flex()
+                .gap_1()
+                .child(
+                    div()
+                        .text_xs()
+                        .font_family("monospace")
+                        .text_color(theme.foreground)
+                        .child(format!("Output: {}", line)),
+                )
+                .when(problems.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(theme.success)
+                            .child("The output is in the form Waybar expects."),
+                    )
+                })
+                .children(problems.iter().map(|problem| {
+                    div()
+                        .text_xs()
+                        .text_color(theme.warning)
+                        .child(problem.clone())
+                }))
+                .into_any_element(),
+        }
+    }
+}
+
+impl Render for CustomModuleBuilder {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let zone_menu = self.render_zone_menu(cx);
+        let test_result = self.render_test_result(cx);
+        let theme = cx.theme();
+        let muted = theme.muted_foreground;
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .child(div().text_xs().text_color(muted).child(
+                "Creates a custom/<name> module that shows the output of a command or script.",
+            ))
+            .child(
+                h_flex()
+                    .gap_4()
+                    .flex_wrap()
+                    .items_end()
+                    .child(labeled_input("Name", &self.name_input, muted, false))
+                    .child(labeled_input("Format", &self.format_input, muted, false))
+                    .child(labeled_input_wide(
+                        "Command",
+                        &self.exec_input,
+                        muted,
+                        false,
+                    )),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(Label::new("Script").text_sm().text_color(muted))
+                    .child(Input::new(&self.script_input).small()),
+            )
+            .child(
+                h_flex()
+                    .gap_4()
+                    .flex_wrap()
+                    .items_end()
+                    .child(labeled_input(
+                        "Interval (seconds)",
+                        &self.interval_input,
+                        muted,
+                        false,
+                    ))
+                    .child(labeled_input("Signal", &self.signal_input, muted, false))
+                    .child(labeled_input_wide(
+                        "On click",
+                        &self.on_click_input,
+                        muted,
+                        false,
+                    )),
+            )
+            .child(
+                Checkbox::new("custom-module-json")
+                    .checked(self.json)
+                    .label("Prints JSON (return-type: json)")
+                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                        this.json = *checked;
+                        cx.notify();
+                    })),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(zone_menu)
+                    .child(
+                        Button::new("custom-module-run")
+                            .label("Run Once")
+                            .small()
+                            .loading(self.running)
+                            .on_click(cx.listener(|this, _, _, cx| this.run_once(cx))),
+                    )
+                    .child(
+                        Button::new("custom-module-add")
+                            .label("Add Module")
+                            .small()
+                            .primary()
+                            .on_click(cx.listener(|this, _, window, cx| this.add(window, cx))),
+                    ),
+            )
+            .child(test_result)
+            .when_some(self.error_message.clone(), |this, error| {
+                this.child(div().text_xs().text_color(theme.danger).child(error))
+            })
+    }
+}
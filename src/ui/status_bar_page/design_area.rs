@@ -1,25 +1,45 @@
-// TODO: Re-enable when Add Module feature is ready
+use std::fs;
+use std::time::SystemTime;
+
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
-    ActiveTheme,
-    // IconName, Sizable,
-    // button::{Button, ButtonVariants as _},
-    // h_flex,
+    ActiveTheme, Disableable, Icon, IconName, Sizable, StyledExt, WindowExt,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
     v_flex,
 };
 
+use crate::system::hyprland_config::monitors::read_monitors;
+use crate::system::waybar::{
+    Diagnostic, Severity, add_waybar_bar, has_errors, remove_bar_setting, remove_waybar_bar,
+    set_bar_setting, validate_waybar_profile, waybar_profile_config_path,
+};
 use crate::ui::status_bar_page::bar_settings::BarSettingsPanel;
 use crate::ui::status_bar_page::module_editor::{ModuleEditorPanel, take_pending_module_edit};
 use crate::ui::status_bar_page::module_library::ModuleLibraryPanel;
+use crate::ui::status_bar_page::style_editor::StyleEditorPanel;
 use crate::ui::status_bar_page::waybar_preview::WaybarPreview;
 
 pub struct DesignArea {
     profile_name: String,
     is_read_only: bool,
+    // Index of the bar being edited, for configs with one bar per monitor or
+    // a top and a bottom bar
+    bar: usize,
+    // Connected monitors, to offer as bar outputs
+    outputs: Vec<String>,
     preview: Entity<WaybarPreview>,
     bar_settings: Entity<BarSettingsPanel>,
     module_editor: Entity<ModuleEditorPanel>,
     module_library: Entity<ModuleLibraryPanel>,
+    style_editor: Entity<StyleEditorPanel>,
+    // Problems in the profile's config, and the profile and file time they
+    // were found for
+    diagnostics: Vec<Diagnostic>,
+    diagnostics_checked: Option<(String, SystemTime)>,
+    _observers: Vec<Subscription>,
 }
 
 impl DesignArea {
@@ -33,7 +53,7 @@ impl DesignArea {
         let preview = cx.new(|_| WaybarPreview::new(&name, is_read_only));
         let bar_settings = {
             let n = name.clone();
-            cx.new(|cx| BarSettingsPanel::new(&n, is_read_only, window, cx))
+            cx.new(|cx| BarSettingsPanel::new(&n, 0, is_read_only, window, cx))
         };
         let module_editor = {
             let n = name.clone();
@@ -45,13 +65,33 @@ impl DesignArea {
             let lib = ModuleLibraryPanel::new(&n, is_read_only, p, window, cx);
             cx.new(|_| lib)
         };
+        let style_editor = {
+            let n = name.clone();
+            let module_keys = module_keys(&preview, cx);
+            cx.new(|cx| StyleEditorPanel::new(&n, is_read_only, module_keys, window, cx))
+        };
+        let outputs = read_monitors()
+            .map(|monitors| monitors.into_iter().map(|m| m.name).collect())
+            .unwrap_or_default();
+        // Re-render with fresh diagnostics after panels that edit the config
+        let observers = vec![
+            cx.observe(&preview, |_, _, cx| cx.notify()),
+            cx.observe(&bar_settings, |_, _, cx| cx.notify()),
+            cx.observe(&module_editor, |_, _, cx| cx.notify()),
+        ];
         Self {
             profile_name: name,
             is_read_only,
+            bar: 0,
+            outputs,
             preview,
             bar_settings,
             module_editor,
             module_library,
+            style_editor,
+            diagnostics: Vec::new(),
+            diagnostics_checked: None,
+            _observers: observers,
         }
     }
 
@@ -64,18 +104,272 @@ impl DesignArea {
     ) {
         self.profile_name = profile_name.to_string();
         self.is_read_only = is_read_only;
+        self.bar = 0;
         self.preview.update(cx, |preview, _| {
             preview.reload(profile_name, is_read_only);
+            preview.select_bar(0);
         });
         self.bar_settings.update(cx, |panel, cx| {
-            panel.reload(profile_name, is_read_only, window, cx);
+            panel.reload(profile_name, 0, is_read_only, window, cx);
         });
         self.module_editor.update(cx, |editor, _| {
             editor.switch_profile(profile_name, is_read_only);
         });
-        self.module_library.update(cx, |lib, _| {
-            lib.switch_profile(profile_name, is_read_only);
+        self.module_library.update(cx, |lib, cx| {
+            lib.switch_profile(profile_name, is_read_only, cx);
+        });
+        let module_keys = module_keys(&self.preview, cx);
+        self.style_editor.update(cx, |panel, cx| {
+            panel.reload(profile_name, is_read_only, module_keys, window, cx);
+        });
+    }
+
+    pub fn select_bar(&mut self, bar: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let profile_name = self.profile_name.clone();
+        let is_read_only = self.is_read_only;
+
+        // Reload too, since adding or removing a bar changes the list
+        self.bar = self.preview.update(cx, |preview, cx| {
+            preview.reload(&profile_name, is_read_only);
+            preview.select_bar(bar);
+            cx.notify();
+            preview.bar
+        });
+        let bar = self.bar;
+        self.bar_settings.update(cx, |panel, cx| {
+            panel.reload(&profile_name, bar, is_read_only, window, cx);
+        });
+        self.module_editor.update(cx, |editor, _| {
+            editor.select_bar(bar);
+        });
+        self.module_library.update(cx, |lib, cx| {
+            lib.select_bar(bar, cx);
+        });
+        let module_keys = module_keys(&self.preview, cx);
+        self.style_editor.update(cx, |panel, cx| {
+            panel.reload(&profile_name, is_read_only, module_keys, window, cx);
         });
+        cx.notify();
+    }
+
+    fn add_bar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match add_waybar_bar(&self.profile_name) {
+            Ok(bar) => self.select_bar(bar, window, cx),
+            Err(e) => window.push_notification(format!("Could not add bar: {}", e), cx),
+        }
+    }
+
+    fn remove_bar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match remove_waybar_bar(&self.profile_name, self.bar) {
+            Ok(()) => self.select_bar(self.bar.saturating_sub(1), window, cx),
+            Err(e) => window.push_notification(format!("Could not remove bar: {}", e), cx),
+        }
+    }
+
+    // `None` puts the bar on every monitor
+    fn set_output(&mut self, output: Option<String>, window: &mut Window, cx: &mut Context<Self>) {
+        let result = match output {
+            Some(output) => set_bar_setting(
+                &self.profile_name,
+                self.bar,
+                "output",
+                &serde_json::Value::from(output),
+            ),
+            None => remove_bar_setting(&self.profile_name, self.bar, "output"),
+        };
+        match result {
+            Ok(()) => self.select_bar(self.bar, window, cx),
+            Err(e) => window.push_notification(format!("Could not set output: {}", e), cx),
+        }
+    }
+
+    // Check the config again whenever its file changes, since every panel
+    // writes to it
+    fn refresh_diagnostics(&mut self) {
+        let modified = waybar_profile_config_path(&self.profile_name)
+            .and_then(|path| fs::metadata(path).ok())
+            .and_then(|meta| meta.modified().ok())
+            .map(|time| (self.profile_name.clone(), time));
+        if modified.is_some() && modified == self.diagnostics_checked {
+            return;
+        }
+        self.diagnostics_checked = modified;
+        self.diagnostics = validate_waybar_profile(&self.profile_name).unwrap_or_default();
+    }
+
+    fn render_diagnostics(&self, cx: &mut Context<Self>) -> AnyElement {
+        if self.diagnostics.is_empty() {
+            return div().into_any_element();
+        }
+
+        let theme = cx.theme();
+        let errors = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        let color = if has_errors(&self.diagnostics) {
+            theme.danger
+        } else {
+            theme.warning
+        };
+        let summary = if errors > 0 {
+            format!(
+                "{} in config.jsonc. This profile won't be applied until {} fixed.",
+                count_label(errors, "error"),
+                if errors == 1 { "it is" } else { "they are" }
+            )
+        } else {
+            format!(
+                "{} in config.jsonc.",
+                count_label(self.diagnostics.len(), "warning")
+            )
+        };
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .p_3()
+            .rounded_md()
+            .border_1()
+            .border_color(color.opacity(0.6))
+            .bg(color.opacity(0.08))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(Icon::new(IconName::TriangleAlert).text_color(color))
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_semibold()
+                            .text_color(theme.foreground)
+                            .child(summary),
+                    ),
+            )
+            .children(self.diagnostics.iter().map(|diagnostic| {
+                div()
+                    .text_xs()
+                    .text_color(match diagnostic.severity {
+                        Severity::Error => theme.danger,
+                        Severity::Warning => theme.warning,
+                    })
+                    .child(diagnostic.to_string())
+            }))
+            .into_any_element()
+    }
+
+    fn render_bar_toolbar(&self, cx: &mut Context<Self>) -> AnyElement {
+        let preview = self.preview.read(cx);
+        let bar_labels: Vec<String> = preview
+            .config
+            .as_ref()
+            .map(|config| {
+                config
+                    .bars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, bar)| match &bar.settings.output {
+                        Some(output) => format!("Bar {} · {}", i + 1, output),
+                        None => format!("Bar {}", i + 1),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let current_output = preview
+            .current_bar()
+            .and_then(|bar| bar.settings.output.clone());
+
+        let selected = self.bar;
+        let read_only = self.is_read_only;
+        let bar_count = bar_labels.len();
+        let entity = cx.entity();
+
+        let bar_buttons: Vec<Button> = bar_labels
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let entity = entity.clone();
+                Button::new(("waybar-bar", i))
+                    .label(label)
+                    .small()
+                    .when(i == selected, |b: Button| b.primary())
+                    .when(i != selected, |b: Button| b.ghost())
+                    .on_click(move |_, window, cx| {
+                        entity.update(cx, |area, cx| area.select_bar(i, window, cx));
+                    })
+            })
+            .collect();
+
+        let outputs = self.outputs.clone();
+        let output_entity = entity.clone();
+        let output_button = Button::new("waybar-bar-output")
+            .label(format!(
+                "Output: {}",
+                current_output.as_deref().unwrap_or("All monitors")
+            ))
+            .small()
+            .disabled(read_only || bar_count == 0)
+            .dropdown_menu(move |menu, _window, _cx| {
+                let entity = output_entity.clone();
+                let mut menu = menu.item(
+                    PopupMenuItem::new("All monitors")
+                        .checked(current_output.is_none())
+                        .on_click(move |_event, window, cx| {
+                            entity.update(cx, |area, cx| area.set_output(None, window, cx));
+                        }),
+                );
+                for output in outputs.iter() {
+                    let entity = output_entity.clone();
+                    let output = output.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(output.clone())
+                            .checked(current_output.as_deref() == Some(output.as_str()))
+                            .on_click(move |_event, window, cx| {
+                                let output = output.clone();
+                                entity.update(cx, |area, cx| {
+                                    area.set_output(Some(output), window, cx)
+                                });
+                            }),
+                    );
+                }
+                menu
+            });
+
+        let add_entity = entity.clone();
+        let remove_entity = entity;
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_center()
+            .flex_wrap()
+            .children(bar_buttons)
+            .child(
+                Button::new("waybar-add-bar")
+                    .icon(IconName::Plus)
+                    .label("Add Bar")
+                    .small()
+                    .ghost()
+                    .disabled(read_only || bar_count == 0)
+                    .on_click(move |_, window, cx| {
+                        add_entity.update(cx, |area, cx| area.add_bar(window, cx));
+                    }),
+            )
+            .child(div().flex_1())
+            .child(output_button)
+            .child(
+                Button::new("waybar-remove-bar")
+                    .icon(IconName::Delete)
+                    .label("Remove Bar")
+                    .small()
+                    .ghost()
+                    .disabled(read_only || bar_count < 2)
+                    .on_click(move |_, window, cx| {
+                        remove_entity.update(cx, |area, cx| area.remove_bar(window, cx));
+                    }),
+            )
+            .into_any_element()
     }
 }
 
@@ -93,21 +387,23 @@ impl Render for DesignArea {
             });
         }
 
+        self.refresh_diagnostics();
+        let diagnostics = self.render_diagnostics(cx);
+        let bar_toolbar = self.render_bar_toolbar(cx);
         let theme = cx.theme();
-        // TODO: Re-enable when Add Module feature is ready
-        // let library_entity = self.module_library.clone();
-        // let library_open = self.module_library.read(cx).is_open();
-
-        // TODO: Add Module button is disabled for now — re-enable when the feature is ready
-        // let add_module_btn = Button::new("add-module-btn")
-        //     .icon(IconName::Plus)
-        //     .label("Add Module")
-        //     .small()
-        //     .when(library_open, |b: Button| b.primary())
-        //     .when(!library_open, |b: Button| b.ghost())
-        //     .on_click(move |_, _window: &mut Window, cx| {
-        //         library_entity.update(cx, |lib, cx| lib.toggle(cx));
-        //     });
+        let library_entity = self.module_library.clone();
+        let library_open = self.module_library.read(cx).is_open();
+
+        let add_module_btn = Button::new("add-module-btn")
+            .icon(IconName::Plus)
+            .label("Add Module")
+            .small()
+            .disabled(self.is_read_only)
+            .when(library_open, |b: Button| b.primary())
+            .when(!library_open, |b: Button| b.ghost())
+            .on_click(move |_, _window: &mut Window, cx| {
+                library_entity.update(cx, |lib, cx| lib.toggle(cx));
+            });
 
         v_flex()
             .w_full()
@@ -116,11 +412,35 @@ impl Render for DesignArea {
             .gap_4()
             .border_1()
             .border_color(theme.border)
-            // .child(h_flex().w_full().justify_end().child(add_module_btn))
+            .child(h_flex().w_full().justify_end().child(add_module_btn))
+            .child(diagnostics)
+            .child(bar_toolbar)
             .child(self.preview.clone())
             .child(self.module_library.clone())
             .child(self.module_editor.clone())
             .child(div().flex_1())
             .child(self.bar_settings.clone())
+            .child(self.style_editor.clone())
+    }
+}
+
+fn count_label(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+// Keys of the modules in every bar, since one style.css styles them all
+fn module_keys(preview: &Entity<WaybarPreview>, cx: &App) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    if let Some(config) = preview.read(cx).config.as_ref() {
+        for module in config.bars.iter().flat_map(|bar| bar.all_modules()) {
+            if !keys.contains(&module.key) {
+                keys.push(module.key.clone());
+            }
+        }
     }
+    keys
 }
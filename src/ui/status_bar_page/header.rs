@@ -7,7 +7,6 @@ use gpui_component::{
     select::{Select, SelectState},
 };
 
-use crate::shell::waybar_sh_commands::restart_waybar;
 use crate::system::waybar::{
     UNKNOWN_MANAGED_PROFILE, current_live_waybar_profile, has_unknown_managed_live_waybar,
     is_read_only_waybar_profile, list_waybar_profiles,
@@ -17,6 +16,7 @@ use crate::ui::dialogs::manage_waybar_profile_dialogs::{
     open_delete_waybar_profile_dialog, open_duplicate_waybar_profile_dialog,
     open_rename_waybar_profile_dialog,
 };
+use crate::ui::status_bar_page::status_bar_view::apply_and_restart;
 
 pub struct StatusBarHeader {
     profile_select: Entity<SelectState<Vec<SharedString>>>,
@@ -217,15 +217,8 @@ impl Render for StatusBarHeader {
                         .ghost()
                         .small()
                         .tooltip("Apply And Restart Waybar")
-                        .on_click(move |_, _, _| {
-                            if let Err(e) =
-                                crate::system::waybar::apply_waybar_profile(&profile_for_restart)
-                            {
-                                eprintln!("Failed to apply waybar profile: {e}");
-                            }
-                            if let Err(e) = restart_waybar() {
-                                eprintln!("Failed to restart waybar: {e}");
-                            }
+                        .on_click(move |_, window, cx| {
+                            apply_and_restart(&profile_for_restart, window, cx);
                         }),
                 ),
             )
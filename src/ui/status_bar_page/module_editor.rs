@@ -30,6 +30,7 @@ pub fn take_pending_module_edit() -> Option<(String, String)> {
 
 pub struct ModuleEditorPanel {
     profile_name: String,
+    bar: usize,
     is_read_only: bool,
     module_key: String,
     is_open: bool,
@@ -59,6 +60,7 @@ impl ModuleEditorPanel {
 
         let subs = Self::build_subs(
             profile_name,
+            0,
             "",
             &format_input,
             &interval_input,
@@ -71,6 +73,7 @@ impl ModuleEditorPanel {
 
         Self {
             profile_name: profile_name.to_string(),
+            bar: 0,
             is_read_only,
             module_key: String::new(),
             is_open: false,
@@ -87,7 +90,7 @@ impl ModuleEditorPanel {
         self.module_key = module_key.to_string();
         self.is_open = true;
 
-        let cfg = get_module_config(&self.profile_name, module_key);
+        let cfg = get_module_config(&self.profile_name, self.bar, module_key);
 
         let str_field = |key: &str| -> String {
             cfg.get(key)
@@ -121,6 +124,7 @@ impl ModuleEditorPanel {
         // Rebuild subscriptions for the new module key
         self._subscriptions = Self::build_subs(
             &self.profile_name,
+            self.bar,
             module_key,
             &self.format_input,
             &self.interval_input,
@@ -142,12 +146,20 @@ impl ModuleEditorPanel {
     pub fn switch_profile(&mut self, profile_name: &str, is_read_only: bool) {
         self.profile_name = profile_name.to_string();
         self.is_read_only = is_read_only;
+        self.bar = 0;
+        self.is_open = false;
+    }
+
+    // Module configs are per bar, so an open editor would edit the wrong one
+    pub fn select_bar(&mut self, bar: usize) {
+        self.bar = bar;
         self.is_open = false;
     }
 
     #[allow(clippy::too_many_arguments)]
     fn build_subs(
         profile_name: &str,
+        bar: usize,
         module_key: &str,
         format_input: &Entity<InputState>,
         interval_input: &Entity<InputState>,
@@ -172,7 +184,7 @@ impl ModuleEditorPanel {
                         if matches!(event, InputEvent::Change) && !mk.is_empty() {
                             let val = input_ref.read(cx).value().to_string();
                             if let Some(v) = $parse(&val) {
-                                if let Err(e) = set_module_config_field(&p, &mk, $field, &v) {
+                                if let Err(e) = set_module_config_field(&p, bar, &mk, $field, &v) {
                                     eprintln!("Module editor save error: {}", e);
                                 }
                             }
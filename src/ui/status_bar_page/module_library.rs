@@ -1,3 +1,4 @@
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
     ActiveTheme, IconName, IndexPath, Sizable, StyledExt, WindowExt,
@@ -11,6 +12,7 @@ use gpui_component::{
 use std::sync::LazyLock;
 
 use crate::system::waybar::{LibraryModule, WaybarZone, add_module_to_zone, module_library};
+use crate::ui::status_bar_page::custom_module_builder::CustomModuleBuilder;
 use crate::ui::status_bar_page::waybar_preview::WaybarPreview;
 
 static MODULE_LIBRARY: LazyLock<Vec<LibraryModule>> = LazyLock::new(module_library);
@@ -38,10 +40,14 @@ impl LibraryRowState {
 // ModuleLibraryPanel — stateful inline panel
 pub struct ModuleLibraryPanel {
     profile_name: String,
+    bar: usize,
     is_read_only: bool,
     is_open: bool,
     rows: Vec<LibraryRowState>,
     preview: Entity<WaybarPreview>,
+    custom_builder: Entity<CustomModuleBuilder>,
+    // Whether the custom module builder is shown instead of the list
+    show_builder: bool,
 }
 
 impl ModuleLibraryPanel {
@@ -57,17 +63,33 @@ impl ModuleLibraryPanel {
             .iter()
             .map(|m| LibraryRowState::new(m, window, cx))
             .collect();
+        let custom_builder = {
+            let preview = preview.clone();
+            cx.new(|cx| CustomModuleBuilder::new(profile_name, preview, window, cx))
+        };
         Self {
             profile_name: profile_name.to_string(),
+            bar: 0,
             is_read_only,
             is_open: false,
             rows,
             preview,
+            custom_builder,
+            show_builder: false,
         }
     }
 
-    pub fn switch_profile(&mut self, profile_name: &str, is_read_only: bool) {
+    pub fn select_bar(&mut self, bar: usize, cx: &mut App) {
+        self.bar = bar;
+        self.custom_builder
+            .update(cx, |builder, _| builder.select_bar(bar));
+    }
+
+    pub fn switch_profile(&mut self, profile_name: &str, is_read_only: bool, cx: &mut App) {
         self.profile_name = profile_name.to_string();
+        self.custom_builder
+            .update(cx, |builder, _| builder.switch_profile(profile_name));
+        self.bar = 0;
         self.is_read_only = is_read_only;
         if is_read_only {
             self.is_open = false;
@@ -90,6 +112,7 @@ impl ModuleLibraryPanel {
 fn render_row(
     row: &LibraryRowState,
     profile: String,
+    bar: usize,
     preview_entity: Entity<WaybarPreview>,
     theme_border: Hsla,
     theme_fg: Hsla,
@@ -116,7 +139,7 @@ fn render_row(
                     _ => WaybarZone::Left,
                 }
             };
-            match add_module_to_zone(&profile, module_key, &zone, default_config) {
+            match add_module_to_zone(&profile, bar, module_key, &zone, default_config) {
                 Ok(()) => {
                     let p = profile.clone();
                     preview_entity.update(cx, |preview, cx| {
@@ -210,6 +233,7 @@ impl Render for ModuleLibraryPanel {
                     let el = render_row(
                         row,
                         self.profile_name.clone(),
+                        self.bar,
                         self.preview.clone(),
                         theme.border,
                         theme.foreground,
@@ -249,6 +273,7 @@ impl Render for ModuleLibraryPanel {
                         render_row(
                             row,
                             self.profile_name.clone(),
+                            self.bar,
                             self.preview.clone(),
                             theme.border,
                             theme.foreground,
@@ -294,6 +319,20 @@ impl Render for ModuleLibraryPanel {
                             .text_color(theme.muted_foreground)
                             .child("Choose a zone and click Add to insert a module into your bar."),
                     )
+                    .child(
+                        Button::new("module-library-custom")
+                            .label(if self.show_builder {
+                                "Back to Library"
+                            } else {
+                                "New Custom Module"
+                            })
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.show_builder = !this.show_builder;
+                                cx.notify();
+                            })),
+                    )
                     .child(
                         Button::new("module-library-close")
                             .icon(IconName::Close)
@@ -312,7 +351,13 @@ impl Render for ModuleLibraryPanel {
                     .max_h(px(400.))
                     .overflow_y_scrollbar()
                     .gap_0()
-                    .children(sections),
+                    .map(|this| {
+                        if self.show_builder {
+                            this.child(self.custom_builder.clone())
+                        } else {
+                            this.children(sections)
+                        }
+                    }),
             )
             .into_any()
     }
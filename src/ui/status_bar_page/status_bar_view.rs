@@ -1,6 +1,8 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
-use gpui_component::{ActiveTheme, Icon, IconName, StyledExt, h_flex, select::SelectEvent, v_flex};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, StyledExt, WindowExt, h_flex, select::SelectEvent, v_flex,
+};
 
 use crate::shell::waybar_sh_commands::restart_waybar;
 use crate::system::waybar::{
@@ -19,9 +21,15 @@ use crate::ui::status_bar_page::header::StatusBarHeader;
 const KEY_CONTEXT: &str = "StatusBar";
 const HEADER_ITEM_COUNT: usize = 6;
 
-fn apply_and_restart(profile_name: &str) {
+// Waybar keeps running its current config when the profile has errors
+pub fn apply_and_restart(profile_name: &str, window: &mut Window, cx: &mut App) {
     if let Err(e) = apply_waybar_profile(profile_name) {
         eprintln!("Failed to apply waybar profile \"{}\": {e}", profile_name);
+        window.push_notification(
+            format!("Waybar profile \"{}\" was not applied: {}", profile_name, e),
+            cx,
+        );
+        return;
     }
     if let Err(e) = restart_waybar() {
         eprintln!("Failed to restart waybar: {e}");
@@ -151,7 +159,7 @@ impl Render for StatusBarView {
                 ProfileManagementResult::Duplicated { new_name } => new_name,
                 ProfileManagementResult::Deleted { switch_to } => switch_to,
             };
-            apply_and_restart(&active_profile);
+            apply_and_restart(&active_profile, window, cx);
             self.header.update(cx, |header, cx| {
                 header.reload_and_select(&active_profile, window, cx);
             });
@@ -228,7 +236,7 @@ impl Render for StatusBarView {
                     }
                     Some(5) => {
                         let profile = this.header.read(cx).current_profile_name(cx);
-                        apply_and_restart(&profile);
+                        apply_and_restart(&profile, window, cx);
                     }
                     _ => {}
                 }
@@ -0,0 +1,400 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable, Icon, IconName, Sizable, StyledExt,
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::system::waybar::{
+    COMMON_STYLE_SELECTORS, STYLE_PROPERTIES, WaybarStyle, load_waybar_style, save_waybar_style,
+    theme_colors,
+};
+use crate::ui::color_utils::hex_to_hsla;
+use crate::ui::status_bar_page::shared::labeled_input;
+
+const COLOR_PROPERTIES: &[&str] = &["color", "background-color"];
+
+pub struct StyleEditorPanel {
+    profile_name: String,
+    is_read_only: bool,
+    expanded: bool,
+    style: WaybarStyle,
+    // Selector whose properties are being edited, e.g. `#clock`
+    selector: String,
+    // Keys of the modules in the profile's bars, to offer their selectors
+    module_keys: Vec<String>,
+    // `(name, value)` of the colors the active theme's `waybar.css` defines
+    theme_colors: Vec<(String, String)>,
+    // One input per entry of `STYLE_PROPERTIES`
+    inputs: Vec<Entity<InputState>>,
+    error_message: Option<String>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl StyleEditorPanel {
+    pub fn new(
+        profile_name: &str,
+        is_read_only: bool,
+        module_keys: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut inputs = Vec::new();
+        let mut subscriptions = Vec::new();
+        for (property, _) in STYLE_PROPERTIES {
+            let input = cx.new(|cx| InputState::new(window, cx).placeholder(placeholder(property)));
+            subscriptions.push(cx.subscribe_in(
+                &input,
+                window,
+                move |this, input, event: &InputEvent, _window, cx| {
+                    if matches!(event, InputEvent::Change) {
+                        let value = input.read(cx).value().trim().to_string();
+                        this.set_property(property, (!value.is_empty()).then_some(&value), cx);
+                    }
+                },
+            ));
+            inputs.push(input);
+        }
+
+        let mut panel = Self {
+            profile_name: profile_name.to_string(),
+            is_read_only,
+            expanded: false,
+            style: WaybarStyle::default(),
+            selector: COMMON_STYLE_SELECTORS[0].0.to_string(),
+            module_keys,
+            theme_colors: Vec::new(),
+            inputs,
+            error_message: None,
+            _subscriptions: subscriptions,
+        };
+        panel.load(window, cx);
+        panel
+    }
+
+    pub fn reload(
+        &mut self,
+        profile_name: &str,
+        is_read_only: bool,
+        module_keys: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.profile_name = profile_name.to_string();
+        self.is_read_only = is_read_only;
+        self.module_keys = module_keys;
+        self.load(window, cx);
+        cx.notify();
+    }
+
+    fn load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.style = load_waybar_style(&self.profile_name).unwrap_or_default();
+        self.theme_colors = theme_colors();
+        self.error_message = None;
+        self.sync_inputs(window, cx);
+    }
+
+    fn select(&mut self, selector: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.selector = selector;
+        self.sync_inputs(window, cx);
+        cx.notify();
+    }
+
+    // Show the selected selector's values in the inputs
+    fn sync_inputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for ((property, _), input) in STYLE_PROPERTIES.iter().zip(&self.inputs) {
+            let value = self
+                .style
+                .property(&self.selector, property)
+                .unwrap_or_default()
+                .to_string();
+            input.update(cx, |state, cx| state.set_value(value, window, cx));
+        }
+    }
+
+    fn set_property(&mut self, property: &str, value: Option<&str>, cx: &mut Context<Self>) {
+        if self.is_read_only || self.style.property(&self.selector, property) == value {
+            return;
+        }
+        self.style.set_property(&self.selector, property, value);
+        self.save(cx);
+    }
+
+    fn set_theme_import(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        if self.is_read_only {
+            return;
+        }
+        self.style.set_theme_import(enabled);
+        self.save(cx);
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        self.error_message = save_waybar_style(&self.profile_name, &self.style).err();
+        cx.notify();
+    }
+
+    // Colors that can be referenced by name: the theme's, while it is
+    // imported, and those defined in the style itself
+    fn color_names(&self) -> Vec<(String, String)> {
+        let theme = if self.style.imports_theme() {
+            self.theme_colors.clone()
+        } else {
+            Vec::new()
+        };
+        theme
+            .into_iter()
+            .chain(self.style.defined_colors())
+            .collect()
+    }
+
+    fn render_selector_menu(&self, cx: &mut Context<Self>) -> AnyElement {
+        let selectors = self.style.selectors(&self.module_keys);
+        let current = self.selector.clone();
+        let panel = cx.entity();
+
+        Button::new("waybar-style-selector")
+            .label(selector_label(&current))
+            .small()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu.scrollable(true).max_h(px(320.));
+                for selector in selectors.iter() {
+                    let panel = panel.clone();
+                    let selector = selector.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(selector_label(&selector))
+                            .checked(selector == current)
+                            .on_click(move |_event, window, cx| {
+                                let selector = selector.clone();
+                                panel.update(cx, |this, cx| this.select(selector, window, cx));
+                            }),
+                    );
+                }
+                menu
+            })
+            .into_any_element()
+    }
+
+    fn render_color_input(
+        &self,
+        index: usize,
+        label: &'static str,
+        property: &'static str,
+        colors: &[(String, String)],
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let theme = cx.theme();
+        let input = self.inputs[index].clone();
+        let value = input.read(cx).value().trim().to_string();
+
+        // Resolve `@name` references so the swatch shows the actual color
+        let hex = match value.strip_prefix('@') {
+            Some(name) => colors
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.clone()),
+            None => Some(value.clone()),
+        };
+        let swatch = hex.as_deref().and_then(hex_to_hsla);
+
+        let panel = cx.entity();
+        let names: Vec<String> = colors
+            .iter()
+            .map(|(name, _)| format!("@{}", name))
+            .collect();
+
+        v_flex()
+            .gap_1()
+            .w(px(220.))
+            .child(
+                Label::new(label)
+                    .text_sm()
+                    .text_color(theme.muted_foreground),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        div()
+                            .size(px(20.))
+                            .flex_shrink_0()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(theme.border)
+                            .when_some(swatch, |this, color| this.bg(color)),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(Input::new(&input).small().disabled(self.is_read_only)),
+                    )
+                    .child(
+                        Button::new(("waybar-style-color", index))
+                            .icon(IconName::Palette)
+                            .xsmall()
+                            .ghost()
+                            .disabled(self.is_read_only || names.is_empty())
+                            .dropdown_menu(move |menu, _window, _cx| {
+                                let mut menu = menu.scrollable(true).max_h(px(320.));
+                                for name in names.iter() {
+                                    let panel = panel.clone();
+                                    let input = input.clone();
+                                    let name = name.clone();
+                                    menu = menu.item(PopupMenuItem::new(name.clone()).on_click(
+                                        move |_event, window, cx| {
+                                            input.update(cx, |state, cx| {
+                                                state.set_value(name.clone(), window, cx)
+                                            });
+                                            panel.update(cx, |this, cx| {
+                                                this.set_property(property, Some(&name), cx)
+                                            });
+                                        },
+                                    ));
+                                }
+                                menu
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for StyleEditorPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let expanded = self.expanded;
+
+        let header = h_flex()
+            .id("waybar-style-header")
+            .w_full()
+            .gap_2()
+            .items_center()
+            .cursor_pointer()
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.expanded = !this.expanded;
+                cx.notify();
+            }))
+            .child(
+                Icon::new(if expanded {
+                    IconName::ChevronDown
+                } else {
+                    IconName::ChevronRight
+                })
+                .text_color(cx.theme().muted_foreground),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .font_semibold()
+                    .text_color(cx.theme().foreground)
+                    .child("Bar Style"),
+            );
+
+        let body: AnyElement = if expanded {
+            let read_only = self.is_read_only;
+            let colors = self.color_names();
+            let selector_menu = self.render_selector_menu(cx);
+
+            let mut fields = h_flex().gap_4().flex_wrap().items_end();
+            for (index, (property, label)) in STYLE_PROPERTIES.iter().enumerate() {
+                fields = if COLOR_PROPERTIES.contains(property) {
+                    fields.child(self.render_color_input(index, label, property, &colors, cx))
+                } else {
+                    fields.child(labeled_input(
+                        label,
+                        &self.inputs[index],
+                        cx.theme().muted_foreground,
+                        read_only,
+                    ))
+                };
+            }
+
+            let theme = cx.theme();
+            v_flex()
+                .pt_3()
+                .gap_4()
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(theme.muted_foreground)
+                        .child(if read_only {
+                            "The style is shown for reference until you import or manage this config."
+                        } else {
+                            "Edits style.css in this profile. Rules without a control here are kept as they are."
+                        }),
+                )
+                .child(
+                    v_flex()
+                        .gap_1()
+                        .child(
+                            Checkbox::new("waybar-style-theme-import")
+                                .checked(self.style.imports_theme())
+                                .label("Use theme colors")
+                                .disabled(read_only)
+                                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                    this.set_theme_import(*checked, cx);
+                                })),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(theme.muted_foreground)
+                                .child("Imports the active theme's waybar.css, so colors like @foreground and @background follow the theme."),
+                        ),
+                )
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(
+                            Label::new("Element")
+                                .text_sm()
+                                .text_color(theme.muted_foreground),
+                        )
+                        .child(selector_menu),
+                )
+                .child(fields)
+                .when_some(self.error_message.clone(), |this, error| {
+                    this.child(div().text_xs().text_color(theme.danger).child(error))
+                })
+                .into_any()
+        } else {
+            div().into_any()
+        };
+
+        let theme = cx.theme();
+        v_flex()
+            .w_full()
+            .p_3()
+            .gap_1()
+            .border_1()
+            .border_color(theme.border)
+            .rounded_md()
+            .child(header)
+            .child(body)
+    }
+}
+
+fn selector_label(selector: &str) -> String {
+    COMMON_STYLE_SELECTORS
+        .iter()
+        .find(|(s, _)| *s == selector)
+        .map(|(s, label)| format!("{} ({})", label, s))
+        .unwrap_or_else(|| selector.to_string())
+}
+
+fn placeholder(property: &str) -> &'static str {
+    match property {
+        "font-family" => "e.g. JetBrainsMono Nerd Font",
+        "font-size" => "e.g. 12px",
+        "padding" => "e.g. 0 6px",
+        "border-radius" => "e.g. 4px",
+        "border" => "e.g. 1px solid @foreground",
+        _ => "e.g. #a9b1d6",
+    }
+}
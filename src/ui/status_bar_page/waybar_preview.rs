@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use gpui::*;
-use gpui_component::{ActiveTheme, h_flex, v_flex};
+use gpui_component::{ActiveTheme, checkbox::Checkbox, h_flex, v_flex};
 
 use crate::system::waybar::{
-    WaybarConfig, WaybarModule, WaybarZone, load_waybar_config, save_waybar_config,
+    LiveSampler, PreviewData, WaybarBar, WaybarConfig, WaybarModule, WaybarZone,
+    load_waybar_config, render_module, save_waybar_config,
 };
 use crate::ui::status_bar_page::waybar_item::{DragWaybarModule, render_module_chip};
 
@@ -10,8 +13,16 @@ pub struct WaybarPreview {
     pub profile_name: String,
     pub config: Option<WaybarConfig>,
     pub is_read_only: bool,
+    // Index of the bar being edited within `config.bars`
+    pub bar: usize,
+    // Values the modules' formats are rendered with
+    data: PreviewData,
+    // Refreshes `data` from the machine while live data is on
+    live_task: Option<Task<()>>,
 }
 
+const LIVE_INTERVAL: Duration = Duration::from_secs(2);
+
 impl WaybarPreview {
     pub fn new(profile_name: &str, is_read_only: bool) -> Self {
         let config = load_config(profile_name);
@@ -19,6 +30,9 @@ impl WaybarPreview {
             profile_name: profile_name.to_string(),
             config,
             is_read_only,
+            bar: 0,
+            data: PreviewData::sample(),
+            live_task: None,
         }
     }
 
@@ -26,6 +40,54 @@ impl WaybarPreview {
         self.profile_name = profile_name.to_string();
         self.is_read_only = is_read_only;
         self.config = load_config(profile_name);
+        self.bar = self.bar.min(self.bar_count().saturating_sub(1));
+    }
+
+    pub fn select_bar(&mut self, bar: usize) {
+        self.bar = bar.min(self.bar_count().saturating_sub(1));
+    }
+
+    pub fn bar_count(&self) -> usize {
+        self.config.as_ref().map_or(0, |c| c.bars.len())
+    }
+
+    pub fn current_bar(&self) -> Option<&WaybarBar> {
+        self.config.as_ref()?.bars.get(self.bar)
+    }
+
+    fn set_live(&mut self, live: bool, cx: &mut Context<Self>) {
+        if !live {
+            self.live_task = None;
+            self.data = PreviewData::sample();
+            cx.notify();
+            return;
+        }
+
+        self.live_task = Some(cx.spawn(async move |this, cx| {
+            // Sampling reads disks, sensors and the battery, so it runs off
+            // the UI thread and only the data comes back
+            let mut sampler = smol::unblock(LiveSampler::new).await;
+            loop {
+                let (returned, data) = smol::unblock(move || {
+                    let data = sampler.sample();
+                    (sampler, data)
+                })
+                .await;
+                sampler = returned;
+
+                if this
+                    .update(cx, |this, cx| {
+                        this.data = data;
+                        cx.notify();
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+                smol::Timer::after(LIVE_INTERVAL).await;
+            }
+        }));
+        cx.notify();
     }
 
     pub fn remove_module(&mut self, zone: &WaybarZone, index: usize) {
@@ -33,14 +95,10 @@ impl WaybarPreview {
             return;
         }
 
-        let Some(cfg) = self.config.as_mut() else {
+        let Some(bar) = self.config.as_mut().and_then(|c| c.bars.get_mut(self.bar)) else {
             return;
         };
-        let modules = match zone {
-            WaybarZone::Left => &mut cfg.modules_left,
-            WaybarZone::Center => &mut cfg.modules_center,
-            WaybarZone::Right => &mut cfg.modules_right,
-        };
+        let modules = bar.modules_mut(zone);
         if index < modules.len() {
             modules.remove(index);
         }
@@ -62,16 +120,12 @@ impl WaybarPreview {
             return;
         }
 
-        let Some(cfg) = self.config.as_mut() else {
+        let Some(bar) = self.config.as_mut().and_then(|c| c.bars.get_mut(self.bar)) else {
             return;
         };
 
         if src_zone == dst_zone {
-            let modules = match src_zone {
-                WaybarZone::Left => &mut cfg.modules_left,
-                WaybarZone::Center => &mut cfg.modules_center,
-                WaybarZone::Right => &mut cfg.modules_right,
-            };
+            let modules = bar.modules_mut(src_zone);
             if src_index < modules.len() {
                 let item = modules.remove(src_index);
                 let insert_at = dst_index.min(modules.len());
@@ -80,11 +134,7 @@ impl WaybarPreview {
         } else {
             // Remove from source
             let module = {
-                let src = match src_zone {
-                    WaybarZone::Left => &mut cfg.modules_left,
-                    WaybarZone::Center => &mut cfg.modules_center,
-                    WaybarZone::Right => &mut cfg.modules_right,
-                };
+                let src = bar.modules_mut(src_zone);
                 if src_index < src.len() {
                     Some(src.remove(src_index))
                 } else {
@@ -94,11 +144,7 @@ impl WaybarPreview {
             // Insert into destination
             if let Some(mut module) = module {
                 module.zone = dst_zone.clone();
-                let dst = match dst_zone {
-                    WaybarZone::Left => &mut cfg.modules_left,
-                    WaybarZone::Center => &mut cfg.modules_center,
-                    WaybarZone::Right => &mut cfg.modules_right,
-                };
+                let dst = bar.modules_mut(dst_zone);
                 let insert_at = dst_index.min(dst.len());
                 dst.insert(insert_at, module);
             }
@@ -117,6 +163,29 @@ fn load_config(profile_name: &str) -> Option<WaybarConfig> {
     load_waybar_config(profile_name)
 }
 
+// Show what each module would print instead of its static icon, keeping the
+// icon for modules that print nothing in the preview
+fn rendered_modules(
+    modules: &[WaybarModule],
+    bar: &WaybarBar,
+    data: &PreviewData,
+) -> Vec<WaybarModule> {
+    modules
+        .iter()
+        .map(|module| {
+            let text = render_module(&module.key, &bar.config, data);
+            WaybarModule {
+                icon: if text.is_empty() {
+                    module.icon.clone()
+                } else {
+                    text
+                },
+                ..module.clone()
+            }
+        })
+        .collect()
+}
+
 fn render_zone(
     entity: Entity<WaybarPreview>,
     zone: WaybarZone,
@@ -177,26 +246,11 @@ fn render_zone(
                 style.border_l_2().border_color(cx.theme().drag_border)
             })
             .on_drop(move |payload: &DragWaybarModule, _window, cx| {
-                let dst_index = {
-                    let preview = entity_end.read(cx);
-                    match &zone_end {
-                        WaybarZone::Left => preview
-                            .config
-                            .as_ref()
-                            .map(|c| c.modules_left.len())
-                            .unwrap_or(0),
-                        WaybarZone::Center => preview
-                            .config
-                            .as_ref()
-                            .map(|c| c.modules_center.len())
-                            .unwrap_or(0),
-                        WaybarZone::Right => preview
-                            .config
-                            .as_ref()
-                            .map(|c| c.modules_right.len())
-                            .unwrap_or(0),
-                    }
-                };
+                let dst_index = entity_end
+                    .read(cx)
+                    .current_bar()
+                    .map(|bar| bar.modules(&zone_end).len())
+                    .unwrap_or(0);
                 entity_end.update(cx, |this, cx| {
                     this.move_module(&payload.zone, payload.index, &zone_end, dst_index);
                     cx.notify();
@@ -221,7 +275,7 @@ impl Render for WaybarPreview {
         let bar_bg = theme.title_bar;
         let border = theme.border;
 
-        let Some(config) = self.config.as_ref() else {
+        let Some(bar) = self.current_bar() else {
             return v_flex()
                 .w_full()
                 .flex_1()
@@ -236,9 +290,10 @@ impl Render for WaybarPreview {
                 .into_any();
         };
 
-        let left = config.modules_left.clone();
-        let center = config.modules_center.clone();
-        let right = config.modules_right.clone();
+        let left = rendered_modules(&bar.modules_left, bar, &self.data);
+        let center = rendered_modules(&bar.modules_center, bar, &self.data);
+        let right = rendered_modules(&bar.modules_right, bar, &self.data);
+        let live = self.live_task.is_some();
         let profile_name = self.profile_name.clone();
         let is_read_only = self.is_read_only;
 
@@ -274,11 +329,26 @@ impl Render for WaybarPreview {
             .flex_1()
             .mb_4()
             .gap_4()
-            .child(div().text_sm().text_color(muted).child(if is_read_only {
-                "This preview is read-only."
-            } else {
-                "Drag modules to reorder between zones."
-            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_4()
+                    .items_center()
+                    .justify_between()
+                    .child(div().text_sm().text_color(muted).child(if is_read_only {
+                        "This preview is read-only."
+                    } else {
+                        "Drag modules to reorder between zones."
+                    }))
+                    .child(
+                        Checkbox::new("waybar-preview-live")
+                            .checked(live)
+                            .label("Live data")
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.set_live(*checked, cx);
+                            })),
+                    ),
+            )
             .child(
                 div()
                     .w_full()
@@ -8,6 +8,7 @@ pub mod lockscreen_tab;
 pub mod menu_tab;
 pub mod notification_tab;
 pub mod shared;
+pub mod snapshots_tab;
 pub mod swayosd_tab;
 pub mod terminal_tab;
 pub mod theme_edit;
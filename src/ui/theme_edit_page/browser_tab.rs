@@ -2,7 +2,7 @@ use crate::shell::theme_sh_commands::execute_bash_command;
 use crate::system::themes::theme_management::{save_theme_data, update_chromium_config};
 use crate::types::themes::{BrowserConfig, EditingTheme};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -110,6 +110,7 @@ impl BrowserTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the chromium config
                 if let Some(ref browser_config) = self.theme_data.apps.chromium
                     && let Err(e) = update_chromium_config(&self.theme_name, browser_config)
@@ -135,6 +136,8 @@ impl BrowserTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for BrowserTab {}
+
 impl Render for BrowserTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
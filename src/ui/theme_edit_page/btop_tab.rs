@@ -1,7 +1,7 @@
 use crate::system::themes::theme_management::{save_theme_data, update_btop_theme};
 use crate::types::themes::{BtopConfig, EditingTheme};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -800,6 +800,7 @@ impl BtopTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the btop.theme file
                 if let Some(ref btop_config) = self.theme_data.apps.btop
                     && let Err(e) = update_btop_theme(&self.theme_name, btop_config)
@@ -818,6 +819,8 @@ impl BtopTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for BtopTab {}
+
 impl Render for BtopTab {
     fn render(&mut self, window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
         let wide = window.viewport_size().width >= px(1000.0);
@@ -1,10 +1,16 @@
 use crate::system::themes::theme_management::save_theme_data;
-use crate::types::themes::EditingTheme;
-use crate::ui::theme_edit_page::shared::{form_section, help_text, tab_container};
+use crate::system::themes::theme_management::vscode::VSCODE_EXTENSION_DIR;
+use crate::types::themes::{EditingTheme, EditorThemeMode, NeovimConfig, VscodeConfig};
+use crate::ui::theme_edit_page::shared::{
+    ThemeSaved, error_message, form_section, help_text, tab_container,
+};
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
-    ActiveTheme,
+    ActiveTheme, h_flex,
     input::{Input, InputEvent, InputState},
+    label::Label,
+    switch::Switch,
     v_flex,
 };
 use std::fs;
@@ -13,8 +19,11 @@ use std::path::PathBuf;
 pub struct EditorTab {
     theme_name: String,
     theme_data: EditingTheme,
+    neovim_mode: EditorThemeMode,
     neovim_input: Entity<InputState>,
-    vscode_input: Entity<InputState>,
+    vscode_mode: EditorThemeMode,
+    vscode_name_input: Entity<InputState>,
+    vscode_extension_input: Entity<InputState>,
     is_saving: bool,
     error_message: Option<String>,
 }
@@ -27,8 +36,14 @@ impl EditorTab {
         cx: &mut Context<Self>,
     ) -> Self {
         // Load file contents
-        let neovim_content = Self::load_neovim_content(&theme_name);
-        let vscode_content = Self::load_vscode_content(&theme_name);
+        let neovim_mode = theme_data
+            .apps
+            .neovim
+            .as_ref()
+            .map(|config| config.mode)
+            .unwrap_or_default();
+        let neovim_content = Self::load_neovim_content(&theme_name, &theme_data);
+        let vscode_config = Self::load_vscode_config(&theme_name, &theme_data);
 
         // Create input states with code editor mode
         let neovim_input = cx.new(|cx| {
@@ -38,18 +53,26 @@ impl EditorTab {
                 .default_value(&neovim_content)
         });
 
-        let vscode_input = cx.new(|cx| {
+        let vscode_name_input = cx.new(|cx| {
             InputState::new(window, cx)
-                .code_editor("json")
-                .line_number(false)
-                .default_value(&vscode_content)
+                .default_value(&vscode_config.name)
+                .placeholder("Sunset Drive")
+        });
+
+        let vscode_extension_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(&vscode_config.extension)
+                .placeholder("TahaYVR.sunset-drive")
         });
 
         let tab = Self {
             theme_name,
             theme_data,
+            neovim_mode,
             neovim_input,
-            vscode_input,
+            vscode_mode: vscode_config.mode,
+            vscode_name_input,
+            vscode_extension_input,
             is_saving: false,
             error_message: None,
         };
@@ -68,30 +91,47 @@ impl EditorTab {
         .detach();
 
         // Subscribe to vscode input changes
-        cx.subscribe_in(
-            &tab.vscode_input,
-            window,
-            |this, _input_state, event: &InputEvent, window, cx| {
-                if let InputEvent::Change = event {
-                    let content = this.vscode_input.read(cx).value().to_string();
-                    this.save_vscode(&content, window, cx);
-                }
-            },
-        )
-        .detach();
+        for input in [&tab.vscode_name_input, &tab.vscode_extension_input] {
+            cx.subscribe_in(
+                input,
+                window,
+                |this, _input_state, event: &InputEvent, window, cx| {
+                    if let InputEvent::Change = event {
+                        this.save_vscode(window, cx);
+                    }
+                },
+            )
+            .detach();
+        }
 
         tab
     }
 
-    fn load_neovim_content(theme_name: &str) -> String {
+    fn load_neovim_content(theme_name: &str, theme_data: &EditingTheme) -> String {
+        // The plugin spec is kept in omarchist.json so it survives a switch to generated mode
+        if let Some(ref config) = theme_data.apps.neovim
+            && !config.plugin_spec.trim().is_empty()
+        {
+            return config.plugin_spec.clone();
+        }
+
         let themes_dir = dirs::home_dir()
             .map(|h| h.join(".config").join("omarchy").join("themes"))
             .unwrap_or_else(|| PathBuf::from("."));
 
         let file_path = themes_dir.join(theme_name).join("neovim.lua");
-        fs::read_to_string(&file_path).unwrap_or_else(|_| {
-            // Return default content if file doesn't exist
-            r#"return {
+        let is_generated = theme_data
+            .apps
+            .neovim
+            .as_ref()
+            .is_some_and(|config| config.mode == EditorThemeMode::Generated);
+
+        fs::read_to_string(&file_path)
+            .ok()
+            .filter(|_| !is_generated)
+            .unwrap_or_else(|| {
+                // Return default content if file doesn't exist
+                r#"return {
     { "tahayvr/sunset-drive.nvim", lazy = false, priority = 1000 },
     {
         "LazyVim/LazyVim",
@@ -100,24 +140,29 @@ impl EditorTab {
         },
     },
 }"#
-            .to_string()
-        })
+                .to_string()
+            })
     }
 
-    fn load_vscode_content(theme_name: &str) -> String {
+    fn load_vscode_config(theme_name: &str, theme_data: &EditingTheme) -> VscodeConfig {
+        if let Some(ref config) = theme_data.apps.vscode {
+            return config.clone();
+        }
+
+        // Themes that predate the typed config only have vscode.json on disk
         let themes_dir = dirs::home_dir()
             .map(|h| h.join(".config").join("omarchy").join("themes"))
             .unwrap_or_else(|| PathBuf::from("."));
 
         let file_path = themes_dir.join(theme_name).join("vscode.json");
-        fs::read_to_string(&file_path).unwrap_or_else(|_| {
-            // Return default content if file doesn't exist
-            r#"{
-	"name": "Sunset Drive",
-	"extension": "TahaYVR.sunset-drive"
-}"#
-            .to_string()
-        })
+        fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(|| VscodeConfig {
+                mode: EditorThemeMode::Plugin,
+                name: "Sunset Drive".to_string(),
+                extension: "TahaYVR.sunset-drive".to_string(),
+            })
     }
 
     fn save_neovim(&mut self, content: &str, _window: &mut Window, cx: &mut Context<Self>) {
@@ -135,39 +180,38 @@ impl EditorTab {
         self.error_message = None;
         cx.notify();
 
-        // Save to neovim.lua file directly
-        let themes_dir = dirs::home_dir()
-            .map(|h| h.join(".config").join("omarchy").join("themes"))
-            .unwrap_or_else(|| PathBuf::from("."));
-
-        let file_path = themes_dir.join(&self.theme_name).join("neovim.lua");
+        // `save_theme_data` writes neovim.lua from the config in either mode
+        self.theme_data.apps.neovim = Some(NeovimConfig {
+            mode: self.neovim_mode,
+            plugin_spec: content.to_string(),
+        });
 
-        match fs::write(&file_path, content) {
-            Ok(()) => {
-                // Also update theme_data.apps.neovim with the content
-                match serde_json::to_value(content) {
-                    Ok(value) => {
-                        self.theme_data.apps.neovim = Some(value);
-                        // Save theme data to update modified_at timestamp
-                        let _ = save_theme_data(&self.theme_name, &self.theme_data);
-                    }
-                    Err(e) => {
-                        self.error_message =
-                            Some(format!("Failed to serialize neovim content: {}", e));
-                    }
-                }
-                self.is_saving = false;
-            }
-            Err(e) => {
-                self.is_saving = false;
-                self.error_message = Some(format!("Failed to write neovim.lua: {}", e));
-            }
+        match save_theme_data(&self.theme_name, &self.theme_data) {
+            Ok(()) => cx.emit(ThemeSaved(self.theme_data.clone())),
+            Err(e) => self.error_message = Some(format!("Failed to save Neovim theme: {}", e)),
         }
+        self.is_saving = false;
 
         cx.notify();
     }
 
-    fn save_vscode(&mut self, content: &str, _window: &mut Window, cx: &mut Context<Self>) {
+    fn on_neovim_mode_toggle(
+        &mut self,
+        generated: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.neovim_mode = if generated {
+            EditorThemeMode::Generated
+        } else {
+            EditorThemeMode::Plugin
+        };
+
+        let content = self.neovim_input.read(cx).value().to_string();
+        self.save_neovim(&content, window, cx);
+    }
+
+    fn save_vscode(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         if self.is_saving {
             return;
         }
@@ -182,45 +226,54 @@ impl EditorTab {
         self.error_message = None;
         cx.notify();
 
-        // Save to vscode.json file directly
-        let themes_dir = dirs::home_dir()
-            .map(|h| h.join(".config").join("omarchy").join("themes"))
-            .unwrap_or_else(|| PathBuf::from("."));
-
-        let file_path = themes_dir.join(&self.theme_name).join("vscode.json");
+        // `save_theme_data` writes vscode.json, and the extension folder in generated mode
+        self.theme_data.apps.vscode = Some(VscodeConfig {
+            mode: self.vscode_mode,
+            name: self.vscode_name_input.read(cx).value().trim().to_string(),
+            extension: self
+                .vscode_extension_input
+                .read(cx)
+                .value()
+                .trim()
+                .to_string(),
+        });
 
-        match fs::write(&file_path, content) {
-            Ok(()) => {
-                // Also update theme_data.apps.vscode with the content
-                match serde_json::to_value(content) {
-                    Ok(value) => {
-                        self.theme_data.apps.vscode = Some(value);
-                        // Save theme data to update modified_at timestamp
-                        let _ = save_theme_data(&self.theme_name, &self.theme_data);
-                    }
-                    Err(e) => {
-                        self.error_message =
-                            Some(format!("Failed to serialize vscode content: {}", e));
-                    }
-                }
-                self.is_saving = false;
-            }
-            Err(e) => {
-                self.is_saving = false;
-                self.error_message = Some(format!("Failed to write vscode.json: {}", e));
-            }
+        match save_theme_data(&self.theme_name, &self.theme_data) {
+            Ok(()) => cx.emit(ThemeSaved(self.theme_data.clone())),
+            Err(e) => self.error_message = Some(format!("Failed to save VS Code theme: {}", e)),
         }
+        self.is_saving = false;
 
         cx.notify();
     }
 
+    fn on_vscode_mode_toggle(
+        &mut self,
+        generated: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.vscode_mode = if generated {
+            EditorThemeMode::Generated
+        } else {
+            EditorThemeMode::Plugin
+        };
+
+        self.save_vscode(window, cx);
+    }
+
     pub fn theme_data(&self) -> &EditingTheme {
         &self.theme_data
     }
 }
 
+impl EventEmitter<ThemeSaved> for EditorTab {}
+
 impl Render for EditorTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_neovim_generated = self.neovim_mode == EditorThemeMode::Generated;
+        let is_vscode_generated = self.vscode_mode == EditorThemeMode::Generated;
+
         tab_container()
             .child(help_text(
                 "Edit the configuration files for Neovim and VSCode: themes:",
@@ -240,15 +293,37 @@ impl Render for EditorTab {
                                     .child("Neovim (neovim.lua)"),
                             )
                             .child(
-                                div().bg(cx.theme().background).h(px(300.)).child(
-                                    Input::new(&self.neovim_input)
-                                        .bg(cx.theme().background)
-                                        .border_1()
-                                        .border_color(cx.theme().border)
-                                        .h_full()
-                                        .appearance(false),
-                                ),
-                            ),
+                                h_flex()
+                                    .gap_4()
+                                    .items_center()
+                                    .child(Label::new("Generate from theme colors"))
+                                    .child(
+                                        Switch::new("neovim-generated-toggle")
+                                            .checked(is_neovim_generated)
+                                            .cursor_pointer()
+                                            .on_click(cx.listener(|this, checked, window, cx| {
+                                                this.on_neovim_mode_toggle(*checked, window, cx);
+                                            })),
+                                    ),
+                            )
+                            .when(is_neovim_generated, |this| {
+                                this.child(help_text(
+                                    "A colorscheme with editor, syntax, treesitter and diagnostic highlights is generated from the palette on every save.",
+                                    cx.theme().muted_foreground,
+                                ))
+                            })
+                            .when(!is_neovim_generated, |this| {
+                                this.child(
+                                    div().bg(cx.theme().background).h(px(300.)).child(
+                                        Input::new(&self.neovim_input)
+                                            .bg(cx.theme().background)
+                                            .border_1()
+                                            .border_color(cx.theme().border)
+                                            .h_full()
+                                            .appearance(false),
+                                    ),
+                                )
+                            }),
                     )
                     .child(
                         // VSCode: section
@@ -261,16 +336,56 @@ impl Render for EditorTab {
                                     .child("VSCode: (vscode.json)"),
                             )
                             .child(
-                                div().bg(cx.theme().background).h(px(200.)).child(
-                                    Input::new(&self.vscode_input)
-                                        .bg(cx.theme().background)
-                                        .border_1()
-                                        .border_color(cx.theme().border)
-                                        .h_full()
-                                        .appearance(false),
-                                ),
-                            ),
+                                h_flex()
+                                    .gap_4()
+                                    .items_center()
+                                    .child(Label::new("Generate from theme colors"))
+                                    .child(
+                                        Switch::new("vscode-generated-toggle")
+                                            .checked(is_vscode_generated)
+                                            .cursor_pointer()
+                                            .on_click(cx.listener(|this, checked, window, cx| {
+                                                this.on_vscode_mode_toggle(*checked, window, cx);
+                                            })),
+                                    ),
+                            )
+                            .when(is_vscode_generated, |this| {
+                                this.child(help_text(
+                                    format!(
+                                        "A color theme extension is generated from the palette into the theme's {} folder on every save, and linked into ~/.vscode/extensions.",
+                                        VSCODE_EXTENSION_DIR
+                                    ),
+                                    cx.theme().muted_foreground,
+                                ))
+                            })
+                            .when(!is_vscode_generated, |this| {
+                                this.child(
+                                    v_flex()
+                                        .gap_2()
+                                        .child(
+                                            Label::new("Theme Name")
+                                                .text_sm()
+                                                .text_color(cx.theme().muted_foreground),
+                                        )
+                                        .child(Input::new(&self.vscode_name_input)),
+                                )
+                                .child(
+                                    v_flex()
+                                        .gap_2()
+                                        .child(
+                                            Label::new("Marketplace Extension ID")
+                                                .text_sm()
+                                                .text_color(cx.theme().muted_foreground),
+                                        )
+                                        .child(Input::new(&self.vscode_extension_input)),
+                                )
+                            }),
                     ),
             )
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| error_message(msg.clone(), cx)),
+            )
     }
 }
@@ -1,7 +1,7 @@
 use crate::shell::theme_sh_commands::execute_bash_command;
 use crate::system::themes::theme_management::{save_theme_data, update_icons_theme};
 use crate::types::themes::EditingTheme;
-use crate::ui::theme_edit_page::shared::{form_section, help_text, tab_container};
+use crate::ui::theme_edit_page::shared::{ThemeSaved, form_section, help_text, tab_container};
 use gpui::*;
 use gpui_component::{ActiveTheme, button::Button, h_flex, radio::Radio, v_flex};
 
@@ -117,6 +117,7 @@ impl FileManagerTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the icons.theme file
                 if let Err(e) = update_icons_theme(&self.theme_name, &self.selected_color) {
                     self.error_message = Some(format!("Failed to update icons.theme: {}", e));
@@ -167,6 +168,8 @@ impl FileManagerTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for FileManagerTab {}
+
 impl Render for FileManagerTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let mut container = v_flex().gap_3();
@@ -4,7 +4,7 @@ use crate::system::themes::theme_management::{
 use crate::types::themes::EditingTheme;
 use crate::ui::color_utils::hex_to_hsla;
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, error_message, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, error_message, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -140,6 +140,7 @@ impl GeneralTab {
         // The new name is stored in theme_data.name but we save to the original folder
         match save_theme_data(&self.original_theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 self.is_saving = false;
             }
             Err(e) => {
@@ -170,6 +171,7 @@ impl GeneralTab {
         // Save theme data
         match save_theme_data(&self.original_theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update colors.toml with new accent color
                 if let Some(ref terminal_config) = self.theme_data.apps.terminal {
                     let colors = colors_config_from_terminal(
@@ -227,6 +229,8 @@ impl GeneralTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for GeneralTab {}
+
 impl Render for GeneralTab {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let is_light = self.theme_data.is_light_theme;
@@ -1,7 +1,7 @@
 use crate::system::themes::theme_management::{save_theme_data, update_hyprlock_conf};
 use crate::types::themes::{EditingTheme, HyprlockConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -206,6 +206,7 @@ impl LockScreenTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the hyprlock.conf file
                 if let Some(ref hyprlock_config) = self.theme_data.apps.hyprlock
                     && let Err(e) = update_hyprlock_conf(&self.theme_name, hyprlock_config)
@@ -224,6 +225,8 @@ impl LockScreenTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for LockScreenTab {}
+
 impl Render for LockScreenTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
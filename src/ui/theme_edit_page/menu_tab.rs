@@ -1,7 +1,7 @@
 use crate::system::themes::theme_management::{save_theme_data, update_walker_css};
 use crate::types::themes::{EditingTheme, WalkerConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -215,6 +215,7 @@ impl MenuTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the walker.css file
                 if let Some(ref walker_config) = self.theme_data.apps.walker
                     && let Err(e) = update_walker_css(&self.theme_name, walker_config)
@@ -233,6 +234,8 @@ impl MenuTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for MenuTab {}
+
 impl Render for MenuTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
@@ -2,7 +2,7 @@ use crate::shell::theme_sh_commands::execute_bash_command;
 use crate::system::themes::theme_management::{save_theme_data, update_mako_ini};
 use crate::types::themes::{EditingTheme, MakoConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -155,6 +155,7 @@ impl NotificationTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the mako.ini file
                 if let Some(ref mako_config) = self.theme_data.apps.mako
                     && let Err(e) = update_mako_ini(&self.theme_name, mako_config)
@@ -181,6 +182,8 @@ impl NotificationTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for NotificationTab {}
+
 impl Render for NotificationTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
@@ -1,3 +1,4 @@
+use crate::types::themes::EditingTheme;
 use gpui::*;
 use gpui_component::{
     ActiveTheme, Colorize,
@@ -128,6 +129,9 @@ pub trait TabInputHandler: Sized {
     fn trigger_save(&mut self, window: &mut Window, cx: &mut Context<Self>);
 }
 
+// Emitted by a tab after it has written its copy of the theme to disk
+pub struct ThemeSaved(pub EditingTheme);
+
 pub fn tab_container() -> Div {
     v_flex().gap_6().pt_4().pb_4()
 }
@@ -0,0 +1,261 @@
+use crate::system::themes::theme_snapshots::{
+    diff_theme_snapshots, list_theme_snapshots, rollback_theme_snapshot,
+    snapshot_limit_from_settings,
+};
+use crate::types::themes::{SnapshotFieldChange, ThemeSnapshot};
+use crate::ui::theme_edit_page::shared::{error_message, help_text, tab_container};
+use chrono::DateTime;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    v_flex,
+};
+
+// Emitted after a snapshot has been copied back over the theme directory
+pub struct SnapshotRestored;
+
+pub struct SnapshotsTab {
+    theme_name: String,
+    snapshots: Vec<ThemeSnapshot>,
+    // Up to two snapshot ids picked for comparison, oldest pick first
+    selected: Vec<String>,
+    changes: Vec<SnapshotFieldChange>,
+    error_message: Option<String>,
+}
+
+impl SnapshotsTab {
+    pub fn new(theme_name: String, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut tab = Self {
+            theme_name,
+            snapshots: Vec::new(),
+            selected: Vec::new(),
+            changes: Vec::new(),
+            error_message: None,
+        };
+
+        tab.reload(cx);
+
+        tab
+    }
+
+    pub fn reload(&mut self, cx: &mut Context<Self>) {
+        match list_theme_snapshots(&self.theme_name) {
+            Ok(snapshots) => {
+                self.snapshots = snapshots;
+                self.selected
+                    .retain(|id| self.snapshots.iter().any(|s| &s.id == id));
+                self.update_diff();
+            }
+            Err(e) => self.error_message = Some(format!("Failed to load snapshots: {}", e)),
+        }
+        cx.notify();
+    }
+
+    fn toggle_selected(&mut self, id: String, cx: &mut Context<Self>) {
+        if let Some(index) = self.selected.iter().position(|s| s == &id) {
+            self.selected.remove(index);
+        } else {
+            if self.selected.len() == 2 {
+                self.selected.remove(0);
+            }
+            self.selected.push(id);
+        }
+
+        self.update_diff();
+        cx.notify();
+    }
+
+    fn update_diff(&mut self) {
+        self.changes.clear();
+        self.error_message = None;
+
+        let [first, second] = self.selected.as_slice() else {
+            return;
+        };
+
+        // Always diff from the older snapshot to the newer one
+        let (old_id, new_id) = if first < second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        match diff_theme_snapshots(&self.theme_name, old_id, new_id) {
+            Ok(changes) => self.changes = changes,
+            Err(e) => self.error_message = Some(e),
+        }
+    }
+
+    fn restore(&mut self, id: &str, cx: &mut Context<Self>) {
+        match rollback_theme_snapshot(&self.theme_name, id) {
+            Ok(()) => {
+                self.reload(cx);
+                cx.emit(SnapshotRestored);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to restore snapshot: {}", e));
+                cx.notify();
+            }
+        }
+    }
+
+    fn format_timestamp(timestamp: &str) -> String {
+        DateTime::parse_from_rfc3339(timestamp)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| timestamp.to_string())
+    }
+
+    fn render_changes(&self, cx: &Context<Self>) -> Div {
+        let theme = cx.theme();
+
+        if self.changes.is_empty() {
+            return v_flex().child(help_text(
+                "The selected snapshots are identical.",
+                theme.muted_foreground,
+            ));
+        }
+
+        let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "(none)".to_string());
+
+        v_flex()
+            .gap_1()
+            .child(
+                Label::new(format!(
+                    "{} changed field{}",
+                    self.changes.len(),
+                    if self.changes.len() == 1 { "" } else { "s" }
+                ))
+                .text_sm()
+                .text_color(theme.muted_foreground),
+            )
+            .children(self.changes.iter().map(|change| {
+                h_flex()
+                    .gap_4()
+                    .text_sm()
+                    .child(div().w(px(320.)).truncate().child(change.path.clone()))
+                    .child(
+                        div()
+                            .text_color(theme.danger)
+                            .child(value(&change.old_value)),
+                    )
+                    .child(div().text_color(theme.muted_foreground).child("→"))
+                    .child(
+                        div()
+                            .text_color(theme.success)
+                            .child(value(&change.new_value)),
+                    )
+            }))
+    }
+}
+
+impl EventEmitter<SnapshotRestored> for SnapshotsTab {}
+
+impl Render for SnapshotsTab {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_enabled = snapshot_limit_from_settings().is_some();
+        let theme = cx.theme();
+
+        tab_container()
+            .child(
+                h_flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        Label::new("Snapshots")
+                            .text_lg()
+                            .font_weight(FontWeight::MEDIUM),
+                    )
+                    .child(
+                        Button::new("reload-snapshots-btn")
+                            .label("Refresh")
+                            .small()
+                            .cursor_pointer()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.reload(cx);
+                            })),
+                    ),
+            )
+            .child(help_text(
+                if is_enabled {
+                    "A snapshot is saved with every change. Select two snapshots to compare them."
+                } else {
+                    "Snapshots are turned off. Enable them in Settings to record a revision on every save."
+                },
+                theme.muted_foreground,
+            ))
+            .when(self.snapshots.is_empty(), |this| {
+                this.child(
+                    Label::new("No snapshots yet")
+                        .text_sm()
+                        .text_color(theme.muted_foreground),
+                )
+            })
+            .child(
+                v_flex()
+                    .gap_2()
+                    .children(self.snapshots.iter().enumerate().map(|(index, snapshot)| {
+                        let is_selected = self.selected.contains(&snapshot.id);
+                        let select_id = snapshot.id.clone();
+                        let restore_id = snapshot.id.clone();
+
+                        h_flex()
+                            .gap_4()
+                            .p_2()
+                            .items_center()
+                            .justify_between()
+                            .border_1()
+                            .border_color(if is_selected {
+                                theme.ring
+                            } else {
+                                theme.border
+                            })
+                            .rounded(theme.radius)
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(Label::new(Self::format_timestamp(&snapshot.created_at)))
+                                    .child(
+                                        Label::new(format!("Theme version {}", snapshot.version))
+                                            .text_xs()
+                                            .text_color(theme.muted_foreground),
+                                    ),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(
+                                        Button::new(("compare-snapshot", index))
+                                            .label(if is_selected { "Selected" } else { "Compare" })
+                                            .small()
+                                            .ghost()
+                                            .cursor_pointer()
+                                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                                this.toggle_selected(select_id.clone(), cx);
+                                            })),
+                                    )
+                                    .child(
+                                        Button::new(("restore-snapshot", index))
+                                            .label("Restore")
+                                            .small()
+                                            .cursor_pointer()
+                                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                                this.restore(&restore_id, cx);
+                                            })),
+                                    ),
+                            )
+                    })),
+            )
+            .when(self.selected.len() == 2, |this| {
+                this.child(self.render_changes(cx))
+            })
+            .children(
+                self.error_message
+                    .as_ref()
+                    .map(|msg| error_message(msg.clone(), cx)),
+            )
+    }
+}
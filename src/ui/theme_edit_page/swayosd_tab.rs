@@ -1,7 +1,7 @@
 use crate::system::themes::theme_management::{save_theme_data, update_swayosd_css};
 use crate::types::themes::{EditingTheme, SwayosdConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -186,6 +186,7 @@ impl SwayosdTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the swayosd.css file
                 if let Some(ref swayosd_config) = self.theme_data.apps.swayosd
                     && let Err(e) = update_swayosd_css(&self.theme_name, swayosd_config)
@@ -204,6 +205,8 @@ impl SwayosdTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for SwayosdTab {}
+
 impl Render for SwayosdTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
@@ -2,7 +2,7 @@ use crate::shell::theme_sh_commands::execute_bash_command;
 use crate::system::themes::theme_management::{save_theme_data, update_terminal_configs};
 use crate::types::themes::{EditingTheme, TerminalConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -213,6 +213,7 @@ impl TerminalTab {
 
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 if let Some(ref terminal_config) = self.theme_data.apps.terminal
                     && let Err(e) = update_terminal_configs(&self.theme_name, terminal_config)
                 {
@@ -237,6 +238,8 @@ impl TerminalTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for TerminalTab {}
+
 impl Render for TerminalTab {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let wide = window.viewport_size().width >= px(1000.0);
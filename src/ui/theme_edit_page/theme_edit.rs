@@ -1,5 +1,7 @@
 use crate::shell::theme_sh_commands::apply_theme;
+use crate::system::themes::contrast::{ContrastTarget, check_theme_contrast, fix_theme_contrast};
 use crate::system::themes::theme_file_ops::is_system_theme;
+use crate::system::themes::theme_history::{ThemeHistory, restore_theme_snapshot};
 use crate::system::themes::theme_management::load_theme_for_editing;
 use crate::types::themes::{EditingTheme, ThemeEditTab};
 use crate::ui::theme_edit_page::backgrounds_tab::BackgroundsTab;
@@ -11,16 +13,19 @@ use crate::ui::theme_edit_page::general_tab::GeneralTab;
 use crate::ui::theme_edit_page::lockscreen_tab::LockScreenTab;
 use crate::ui::theme_edit_page::menu_tab::MenuTab;
 use crate::ui::theme_edit_page::notification_tab::NotificationTab;
-use crate::ui::theme_edit_page::shared::error_message;
+use crate::ui::theme_edit_page::shared::{ThemeSaved, error_message};
+use crate::ui::theme_edit_page::snapshots_tab::{SnapshotRestored, SnapshotsTab};
 use crate::ui::theme_edit_page::swayosd_tab::SwayosdTab;
 use crate::ui::theme_edit_page::terminal_tab::TerminalTab;
 use crate::ui::theme_edit_page::waybar_tab::WaybarTab;
 use crate::ui::theme_edit_page::windows_tab::WindowsTab;
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
     ActiveTheme,
     button::Button,
     h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
     tab::{Tab, TabBar},
     v_flex,
 };
@@ -41,11 +46,9 @@ thread_local! {
 #[action(no_json)]
 pub struct SaveTheme;
 
-pub struct ThemeEditPage {
-    theme_name: String,
-    active_tab: usize,
-    tab_count: usize,
-    error_message: Option<String>,
+// Tabs that edit `EditingTheme`. They are rebuilt together when a history
+// snapshot is restored so every picker reflects the restored values.
+struct ThemeTabs {
     general_tab: Entity<GeneralTab>,
     waybar_tab: Entity<WaybarTab>,
     windows_tab: Entity<WindowsTab>,
@@ -58,7 +61,84 @@ pub struct ThemeEditPage {
     editor_tab: Entity<EditorTab>,
     btop_tab: Entity<BtopTab>,
     swayosd_tab: Entity<SwayosdTab>,
+}
+
+impl ThemeTabs {
+    fn new(
+        theme_name: &str,
+        theme_data: &EditingTheme,
+        window: &mut Window,
+        cx: &mut Context<ThemeEditPage>,
+    ) -> Self {
+        let theme_name = theme_name.to_string();
+
+        let tabs = Self {
+            general_tab: cx
+                .new(|cx| GeneralTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            waybar_tab: cx
+                .new(|cx| WaybarTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            windows_tab: cx
+                .new(|cx| WindowsTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            menu_tab: cx.new(|cx| MenuTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            terminal_tab: cx
+                .new(|cx| TerminalTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            browser_tab: cx
+                .new(|cx| BrowserTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            file_manager_tab: cx
+                .new(|cx| FileManagerTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            lockscreen_tab: cx
+                .new(|cx| LockScreenTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            notification_tab: cx
+                .new(|cx| NotificationTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            editor_tab: cx
+                .new(|cx| EditorTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            btop_tab: cx.new(|cx| BtopTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+            swayosd_tab: cx
+                .new(|cx| SwayosdTab::new(theme_name.clone(), theme_data.clone(), window, cx)),
+        };
+
+        record_saves(&tabs.general_tab, ThemeEditTab::General, cx);
+        record_saves(&tabs.waybar_tab, ThemeEditTab::Waybar, cx);
+        record_saves(&tabs.windows_tab, ThemeEditTab::Windows, cx);
+        record_saves(&tabs.menu_tab, ThemeEditTab::Menu, cx);
+        record_saves(&tabs.terminal_tab, ThemeEditTab::Terminal, cx);
+        record_saves(&tabs.browser_tab, ThemeEditTab::Browser, cx);
+        record_saves(&tabs.file_manager_tab, ThemeEditTab::FileManager, cx);
+        record_saves(&tabs.lockscreen_tab, ThemeEditTab::LockScreen, cx);
+        record_saves(&tabs.notification_tab, ThemeEditTab::Notification, cx);
+        record_saves(&tabs.editor_tab, ThemeEditTab::Editor, cx);
+        record_saves(&tabs.btop_tab, ThemeEditTab::Btop, cx);
+        record_saves(&tabs.swayosd_tab, ThemeEditTab::Swayosd, cx);
+
+        tabs
+    }
+}
+
+// Add a history entry whenever `tab` writes the theme to disk
+fn record_saves<T: EventEmitter<ThemeSaved>>(
+    tab: &Entity<T>,
+    source: ThemeEditTab,
+    cx: &mut Context<ThemeEditPage>,
+) {
+    cx.subscribe(tab, move |this, _tab, event: &ThemeSaved, cx| {
+        let label = format!("{} change", source.as_str());
+        if this.history.record(label, event.0.clone()) {
+            cx.notify();
+        }
+        this.snapshots_tab.update(cx, |tab, cx| tab.reload(cx));
+    })
+    .detach();
+}
+
+pub struct ThemeEditPage {
+    theme_name: String,
+    active_tab: usize,
+    tab_count: usize,
+    error_message: Option<String>,
+    history: ThemeHistory,
+    tabs: ThemeTabs,
     backgrounds_tab: Entity<BackgroundsTab>,
+    snapshots_tab: Entity<SnapshotsTab>,
     pub focus_handle: FocusHandle,
 }
 
@@ -76,58 +156,23 @@ impl ThemeEditPage {
             }
         };
 
-        // Create General tab instance
-        let general_tab =
-            cx.new(|cx| GeneralTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Waybar tab instance
-        let waybar_tab =
-            cx.new(|cx| WaybarTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Windows tab instance
-        let windows_tab =
-            cx.new(|cx| WindowsTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Menu tab instance
-        let menu_tab =
-            cx.new(|cx| MenuTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Terminal tab instance
-        let terminal_tab =
-            cx.new(|cx| TerminalTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Browser tab instance
-        let browser_tab =
-            cx.new(|cx| BrowserTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create File Manager tab instance
-        let file_manager_tab =
-            cx.new(|cx| FileManagerTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Lock Screen tab instance
-        let lockscreen_tab =
-            cx.new(|cx| LockScreenTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Notification tab instance
-        let notification_tab =
-            cx.new(|cx| NotificationTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Editor tab instance
-        let editor_tab =
-            cx.new(|cx| EditorTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create Btop tab instance
-        let btop_tab =
-            cx.new(|cx| BtopTab::new(theme_name.clone(), theme_data.clone(), window, cx));
-
-        // Create SwayOSD tab instance
-        let swayosd_tab =
-            cx.new(|cx| SwayosdTab::new(theme_name.clone(), theme_data.clone(), window, cx));
+        let tabs = ThemeTabs::new(&theme_name, &theme_data, window, cx);
 
         // Create Backgrounds tab instance
         let backgrounds_tab =
             cx.new(|cx| BackgroundsTab::new(theme_name.clone(), is_system, window, cx));
 
+        // Create Snapshots tab instance
+        let snapshots_tab = cx.new(|cx| SnapshotsTab::new(theme_name.clone(), window, cx));
+        cx.subscribe_in(
+            &snapshots_tab,
+            window,
+            |this, _tab, _event: &SnapshotRestored, window, cx| {
+                this.reload_from_disk("Restored snapshot", window, cx);
+            },
+        )
+        .detach();
+
         let tab_count = ThemeEditTab::all().len();
 
         // Create focus handle and request focus immediately
@@ -139,19 +184,10 @@ impl ThemeEditPage {
             active_tab: 0,
             tab_count,
             error_message: None,
-            general_tab,
-            waybar_tab,
-            windows_tab,
-            menu_tab,
-            terminal_tab,
-            browser_tab,
-            file_manager_tab,
-            lockscreen_tab,
-            notification_tab,
-            editor_tab,
-            btop_tab,
-            swayosd_tab,
+            history: ThemeHistory::new(theme_data),
+            tabs,
             backgrounds_tab,
+            snapshots_tab,
             focus_handle,
         }
     }
@@ -170,6 +206,103 @@ impl ThemeEditPage {
         });
     }
 
+    fn undo(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(theme) = self.history.undo().cloned() {
+            self.restore(theme, window, cx);
+        }
+    }
+
+    fn redo(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(theme) = self.history.redo().cloned() {
+            self.restore(theme, window, cx);
+        }
+    }
+
+    fn jump_to_history(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(theme) = self.history.jump_to(index).cloned() {
+            self.restore(theme, window, cx);
+        }
+    }
+
+    // Save a history snapshot and rebuild the tabs from it
+    fn restore(&mut self, theme: EditingTheme, window: &mut Window, cx: &mut Context<Self>) {
+        self.error_message = restore_theme_snapshot(&self.theme_name, &theme)
+            .err()
+            .map(|e| format!("Failed to restore theme: {}", e));
+        self.tabs = ThemeTabs::new(&self.theme_name, &theme, window, cx);
+        self.snapshots_tab.update(cx, |tab, cx| tab.reload(cx));
+        cx.notify();
+    }
+
+    // Pick up changes written to the theme directory outside the tabs
+    fn reload_from_disk(&mut self, label: &str, window: &mut Window, cx: &mut Context<Self>) {
+        match load_theme_for_editing(&self.theme_name) {
+            Ok(theme) => {
+                self.history.record(label, theme.clone());
+                self.tabs = ThemeTabs::new(&self.theme_name, &theme, window, cx);
+                self.error_message = None;
+            }
+            Err(e) => self.error_message = Some(format!("Failed to reload theme: {}", e)),
+        }
+        cx.notify();
+    }
+
+    fn render_history_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let page = cx.entity();
+        let can_undo = self.history.can_undo();
+        let can_redo = self.history.can_redo();
+        let position = self.history.position();
+        let entries: Vec<(usize, String)> = self
+            .history
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                (
+                    index,
+                    format!("{} ({})", entry.label, entry.recorded_at.format("%H:%M:%S")),
+                )
+            })
+            .collect();
+
+        Button::new("history-btn")
+            .label("History")
+            .compact()
+            .cursor_pointer()
+            .dropdown_menu(move |menu, _window, _cx| {
+                let undo_page = page.clone();
+                let redo_page = page.clone();
+                let mut menu = menu
+                    .scrollable(true)
+                    .max_h(px(400.))
+                    .item(PopupMenuItem::new("Undo").disabled(!can_undo).on_click(
+                        move |_event, window, cx| {
+                            undo_page.update(cx, |this, cx| this.undo(window, cx));
+                        },
+                    ))
+                    .item(PopupMenuItem::new("Redo").disabled(!can_redo).on_click(
+                        move |_event, window, cx| {
+                            redo_page.update(cx, |this, cx| this.redo(window, cx));
+                        },
+                    ))
+                    .separator();
+
+                // Newest first
+                for (index, label) in entries.iter().rev().cloned() {
+                    let page = page.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(label)
+                            .checked(index == position)
+                            .on_click(move |_event, window, cx| {
+                                page.update(cx, |this, cx| this.jump_to_history(index, window, cx));
+                            }),
+                    );
+                }
+
+                menu
+            })
+    }
+
     fn next_tab(&mut self, cx: &mut Context<Self>) {
         if self.active_tab < self.tab_count.saturating_sub(1) {
             self.active_tab += 1;
@@ -184,66 +317,163 @@ impl ThemeEditPage {
         }
     }
 
-    fn render_tab_content(&self, _window: &mut Window, _cx: &mut Context<Self>) -> AnyElement {
-        let tabs = ThemeEditTab::all();
-        let active_tab = tabs
+    fn active_tab_kind(&self) -> ThemeEditTab {
+        ThemeEditTab::all()
             .get(self.active_tab)
             .copied()
-            .unwrap_or(ThemeEditTab::General);
+            .unwrap_or(ThemeEditTab::General)
+    }
 
-        match active_tab {
+    // Nudge the foreground of each pair until it passes WCAG AA, then save
+    fn fix_contrast(
+        &mut self,
+        targets: Vec<ContrastTarget>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut theme = self.history.current().theme.clone();
+        let unfixable: Vec<String> = targets
+            .into_iter()
+            .filter(|target| !fix_theme_contrast(&mut theme, *target))
+            .map(|target| target.label())
+            .collect();
+
+        self.history.record("Contrast fix", theme.clone());
+        self.restore(theme, window, cx);
+
+        if !unfixable.is_empty() {
+            self.error_message = Some(format!(
+                "Could not reach WCAG AA contrast for: {}",
+                unfixable.join(", ")
+            ));
+        }
+    }
+
+    fn render_contrast_warnings(&self, cx: &mut Context<Self>) -> Option<Div> {
+        let active_tab = self.active_tab_kind();
+        let issues: Vec<_> = check_theme_contrast(&self.history.current().theme)
+            .into_iter()
+            .filter(|issue| issue.target.tab() == active_tab)
+            .collect();
+
+        if issues.is_empty() {
+            return None;
+        }
+
+        let theme = cx.theme();
+        let all_targets: Vec<ContrastTarget> = issues.iter().map(|i| i.target).collect();
+
+        Some(
+            v_flex()
+                .gap_2()
+                .p_3()
+                .mb_4()
+                .bg(theme.warning.opacity(0.1))
+                .border_1()
+                .border_color(theme.warning)
+                .child(
+                    h_flex()
+                        .items_center()
+                        .justify_between()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(theme.warning)
+                                .child("Low contrast"),
+                        )
+                        .when(issues.len() > 1, |this| {
+                            this.child(
+                                Button::new("fix-all-contrast")
+                                    .label("Fix All")
+                                    .compact()
+                                    .cursor_pointer()
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.fix_contrast(all_targets.clone(), window, cx);
+                                    })),
+                            )
+                        }),
+                )
+                .children(issues.into_iter().enumerate().map(|(index, issue)| {
+                    let target = issue.target;
+                    h_flex()
+                        .gap_4()
+                        .items_center()
+                        .justify_between()
+                        .child(div().text_sm().text_color(theme.foreground).child(format!(
+                            "{} on its background is {:.1}:1, WCAG AA needs {:.1}:1",
+                            issue.target.label(),
+                            issue.ratio,
+                            issue.min_ratio
+                        )))
+                        .child(
+                            Button::new(("fix-contrast", index))
+                                .label("Fix")
+                                .compact()
+                                .cursor_pointer()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.fix_contrast(vec![target], window, cx);
+                                })),
+                        )
+                })),
+        )
+    }
+
+    fn render_tab_content(&self, _window: &mut Window, _cx: &mut Context<Self>) -> AnyElement {
+        match self.active_tab_kind() {
             ThemeEditTab::General => {
                 // Use the GeneralTab entity
-                self.general_tab.clone().into_any_element()
+                self.tabs.general_tab.clone().into_any_element()
             }
             ThemeEditTab::Waybar => {
                 // Use the WaybarTab entity
-                self.waybar_tab.clone().into_any_element()
+                self.tabs.waybar_tab.clone().into_any_element()
             }
             ThemeEditTab::Windows => {
                 // Use the WindowsTab entity
-                self.windows_tab.clone().into_any_element()
+                self.tabs.windows_tab.clone().into_any_element()
             }
             ThemeEditTab::Menu => {
                 // Use the MenuTab entity
-                self.menu_tab.clone().into_any_element()
+                self.tabs.menu_tab.clone().into_any_element()
             }
             ThemeEditTab::Terminal => {
                 // Use the TerminalTab entity
-                self.terminal_tab.clone().into_any_element()
+                self.tabs.terminal_tab.clone().into_any_element()
             }
             ThemeEditTab::Browser => {
                 // Use the BrowserTab entity
-                self.browser_tab.clone().into_any_element()
+                self.tabs.browser_tab.clone().into_any_element()
             }
             ThemeEditTab::FileManager => {
                 // Use the FileManagerTab entity
-                self.file_manager_tab.clone().into_any_element()
+                self.tabs.file_manager_tab.clone().into_any_element()
             }
             ThemeEditTab::LockScreen => {
                 // Use the LockScreenTab entity
-                self.lockscreen_tab.clone().into_any_element()
+                self.tabs.lockscreen_tab.clone().into_any_element()
             }
             ThemeEditTab::Notification => {
                 // Use the NotificationTab entity
-                self.notification_tab.clone().into_any_element()
+                self.tabs.notification_tab.clone().into_any_element()
             }
             ThemeEditTab::Editor => {
                 // Use the EditorTab entity
-                self.editor_tab.clone().into_any_element()
+                self.tabs.editor_tab.clone().into_any_element()
             }
             ThemeEditTab::Btop => {
                 // Use the BtopTab entity
-                self.btop_tab.clone().into_any_element()
+                self.tabs.btop_tab.clone().into_any_element()
             }
             ThemeEditTab::Swayosd => {
                 // Use the SwayosdTab entity
-                self.swayosd_tab.clone().into_any_element()
+                self.tabs.swayosd_tab.clone().into_any_element()
             }
             ThemeEditTab::Backgrounds => {
                 // Use the BackgroundsTab entity
                 self.backgrounds_tab.clone().into_any_element()
             }
+            ThemeEditTab::Snapshots => self.snapshots_tab.clone().into_any_element(),
         }
     }
 }
@@ -272,6 +502,16 @@ impl Render for ThemeEditPage {
                     this.prev_tab(cx);
                 },
             ))
+            .on_action(cx.listener(
+                |this, _: &crate::ui::menu::app_menu::ThemeEditUndo, window, cx| {
+                    this.undo(window, cx);
+                },
+            ))
+            .on_action(cx.listener(
+                |this, _: &crate::ui::menu::app_menu::ThemeEditRedo, window, cx| {
+                    this.redo(window, cx);
+                },
+            ))
             .on_action(cx.listener(
                 |this, _: &crate::ui::menu::app_menu::NavigateBack, window, cx| {
                     this.navigate_back(window, cx);
@@ -307,6 +547,7 @@ impl Render for ThemeEditPage {
                                 .detach();
                             })),
                     )
+                    .child(self.render_history_menu(cx))
                     .child(
                         div().flex_1().min_w_0().child(
                             TabBar::new("theme-edit-tabs")
@@ -333,6 +574,7 @@ impl Render for ThemeEditPage {
                     .overflow_y_scroll()
                     .pt_4()
                     .pb_8()
+                    .children(self.render_contrast_warnings(cx))
                     .child(self.render_tab_content(window, cx)),
             )
     }
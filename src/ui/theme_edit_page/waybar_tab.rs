@@ -1,7 +1,7 @@
 use crate::system::themes::theme_management::{save_theme_data, update_waybar_css};
 use crate::types::themes::{EditingTheme, WaybarConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -130,6 +130,7 @@ impl WaybarTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the waybar.css file
                 if let Some(ref waybar_config) = self.theme_data.apps.waybar
                     && let Err(e) = update_waybar_css(&self.theme_name, waybar_config)
@@ -148,6 +149,8 @@ impl WaybarTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for WaybarTab {}
+
 impl Render for WaybarTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
@@ -1,7 +1,7 @@
 use crate::system::themes::theme_management::{save_theme_data, update_hyprland_conf};
 use crate::types::themes::{EditingTheme, HyprlandConfig};
 use crate::ui::theme_edit_page::shared::{
-    color_picker_with_clipboard, form_section, help_text, tab_container,
+    ThemeSaved, color_picker_with_clipboard, form_section, help_text, tab_container,
 };
 use gpui::*;
 use gpui_component::{
@@ -141,6 +141,7 @@ impl WindowsTab {
         // Save theme data
         match save_theme_data(&self.theme_name, &self.theme_data) {
             Ok(()) => {
+                cx.emit(ThemeSaved(self.theme_data.clone()));
                 // Also update the hyprland.conf file
                 if let Some(ref hyprland_config) = self.theme_data.apps.hyprland
                     && let Err(e) = update_hyprland_conf(&self.theme_name, hyprland_config)
@@ -159,6 +160,8 @@ impl WindowsTab {
     }
 }
 
+impl EventEmitter<ThemeSaved> for WindowsTab {}
+
 impl Render for WindowsTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tab_container()
@@ -1,7 +1,9 @@
 use crate::shell::theme_sh_commands::apply_theme;
+use crate::system::themes::theme_convert::duplicate_as_editable;
 use crate::system::themes::theme_file_ops::{delete_theme, open_theme_folder};
 use crate::types::themes::ThemeEntry;
 use crate::ui::color_utils::hex_to_hsla;
+use crate::ui::dialogs::theme_bundle_dialogs::open_export_theme_picker;
 use gpui::prelude::*;
 use gpui::*;
 use gpui_component::{
@@ -115,6 +117,8 @@ impl Render for ThemeCard {
                             .dropdown_menu(move |menu, _, _cx| {
                                 let theme_dir_open = theme_dir_clone.clone();
                                 let theme_dir_edit = theme_dir_clone.clone();
+                                let theme_dir_export = theme_dir_clone.clone();
+                                let theme_dir_duplicate = theme_dir_clone.clone();
                                 let theme_dir_delete = theme_dir_clone.clone();
                                 menu.item(
                                     PopupMenuItem::new("Open Folder")
@@ -132,6 +136,33 @@ impl Render for ThemeCard {
                                                 cx.refresh_windows();
                                             }),
                                     )
+                                    .item(
+                                        PopupMenuItem::new("Export Theme...")
+                                            .on_click(move |_event, window, cx| {
+                                                open_export_theme_picker(theme_dir_export.clone(), window, cx);
+                                            }),
+                                    )
+                                })
+                                .when(!is_editable, |this| {
+                                    this.item(
+                                        PopupMenuItem::new("Duplicate as Editable")
+                                            .on_click(move |_event, _window, cx| {
+                                                match duplicate_as_editable(&theme_dir_duplicate, is_system) {
+                                                    Ok(new_name) => {
+                                                        crate::ui::dialogs::create_theme_dialog::PENDING_REFRESH_THEMES.with(|flag| {
+                                                            *flag.borrow_mut() = true;
+                                                        });
+                                                        crate::ui::dialogs::create_theme_dialog::PENDING_THEME_NAVIGATION.with(|nav| {
+                                                            *nav.borrow_mut() = Some(new_name);
+                                                        });
+                                                        cx.refresh_windows();
+                                                    }
+                                                    Err(e) => {
+                                                        eprintln!("Failed to duplicate theme: {}", e);
+                                                    }
+                                                }
+                                            }),
+                                    )
                                 })
                                 .separator()
                                 .when(is_deletable, |this| {